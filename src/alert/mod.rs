@@ -0,0 +1,138 @@
+use std::{fmt::Display, future::Future, net::IpAddr, time::Duration};
+
+use anyhow::Context;
+use monitor::AlertThresholds;
+use serde::{Deserialize, Serialize};
+use smtp::{SmtpAlertNotifier, SmtpConfig};
+use webhook::{WebhookAlertNotifier, WebhookConfig};
+
+pub mod monitor;
+pub mod smtp;
+pub mod webhook;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertConfig {
+    #[serde(flatten)]
+    pub thresholds: AlertThresholds,
+    pub webhook: Option<WebhookConfig>,
+    pub smtp: Option<SmtpConfig>,
+}
+
+/// An alert that is sent to the operators once a threshold is crossed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "alert_type")]
+pub enum Alert {
+    /// Too many invalid API keys were sent from the same IP address within the configured window.
+    InvalidApiKeys {
+        ip: IpAddr,
+        count: u32,
+        #[serde(with = "duration_secs")]
+        window: Duration,
+    },
+    /// Refreshing the JWKS has been failing for longer than the configured duration.
+    JwksRefreshFailing {
+        #[serde(with = "duration_secs")]
+        failing_for: Duration,
+        last_error: String,
+    },
+}
+
+impl Alert {
+    /// Short one-line summary of the alert.
+    pub fn subject(&self) -> &'static str {
+        match self {
+            Alert::InvalidApiKeys { .. } => "Too many invalid API keys",
+            Alert::JwksRefreshFailing { .. } => "JWKS refresh failing",
+        }
+    }
+}
+
+impl Display for Alert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Alert::InvalidApiKeys { ip, count, window } => write!(
+                f,
+                "{count} invalid API keys were sent from {ip} within {} seconds",
+                window.as_secs()
+            ),
+            Alert::JwksRefreshFailing {
+                failing_for,
+                last_error,
+            } => write!(
+                f,
+                "Refreshing the JWKS has been failing for {} seconds. Last error: {last_error}",
+                failing_for.as_secs()
+            ),
+        }
+    }
+}
+
+/// Sends [`Alert`]s to the operators.
+pub trait AlertNotifier {
+    type Error;
+
+    /// Sends the alert.
+    fn notify(&self, alert: &Alert) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// Sends the alert to all configured notifiers.
+#[derive(Clone)]
+pub struct AlertNotifiers {
+    webhook: Option<WebhookAlertNotifier>,
+    smtp: Option<SmtpAlertNotifier>,
+}
+
+impl AlertNotifiers {
+    pub fn from_config(config: &AlertConfig, http_client: reqwest::Client) -> anyhow::Result<Self> {
+        let webhook = config
+            .webhook
+            .clone()
+            .map(|webhook| WebhookAlertNotifier::new(webhook, http_client));
+
+        let smtp = config
+            .smtp
+            .clone()
+            .map(SmtpAlertNotifier::new)
+            .transpose()
+            .context("Failed to create SMTP alert notifier")?;
+
+        Ok(Self { webhook, smtp })
+    }
+}
+
+impl AlertNotifier for AlertNotifiers {
+    type Error = anyhow::Error;
+
+    async fn notify(&self, alert: &Alert) -> Result<(), Self::Error> {
+        let webhook = async {
+            match &self.webhook {
+                Some(webhook) => webhook.notify(alert).await.map_err(anyhow::Error::from),
+                None => Ok(()),
+            }
+        };
+
+        let smtp = async {
+            match &self.smtp {
+                Some(smtp) => smtp.notify(alert).await.map_err(anyhow::Error::from),
+                None => Ok(()),
+            }
+        };
+
+        let (webhook, smtp) = tokio::join!(webhook, smtp);
+
+        webhook.context("Webhook alert notifier failed")?;
+        smtp.context("SMTP alert notifier failed")?;
+
+        Ok(())
+    }
+}
+
+mod duration_secs {
+    use std::time::Duration;
+
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_secs())
+    }
+}
@@ -0,0 +1,153 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+
+use super::{Alert, AlertNotifier};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertThresholds {
+    /// Number of invalid API keys from one IP address that triggers an alert.
+    pub invalid_api_keys_per_ip: u32,
+    /// Window in which invalid API keys from one IP address are counted.
+    pub invalid_api_keys_window_in_seconds: u64,
+    /// Duration after which a failing JWKS refresh triggers an alert.
+    pub jwks_refresh_failing_for_in_seconds: u64,
+}
+
+struct InvalidApiKeysWindow {
+    started: Instant,
+    count: u32,
+    alerted: bool,
+}
+
+struct JwksRefreshFailure {
+    since: Instant,
+    alerted: bool,
+}
+
+/// Counts auth failures and triggers the [`AlertNotifier`] once a threshold is crossed.
+///
+/// Every threshold triggers at most one alert until it resets,
+/// e.g. the window of an IP address expires or the JWKS refresh succeeds again.
+pub struct AlertMonitor<N> {
+    notifier: Arc<N>,
+    thresholds: AlertThresholds,
+    invalid_api_keys: Mutex<HashMap<IpAddr, InvalidApiKeysWindow>>,
+    jwks_refresh_failure: Mutex<Option<JwksRefreshFailure>>,
+}
+
+impl<N> AlertMonitor<N>
+where
+    N: AlertNotifier + Send + Sync + 'static,
+    N::Error: Display,
+{
+    pub fn new(notifier: N, thresholds: AlertThresholds) -> Self {
+        Self {
+            notifier: Arc::new(notifier),
+            thresholds,
+            invalid_api_keys: Mutex::new(HashMap::new()),
+            jwks_refresh_failure: Mutex::new(None),
+        }
+    }
+
+    /// Records an invalid API key sent from `ip`.
+    pub fn record_invalid_api_key(&self, ip: IpAddr) {
+        let window = Duration::from_secs(self.thresholds.invalid_api_keys_window_in_seconds);
+
+        let count = {
+            let mut invalid_api_keys = self
+                .invalid_api_keys
+                .lock()
+                .expect("invalid API keys mutex poisoned");
+
+            if !invalid_api_keys.contains_key(&ip) {
+                invalid_api_keys.retain(|_, entry| entry.started.elapsed() <= window);
+            }
+
+            let entry = invalid_api_keys
+                .entry(ip)
+                .or_insert_with(|| InvalidApiKeysWindow {
+                    started: Instant::now(),
+                    count: 0,
+                    alerted: false,
+                });
+
+            if entry.started.elapsed() > window {
+                *entry = InvalidApiKeysWindow {
+                    started: Instant::now(),
+                    count: 0,
+                    alerted: false,
+                };
+            }
+
+            entry.count += 1;
+
+            if entry.alerted || entry.count <= self.thresholds.invalid_api_keys_per_ip {
+                return;
+            }
+
+            entry.alerted = true;
+            entry.count
+        };
+
+        self.notify(Alert::InvalidApiKeys { ip, count, window });
+    }
+
+    /// Records a failed JWKS refresh.
+    pub fn record_jwks_refresh_failure(&self, err: &impl Display) {
+        let threshold = Duration::from_secs(self.thresholds.jwks_refresh_failing_for_in_seconds);
+
+        let failing_for = {
+            let mut failure = self
+                .jwks_refresh_failure
+                .lock()
+                .expect("JWKS refresh failure mutex poisoned");
+
+            let failure = failure.get_or_insert_with(|| JwksRefreshFailure {
+                since: Instant::now(),
+                alerted: false,
+            });
+
+            let failing_for = failure.since.elapsed();
+
+            if failure.alerted || failing_for <= threshold {
+                return;
+            }
+
+            failure.alerted = true;
+            failing_for
+        };
+
+        self.notify(Alert::JwksRefreshFailing {
+            failing_for,
+            last_error: err.to_string(),
+        });
+    }
+
+    /// Records a successful JWKS refresh.
+    pub fn record_jwks_refresh_success(&self) {
+        self.jwks_refresh_failure
+            .lock()
+            .expect("JWKS refresh failure mutex poisoned")
+            .take();
+    }
+
+    /// Sends the alert in the background, so the request is not held up by the notifier.
+    fn notify(&self, alert: Alert) {
+        tracing::warn!(%alert, "Alert triggered");
+
+        let notifier = self.notifier.clone();
+
+        tokio::spawn(async move {
+            if let Err(err) = notifier.notify(&alert).await {
+                tracing::error!(%err, "Failed to send alert");
+            }
+        });
+    }
+}
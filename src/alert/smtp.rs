@@ -0,0 +1,83 @@
+use derivative::Derivative;
+use lettre::{
+    message::Mailbox, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
+    AsyncTransport, Message, Tokio1Executor,
+};
+use serde::Deserialize;
+
+use super::{Alert, AlertNotifier};
+
+#[derive(Derivative, Clone, Deserialize)]
+#[derivative(Debug)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: Option<u16>,
+    pub username: String,
+    #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+    pub password: String,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SmtpAlertError {
+    #[error("Invalid mailbox: {0}")]
+    Address(#[from] lettre::address::AddressError),
+    #[error("Failed to build email: {0}")]
+    Build(#[from] lettre::error::Error),
+    #[error("Failed to send email: {0}")]
+    Send(#[from] lettre::transport::smtp::Error),
+}
+
+/// Sends alerts as emails over SMTP.
+#[derive(Clone)]
+pub struct SmtpAlertNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl SmtpAlertNotifier {
+    pub fn new(config: SmtpConfig) -> Result<Self, SmtpAlertError> {
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)?
+            .credentials(Credentials::new(config.username, config.password));
+
+        if let Some(port) = config.port {
+            builder = builder.port(port);
+        }
+
+        let from = config.from.parse()?;
+        let to = config
+            .to
+            .iter()
+            .map(|to| to.parse())
+            .collect::<Result<Vec<Mailbox>, _>>()?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+            to,
+        })
+    }
+}
+
+impl AlertNotifier for SmtpAlertNotifier {
+    type Error = SmtpAlertError;
+
+    #[tracing::instrument(name = "smtp_alert_notifier", skip_all)]
+    async fn notify(&self, alert: &Alert) -> Result<(), Self::Error> {
+        for to in self.to.iter() {
+            let message = Message::builder()
+                .from(self.from.clone())
+                .to(to.clone())
+                .subject(alert.subject())
+                .body(alert.to_string())?;
+
+            self.transport.send(message).await?;
+        }
+
+        tracing::debug!("Alert sent");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Alert, AlertNotifier};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookAlertError {
+    #[error("Failed to send alert to webhook: {0}")]
+    Send(#[source] reqwest::Error),
+    #[error("Webhook responded with an error: {0}")]
+    Status(#[source] reqwest::Error),
+}
+
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    subject: &'static str,
+    message: String,
+    #[serde(flatten)]
+    alert: &'a Alert,
+}
+
+/// Posts alerts as JSON to a webhook.
+#[derive(Debug, Clone)]
+pub struct WebhookAlertNotifier {
+    url: String,
+    http_client: reqwest::Client,
+}
+
+impl WebhookAlertNotifier {
+    pub fn new(config: WebhookConfig, http_client: reqwest::Client) -> Self {
+        Self {
+            url: config.url,
+            http_client,
+        }
+    }
+}
+
+impl AlertNotifier for WebhookAlertNotifier {
+    type Error = WebhookAlertError;
+
+    #[tracing::instrument(name = "webhook_alert_notifier", skip_all)]
+    async fn notify(&self, alert: &Alert) -> Result<(), Self::Error> {
+        let payload = WebhookPayload {
+            subject: alert.subject(),
+            message: alert.to_string(),
+            alert,
+        };
+
+        self.http_client
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(WebhookAlertError::Send)?
+            .error_for_status()
+            .map_err(WebhookAlertError::Status)?;
+
+        tracing::debug!("Alert sent");
+
+        Ok(())
+    }
+}
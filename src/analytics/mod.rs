@@ -0,0 +1,174 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+pub mod sink;
+
+/// Maximum number of latency samples kept per principal and route in one window.
+///
+/// Requests beyond this limit are still counted.
+const MAX_LATENCY_SAMPLES: usize = 10_000;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UsageAnalyticsConfig {
+    pub flush_interval_in_seconds: u64,
+}
+
+pub trait UsageAnalyticsProvider {
+    /// Records a handled request.
+    fn record_usage(&self, principal: Option<String>, route: String, latency: Duration);
+}
+
+/// Receives the usage rollups on every flush.
+pub trait UsageSink {
+    type Error;
+
+    fn flush(
+        &self,
+        rollups: &[UsageRollup],
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct UsageKey {
+    principal: Option<String>,
+    route: String,
+}
+
+#[derive(Debug, Default)]
+struct UsageStats {
+    count: u64,
+    latencies_in_micros: Vec<u64>,
+}
+
+impl UsageStats {
+    fn record(&mut self, latency: Duration) {
+        self.count += 1;
+
+        if self.latencies_in_micros.len() < MAX_LATENCY_SAMPLES {
+            self.latencies_in_micros
+                .push(latency.as_micros().try_into().unwrap_or(u64::MAX));
+        }
+    }
+
+    fn rollup(&self, key: &UsageKey) -> UsageRollup {
+        let mut latencies_in_micros = self.latencies_in_micros.clone();
+        latencies_in_micros.sort_unstable();
+
+        let percentile = |p: usize| match latencies_in_micros.len() {
+            0 => 0.0,
+            len => latencies_in_micros[(len - 1) * p / 100] as f64 / 1000.0,
+        };
+
+        UsageRollup {
+            principal: key.principal.clone(),
+            route: key.route.clone(),
+            count: self.count,
+            p50_in_millis: percentile(50),
+            p90_in_millis: percentile(90),
+            p99_in_millis: percentile(99),
+        }
+    }
+}
+
+/// Aggregated usage of one principal on one route.
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageRollup {
+    /// `None` for anonymous requests.
+    pub principal: Option<String>,
+    pub route: String,
+    pub count: u64,
+    pub p50_in_millis: f64,
+    pub p90_in_millis: f64,
+    pub p99_in_millis: f64,
+}
+
+/// Aggregates the usage in memory and flushes it periodically to the [`UsageSink`].
+pub struct UsageAnalytics<K> {
+    sink: K,
+    current: Mutex<HashMap<UsageKey, UsageStats>>,
+    last_flushed: Mutex<Vec<UsageRollup>>,
+}
+
+impl<K> UsageAnalytics<K>
+where
+    K: UsageSink + Send + Sync + 'static,
+    K::Error: Display,
+{
+    pub fn new(sink: K) -> Self {
+        Self {
+            sink,
+            current: Mutex::new(HashMap::new()),
+            last_flushed: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn record(&self, principal: Option<String>, route: String, latency: Duration) {
+        self.current
+            .lock()
+            .expect("usage mutex poisoned")
+            .entry(UsageKey { principal, route })
+            .or_default()
+            .record(latency);
+    }
+
+    /// Returns the rollups of the current window.
+    pub fn current_rollups(&self) -> Vec<UsageRollup> {
+        self.current
+            .lock()
+            .expect("usage mutex poisoned")
+            .iter()
+            .map(|(key, stats)| stats.rollup(key))
+            .collect()
+    }
+
+    /// Returns the rollups of the last flushed window.
+    pub fn last_flushed_rollups(&self) -> Vec<UsageRollup> {
+        self.last_flushed
+            .lock()
+            .expect("last flushed usage mutex poisoned")
+            .clone()
+    }
+
+    /// Ends the current window and sends its rollups to the sink.
+    #[tracing::instrument(skip_all)]
+    pub async fn flush(&self) {
+        let current = std::mem::take(&mut *self.current.lock().expect("usage mutex poisoned"));
+
+        let rollups = current
+            .iter()
+            .map(|(key, stats)| stats.rollup(key))
+            .collect::<Vec<_>>();
+
+        tracing::debug!(count = rollups.len(), "Flushing usage rollups");
+
+        if let Err(err) = self.sink.flush(&rollups).await {
+            tracing::error!(%err, "Failed to flush usage rollups");
+        }
+
+        *self
+            .last_flushed
+            .lock()
+            .expect("last flushed usage mutex poisoned") = rollups;
+    }
+
+    /// Spawns a task that flushes the usage every `interval`.
+    pub fn spawn_flush_task(self: Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                self.flush().await;
+            }
+        });
+    }
+}
@@ -0,0 +1,19 @@
+use std::convert::Infallible;
+
+use super::{UsageRollup, UsageSink};
+
+/// Logs the usage rollups.
+#[derive(Debug, Clone)]
+pub struct TracingUsageSink;
+
+impl UsageSink for TracingUsageSink {
+    type Error = Infallible;
+
+    async fn flush(&self, rollups: &[UsageRollup]) -> Result<(), Self::Error> {
+        for rollup in rollups {
+            tracing::info!(?rollup, "Usage");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+//! Audit trail of the calls to the API.
+//!
+//! Every call that claims an identity is recorded as an [`AuditEvent`] and sent to the configured [`AuditSink`].
+//! Recording happens in the background, so a slow sink does not delay the responses.
+
+use std::{fmt::Display, future::Future, net::IpAddr};
+
+use anyhow::Context;
+use axum::http::request::Parts;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sink::{FileAuditSink, TracingAuditSink, WebhookAuditSink};
+
+pub mod sink;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuditConfig {
+    #[serde(default)]
+    pub sink: AuditSinkConfig,
+    /// Requests whose path starts with one of these prefixes are not recorded.
+    #[serde(default = "default_excluded_path_prefixes")]
+    pub excluded_path_prefixes: Vec<String>,
+    /// Whether anonymous requests are skipped.
+    #[serde(default = "default_authenticated_only")]
+    pub authenticated_only: bool,
+}
+
+fn default_excluded_path_prefixes() -> Vec<String> {
+    vec![String::from("/health")]
+}
+
+fn default_authenticated_only() -> bool {
+    true
+}
+
+impl AuditConfig {
+    /// Returns whether requests to the path are recorded.
+    pub fn applies_to(&self, path: &str) -> bool {
+        !self.excluded_path_prefixes.iter().any(|prefix| {
+            let prefix = prefix.trim_end_matches('/');
+
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+}
+
+/// Where the audit events are sent to.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "type")]
+pub enum AuditSinkConfig {
+    /// Logged with the `audit` target.
+    #[default]
+    Tracing,
+    /// Appended as JSON lines to a file.
+    File { path: String },
+    /// Posted as JSON to a webhook.
+    Webhook { url: String },
+}
+
+/// An identity claimed by a request.
+///
+/// The identity is taken from the credentials as sent, whether they are valid is reflected by the status of the event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum AuditIdentity {
+    Basic {
+        username: String,
+    },
+    /// Identified by the [`api_key_digest`](crate::credentials::api_key_digest), so the key is never recorded.
+    ApiKey {
+        digest: String,
+    },
+    Bearer {
+        subject: Option<String>,
+    },
+}
+
+impl Display for AuditIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditIdentity::Basic { username } => write!(f, "basic:{username}"),
+            AuditIdentity::ApiKey { digest } => write!(f, "api_key:{digest}"),
+            AuditIdentity::Bearer { subject } => {
+                write!(f, "bearer:{}", subject.as_deref().unwrap_or("unknown"))
+            }
+        }
+    }
+}
+
+/// A recorded call to the API.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEvent {
+    pub timestamp: DateTime<Utc>,
+    pub request_id: Option<String>,
+    /// The first of the `identities`. `None` for anonymous requests.
+    pub principal: Option<String>,
+    pub identities: Vec<AuditIdentity>,
+    pub client_ip: Option<IpAddr>,
+    pub method: String,
+    /// The matched route, e.g. `/books/:id`.
+    pub route: String,
+    pub path: String,
+    pub status: u16,
+    pub latency_in_millis: f64,
+}
+
+pub trait AuditProvider {
+    /// Returns the identities claimed by the request.
+    fn audit_identities(&self, parts: &Parts) -> Vec<AuditIdentity>;
+
+    /// Records the event without blocking the response.
+    fn record_audit_event(&self, event: AuditEvent);
+}
+
+/// Receives the audit events.
+pub trait AuditSink {
+    type Error;
+
+    fn record(&self, event: &AuditEvent) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// The audit sink selected by the [`AuditSinkConfig`].
+pub enum ConfiguredAuditSink {
+    Tracing(TracingAuditSink),
+    File(FileAuditSink),
+    Webhook(WebhookAuditSink),
+}
+
+impl ConfiguredAuditSink {
+    pub async fn from_config(
+        config: &AuditSinkConfig,
+        http_client: reqwest::Client,
+    ) -> anyhow::Result<Self> {
+        match config {
+            AuditSinkConfig::Tracing => Ok(Self::Tracing(TracingAuditSink)),
+            AuditSinkConfig::File { path } => Ok(Self::File(
+                FileAuditSink::open(path)
+                    .await
+                    .context("Failed to open audit log file")?,
+            )),
+            AuditSinkConfig::Webhook { url } => Ok(Self::Webhook(WebhookAuditSink::new(
+                url.clone(),
+                http_client,
+            ))),
+        }
+    }
+}
+
+impl AuditSink for ConfiguredAuditSink {
+    type Error = anyhow::Error;
+
+    async fn record(&self, event: &AuditEvent) -> Result<(), Self::Error> {
+        match self {
+            Self::Tracing(sink) => sink.record(event).await.map_err(anyhow::Error::from),
+            Self::File(sink) => sink.record(event).await.map_err(anyhow::Error::from),
+            Self::Webhook(sink) => sink.record(event).await.map_err(anyhow::Error::from),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::{convert::Infallible, path::Path};
+
+use tokio::{fs::File, io::AsyncWriteExt, sync::Mutex};
+
+use super::{AuditEvent, AuditSink};
+
+/// Logs the audit events with the `audit` target.
+#[derive(Debug, Clone)]
+pub struct TracingAuditSink;
+
+impl AuditSink for TracingAuditSink {
+    type Error = Infallible;
+
+    async fn record(&self, event: &AuditEvent) -> Result<(), Self::Error> {
+        tracing::info!(target: "audit", ?event, "Audit");
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FileAuditError {
+    #[error("Failed to serialize audit event: {0}")]
+    Serialize(#[source] serde_json::Error),
+    #[error("Failed to write audit event: {0}")]
+    Write(#[source] std::io::Error),
+}
+
+/// Appends the audit events as JSON lines to a file.
+#[derive(Debug)]
+pub struct FileAuditSink {
+    file: Mutex<File>,
+}
+
+impl FileAuditSink {
+    /// Opens the file for appending and creates it if it does not exist.
+    pub async fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl AuditSink for FileAuditSink {
+    type Error = FileAuditError;
+
+    async fn record(&self, event: &AuditEvent) -> Result<(), Self::Error> {
+        let mut line = serde_json::to_vec(event).map_err(FileAuditError::Serialize)?;
+        line.push(b'\n');
+
+        let mut file = self.file.lock().await;
+
+        file.write_all(&line).await.map_err(FileAuditError::Write)?;
+        file.flush().await.map_err(FileAuditError::Write)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookAuditError {
+    #[error("Failed to send audit event to webhook: {0}")]
+    Send(#[source] reqwest::Error),
+    #[error("Webhook responded with an error: {0}")]
+    Status(#[source] reqwest::Error),
+}
+
+/// Posts the audit events as JSON to a webhook.
+#[derive(Debug, Clone)]
+pub struct WebhookAuditSink {
+    url: String,
+    http_client: reqwest::Client,
+}
+
+impl WebhookAuditSink {
+    pub fn new(url: String, http_client: reqwest::Client) -> Self {
+        Self { url, http_client }
+    }
+}
+
+impl AuditSink for WebhookAuditSink {
+    type Error = WebhookAuditError;
+
+    async fn record(&self, event: &AuditEvent) -> Result<(), Self::Error> {
+        self.http_client
+            .post(&self.url)
+            .json(event)
+            .send()
+            .await
+            .map_err(WebhookAuditError::Send)?
+            .error_for_status()
+            .map_err(WebhookAuditError::Status)?;
+
+        Ok(())
+    }
+}
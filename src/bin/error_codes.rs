@@ -0,0 +1,9 @@
+//! Prints the catalogue of the error codes as markdown.
+//!
+//! ```sh
+//! cargo run --bin error_codes > error_codes.md
+//! ```
+
+fn main() {
+    print!("{}", the_axum::error_codes::catalogue_markdown());
+}
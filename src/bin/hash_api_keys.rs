@@ -0,0 +1,49 @@
+//! Replaces the plaintext `api_keys` of a config file with salted hashes.
+//!
+//! Keys must have at least 32 characters. Their first 8 characters are kept as `prefix`,
+//! so only one hash is verified per request.
+//!
+//! The migrated config is written to stdout. Comments are not preserved.
+
+use anyhow::Context;
+use clap::Parser;
+use the_axum::types::stored_api_key::{ApiKeyHashAlgorithm, ConfiguredApiKey};
+
+#[derive(Parser)]
+#[command(author, about, version)]
+struct Args {
+    /// Path to the configuration file.
+    #[clap(long, env = "CONFIG_FILE", default_value = "config.yaml")]
+    config_file: String,
+    /// The algorithm used to hash the plaintext keys.
+    #[clap(long, value_enum, default_value_t = ApiKeyHashAlgorithm::Argon2)]
+    algorithm: ApiKeyHashAlgorithm,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let config =
+        std::fs::read_to_string(&args.config_file).context("Failed to read config file")?;
+    let mut config: serde_yaml::Value =
+        serde_yaml::from_str(&config).context("Failed to parse config file")?;
+
+    let api_keys = config
+        .get_mut("api_keys")
+        .context("Config file has no api_keys")?;
+
+    let hashed = serde_yaml::from_value::<Vec<ConfiguredApiKey>>(api_keys.clone())
+        .context("Failed to parse api_keys")?
+        .into_iter()
+        .map(|key| key.into_hashed(args.algorithm))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    *api_keys = serde_yaml::to_value(hashed).context("Failed to serialize api_keys")?;
+
+    print!(
+        "{}",
+        serde_yaml::to_string(&config).context("Failed to serialize config")?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+//! Hashes a basic auth password read from stdin for the `password_hash` of `basic_auth_users`.
+
+use std::io::BufRead;
+
+use anyhow::Context;
+use clap::Parser;
+use the_axum::types::used_basic_auth::{hash_password, PasswordHashAlgorithm};
+
+#[derive(Parser)]
+#[command(author, about, version)]
+struct Args {
+    /// The algorithm used to hash the password.
+    #[clap(long, value_enum, default_value_t = PasswordHashAlgorithm::Argon2)]
+    algorithm: PasswordHashAlgorithm,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let mut password = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut password)
+        .context("Failed to read password from stdin")?;
+
+    let password = password.trim_end_matches(['\r', '\n']);
+    anyhow::ensure!(!password.is_empty(), "Password is empty");
+
+    println!("{}", hash_password(args.algorithm, password)?);
+
+    Ok(())
+}
@@ -0,0 +1,232 @@
+//! Tracing of request and response bodies.
+//!
+//! Bodies are only read if the `trace` level is enabled, the request is sampled and the content type is traced.
+//! Fields of JSON and form bodies with a redacted name are replaced before the body is logged.
+
+use axum::http::{header::CONTENT_TYPE, Extensions, HeaderMap};
+use rand::Rng;
+use serde::Deserialize;
+use serde_json::Value;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Captured bodies up to this size are parsed and redacted before they are truncated to `max_logged_bytes`.
+const MAX_PARSED_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BodyTraceConfig {
+    #[serde(default = "default_true")]
+    pub trace_requests: bool,
+    #[serde(default = "default_true")]
+    pub trace_responses: bool,
+    /// Longer bodies are truncated.
+    #[serde(default = "default_max_logged_bytes")]
+    pub max_logged_bytes: usize,
+    /// Only bodies whose content type starts with one of these are traced.
+    #[serde(default = "default_content_types")]
+    pub content_types: Vec<String>,
+    /// Names of the JSON and form fields whose values are redacted, compared case insensitively.
+    #[serde(default = "default_redacted_fields")]
+    pub redacted_fields: Vec<String>,
+    /// Fraction of the requests whose bodies are traced, between `0.0` and `1.0`.
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: f64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_logged_bytes() -> usize {
+    4096
+}
+
+fn default_content_types() -> Vec<String> {
+    vec![
+        String::from("application/json"),
+        String::from("application/x-www-form-urlencoded"),
+        String::from("text/"),
+    ]
+}
+
+fn default_redacted_fields() -> Vec<String> {
+    [
+        "password",
+        "token",
+        "access_token",
+        "refresh_token",
+        "id_token",
+        "client_secret",
+        "secret",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_sample_rate() -> f64 {
+    1.0
+}
+
+impl Default for BodyTraceConfig {
+    fn default() -> Self {
+        Self {
+            trace_requests: default_true(),
+            trace_responses: default_true(),
+            max_logged_bytes: default_max_logged_bytes(),
+            content_types: default_content_types(),
+            redacted_fields: default_redacted_fields(),
+            sample_rate: default_sample_rate(),
+        }
+    }
+}
+
+/// Whether the bodies of a request are traced, decided once per request and kept as a request extension.
+#[derive(Debug, Clone, Copy)]
+struct BodyTraceSampled(bool);
+
+impl BodyTraceConfig {
+    /// Returns whether the bodies of the request are traced.
+    ///
+    /// The request and response body middlewares share the decision through the request extensions.
+    pub fn is_sampled(&self, extensions: &mut Extensions) -> bool {
+        if !tracing::enabled!(tracing::Level::TRACE) {
+            return false;
+        }
+
+        if let Some(BodyTraceSampled(sampled)) = extensions.get::<BodyTraceSampled>() {
+            return *sampled;
+        }
+
+        let sampled = rand::thread_rng().gen_bool(self.sample_rate.clamp(0.0, 1.0));
+
+        extensions.insert(BodyTraceSampled(sampled));
+
+        sampled
+    }
+
+    /// Returns whether bodies with the content type of the headers are traced.
+    pub fn traces_content_type(&self, headers: &HeaderMap) -> bool {
+        let Some(content_type) = headers
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+        else {
+            return false;
+        };
+
+        self.content_types
+            .iter()
+            .any(|traced| content_type.starts_with(traced.as_str()))
+    }
+
+    fn is_redacted(&self, field: &str) -> bool {
+        self.redacted_fields
+            .iter()
+            .any(|redacted| redacted.eq_ignore_ascii_case(field))
+    }
+
+    fn redact_json(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if self.is_redacted(key) {
+                        *value = Value::String(String::from(REDACTED));
+                    } else {
+                        self.redact_json(value);
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|value| self.redact_json(value)),
+            _ => {}
+        }
+    }
+
+    fn redact_form(&self, body: &[u8]) -> String {
+        let mut serializer = form_urlencoded::Serializer::new(String::new());
+
+        for (key, value) in form_urlencoded::parse(body) {
+            if self.is_redacted(&key) {
+                serializer.append_pair(&key, REDACTED);
+            } else {
+                serializer.append_pair(&key, &value);
+            }
+        }
+
+        serializer.finish()
+    }
+
+    /// Returns how many bytes of a streamed body are captured for [`render_captured`](Self::render_captured).
+    pub fn max_captured_bytes(&self) -> usize {
+        self.max_logged_bytes.max(MAX_PARSED_BYTES)
+    }
+
+    /// Redacts and truncates the captured beginning of a body that was `total_bytes` long.
+    ///
+    /// Cut off JSON can not be parsed, so it is logged as a marker with its size instead.
+    /// Returns `None` if the body is not valid UTF-8.
+    pub fn render_captured(
+        &self,
+        headers: &HeaderMap,
+        captured: &[u8],
+        total_bytes: usize,
+    ) -> Option<String> {
+        if total_bytes > captured.len() && content_type(headers).starts_with("application/json") {
+            return Some(format!("[JSON BODY OF {total_bytes} BYTES]"));
+        }
+
+        self.render(headers, captured)
+    }
+
+    /// Redacts and truncates the body for logging.
+    ///
+    /// Returns `None` if the body is not valid UTF-8.
+    pub fn render(&self, headers: &HeaderMap, body: &[u8]) -> Option<String> {
+        let content_type = content_type(headers);
+
+        let rendered = if content_type.starts_with("application/json") {
+            match serde_json::from_slice::<Value>(body) {
+                Ok(mut value) => {
+                    self.redact_json(&mut value);
+
+                    value.to_string()
+                }
+                // Invalid JSON may still contain secrets.
+                Err(_) => String::from("[INVALID JSON]"),
+            }
+        } else if content_type.starts_with("application/x-www-form-urlencoded") {
+            self.redact_form(body)
+        } else {
+            std::str::from_utf8(body).ok()?.to_owned()
+        };
+
+        Some(self.truncate(rendered))
+    }
+
+    fn truncate(&self, mut rendered: String) -> String {
+        if rendered.len() <= self.max_logged_bytes {
+            return rendered;
+        }
+
+        let mut end = self.max_logged_bytes;
+        while !rendered.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        let truncated = rendered.len() - end;
+        rendered.truncate(end);
+        rendered.push_str(&format!("... ({truncated} bytes truncated)"));
+
+        rendered
+    }
+}
+
+fn content_type(headers: &HeaderMap) -> &str {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .unwrap_or_default()
+}
+
+pub trait BodyTraceProvider {
+    fn body_trace_config(&self) -> &BodyTraceConfig;
+}
@@ -0,0 +1,36 @@
+//! Counting of the panics caught by the [`CatchPanicLayer`](crate::middleware::catch_panic::layer::CatchPanicLayer).
+
+use std::{
+    any::Any,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Counts the caught panics.
+#[derive(Debug, Default)]
+pub struct PanicCounter {
+    count: AtomicU64,
+}
+
+impl PanicCounter {
+    pub fn increment(&self) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+pub trait PanicCounterProvider {
+    fn panic_counter(&self) -> &PanicCounter;
+}
+
+/// Returns the message of a panic payload.
+///
+/// Panics with `panic!("...")` carry a `&str` or a `String`, other payloads have no message.
+pub fn panic_message(payload: &(dyn Any + Send)) -> Option<&str> {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::extractor::jwt_roles::HasRoles;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub email_verified: bool,
     pub name: String,
@@ -8,4 +10,27 @@ pub struct Claims {
     pub given_name: String,
     pub family_name: String,
     pub email: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub realm_access: RealmAccess,
+    /// Space-separated scopes.
+    #[serde(default)]
+    pub scope: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RealmAccess {
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+impl HasRoles for Claims {
+    fn has_role(&self, role: &str) -> bool {
+        self.roles
+            .iter()
+            .chain(self.realm_access.roles.iter())
+            .any(|claimed| claimed == role)
+            || self.scope.split_whitespace().any(|scope| scope == role)
+    }
 }
@@ -0,0 +1,67 @@
+//! Compression of the responses and decompression of the requests.
+
+use serde::Deserialize;
+use tower_http::{compression::CompressionLayer, decompression::RequestDecompressionLayer};
+
+/// A content coding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum CompressionAlgorithm {
+    Gzip,
+    Brotli,
+    Zstd,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompressionConfig {
+    /// Algorithms responses are compressed with, if the client accepts them.
+    #[serde(default = "default_algorithms")]
+    pub algorithms: Vec<CompressionAlgorithm>,
+    /// Algorithms requests may be compressed with. Other content codings are rejected with a `415 Unsupported Media Type`.
+    #[serde(default = "default_algorithms")]
+    pub decompression_algorithms: Vec<CompressionAlgorithm>,
+    /// Decompressed request bodies exceeding this size are rejected, so small compressed bodies can not expand without bound.
+    #[serde(default = "default_max_decompressed_size_in_bytes")]
+    pub max_decompressed_size_in_bytes: usize,
+}
+
+fn default_algorithms() -> Vec<CompressionAlgorithm> {
+    vec![
+        CompressionAlgorithm::Gzip,
+        CompressionAlgorithm::Brotli,
+        CompressionAlgorithm::Zstd,
+    ]
+}
+
+fn default_max_decompressed_size_in_bytes() -> usize {
+    16 * 1024 * 1024
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            algorithms: default_algorithms(),
+            decompression_algorithms: default_algorithms(),
+            max_decompressed_size_in_bytes: default_max_decompressed_size_in_bytes(),
+        }
+    }
+}
+
+impl CompressionConfig {
+    pub fn compression_layer(&self) -> CompressionLayer {
+        let enabled = |algorithm| self.algorithms.contains(&algorithm);
+
+        CompressionLayer::new()
+            .gzip(enabled(CompressionAlgorithm::Gzip))
+            .br(enabled(CompressionAlgorithm::Brotli))
+            .zstd(enabled(CompressionAlgorithm::Zstd))
+    }
+
+    pub fn decompression_layer(&self) -> RequestDecompressionLayer {
+        let enabled = |algorithm| self.decompression_algorithms.contains(&algorithm);
+
+        RequestDecompressionLayer::new()
+            .gzip(enabled(CompressionAlgorithm::Gzip))
+            .br(enabled(CompressionAlgorithm::Brotli))
+            .zstd(enabled(CompressionAlgorithm::Zstd))
+    }
+}
@@ -0,0 +1,148 @@
+//! Limits the number of requests that are handled at the same time, for the whole server and per route.
+//!
+//! Requests over a limit are shed right away instead of being queued.
+
+use std::{cmp::Reverse, sync::Arc, time::Duration};
+
+use serde::Deserialize;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RouteConcurrencyLimit {
+    /// Requests whose path starts with this prefix count against the limit.
+    pub path_prefix: String,
+    pub max_in_flight_requests: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConcurrencyLimitConfig {
+    /// The limit of the whole server. Unlimited if not set.
+    pub max_in_flight_requests: Option<usize>,
+    #[serde(default)]
+    pub routes: Vec<RouteConcurrencyLimit>,
+    /// Sent in the `Retry-After` header of shed requests.
+    #[serde(default = "default_retry_after_in_seconds")]
+    pub retry_after_in_seconds: u64,
+}
+
+fn default_retry_after_in_seconds() -> u64 {
+    1
+}
+
+impl Default for ConcurrencyLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight_requests: None,
+            routes: Vec::new(),
+            retry_after_in_seconds: default_retry_after_in_seconds(),
+        }
+    }
+}
+
+struct Limit {
+    max_in_flight_requests: usize,
+    semaphore: Arc<Semaphore>,
+}
+
+impl Limit {
+    fn new(max_in_flight_requests: usize) -> Self {
+        Self {
+            max_in_flight_requests,
+            semaphore: Arc::new(Semaphore::new(max_in_flight_requests)),
+        }
+    }
+
+    fn try_acquire(&self, retry_after: Duration) -> Result<OwnedSemaphorePermit, Overloaded> {
+        self.semaphore
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| Overloaded {
+                limit: self.max_in_flight_requests,
+                retry_after,
+            })
+    }
+}
+
+/// Holds the slots of a request until it is dropped.
+pub struct ConcurrencyPermit {
+    _global: Option<OwnedSemaphorePermit>,
+    _route: Option<OwnedSemaphorePermit>,
+}
+
+/// A request that was shed because a limit was reached.
+#[derive(Debug, Clone, Copy)]
+pub struct Overloaded {
+    /// The limit that was reached.
+    pub limit: usize,
+    pub retry_after: Duration,
+}
+
+pub struct ConcurrencyLimiter {
+    global: Option<Limit>,
+    /// Sorted by descending prefix length, so the most specific route is found first.
+    routes: Vec<(String, Limit)>,
+    retry_after: Duration,
+}
+
+impl ConcurrencyLimiter {
+    /// Creates a limiter with the given limit of the whole server, or no limit if `None`.
+    pub fn new(max_in_flight_requests: Option<usize>, retry_after: Duration) -> Self {
+        Self {
+            global: max_in_flight_requests.map(Limit::new),
+            routes: Vec::new(),
+            retry_after,
+        }
+    }
+
+    /// Limits the requests whose path starts with `path_prefix`.
+    ///
+    /// Only the limit of the longest matching prefix applies to a request.
+    pub fn route(mut self, path_prefix: impl Into<String>, max_in_flight_requests: usize) -> Self {
+        let path_prefix = path_prefix.into().trim_end_matches('/').to_owned();
+
+        self.routes
+            .push((path_prefix, Limit::new(max_in_flight_requests)));
+        self.routes.sort_by_key(|(prefix, _)| Reverse(prefix.len()));
+
+        self
+    }
+
+    pub fn from_config(config: ConcurrencyLimitConfig) -> Self {
+        config.routes.into_iter().fold(
+            Self::new(
+                config.max_in_flight_requests,
+                Duration::from_secs(config.retry_after_in_seconds),
+            ),
+            |limiter, route| limiter.route(route.path_prefix, route.max_in_flight_requests),
+        )
+    }
+
+    fn route_limit(&self, path: &str) -> Option<&Limit> {
+        self.routes.iter().find_map(|(prefix, limit)| {
+            let rest = path.strip_prefix(prefix.as_str())?;
+
+            (rest.is_empty() || rest.starts_with('/')).then_some(limit)
+        })
+    }
+
+    /// Takes a slot of the global limit and of the route limit of the path.
+    ///
+    /// The route limit is checked first, so a busy route does not take up slots of the whole server.
+    pub fn try_acquire(&self, path: &str) -> Result<ConcurrencyPermit, Overloaded> {
+        let route = self
+            .route_limit(path)
+            .map(|limit| limit.try_acquire(self.retry_after))
+            .transpose()?;
+
+        let global = self
+            .global
+            .as_ref()
+            .map(|limit| limit.try_acquire(self.retry_after))
+            .transpose()?;
+
+        Ok(ConcurrencyPermit {
+            _global: global,
+            _route: route,
+        })
+    }
+}
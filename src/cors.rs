@@ -0,0 +1,186 @@
+//! Builds the [`CorsLayer`] from the `cors` section of the config.
+
+use std::time::Duration;
+
+use axum::http::{header::InvalidHeaderValue, request::Parts, HeaderName, HeaderValue, Method};
+use serde::Deserialize;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer, ExposeHeaders};
+
+const WILDCARD: &str = "*";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CorsConfig {
+    /// Exact origins, `*` for any origin, or patterns with a single `*` matching a subdomain, e.g. `https://*.example.com`.
+    pub allowed_origins: Vec<String>,
+    /// Method names or `*` for any method.
+    #[serde(default = "default_allowed_methods")]
+    pub allowed_methods: Vec<String>,
+    /// Header names or `*` for any header.
+    #[serde(default)]
+    pub allowed_headers: Vec<String>,
+    #[serde(default)]
+    pub exposed_headers: Vec<String>,
+    #[serde(default)]
+    pub allow_credentials: bool,
+    pub max_age_in_seconds: Option<u64>,
+}
+
+fn default_allowed_methods() -> Vec<String> {
+    ["GET", "HEAD", "POST"].map(String::from).to_vec()
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CorsConfigError {
+    #[error("Invalid origin {origin}: {err}")]
+    InvalidOrigin {
+        origin: String,
+        #[source]
+        err: InvalidHeaderValue,
+    },
+    #[error("Invalid origin pattern {0}: only a single `*` is allowed")]
+    InvalidOriginPattern(String),
+    #[error("Invalid method {0}")]
+    InvalidMethod(String),
+    #[error("Invalid header name {0}")]
+    InvalidHeaderName(String),
+    #[error("Credentials can not be allowed for any {0}")]
+    WildcardWithCredentials(&'static str),
+}
+
+/// An allowed origin.
+#[derive(Debug, Clone)]
+enum OriginPattern {
+    Exact(HeaderValue),
+    /// The parts before and after the `*`.
+    Wildcard {
+        prefix: String,
+        suffix: String,
+    },
+}
+
+impl OriginPattern {
+    fn parse(origin: &str) -> Result<Self, CorsConfigError> {
+        match origin.split_once(WILDCARD) {
+            None => HeaderValue::from_str(origin)
+                .map(Self::Exact)
+                .map_err(|err| CorsConfigError::InvalidOrigin {
+                    origin: origin.to_owned(),
+                    err,
+                }),
+            Some((_, suffix)) if suffix.contains(WILDCARD) => {
+                Err(CorsConfigError::InvalidOriginPattern(origin.to_owned()))
+            }
+            Some((prefix, suffix)) => Ok(Self::Wildcard {
+                prefix: prefix.to_owned(),
+                suffix: suffix.to_owned(),
+            }),
+        }
+    }
+
+    fn matches(&self, origin: &HeaderValue) -> bool {
+        match self {
+            Self::Exact(exact) => exact == origin,
+            Self::Wildcard { prefix, suffix } => {
+                let Ok(origin) = origin.to_str() else {
+                    return false;
+                };
+
+                // The `*` only matches subdomain labels, so it can not be used to smuggle in a different host.
+                origin
+                    .strip_prefix(prefix.as_str())
+                    .and_then(|rest| rest.strip_suffix(suffix.as_str()))
+                    .is_some_and(|matched| {
+                        !matched.is_empty()
+                            && matched
+                                .bytes()
+                                .all(|byte| byte.is_ascii_alphanumeric() || b"-.".contains(&byte))
+                    })
+            }
+        }
+    }
+}
+
+fn is_wildcard(values: &[String]) -> bool {
+    values.iter().any(|value| value == WILDCARD)
+}
+
+fn header_names(names: &[String]) -> Result<Vec<HeaderName>, CorsConfigError> {
+    names
+        .iter()
+        .map(|name| {
+            HeaderName::try_from(name.as_str())
+                .map_err(|_| CorsConfigError::InvalidHeaderName(name.clone()))
+        })
+        .collect()
+}
+
+impl CorsConfig {
+    /// Validates the config and builds the layer.
+    pub fn into_layer(self) -> Result<CorsLayer, CorsConfigError> {
+        let any_origin = is_wildcard(&self.allowed_origins);
+        let any_method = is_wildcard(&self.allowed_methods);
+        let any_header = is_wildcard(&self.allowed_headers);
+
+        if self.allow_credentials {
+            let wildcard = [
+                (any_origin, "origin"),
+                (any_method, "method"),
+                (any_header, "header"),
+            ]
+            .into_iter()
+            .find_map(|(is_wildcard, name)| is_wildcard.then_some(name));
+
+            if let Some(wildcard) = wildcard {
+                return Err(CorsConfigError::WildcardWithCredentials(wildcard));
+            }
+        }
+
+        let allow_origin = if any_origin {
+            AllowOrigin::any()
+        } else {
+            let patterns = self
+                .allowed_origins
+                .iter()
+                .map(|origin| OriginPattern::parse(origin))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            AllowOrigin::predicate(move |origin: &HeaderValue, _: &Parts| {
+                patterns.iter().any(|pattern| pattern.matches(origin))
+            })
+        };
+
+        let allow_methods = if any_method {
+            AllowMethods::any()
+        } else {
+            let methods = self
+                .allowed_methods
+                .iter()
+                .map(|method| {
+                    Method::from_bytes(method.as_bytes())
+                        .map_err(|_| CorsConfigError::InvalidMethod(method.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            AllowMethods::list(methods)
+        };
+
+        let allow_headers = if any_header {
+            AllowHeaders::any()
+        } else {
+            AllowHeaders::list(header_names(&self.allowed_headers)?)
+        };
+
+        let mut layer = CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(allow_methods)
+            .allow_headers(allow_headers)
+            .expose_headers(ExposeHeaders::list(header_names(&self.exposed_headers)?))
+            .allow_credentials(self.allow_credentials);
+
+        if let Some(max_age_in_seconds) = self.max_age_in_seconds {
+            layer = layer.max_age(Duration::from_secs(max_age_in_seconds));
+        }
+
+        Ok(layer)
+    }
+}
@@ -0,0 +1,276 @@
+//! Stores for API keys and basic auth users.
+//!
+//! By default the credentials are read from the `api_keys` and `basic_auth_users` of the config file.
+//! With the `credentials-sqlx` or `credentials-redis` features they can be read from a database instead.
+//!
+//! External stores look API keys up by their [`api_key_digest`], so the plaintext keys are never stored.
+
+#[cfg(feature = "credentials-redis")]
+pub mod redis_store;
+#[cfg(feature = "credentials-sqlx")]
+pub mod sqlx_store;
+
+use std::collections::HashSet;
+
+use derivative::Derivative;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    extractor::{api_key::ApiKeyProviderError, basic_auth::BasicAuthProviderError},
+    types::{
+        stored_api_key::{ConfiguredApiKey, StoredApiKey},
+        used_api_key::KeyInfo,
+        used_basic_auth::ConfiguredBasicAuthUser,
+    },
+};
+
+/// Where API keys and basic auth users are read from.
+#[derive(Derivative, Clone, Default, Deserialize)]
+#[derivative(Debug)]
+#[serde(tag = "type")]
+pub enum CredentialStoreConfig {
+    /// The `api_keys` and `basic_auth_users` of the config file.
+    #[default]
+    Config,
+    /// A Postgres database with the `api_keys` and `basic_auth_users` tables.
+    #[cfg(feature = "credentials-sqlx")]
+    Sqlx {
+        #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+        url: String,
+        #[serde(default = "default_max_connections")]
+        max_connections: u32,
+    },
+    /// A Redis instance with `api_key:{digest}` and `basic_auth_user:{username}` keys.
+    #[cfg(feature = "credentials-redis")]
+    Redis {
+        #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+        url: String,
+    },
+}
+
+#[cfg(feature = "credentials-sqlx")]
+fn default_max_connections() -> u32 {
+    5
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CredentialStoreError {
+    #[cfg(feature = "credentials-sqlx")]
+    #[error("Database error: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[cfg(feature = "credentials-redis")]
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+    #[cfg(feature = "credentials-redis")]
+    #[error("Failed to deserialize credential: {0}")]
+    Data(#[from] serde_json::Error),
+    #[error("Hashed API keys share the prefix {0}")]
+    DuplicateApiKeyPrefix(String),
+    #[error("Failed to verify API key: {0}")]
+    Verify(#[from] tokio::task::JoinError),
+}
+
+/// Returns the hex encoded SHA-256 digest external stores use to look up the given API key.
+pub fn api_key_digest(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// The credential store selected by the [`CredentialStoreConfig`].
+///
+/// Created once and kept in the state, so the size difference between the variants does not matter.
+#[allow(clippy::large_enum_variant)]
+pub enum ConfiguredCredentialStore {
+    Config {
+        api_keys: Vec<ConfiguredApiKey>,
+        basic_auth_users: Vec<ConfiguredBasicAuthUser>,
+    },
+    #[cfg(feature = "credentials-sqlx")]
+    Sqlx {
+        api_keys: sqlx_store::SqlxApiKeyProvider,
+        basic_auth: sqlx_store::SqlxBasicAuthProvider,
+    },
+    #[cfg(feature = "credentials-redis")]
+    Redis {
+        api_keys: redis_store::RedisApiKeyProvider,
+        basic_auth: redis_store::RedisBasicAuthProvider,
+    },
+}
+
+impl ConfiguredCredentialStore {
+    /// Connects to the configured store.
+    ///
+    /// The `api_keys` and `basic_auth_users` are only used by [`CredentialStoreConfig::Config`].
+    #[cfg_attr(
+        not(any(feature = "credentials-sqlx", feature = "credentials-redis")),
+        allow(unused_variables)
+    )]
+    pub async fn from_config(
+        config: &CredentialStoreConfig,
+        api_key_header_name: &str,
+        api_keys: Vec<ConfiguredApiKey>,
+        basic_auth_users: Vec<ConfiguredBasicAuthUser>,
+    ) -> Result<Self, CredentialStoreError> {
+        match config {
+            CredentialStoreConfig::Config => {
+                let mut prefixes = HashSet::new();
+
+                for prefix in api_keys.iter().filter_map(|key| key.stored().prefix()) {
+                    if !prefixes.insert(prefix) {
+                        return Err(CredentialStoreError::DuplicateApiKeyPrefix(
+                            prefix.to_owned(),
+                        ));
+                    }
+                }
+
+                Ok(Self::Config {
+                    api_keys,
+                    basic_auth_users,
+                })
+            }
+            #[cfg(feature = "credentials-sqlx")]
+            CredentialStoreConfig::Sqlx {
+                url,
+                max_connections,
+            } => {
+                let pool = sqlx::postgres::PgPoolOptions::new()
+                    .max_connections(*max_connections)
+                    .connect(url)
+                    .await?;
+
+                Ok(Self::Sqlx {
+                    api_keys: sqlx_store::SqlxApiKeyProvider::new(
+                        pool.clone(),
+                        api_key_header_name.to_owned(),
+                    ),
+                    basic_auth: sqlx_store::SqlxBasicAuthProvider::new(pool),
+                })
+            }
+            #[cfg(feature = "credentials-redis")]
+            CredentialStoreConfig::Redis { url } => {
+                let client = redis::Client::open(url.as_str())?;
+                let connection = redis::aio::ConnectionManager::new(client).await?;
+
+                Ok(Self::Redis {
+                    api_keys: redis_store::RedisApiKeyProvider::new(
+                        connection.clone(),
+                        api_key_header_name.to_owned(),
+                    ),
+                    basic_auth: redis_store::RedisBasicAuthProvider::new(connection),
+                })
+            }
+        }
+    }
+
+    /// Validates the API key and returns its metadata.
+    ///
+    /// Hashed keys of the config are looked up by their unique prefix,
+    /// so at most one hash is verified, on the blocking thread pool.
+    pub async fn validate_api_key(
+        &self,
+        key: &str,
+    ) -> Result<KeyInfo, ApiKeyProviderError<CredentialStoreError>> {
+        match self {
+            Self::Config { api_keys, .. } => {
+                if let Some(info) = api_keys
+                    .iter()
+                    .filter(|valid_key| matches!(valid_key.stored(), StoredApiKey::Plaintext(_)))
+                    .find_map(|valid_key| valid_key.verify(key))
+                {
+                    return Ok(info);
+                }
+
+                let Some(hashed_key) = api_keys.iter().find(|valid_key| {
+                    valid_key
+                        .stored()
+                        .prefix()
+                        .is_some_and(|prefix| key.starts_with(prefix))
+                }) else {
+                    return Err(ApiKeyProviderError::Invalid);
+                };
+
+                let hashed_key = hashed_key.clone();
+                let key = key.to_owned();
+
+                tokio::task::spawn_blocking(move || hashed_key.verify(&key))
+                    .await
+                    .map_err(CredentialStoreError::from)?
+                    .ok_or(ApiKeyProviderError::Invalid)
+            }
+            #[cfg(feature = "credentials-sqlx")]
+            Self::Sqlx { api_keys, .. } => {
+                crate::extractor::api_key::ApiKeyProvider::validate(api_keys, key).await
+            }
+            #[cfg(feature = "credentials-redis")]
+            Self::Redis { api_keys, .. } => {
+                crate::extractor::api_key::ApiKeyProvider::validate(api_keys, key).await
+            }
+        }
+    }
+
+    /// Authenticates the basic auth user.
+    pub async fn authenticate(
+        &self,
+        username: &str,
+        password: Option<&str>,
+    ) -> Result<(), BasicAuthProviderError<CredentialStoreError>> {
+        match self {
+            Self::Config {
+                basic_auth_users, ..
+            } => {
+                let authenticated = basic_auth_users.iter().any(|valid_user| {
+                    valid_user.username == username && valid_user.verify(password)
+                });
+
+                if authenticated {
+                    return Ok(());
+                }
+
+                Err(BasicAuthProviderError::Unauthenticated)
+            }
+            #[cfg(feature = "credentials-sqlx")]
+            Self::Sqlx { basic_auth, .. } => {
+                crate::extractor::basic_auth::BasicAuthProvider::authenticate(
+                    basic_auth, username, password,
+                )
+                .await
+            }
+            #[cfg(feature = "credentials-redis")]
+            Self::Redis { basic_auth, .. } => {
+                crate::extractor::basic_auth::BasicAuthProvider::authenticate(
+                    basic_auth, username, password,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Returns the plaintext password of the given user.
+    ///
+    /// Only users of the config file without a `password_hash` have one.
+    pub fn plaintext_password(&self, username: &str) -> Option<String> {
+        match self {
+            Self::Config {
+                basic_auth_users, ..
+            } => basic_auth_users
+                .iter()
+                .find(|user| user.username == username && user.password_hash.is_none())
+                .and_then(|user| user.password.clone()),
+            #[cfg(feature = "credentials-sqlx")]
+            Self::Sqlx { .. } => None,
+            #[cfg(feature = "credentials-redis")]
+            Self::Redis { .. } => None,
+        }
+    }
+
+    /// Checks that the store is reachable.
+    pub async fn health_check(&self) -> Result<(), CredentialStoreError> {
+        match self {
+            Self::Config { .. } => Ok(()),
+            #[cfg(feature = "credentials-sqlx")]
+            Self::Sqlx { api_keys, .. } => api_keys.health_check().await,
+            #[cfg(feature = "credentials-redis")]
+            Self::Redis { api_keys, .. } => api_keys.health_check().await,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Credentials in Redis.
+//!
+//! API keys are stored as the JSON encoded [`KeyInfo`] under `api_key:{digest}`.
+//! Basic auth users are stored as `{"password": "..."}` or `{"password_hash": "..."}` under `basic_auth_user:{username}`.
+
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::Deserialize;
+
+use crate::{
+    extractor::{
+        api_key::{ApiKeyProvider, ApiKeyProviderError},
+        basic_auth::{BasicAuthProvider, BasicAuthProviderError},
+    },
+    types::{used_api_key::KeyInfo, used_basic_auth::verify_password},
+};
+
+use super::{api_key_digest, CredentialStoreError};
+
+const API_KEY_PREFIX: &str = "api_key:";
+const BASIC_AUTH_USER_PREFIX: &str = "basic_auth_user:";
+
+/// Looks API keys up under `api_key:{digest}`.
+#[derive(Clone)]
+pub struct RedisApiKeyProvider {
+    connection: ConnectionManager,
+    header_name: String,
+}
+
+impl RedisApiKeyProvider {
+    pub fn new(connection: ConnectionManager, header_name: String) -> Self {
+        Self {
+            connection,
+            header_name,
+        }
+    }
+
+    pub async fn health_check(&self) -> Result<(), CredentialStoreError> {
+        redis::cmd("PING")
+            .query_async::<()>(&mut self.connection.clone())
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl ApiKeyProvider for RedisApiKeyProvider {
+    type Error = CredentialStoreError;
+
+    fn header_name(&self) -> &str {
+        &self.header_name
+    }
+
+    async fn validate(&self, key: &str) -> Result<KeyInfo, ApiKeyProviderError<Self::Error>> {
+        let info: Option<String> = self
+            .connection
+            .clone()
+            .get(format!("{API_KEY_PREFIX}{}", api_key_digest(key)))
+            .await
+            .map_err(CredentialStoreError::from)?;
+
+        let Some(info) = info else {
+            return Err(ApiKeyProviderError::Invalid);
+        };
+
+        Ok(serde_json::from_str(&info).map_err(CredentialStoreError::from)?)
+    }
+}
+
+#[derive(Deserialize)]
+struct StoredBasicAuthUser {
+    #[serde(default)]
+    password: Option<String>,
+    #[serde(default)]
+    password_hash: Option<String>,
+}
+
+/// Looks basic auth users up under `basic_auth_user:{username}`.
+#[derive(Clone)]
+pub struct RedisBasicAuthProvider {
+    connection: ConnectionManager,
+}
+
+impl RedisBasicAuthProvider {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self { connection }
+    }
+}
+
+impl BasicAuthProvider for RedisBasicAuthProvider {
+    type Error = CredentialStoreError;
+
+    async fn authenticate(
+        &self,
+        username: &str,
+        password: Option<&str>,
+    ) -> Result<(), BasicAuthProviderError<Self::Error>> {
+        let user: Option<String> = self
+            .connection
+            .clone()
+            .get(format!("{BASIC_AUTH_USER_PREFIX}{username}"))
+            .await
+            .map_err(CredentialStoreError::from)?;
+
+        let Some(user) = user else {
+            return Err(BasicAuthProviderError::Unauthenticated);
+        };
+
+        let user: StoredBasicAuthUser =
+            serde_json::from_str(&user).map_err(CredentialStoreError::from)?;
+
+        if verify_password(
+            user.password.as_deref(),
+            user.password_hash.as_deref(),
+            password,
+        ) {
+            return Ok(());
+        }
+
+        Err(BasicAuthProviderError::Unauthenticated)
+    }
+}
@@ -0,0 +1,145 @@
+//! Credentials in a Postgres database.
+//!
+//! ```sql
+//! CREATE TABLE api_keys (
+//!     key_digest TEXT PRIMARY KEY,
+//!     owner TEXT,
+//!     scopes TEXT[] NOT NULL DEFAULT '{}',
+//!     expires_at TIMESTAMPTZ,
+//!     rate_limit_requests BIGINT,
+//!     rate_limit_window_in_seconds BIGINT
+//! );
+//!
+//! CREATE TABLE basic_auth_users (
+//!     username TEXT PRIMARY KEY,
+//!     password TEXT,
+//!     password_hash TEXT
+//! );
+//! ```
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+
+use crate::{
+    extractor::{
+        api_key::{ApiKeyProvider, ApiKeyProviderError},
+        basic_auth::{BasicAuthProvider, BasicAuthProviderError},
+    },
+    types::{
+        used_api_key::{KeyInfo, KeyRateLimit},
+        used_basic_auth::verify_password,
+    },
+};
+
+use super::{api_key_digest, CredentialStoreError};
+
+#[derive(FromRow)]
+struct ApiKeyRow {
+    owner: Option<String>,
+    scopes: Vec<String>,
+    expires_at: Option<DateTime<Utc>>,
+    rate_limit_requests: Option<i64>,
+    rate_limit_window_in_seconds: Option<i64>,
+}
+
+impl From<ApiKeyRow> for KeyInfo {
+    fn from(row: ApiKeyRow) -> Self {
+        let rate_limit = match (row.rate_limit_requests, row.rate_limit_window_in_seconds) {
+            (Some(requests), Some(window_in_seconds)) => Some(KeyRateLimit {
+                requests: requests.max(0) as u64,
+                window_in_seconds: window_in_seconds.max(0) as u64,
+            }),
+            _ => None,
+        };
+
+        KeyInfo {
+            owner: row.owner,
+            scopes: row.scopes,
+            expires_at: row.expires_at,
+            rate_limit,
+        }
+    }
+}
+
+/// Looks API keys up in the `api_keys` table.
+#[derive(Clone)]
+pub struct SqlxApiKeyProvider {
+    pool: PgPool,
+    header_name: String,
+}
+
+impl SqlxApiKeyProvider {
+    pub fn new(pool: PgPool, header_name: String) -> Self {
+        Self { pool, header_name }
+    }
+
+    pub async fn health_check(&self) -> Result<(), CredentialStoreError> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+
+        Ok(())
+    }
+}
+
+impl ApiKeyProvider for SqlxApiKeyProvider {
+    type Error = CredentialStoreError;
+
+    fn header_name(&self) -> &str {
+        &self.header_name
+    }
+
+    async fn validate(&self, key: &str) -> Result<KeyInfo, ApiKeyProviderError<Self::Error>> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(
+            "SELECT owner, scopes, expires_at, rate_limit_requests, rate_limit_window_in_seconds \
+             FROM api_keys WHERE key_digest = $1",
+        )
+        .bind(api_key_digest(key))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(CredentialStoreError::from)?;
+
+        row.map(KeyInfo::from).ok_or(ApiKeyProviderError::Invalid)
+    }
+}
+
+/// Looks basic auth users up in the `basic_auth_users` table.
+#[derive(Clone)]
+pub struct SqlxBasicAuthProvider {
+    pool: PgPool,
+}
+
+impl SqlxBasicAuthProvider {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl BasicAuthProvider for SqlxBasicAuthProvider {
+    type Error = CredentialStoreError;
+
+    async fn authenticate(
+        &self,
+        username: &str,
+        password: Option<&str>,
+    ) -> Result<(), BasicAuthProviderError<Self::Error>> {
+        let stored_password = sqlx::query_as::<_, (Option<String>, Option<String>)>(
+            "SELECT password, password_hash FROM basic_auth_users WHERE username = $1",
+        )
+        .bind(username)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(CredentialStoreError::from)?;
+
+        match stored_password {
+            Some((stored_password, password_hash))
+                if verify_password(
+                    stored_password.as_deref(),
+                    password_hash.as_deref(),
+                    password,
+                ) =>
+            {
+                Ok(())
+            }
+            _ => Err(BasicAuthProviderError::Unauthenticated),
+        }
+    }
+}
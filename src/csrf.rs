@@ -0,0 +1,106 @@
+//! Double-submit-cookie CSRF protection.
+//!
+//! A random token is issued in a cookie readable by the client.
+//! Requests with unsafe methods must send the same token in a header,
+//! which a cross-site attacker can not do since they can not read the cookie.
+//!
+//! See [`csrf`](crate::middleware::csrf::csrf).
+
+use axum::http::{HeaderValue, Method};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::RngCore;
+use serde::Deserialize;
+use subtle::ConstantTimeEq;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CsrfConfig {
+    #[serde(default = "default_cookie_name")]
+    pub cookie_name: String,
+    #[serde(default = "default_header_name")]
+    pub header_name: String,
+    /// Requests whose path starts with one of these prefixes are not checked.
+    #[serde(default)]
+    pub exempt_path_prefixes: Vec<String>,
+}
+
+fn default_cookie_name() -> String {
+    String::from("csrf_token")
+}
+
+fn default_header_name() -> String {
+    String::from("x-csrf-token")
+}
+
+impl Default for CsrfConfig {
+    fn default() -> Self {
+        Self {
+            cookie_name: default_cookie_name(),
+            header_name: default_header_name(),
+            exempt_path_prefixes: Vec::new(),
+        }
+    }
+}
+
+impl CsrfConfig {
+    pub fn is_exempt(&self, path: &str) -> bool {
+        self.exempt_path_prefixes.iter().any(|prefix| {
+            let prefix = prefix.trim_end_matches('/');
+
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    /// Returns the `Set-Cookie` header issuing the token.
+    ///
+    /// The cookie is not `HttpOnly`, so the client can copy it into the header.
+    pub fn cookie(&self, token: &CsrfToken) -> Option<HeaderValue> {
+        HeaderValue::from_str(&format!(
+            "{}={}; Secure; SameSite=Strict; Path=/",
+            self.cookie_name, token.0
+        ))
+        .ok()
+    }
+}
+
+pub trait CsrfProvider {
+    /// Returns the CSRF config.
+    ///
+    /// Returns `None` if CSRF protection is disabled.
+    fn csrf_config(&self) -> Option<&CsrfConfig>;
+}
+
+/// Returns whether requests with the method must carry the CSRF token.
+pub fn is_unsafe_method(method: &Method) -> bool {
+    !matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+    )
+}
+
+/// The CSRF token of the request, put as an extension for the handlers, e.g. to embed it in forms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrfToken(String);
+
+impl CsrfToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// Generates a new random token.
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+
+        Self(URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Compares the tokens in constant time.
+    pub fn matches(&self, other: &str) -> bool {
+        self.0.as_bytes().ct_eq(other.as_bytes()).into()
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use reqwest::{Method, RequestBuilder};
+use tokio::sync::RwLock;
+
+use crate::oidc::token_client::{CachedToken, TokenClient, TokenClientConfig, TokenError};
+
+const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+pub type DownstreamConfig = TokenClientConfig;
+
+/// HTTP client for calling downstream APIs.
+///
+/// Attaches client-credentials or on-behalf-of tokens to outgoing requests.
+/// Tokens are cached and refreshed before they expire.
+pub struct DownstreamClient {
+    http_client: reqwest::Client,
+    token_client: TokenClient,
+    on_behalf_of_tokens: RwLock<HashMap<String, CachedToken>>,
+}
+
+impl DownstreamClient {
+    pub fn new(
+        http_client: reqwest::Client,
+        token_endpoint: String,
+        config: DownstreamConfig,
+    ) -> Self {
+        Self {
+            token_client: TokenClient::new(http_client.clone(), token_endpoint, config),
+            http_client,
+            on_behalf_of_tokens: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the client obtaining the tokens.
+    pub fn token_client(&self) -> &TokenClient {
+        &self.token_client
+    }
+
+    /// Returns a cached client-credentials token or obtains a new one.
+    pub async fn client_credentials_token(&self) -> Result<String, TokenError> {
+        self.token_client.client_credentials_token().await
+    }
+
+    /// Returns a cached on-behalf-of token for the given subject token or exchanges a new one.
+    pub async fn on_behalf_of_token(&self, subject_token: &str) -> Result<String, TokenError> {
+        if let Some(token) = self.on_behalf_of_tokens.read().await.get(subject_token) {
+            if token.is_fresh() {
+                return Ok(token.access_token.clone());
+            }
+        }
+
+        let token = self
+            .token_client
+            .obtain_token(&[
+                ("grant_type", TOKEN_EXCHANGE_GRANT_TYPE),
+                ("subject_token", subject_token),
+                ("subject_token_type", ACCESS_TOKEN_TYPE),
+            ])
+            .await?;
+        let access_token = token.access_token.clone();
+
+        let mut cached = self.on_behalf_of_tokens.write().await;
+
+        cached.retain(|_, token| token.is_fresh());
+        cached.insert(subject_token.to_string(), token);
+
+        Ok(access_token)
+    }
+
+    /// Creates a request authorized with the client-credentials token.
+    pub async fn request(&self, method: Method, url: &str) -> Result<RequestBuilder, TokenError> {
+        let token = self.client_credentials_token().await?;
+
+        Ok(self.http_client.request(method, url).bearer_auth(token))
+    }
+
+    /// Creates a request authorized with an on-behalf-of token for the given subject token.
+    pub async fn request_on_behalf_of(
+        &self,
+        method: Method,
+        url: &str,
+        subject_token: &str,
+    ) -> Result<RequestBuilder, TokenError> {
+        let token = self.on_behalf_of_token(subject_token).await?;
+
+        Ok(self.http_client.request(method, url).bearer_auth(token))
+    }
+}
@@ -1,36 +1,65 @@
-use std::{borrow::Cow, string::FromUtf8Error};
+use std::{
+    backtrace::BacktraceStatus,
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    net::IpAddr,
+    string::FromUtf8Error,
+    time::Duration,
+};
 
 use axum::{
     extract::{
+        multipart::MultipartError as AxumMultipartError,
         path::ErrorKind as PathErrorKind,
-        rejection::{JsonRejection, PathRejection, QueryRejection},
+        rejection::{FormRejection, JsonRejection, PathRejection, QueryRejection},
+    },
+    http::{
+        header::{CONTENT_LANGUAGE, RETRY_AFTER},
+        HeaderMap, HeaderValue, StatusCode,
     },
-    http::{HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use base64::DecodeError;
+use chrono::{DateTime, Utc};
 use derive_more::From;
 use reqwest::header::ToStrError;
 use schemars::{schema_for, JsonSchema};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
-use validator::ValidationErrors;
-
-use crate::extractor::jwt::validation::JwtValidationError;
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::{
+    concurrency_limit::Overloaded,
+    error_codes,
+    error_metrics::CountedError,
+    error_sink::ReportedError,
+    extractor::jwt::validation::JwtValidationError,
+    ip_filter::IpFilterRule,
+    locale::LocalizedMessages,
+    openapi::OperationOutput,
+    problem_details::{ErrorFormat, ErrorFormatContext, ExpectedSchemaFormat, ProblemDetails},
+    rate_limit::{ceil_secs, RateLimitExceeded},
+    request_id::RequestId,
+    state::PrivateErrorVerbosity,
+    verbosity_policy::{random_status_code, ErrorCategory, RouteVerbosity},
+};
 
 pub trait ErrorVerbosityProvider {
     /// Returns the error verbosity.
-    fn error_verbosity(&self) -> ErrorVerbosity;
+    fn error_verbosity(&self) -> PrivateErrorVerbosity;
 }
 
-// FIXME: Must not be public to all routes, to prevent defining arbitrary error verbosity.
-// Create PrivateErrorVerbosity in state.rs. and use it as input here.
-// TODO: add a RandomStatus code that returns only a random status code.
-#[derive(Debug, Default, Clone, Copy, Deserialize)]
+/// Ordered from the least to the most verbose level.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
 pub enum ErrorVerbosity {
     /// Server returns an empty response with [`StatusCode::NO_CONTENT`] for all errors.
     None,
+    /// Server returns an empty response with a random status code for all errors.
+    ///
+    /// The status codes are drawn from the `random_status_codes` of the [`VerbosityPolicy`](crate::verbosity_policy::VerbosityPolicy).
+    RandomStatus,
     /// Server returns only the appropriate status code.
     #[default]
     StatusCode,
@@ -46,13 +75,61 @@ impl ErrorVerbosity {
     fn should_generate_error_context(&self) -> bool {
         matches!(self, ErrorVerbosity::Full)
     }
+
+    /// Returns the verbosity an error of the category is rendered with.
+    ///
+    /// Lowers the verbosity the error was created with to the level of the [`RouteVerbosity`] of the current request.
+    fn for_category(self, category: ErrorCategory) -> Self {
+        RouteVerbosity::current().map_or(self, |route_verbosity| {
+            self.min(route_verbosity.verbosity(category))
+        })
+    }
+}
+
+impl PrivateErrorVerbosity {
+    fn should_generate_error_context(&self) -> bool {
+        self.get().should_generate_error_context()
+    }
+}
+
+/// Clears the content of a serialized error that was created with a higher verbosity than it is rendered with.
+///
+/// Only the `kept` fields of the content are kept.
+fn clear_error_content(error: &mut serde_json::Value, kept: &[&str]) {
+    let Some(content) = error.get_mut("error") else {
+        return;
+    };
+
+    if let serde_json::Value::Object(fields) = content {
+        fields.retain(|key, _| kept.contains(&key.as_str()));
+
+        if !fields.is_empty() {
+            return;
+        }
+    }
+
+    *content = serde_json::Value::Null;
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 struct ApiErrorResponse {
     #[serde(flatten)]
     error: ApiError,
-    message: &'static str,
+    /// The verbosity the error is rendered with.
+    #[serde(skip)]
+    verbosity: ErrorVerbosity,
+    /// The message of the error, localized if the request negotiated a locale.
+    message: Cow<'static, str>,
+    /// The stable code of the error.
+    ///
+    /// Only set if the error verbosity is [`ErrorVerbosity::Type`] or [`ErrorVerbosity::Full`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<&'static str>,
+    /// The id of the request the error occurred in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    /// When the error occurred.
+    timestamp: DateTime<Utc>,
 }
 
 /// Holds only the message of the error.
@@ -60,24 +137,59 @@ struct ApiErrorResponse {
 /// Used if the error verbosity is set to [`ErrorVerbosity::Message`].
 #[derive(Debug, Serialize)]
 struct ErrorMessage {
-    message: &'static str,
+    message: Cow<'static, str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    timestamp: DateTime<Utc>,
 }
 
 impl From<ApiErrorResponse> for ErrorMessage {
     fn from(response: ApiErrorResponse) -> Self {
         ErrorMessage {
             message: response.message,
+            request_id: response.request_id,
+            timestamp: response.timestamp,
         }
     }
 }
 
 impl IntoResponse for ApiErrorResponse {
     fn into_response(self) -> Response {
-        let headers = self.error.headers().unwrap_or_default();
+        let mut headers = self.error.headers().unwrap_or_default();
+        let lowered = self.verbosity < self.error.verbosity();
+        let error_code = self.error.error_code();
+        let localized = localized_messages(self.verbosity, &mut headers);
 
-        match self.error.verbosity() {
+        match self.verbosity {
             ErrorVerbosity::None => StatusCode::NO_CONTENT.into_response(),
+            ErrorVerbosity::RandomStatus => random_status_code().into_response(),
             ErrorVerbosity::StatusCode => (self.error.status_code(), headers).into_response(),
+            verbosity @ (ErrorVerbosity::Message | ErrorVerbosity::Type | ErrorVerbosity::Full)
+                if is_problem_details() =>
+            {
+                let error = (!matches!(verbosity, ErrorVerbosity::Message))
+                    .then(|| serde_json::to_value(&self.error).ok())
+                    .flatten()
+                    .map(|mut error| {
+                        if lowered {
+                            clear_error_content(&mut error, &["type"]);
+                        }
+
+                        localize_reason(&mut error, error_code, localized.as_ref());
+
+                        error
+                    });
+
+                problem_details_response(
+                    self.error.status_code(),
+                    headers,
+                    self.message,
+                    error,
+                    self.error_code,
+                    self.request_id,
+                    self.timestamp,
+                )
+            }
             ErrorVerbosity::Message => (
                 self.error.status_code(),
                 headers,
@@ -85,13 +197,131 @@ impl IntoResponse for ApiErrorResponse {
             )
                 .into_response(),
             // error content is (cleared/not cleared) on error creation
-            ErrorVerbosity::Type | ErrorVerbosity::Full => {
+            ErrorVerbosity::Type | ErrorVerbosity::Full if !lowered && localized.is_none() => {
                 (self.error.status_code(), headers, Json(self)).into_response()
             }
+            ErrorVerbosity::Type | ErrorVerbosity::Full => {
+                let status_code = self.error.status_code();
+
+                match serde_json::to_value(&self) {
+                    Ok(mut response) => {
+                        if lowered {
+                            clear_error_content(&mut response, &["type"]);
+                        }
+
+                        localize_reason(&mut response, error_code, localized.as_ref());
+
+                        (status_code, headers, Json(response)).into_response()
+                    }
+                    Err(_) => (status_code, headers).into_response(),
+                }
+            }
         }
     }
 }
 
+/// Returns the [`LocalizedMessages::current`] messages if the verbosity includes the message.
+///
+/// Sets the `Content-Language` of the response to the locale of the messages.
+fn localized_messages(
+    verbosity: ErrorVerbosity,
+    headers: &mut HeaderMap,
+) -> Option<LocalizedMessages> {
+    if verbosity < ErrorVerbosity::Message {
+        return None;
+    }
+
+    let messages = LocalizedMessages::current()?;
+
+    if let Ok(locale) = HeaderValue::from_str(messages.locale()) {
+        headers.insert(CONTENT_LANGUAGE, locale);
+    }
+
+    Some(messages)
+}
+
+/// Returns the message of the error in the [`LocalizedMessages::current`] locale.
+///
+/// The placeholders of the message are only replaced with the error content if the error is rendered with [`ErrorVerbosity::Full`].
+fn localized_message(
+    error_code: &str,
+    message: &'static str,
+    error: &impl Serialize,
+    verbosity: ErrorVerbosity,
+) -> Cow<'static, str> {
+    let Some(messages) = LocalizedMessages::current() else {
+        return Cow::Borrowed(message);
+    };
+
+    let error = matches!(verbosity, ErrorVerbosity::Full)
+        .then(|| serde_json::to_value(error).ok())
+        .flatten();
+    let content = error.as_ref().and_then(|error| error.get("error"));
+
+    messages
+        .message(error_code, content)
+        .map_or(Cow::Borrowed(message), Cow::Owned)
+}
+
+/// Replaces the `reason` of a serialized error with its localized reason.
+fn localize_reason(
+    error: &mut serde_json::Value,
+    error_code: &str,
+    messages: Option<&LocalizedMessages>,
+) {
+    let Some(messages) = messages else {
+        return;
+    };
+
+    let Some(content) = error.get_mut("error") else {
+        return;
+    };
+
+    if content.get("reason").is_none_or(serde_json::Value::is_null) {
+        return;
+    }
+
+    if let Some(reason) = messages.reason(error_code, Some(content)) {
+        content["reason"] = serde_json::Value::String(reason);
+    }
+}
+
+/// Whether the errors of the current request are rendered as [`ProblemDetails`].
+/// Returns whether the error of the current request is rendered as [`ProblemDetails`] or as an HTML page.
+fn is_problem_details() -> bool {
+    ErrorFormatContext::current().is_some_and(|context| context.format != ErrorFormat::Json)
+}
+
+/// Renders the error of the current request as [`ProblemDetails`] or as an HTML page.
+fn problem_details_response(
+    status_code: StatusCode,
+    headers: HeaderMap,
+    message: Cow<'static, str>,
+    error: Option<serde_json::Value>,
+    error_code: Option<&'static str>,
+    request_id: Option<String>,
+    timestamp: DateTime<Utc>,
+) -> Response {
+    let Some(context) = ErrorFormatContext::current() else {
+        return (status_code, headers).into_response();
+    };
+
+    let problem = ProblemDetails::new(
+        &context,
+        status_code,
+        message,
+        error,
+        error_code,
+        request_id,
+        timestamp,
+    );
+
+    match &context.html_template {
+        Some(template) => problem.into_html_response(headers, template),
+        None => problem.into_response(headers),
+    }
+}
+
 #[derive(Debug, From, Serialize, ToSchema)]
 #[serde(tag = "error_type", content = "error")]
 /// API error.
@@ -104,10 +334,38 @@ pub enum ApiError {
     ///
     /// This error is returned when the query parameters are not as expected.
     Query(QueryError),
+    /// Header error.
+    ///
+    /// This error is returned when the headers are not as expected.
+    Header(HeaderError),
+    /// Cookie error.
+    ///
+    /// This error is returned when the cookies are not as expected.
+    Cookie(CookieError),
     /// Json Body error.
     ///
     /// This error is returned when the body is not as expected.
     JsonBody(JsonBodyError),
+    /// MessagePack body error.
+    ///
+    /// This error is returned when the MessagePack body is not as expected.
+    MsgPackBody(MsgPackBodyError),
+    /// CBOR body error.
+    ///
+    /// This error is returned when the CBOR body is not as expected.
+    CborBody(CborBodyError),
+    /// XML body error.
+    ///
+    /// This error is returned when the XML body is not as expected.
+    XmlBody(XmlBodyError),
+    /// Form body error.
+    ///
+    /// This error is returned when the form body is not as expected.
+    FormBody(FormBodyError),
+    /// Multipart error.
+    ///
+    /// This error is returned when the multipart body is not as expected.
+    Multipart(MultipartError),
     /// Path error.
     ///
     /// This error is returned when the path is not as expected.
@@ -120,6 +378,54 @@ pub enum ApiError {
     ///
     /// This error is returned when the requested resource is not found.
     NotFound(NotFoundError),
+    /// Client IP error.
+    ///
+    /// This error is returned when the client IP can not be resolved from the forwarding headers.
+    ClientIp(ClientIpError),
+    /// Tenant error.
+    ///
+    /// This error is returned when the tenant can not be resolved or is unknown.
+    Tenant(TenantError),
+    /// Forbidden error.
+    ///
+    /// This error is returned when the authenticated identity is not granted the required policy.
+    Forbidden(ForbiddenError),
+    /// CSRF error.
+    ///
+    /// This error is returned when a request with an unsafe method does not carry the CSRF token of its cookie.
+    Csrf(CsrfError),
+    /// Idempotency error.
+    ///
+    /// This error is returned when an `Idempotency-Key` is invalid, still in use or reused for a different request.
+    Idempotency(IdempotencyError),
+    /// URL parts error.
+    ///
+    /// This error is returned when either the path or the query parameters are not as expected.
+    UrlParts(UrlPartsError),
+    /// Pagination error.
+    ///
+    /// This error is returned when the pagination parameters are invalid or out of range.
+    Pagination(PaginationError),
+    /// Precondition failed.
+    ///
+    /// This error is returned when a conditional request's preconditions do not hold.
+    PreconditionFailed(PreconditionFailedError),
+    /// Deadline exceeded.
+    ///
+    /// This error is returned when the request did not complete before its deadline.
+    DeadlineExceeded(DeadlineExceededError),
+    /// Request timeout.
+    ///
+    /// This error is returned when the request was not handled within the configured timeout.
+    RequestTimeout(RequestTimeoutError),
+    /// Payload too large.
+    ///
+    /// This error is returned when the request body exceeds the configured limit.
+    PayloadTooLarge(PayloadTooLargeError),
+    /// Text body error.
+    ///
+    /// This error is returned when the body is not valid UTF-8.
+    TextBody(TextBodyError),
     /// API key error.
     ///
     /// This error is returned when the API key is not as expected.
@@ -136,10 +442,54 @@ pub enum ApiError {
     ///
     /// This error is returned when the JWT is not as expected.
     Jwt(JwtError),
+    /// Login error.
+    ///
+    /// This error is returned when the OpenID Connect login callback is not as expected.
+    Login(LoginError),
+    /// Session error.
+    ///
+    /// This error is returned when the request has no session or the session data is not as expected.
+    Session(SessionError),
+    /// Token grant error.
+    ///
+    /// This error is returned when a token can not be issued for the grant.
+    TokenGrant(TokenGrantError),
+    /// Signature error.
+    ///
+    /// This error is returned when the request signature is missing, expired or invalid.
+    Signature(SignatureError),
+    /// Digest auth error.
+    ///
+    /// This error is returned when the digest credentials are missing or invalid.
+    DigestAuth(DigestAuthError),
+    /// Client certificate error.
+    ///
+    /// This error is returned when a mutual TLS client certificate is missing, invalid or not authorized.
+    ClientCert(ClientCertError),
+    /// Principal error.
+    ///
+    /// This error is returned when a request carries no credentials or its claims can not be mapped to a principal.
+    Principal(PrincipalError),
+    /// Too many requests error.
+    ///
+    /// This error is returned when the client exceeded its rate limit.
+    TooManyRequests(TooManyRequestsError),
+    /// Service unavailable error.
+    ///
+    /// This error is returned when the server is overloaded and sheds the request or is in maintenance mode.
+    ServiceUnavailable(ServiceUnavailableError),
     /// Validation error.
     ///
     /// This error is returned when the validation of the extracted data fails.
     Validation(ValidationError),
+    /// GeoIP error.
+    ///
+    /// This error is returned when the request is rejected based on the client's location.
+    GeoIp(GeoIpError),
+    /// IP filter error.
+    ///
+    /// This error is returned when the client IP is denied by the IP filter.
+    IpFilter(IpFilterError),
 }
 
 /// A default [`ApiError`] does not need [`ErrorVerbosity`] and returns an empty [`InternalServerError`].
@@ -154,17 +504,47 @@ impl Default for ApiError {
 impl ApiError {
     fn verbosity(&self) -> ErrorVerbosity {
         match self {
-            ApiError::InternalServerError(err) => err.verbosity,
-            ApiError::Query(err) => err.verbosity,
-            ApiError::JsonBody(err) => err.verbosity,
-            ApiError::Path(err) => err.verbosity,
-            ApiError::MethodNotAllowed(err) => err.verbosity,
-            ApiError::NotFound(err) => err.verbosity,
-            ApiError::ApiKey(err) => err.verbosity,
-            ApiError::BasicAuth(err) => err.verbosity,
-            ApiError::Bearer(err) => err.verbosity,
-            ApiError::Jwt(err) => err.verbosity,
-            ApiError::Validation(err) => err.verbosity,
+            ApiError::InternalServerError(err) => err.verbosity.get(),
+            ApiError::Query(err) => err.verbosity.get(),
+            ApiError::JsonBody(err) => err.verbosity.get(),
+            ApiError::FormBody(err) => err.verbosity.get(),
+            ApiError::MsgPackBody(err) => err.verbosity.get(),
+            ApiError::CborBody(err) => err.verbosity.get(),
+            ApiError::XmlBody(err) => err.verbosity.get(),
+            ApiError::Header(err) => err.verbosity.get(),
+            ApiError::Cookie(err) => err.verbosity.get(),
+            ApiError::Multipart(err) => err.verbosity.get(),
+            ApiError::Path(err) => err.verbosity.get(),
+            ApiError::MethodNotAllowed(err) => err.verbosity.get(),
+            ApiError::NotFound(err) => err.verbosity.get(),
+            ApiError::PayloadTooLarge(err) => err.verbosity.get(),
+            ApiError::DeadlineExceeded(err) => err.verbosity.get(),
+            ApiError::RequestTimeout(err) => err.verbosity.get(),
+            ApiError::PreconditionFailed(err) => err.verbosity.get(),
+            ApiError::Pagination(err) => err.verbosity.get(),
+            ApiError::ClientIp(err) => err.verbosity.get(),
+            ApiError::Tenant(err) => err.verbosity.get(),
+            ApiError::Forbidden(err) => err.verbosity.get(),
+            ApiError::Csrf(err) => err.verbosity.get(),
+            ApiError::Idempotency(err) => err.verbosity.get(),
+            ApiError::UrlParts(err) => err.error.verbosity(),
+            ApiError::TextBody(err) => err.verbosity.get(),
+            ApiError::ApiKey(err) => err.verbosity.get(),
+            ApiError::BasicAuth(err) => err.verbosity.get(),
+            ApiError::Bearer(err) => err.verbosity.get(),
+            ApiError::Jwt(err) => err.verbosity.get(),
+            ApiError::Login(err) => err.verbosity.get(),
+            ApiError::Session(err) => err.verbosity.get(),
+            ApiError::TokenGrant(err) => err.verbosity.get(),
+            ApiError::Signature(err) => err.verbosity.get(),
+            ApiError::DigestAuth(err) => err.verbosity.get(),
+            ApiError::ClientCert(err) => err.verbosity.get(),
+            ApiError::Principal(err) => err.verbosity.get(),
+            ApiError::TooManyRequests(err) => err.verbosity.get(),
+            ApiError::ServiceUnavailable(err) => err.verbosity.get(),
+            ApiError::Validation(err) => err.verbosity.get(),
+            ApiError::GeoIp(err) => err.verbosity.get(),
+            ApiError::IpFilter(err) => err.verbosity.get(),
         }
     }
 
@@ -173,14 +553,44 @@ impl ApiError {
             ApiError::InternalServerError(_) => "An internal server error has occurred",
             ApiError::Query(_) => "Failed to parse query parameters",
             ApiError::JsonBody(_) => "Failed to parse request body",
+            ApiError::FormBody(_) => "Failed to parse form body",
+            ApiError::MsgPackBody(_) => "Failed to parse MessagePack body",
+            ApiError::CborBody(_) => "Failed to parse CBOR body",
+            ApiError::XmlBody(_) => "Failed to parse XML body",
+            ApiError::Header(_) => "Failed to parse headers",
+            ApiError::Cookie(_) => "Failed to parse cookies",
+            ApiError::Multipart(_) => "Failed to parse multipart body",
             ApiError::Path(_) => "Failed to parse path parameters",
             ApiError::MethodNotAllowed(_) => "Method not allowed",
             ApiError::NotFound(_) => "The requested resource was not found",
+            ApiError::PayloadTooLarge(_) => "Payload too large",
+            ApiError::DeadlineExceeded(_) => "Request deadline exceeded",
+            ApiError::RequestTimeout(_) => "Request timed out",
+            ApiError::PreconditionFailed(_) => "Precondition failed",
+            ApiError::Pagination(_) => "Invalid pagination",
+            ApiError::ClientIp(_) => "Failed to resolve client IP",
+            ApiError::Tenant(_) => "Failed to resolve tenant",
+            ApiError::Forbidden(_) => "Forbidden",
+            ApiError::Csrf(_) => "CSRF check failed",
+            ApiError::Idempotency(_) => "Idempotency check failed",
+            ApiError::UrlParts(err) => err.error.message(),
+            ApiError::TextBody(_) => "Failed to parse text body",
             ApiError::ApiKey(_) => "API key error",
             ApiError::BasicAuth(_) => "Basic auth error",
             ApiError::Bearer(_) => "Bearer auth error",
             ApiError::Jwt(_) => "JWT error",
+            ApiError::Login(_) => "Login failed",
+            ApiError::Session(_) => "Session error",
+            ApiError::TokenGrant(_) => "Token grant error",
+            ApiError::Signature(_) => "Request signature error",
+            ApiError::DigestAuth(_) => "Digest auth error",
+            ApiError::ClientCert(_) => "Client certificate error",
+            ApiError::Principal(_) => "Authentication error",
+            ApiError::TooManyRequests(_) => "Too many requests",
+            ApiError::ServiceUnavailable(_) => "Service unavailable",
             ApiError::Validation(_) => "Validation error",
+            ApiError::GeoIp(_) => "Access denied from your location",
+            ApiError::IpFilter(_) => "Access denied from your IP",
         }
     }
 
@@ -189,14 +599,184 @@ impl ApiError {
             ApiError::InternalServerError(err) => err.status_code(),
             ApiError::Query(err) => err.status_code(),
             ApiError::JsonBody(err) => err.status_code(),
+            ApiError::FormBody(err) => err.status_code(),
+            ApiError::MsgPackBody(err) => err.status_code(),
+            ApiError::CborBody(err) => err.status_code(),
+            ApiError::XmlBody(err) => err.status_code(),
+            ApiError::Header(err) => err.status_code(),
+            ApiError::Cookie(err) => err.status_code(),
+            ApiError::Multipart(err) => err.status_code(),
             ApiError::Path(err) => err.status_code(),
             ApiError::MethodNotAllowed(err) => err.status_code(),
             ApiError::NotFound(err) => err.status_code(),
+            ApiError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::DeadlineExceeded(_) => StatusCode::GATEWAY_TIMEOUT,
+            ApiError::RequestTimeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            ApiError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            ApiError::Pagination(_) => StatusCode::BAD_REQUEST,
+            ApiError::ClientIp(_) => StatusCode::BAD_REQUEST,
+            ApiError::Tenant(err) => err.status_code(),
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::Csrf(_) => StatusCode::FORBIDDEN,
+            ApiError::Idempotency(err) => err.status_code(),
+            ApiError::UrlParts(err) => err.error.status_code(),
+            ApiError::TextBody(_) => StatusCode::BAD_REQUEST,
             ApiError::ApiKey(err) => err.status_code(),
             ApiError::BasicAuth(err) => err.status_code(),
             ApiError::Bearer(err) => err.status_code(),
             ApiError::Jwt(err) => err.status_code(),
+            ApiError::Login(err) => err.status_code(),
+            ApiError::Session(_) => StatusCode::UNAUTHORIZED,
+            ApiError::TokenGrant(_) => StatusCode::BAD_REQUEST,
+            ApiError::Signature(_) => StatusCode::UNAUTHORIZED,
+            ApiError::DigestAuth(_) => StatusCode::UNAUTHORIZED,
+            ApiError::ClientCert(err) => err.status_code(),
+            ApiError::Principal(_) => StatusCode::UNAUTHORIZED,
+            ApiError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
             ApiError::Validation(err) => err.status_code(),
+            ApiError::GeoIp(err) => err.status_code(),
+            ApiError::IpFilter(_) => StatusCode::FORBIDDEN,
+        }
+    }
+
+    /// Returns the category of the error. See [`VerbosityPolicy`](crate::verbosity_policy::VerbosityPolicy).
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            ApiError::InternalServerError(_) => ErrorCategory::Internal,
+            ApiError::Query(_)
+            | ApiError::JsonBody(_)
+            | ApiError::FormBody(_)
+            | ApiError::MsgPackBody(_)
+            | ApiError::CborBody(_)
+            | ApiError::XmlBody(_)
+            | ApiError::Header(_)
+            | ApiError::Cookie(_)
+            | ApiError::Multipart(_)
+            | ApiError::Path(_)
+            | ApiError::TextBody(_)
+            | ApiError::PayloadTooLarge(_)
+            | ApiError::PreconditionFailed(_)
+            | ApiError::Pagination(_)
+            | ApiError::ClientIp(_)
+            | ApiError::Tenant(_)
+            | ApiError::Idempotency(_) => ErrorCategory::Request,
+            ApiError::UrlParts(err) => err.error.category(),
+            ApiError::Validation(_) => ErrorCategory::Validation,
+            ApiError::ApiKey(_)
+            | ApiError::BasicAuth(_)
+            | ApiError::Bearer(_)
+            | ApiError::Jwt(_)
+            | ApiError::Login(_)
+            | ApiError::Session(_)
+            | ApiError::TokenGrant(_)
+            | ApiError::Signature(_)
+            | ApiError::DigestAuth(_)
+            | ApiError::ClientCert(_)
+            | ApiError::Principal(_) => ErrorCategory::Auth,
+            ApiError::Forbidden(_)
+            | ApiError::Csrf(_)
+            | ApiError::GeoIp(_)
+            | ApiError::IpFilter(_) => ErrorCategory::Access,
+            ApiError::MethodNotAllowed(_) | ApiError::NotFound(_) => ErrorCategory::Routing,
+            ApiError::DeadlineExceeded(_)
+            | ApiError::RequestTimeout(_)
+            | ApiError::TooManyRequests(_)
+            | ApiError::ServiceUnavailable(_) => ErrorCategory::Availability,
+        }
+    }
+
+    /// Returns the stable code of the error. See [`error_codes`].
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            ApiError::InternalServerError(_) => error_codes::INTERNAL_SERVER_ERROR,
+            ApiError::Query(err) => err.error_code(),
+            ApiError::JsonBody(err) => err.error_code(),
+            ApiError::FormBody(err) => err.error_code(),
+            ApiError::MsgPackBody(err) => err.error_code(),
+            ApiError::CborBody(err) => err.error_code(),
+            ApiError::XmlBody(err) => err.error_code(),
+            ApiError::Header(err) => err.error_code(),
+            ApiError::Cookie(err) => err.error_code(),
+            ApiError::Multipart(err) => err.error_code(),
+            ApiError::Path(err) => err.error_code(),
+            ApiError::MethodNotAllowed(_) => error_codes::METHOD_NOT_ALLOWED,
+            ApiError::NotFound(_) => error_codes::NOT_FOUND,
+            ApiError::PayloadTooLarge(_) => error_codes::BODY_TOO_LARGE,
+            ApiError::DeadlineExceeded(_) => error_codes::DEADLINE_EXCEEDED,
+            ApiError::RequestTimeout(_) => error_codes::REQUEST_TIMEOUT,
+            ApiError::PreconditionFailed(_) => error_codes::PRECONDITION_FAILED,
+            ApiError::Pagination(err) => err.error_code(),
+            ApiError::ClientIp(err) => err.error_code(),
+            ApiError::Tenant(err) => err.error_code(),
+            ApiError::Forbidden(_) => error_codes::FORBIDDEN,
+            ApiError::Csrf(err) => err.error_code(),
+            ApiError::Idempotency(err) => err.error_code(),
+            ApiError::UrlParts(err) => err.error.error_code(),
+            ApiError::TextBody(_) => error_codes::BODY_INVALID_UTF8,
+            ApiError::ApiKey(err) => err.error_code(),
+            ApiError::BasicAuth(err) => err.error_code(),
+            ApiError::Bearer(err) => err.error_code(),
+            ApiError::Jwt(err) => err.error_code(),
+            ApiError::Login(err) => err.error_code(),
+            ApiError::Session(err) => err.error_code(),
+            ApiError::TokenGrant(err) => err.error_code(),
+            ApiError::Signature(err) => err.error_code(),
+            ApiError::DigestAuth(err) => err.error_code(),
+            ApiError::ClientCert(err) => err.error_code(),
+            ApiError::Principal(err) => err.error_code(),
+            ApiError::TooManyRequests(_) => error_codes::TOO_MANY_REQUESTS,
+            ApiError::ServiceUnavailable(err) => err.error_code(),
+            ApiError::Validation(_) => error_codes::VALIDATION_FAILED,
+            ApiError::GeoIp(err) => err.error_code(),
+            ApiError::IpFilter(_) => error_codes::IP_BLOCKED,
+        }
+    }
+
+    /// Returns the name of the variant, as serialized in the `error_type` field.
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            ApiError::InternalServerError(_) => "InternalServerError",
+            ApiError::Query(_) => "Query",
+            ApiError::JsonBody(_) => "JsonBody",
+            ApiError::FormBody(_) => "FormBody",
+            ApiError::MsgPackBody(_) => "MsgPackBody",
+            ApiError::CborBody(_) => "CborBody",
+            ApiError::XmlBody(_) => "XmlBody",
+            ApiError::Header(_) => "Header",
+            ApiError::Cookie(_) => "Cookie",
+            ApiError::Multipart(_) => "Multipart",
+            ApiError::Path(_) => "Path",
+            ApiError::MethodNotAllowed(_) => "MethodNotAllowed",
+            ApiError::NotFound(_) => "NotFound",
+            ApiError::PayloadTooLarge(_) => "PayloadTooLarge",
+            ApiError::DeadlineExceeded(_) => "DeadlineExceeded",
+            ApiError::RequestTimeout(_) => "RequestTimeout",
+            ApiError::PreconditionFailed(_) => "PreconditionFailed",
+            ApiError::Pagination(_) => "Pagination",
+            ApiError::ClientIp(_) => "ClientIp",
+            ApiError::Tenant(_) => "Tenant",
+            ApiError::Forbidden(_) => "Forbidden",
+            ApiError::Csrf(_) => "Csrf",
+            ApiError::Idempotency(_) => "Idempotency",
+            ApiError::UrlParts(_) => "UrlParts",
+            ApiError::TextBody(_) => "TextBody",
+            ApiError::ApiKey(_) => "ApiKey",
+            ApiError::BasicAuth(_) => "BasicAuth",
+            ApiError::Bearer(_) => "Bearer",
+            ApiError::Jwt(_) => "Jwt",
+            ApiError::Login(_) => "Login",
+            ApiError::Session(_) => "Session",
+            ApiError::TokenGrant(_) => "TokenGrant",
+            ApiError::Signature(_) => "Signature",
+            ApiError::DigestAuth(_) => "DigestAuth",
+            ApiError::ClientCert(_) => "ClientCert",
+            ApiError::Principal(_) => "Principal",
+            ApiError::TooManyRequests(_) => "TooManyRequests",
+            ApiError::ServiceUnavailable(_) => "ServiceUnavailable",
+            ApiError::Validation(_) => "Validation",
+            ApiError::GeoIp(_) => "GeoIp",
+            ApiError::IpFilter(_) => "IpFilter",
         }
     }
 
@@ -214,48 +794,193 @@ impl ApiError {
 
                 Some(headers)
             }
+            ApiError::Principal(_) => {
+                let mut headers = HeaderMap::new();
+                headers.append("WWW-Authenticate", HeaderValue::from_static("Bearer"));
+                headers.append("WWW-Authenticate", HeaderValue::from_static("Basic"));
+
+                Some(headers)
+            }
+            ApiError::TooManyRequests(err) => Some(*err.headers.clone()),
+            ApiError::ServiceUnavailable(err) => Some(*err.headers.clone()),
+            ApiError::DigestAuth(err) => {
+                let mut headers = HeaderMap::new();
+                for challenge in err.challenges.iter() {
+                    headers.append("WWW-Authenticate", challenge.clone());
+                }
+
+                Some(headers)
+            }
             _ => None,
         }
     }
 
-    pub fn from_generic_error<E: Into<anyhow::Error>>(verbosity: ErrorVerbosity, err: E) -> Self {
+    pub fn from_generic_error<E: Into<anyhow::Error>>(
+        verbosity: PrivateErrorVerbosity,
+        err: E,
+    ) -> Self {
         InternalServerError::from_generic_error(verbosity, err).into()
     }
+
+    /// Returns whether the error was caused by a missing credential rather than a malformed or invalid one.
+    pub fn is_missing(&self) -> bool {
+        matches!(
+            self,
+            ApiError::ApiKey(ApiKeyError {
+                r#type: ApiKeyErrorType::Missing,
+                ..
+            }) | ApiError::BasicAuth(BasicAuthError {
+                r#type: BasicAuthErrorType::AuthMissing,
+                ..
+            }) | ApiError::Bearer(BearerError {
+                r#type: BearerErrorType::AuthMissing,
+                ..
+            }) | ApiError::DigestAuth(DigestAuthError {
+                r#type: DigestAuthErrorType::AuthMissing,
+                ..
+            }) | ApiError::Principal(PrincipalError {
+                r#type: PrincipalErrorType::Missing,
+                ..
+            })
+        )
+    }
 }
 
 impl From<ApiError> for ApiErrorResponse {
     fn from(error: ApiError) -> Self {
-        let message = match error.verbosity() {
-            ErrorVerbosity::None => "",
-            _ => error.message(),
+        let verbosity = error.verbosity().for_category(error.category());
+
+        let message = match verbosity {
+            ErrorVerbosity::None | ErrorVerbosity::RandomStatus => Cow::Borrowed(""),
+            _ => localized_message(error.error_code(), error.message(), &error, verbosity),
         };
 
-        ApiErrorResponse { error, message }
+        let error_code = matches!(verbosity, ErrorVerbosity::Type | ErrorVerbosity::Full)
+            .then(|| error.error_code());
+
+        ApiErrorResponse {
+            error,
+            verbosity,
+            message,
+            error_code,
+            request_id: RequestId::current().map(|request_id| request_id.to_string()),
+            timestamp: Utc::now(),
+        }
     }
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        ApiErrorResponse::from(self).into_response()
+        let status_code = self.status_code();
+
+        let reported = status_code.is_server_error().then(|| {
+            let cause = match &self {
+                ApiError::InternalServerError(err) => err.cause.clone(),
+                _ => None,
+            };
+
+            ReportedError::new(
+                matches!(self, ApiError::InternalServerError(_)),
+                status_code,
+                serde_json::to_value(&self).ok(),
+                self.error_code(),
+                self.message(),
+                cause,
+            )
+        });
+
+        let counted = CountedError {
+            error_type: self.error_type(),
+            status: status_code,
+        };
+
+        let mut response = ApiErrorResponse::from(self).into_response();
+
+        response.extensions_mut().insert(counted);
+
+        if let Some(reported) = reported {
+            response.extensions_mut().insert(reported);
+        }
+
+        response
     }
 }
 
+/// Errors are described by the `default` response of every operation.
+impl OperationOutput for ApiError {}
+
+/// An error of the chain of an [`InternalServerError`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorCause {
+    pub message: String,
+    /// The index of the error that caused this one, `None` for the root cause.
+    pub source_index: Option<usize>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct InternalServerError {
     #[serde(skip)]
-    verbosity: ErrorVerbosity,
-    error: Option<String>,
+    verbosity: PrivateErrorVerbosity,
+    /// The error chain, starting with the outermost error.
+    causes: Option<Vec<ErrorCause>>,
+    /// Only set if [`VerbosityPolicyConfig::backtraces`](crate::verbosity_policy::VerbosityPolicyConfig::backtraces) is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backtrace: Option<String>,
+    /// The cause of the error, kept regardless of the verbosity for the [`ErrorSink`](crate::error_sink::ErrorSink).
+    #[serde(skip)]
+    cause: Option<String>,
 }
 
 impl InternalServerError {
-    pub fn from_generic_error<E: Into<anyhow::Error>>(verbosity: ErrorVerbosity, err: E) -> Self {
+    pub fn from_generic_error<E: Into<anyhow::Error>>(
+        verbosity: PrivateErrorVerbosity,
+        err: E,
+    ) -> Self {
         let err: anyhow::Error = err.into();
-        let err = format!("{err:#}");
-        tracing::error!(%err, "Internal server error");
+        let cause = format!("{err:#}");
+        tracing::error!(err = %cause, "Internal server error");
+
+        let causes = verbosity.should_generate_error_context().then(|| {
+            let len = err.chain().len();
+
+            err.chain()
+                .enumerate()
+                .map(|(index, source)| ErrorCause {
+                    message: source.to_string(),
+                    source_index: (index + 1 < len).then_some(index + 1),
+                })
+                .collect()
+        });
+
+        let backtrace = (verbosity.should_generate_error_context()
+            && RouteVerbosity::current().is_some_and(|route| route.includes_backtraces())
+            && err.backtrace().status() == BacktraceStatus::Captured)
+            .then(|| err.backtrace().to_string());
+
+        Self {
+            verbosity,
+            causes,
+            backtrace,
+            cause: Some(cause),
+        }
+    }
 
-        let error = verbosity.should_generate_error_context().then_some(err);
+    /// The panic message is only kept if the verbosity is full.
+    pub fn from_panic(verbosity: PrivateErrorVerbosity, message: Option<&str>) -> Self {
+        let cause = format!("Panic: {}", message.unwrap_or("unknown"));
+        let causes = verbosity.should_generate_error_context().then(|| {
+            vec![ErrorCause {
+                message: cause.clone(),
+                source_index: None,
+            }]
+        });
 
-        Self { verbosity, error }
+        Self {
+            verbosity,
+            causes,
+            backtrace: None,
+            cause: Some(cause),
+        }
     }
 
     fn status_code(&self) -> StatusCode {
@@ -268,8 +993,10 @@ impl Default for InternalServerError {
         tracing::error!("Internal server error");
 
         Self {
-            verbosity: Default::default(),
-            error: None,
+            verbosity: PrivateErrorVerbosity::DEFAULT,
+            causes: None,
+            backtrace: None,
+            cause: None,
         }
     }
 }
@@ -278,20 +1005,24 @@ impl Default for InternalServerError {
 pub enum QueryErrorType {
     /// Query parameters deserialization failed.
     DeserializeError,
+    /// Query parameters contain unknown fields.
+    ///
+    /// Only returned if strict deserialization is enabled.
+    UnknownFields,
 }
 
 #[derive(Debug, Serialize)]
 pub struct QueryError {
     #[serde(skip)]
-    verbosity: ErrorVerbosity,
+    verbosity: PrivateErrorVerbosity,
     r#type: QueryErrorType,
     reason: Option<String>,
-    expected_schema: Option<String>,
+    expected_schema: Option<ExpectedSchema>,
 }
 
 impl QueryError {
     pub fn from_query_rejection<T: JsonSchema>(
-        verbosity: ErrorVerbosity,
+        verbosity: PrivateErrorVerbosity,
         query_rejection: QueryRejection,
     ) -> ApiError {
         let r#type = match query_rejection {
@@ -302,7 +1033,7 @@ impl QueryError {
         let (reason, expected_schema) = match verbosity.should_generate_error_context() {
             true => {
                 let reason = query_rejection.body_text();
-                let expected_schema = match serde_yaml::to_string(&schema_for!(T)) {
+                let expected_schema = match expected_schema::<T>() {
                     Ok(schema) => schema,
                     Err(err) => return ApiError::from_generic_error(verbosity, err),
                 };
@@ -321,46 +1052,126 @@ impl QueryError {
         .into()
     }
 
+    pub fn from_deserialize_error<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        reason: String,
+    ) -> ApiError {
+        let (reason, expected_schema) = match verbosity.should_generate_error_context() {
+            true => {
+                let expected_schema = match expected_schema::<T>() {
+                    Ok(schema) => schema,
+                    Err(err) => return ApiError::from_generic_error(verbosity, err),
+                };
+
+                (Some(reason), Some(expected_schema))
+            }
+            false => (None, None),
+        };
+
+        QueryError {
+            verbosity,
+            r#type: QueryErrorType::DeserializeError,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    pub fn from_unknown_fields<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        unknown_fields: Vec<String>,
+    ) -> ApiError {
+        let (reason, expected_schema) = match unknown_fields_context::<T>(verbosity, unknown_fields)
+        {
+            Ok(context) => context,
+            Err(err) => return ApiError::from_generic_error(verbosity, err),
+        };
+
+        QueryError {
+            verbosity,
+            r#type: QueryErrorType::UnknownFields,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
     fn status_code(&self) -> StatusCode {
-        StatusCode::BAD_REQUEST
+        match self.r#type {
+            QueryErrorType::DeserializeError => StatusCode::BAD_REQUEST,
+            QueryErrorType::UnknownFields => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            QueryErrorType::DeserializeError => error_codes::QUERY_DESERIALIZE,
+            QueryErrorType::UnknownFields => error_codes::QUERY_UNKNOWN_FIELDS,
+        }
     }
 }
 
 #[derive(Debug, Serialize)]
-pub enum JsonBodyErrorType {
-    /// JSON data could not be deserialized to the target type.
-    DataError,
-    /// JSON syntax error. Invalid JSON.
-    SyntaxError,
-    /// Missing JSON content type.
-    MissingJsonContentType,
+pub enum HeaderErrorType {
+    /// A header value contains non visible ASCII characters.
+    InvalidHeaderValue,
+    /// Headers are missing or could not be deserialized to the target type.
+    DeserializeError,
 }
 
 #[derive(Debug, Serialize)]
-pub struct JsonBodyError {
+pub struct HeaderError {
     #[serde(skip)]
-    verbosity: ErrorVerbosity,
-    r#type: JsonBodyErrorType,
+    verbosity: PrivateErrorVerbosity,
+    r#type: HeaderErrorType,
     reason: Option<String>,
-    expected_schema: Option<String>,
+    expected_schema: Option<ExpectedSchema>,
 }
 
-impl JsonBodyError {
-    pub fn from_json_rejection<T: JsonSchema>(
-        verbosity: ErrorVerbosity,
-        json_rejection: JsonRejection,
+impl HeaderError {
+    pub fn from_invalid_header_value(verbosity: PrivateErrorVerbosity, name: &str) -> ApiError {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| format!("Header {name} contains invalid characters"));
+
+        HeaderError {
+            verbosity,
+            r#type: HeaderErrorType::InvalidHeaderValue,
+            reason,
+            expected_schema: None,
+        }
+        .into()
+    }
+
+    pub fn from_missing_or_invalid_header(
+        verbosity: PrivateErrorVerbosity,
+        name: &str,
+        err: Option<&dyn Display>,
     ) -> ApiError {
-        let r#type = match json_rejection {
-            JsonRejection::JsonDataError(_) => JsonBodyErrorType::DataError,
-            JsonRejection::JsonSyntaxError(_) => JsonBodyErrorType::SyntaxError,
-            JsonRejection::MissingJsonContentType(_) => JsonBodyErrorType::MissingJsonContentType,
-            _ => return ApiError::from_generic_error(verbosity, json_rejection),
-        };
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| match err {
+                Some(err) => format!("Header {name} is invalid: {err}"),
+                None => format!("Header {name} is missing"),
+            });
+
+        HeaderError {
+            verbosity,
+            r#type: HeaderErrorType::DeserializeError,
+            reason,
+            expected_schema: None,
+        }
+        .into()
+    }
 
+    pub fn from_deserialize_error<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        err: serde_urlencoded::de::Error,
+    ) -> ApiError {
         let (reason, expected_schema) = match verbosity.should_generate_error_context() {
             true => {
-                let reason = json_rejection.body_text();
-                let expected_schema = match serde_yaml::to_string(&schema_for!(T)) {
+                let reason = format!("Missing or invalid headers: {err}");
+                let expected_schema = match expected_schema::<T>() {
                     Ok(schema) => schema,
                     Err(err) => return ApiError::from_generic_error(verbosity, err),
                 };
@@ -370,9 +1181,9 @@ impl JsonBodyError {
             false => (None, None),
         };
 
-        JsonBodyError {
+        HeaderError {
             verbosity,
-            r#type,
+            r#type: HeaderErrorType::DeserializeError,
             reason,
             expected_schema,
         }
@@ -380,343 +1191,2351 @@ impl JsonBodyError {
     }
 
     fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_code(&self) -> &'static str {
         match self.r#type {
-            JsonBodyErrorType::DataError => StatusCode::UNPROCESSABLE_ENTITY,
-            JsonBodyErrorType::SyntaxError => StatusCode::BAD_REQUEST,
-            JsonBodyErrorType::MissingJsonContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            HeaderErrorType::InvalidHeaderValue => error_codes::HEADER_INVALID_VALUE,
+            HeaderErrorType::DeserializeError => error_codes::HEADER_DESERIALIZE,
         }
     }
 }
 
 #[derive(Debug, Serialize)]
-pub enum PathErrorType {
-    /// Path parameters deserialization failed.
+pub enum CookieErrorType {
+    /// The `Cookie` header is malformed.
+    InvalidCookieHeader,
+    /// A signed cookie has an invalid signature.
+    InvalidSignature {
+        #[serde(skip)]
+        name: String,
+    },
+    /// Cookies are missing or could not be deserialized to the target type.
     DeserializeError,
 }
 
 #[derive(Debug, Serialize)]
-pub struct PathError {
+pub struct CookieError {
     #[serde(skip)]
-    verbosity: ErrorVerbosity,
-    r#type: PathErrorType,
+    verbosity: PrivateErrorVerbosity,
+    r#type: CookieErrorType,
     reason: Option<String>,
+    expected_schema: Option<ExpectedSchema>,
 }
 
-impl PathError {
-    pub fn from_path_rejection(
-        verbosity: ErrorVerbosity,
-        path_rejection: PathRejection,
-    ) -> ApiError {
-        let r#type = match path_rejection {
-            PathRejection::FailedToDeserializePathParams(ref err) => match err.kind() {
-                PathErrorKind::Message(_)
-                | PathErrorKind::InvalidUtf8InPathParam { .. }
-                | PathErrorKind::ParseError { .. }
-                | PathErrorKind::ParseErrorAtIndex { .. }
-                | PathErrorKind::ParseErrorAtKey { .. } => PathErrorType::DeserializeError,
-                _ => return ApiError::from_generic_error(verbosity, path_rejection),
-            },
-            _ => return ApiError::from_generic_error(verbosity, path_rejection),
-        };
-
+impl CookieError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: CookieErrorType) -> Self {
         let reason = verbosity
             .should_generate_error_context()
-            .then_some(path_rejection.body_text());
-
-        PathError {
+            .then(|| match &r#type {
+                CookieErrorType::InvalidCookieHeader => String::from("Cookie header is malformed"),
+                CookieErrorType::InvalidSignature { name } => {
+                    format!("Cookie {name} has an invalid signature")
+                }
+                CookieErrorType::DeserializeError => String::from("Missing or invalid cookies"),
+            });
+
+        CookieError {
             verbosity,
             r#type,
             reason,
+            expected_schema: None,
         }
-        .into()
     }
 
-    fn status_code(&self) -> StatusCode {
-        StatusCode::BAD_REQUEST
-    }
-}
+    pub fn from_deserialize_error<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        err: serde_urlencoded::de::Error,
+    ) -> ApiError {
+        let mut error = Self::new(verbosity, CookieErrorType::DeserializeError);
 
-#[derive(Debug, Serialize)]
-pub struct MethodNotAllowedError {
-    #[serde(skip)]
-    verbosity: ErrorVerbosity,
+        if verbosity.should_generate_error_context() {
+            error.reason = Some(format!("Missing or invalid cookies: {err}"));
+
+            match expected_schema::<T>() {
+                Ok(schema) => error.expected_schema = Some(schema),
+                Err(err) => return ApiError::from_generic_error(verbosity, err),
+            }
+        }
+
+        error.into()
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            CookieErrorType::InvalidCookieHeader | CookieErrorType::DeserializeError => {
+                StatusCode::BAD_REQUEST
+            }
+            CookieErrorType::InvalidSignature { .. } => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            CookieErrorType::InvalidCookieHeader => error_codes::COOKIE_INVALID_HEADER,
+            CookieErrorType::InvalidSignature { .. } => error_codes::COOKIE_INVALID_SIGNATURE,
+            CookieErrorType::DeserializeError => error_codes::COOKIE_DESERIALIZE,
+        }
+    }
+}
+
+/// The schema a request part was expected to match.
+///
+/// Rendered in the [`ExpectedSchemaFormat`] of the current request.
+/// The contents are boxed to keep the size of [`ApiError`] small.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ExpectedSchema {
+    /// The schema as a YAML string.
+    Yaml(Box<str>),
+    /// The schema as a JSON object.
+    Json(Box<serde_json::Value>),
+    /// A URL pointing at the schema component in the served OpenAPI document.
+    Url(Box<str>),
+}
+
+/// Generates the expected schema of `T` in the [`ExpectedSchemaFormat`] of the current request.
+///
+/// Falls back to [`ExpectedSchemaFormat::Yaml`] outside of [`ErrorFormatContext::scope`].
+fn expected_schema<T: JsonSchema>() -> anyhow::Result<ExpectedSchema> {
+    let format = ErrorFormatContext::current()
+        .map(|context| context.expected_schema)
+        .unwrap_or_default();
+
+    let expected_schema = match format {
+        ExpectedSchemaFormat::Yaml => {
+            ExpectedSchema::Yaml(serde_yaml::to_string(&schema_for!(T))?.into())
+        }
+        ExpectedSchemaFormat::Json => {
+            ExpectedSchema::Json(Box::new(serde_json::to_value(schema_for!(T))?))
+        }
+        ExpectedSchemaFormat::Url { base_url } => {
+            ExpectedSchema::Url(format!("{base_url}{}", T::schema_name()).into())
+        }
+    };
+
+    Ok(expected_schema)
+}
+
+/// Generates the reason listing the unknown fields and the expected schema if the verbosity allows it.
+fn unknown_fields_context<T: JsonSchema>(
+    verbosity: PrivateErrorVerbosity,
+    unknown_fields: Vec<String>,
+) -> anyhow::Result<(Option<String>, Option<ExpectedSchema>)> {
+    match verbosity.should_generate_error_context() {
+        true => {
+            let reason = format!("Unknown fields: {}", unknown_fields.join(", "));
+            let expected_schema = expected_schema::<T>()?;
+
+            Ok((Some(reason), Some(expected_schema)))
+        }
+        false => Ok((None, None)),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum JsonBodyErrorType {
+    /// JSON data could not be deserialized to the target type.
+    DataError,
+    /// JSON syntax error. Invalid JSON.
+    SyntaxError,
+    /// Missing JSON content type.
+    MissingJsonContentType,
+    /// JSON contains unknown fields.
+    ///
+    /// Only returned if strict deserialization is enabled.
+    UnknownFields,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonBodyError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: JsonBodyErrorType,
+    reason: Option<String>,
+    expected_schema: Option<ExpectedSchema>,
+}
+
+impl JsonBodyError {
+    pub fn from_json_rejection<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        json_rejection: JsonRejection,
+    ) -> ApiError {
+        let r#type = match json_rejection {
+            JsonRejection::JsonDataError(_) => JsonBodyErrorType::DataError,
+            JsonRejection::JsonSyntaxError(_) => JsonBodyErrorType::SyntaxError,
+            JsonRejection::MissingJsonContentType(_) => JsonBodyErrorType::MissingJsonContentType,
+            _ => return ApiError::from_generic_error(verbosity, json_rejection),
+        };
+
+        let (reason, expected_schema) = match verbosity.should_generate_error_context() {
+            true => {
+                let reason = json_rejection.body_text();
+                let expected_schema = match expected_schema::<T>() {
+                    Ok(schema) => schema,
+                    Err(err) => return ApiError::from_generic_error(verbosity, err),
+                };
+
+                (Some(reason), Some(expected_schema))
+            }
+            false => (None, None),
+        };
+
+        JsonBodyError {
+            verbosity,
+            r#type,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    pub fn from_unknown_fields<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        unknown_fields: Vec<String>,
+    ) -> ApiError {
+        let (reason, expected_schema) = match unknown_fields_context::<T>(verbosity, unknown_fields)
+        {
+            Ok(context) => context,
+            Err(err) => return ApiError::from_generic_error(verbosity, err),
+        };
+
+        JsonBodyError {
+            verbosity,
+            r#type: JsonBodyErrorType::UnknownFields,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    pub fn missing_json_lines_content_type<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+    ) -> ApiError {
+        Self::with_context::<T>(
+            verbosity,
+            JsonBodyErrorType::MissingJsonContentType,
+            String::from("Expected request with `Content-Type: application/x-ndjson`"),
+        )
+    }
+
+    /// Creates an error for the given (1-based) line of a JSON lines body.
+    pub fn from_json_lines_error<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        line: usize,
+        err: serde_json::Error,
+    ) -> ApiError {
+        let r#type = match err.classify() {
+            serde_json::error::Category::Data => JsonBodyErrorType::DataError,
+            serde_json::error::Category::Io
+            | serde_json::error::Category::Syntax
+            | serde_json::error::Category::Eof => JsonBodyErrorType::SyntaxError,
+        };
+
+        Self::with_context::<T>(verbosity, r#type, format!("Line {line}: {err}"))
+    }
+
+    fn with_context<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        r#type: JsonBodyErrorType,
+        reason: String,
+    ) -> ApiError {
+        let (reason, expected_schema) = match verbosity.should_generate_error_context() {
+            true => {
+                let expected_schema = match expected_schema::<T>() {
+                    Ok(schema) => schema,
+                    Err(err) => return ApiError::from_generic_error(verbosity, err),
+                };
+
+                (Some(reason), Some(expected_schema))
+            }
+            false => (None, None),
+        };
+
+        JsonBodyError {
+            verbosity,
+            r#type,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            JsonBodyErrorType::DataError | JsonBodyErrorType::UnknownFields => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            JsonBodyErrorType::SyntaxError => StatusCode::BAD_REQUEST,
+            JsonBodyErrorType::MissingJsonContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            JsonBodyErrorType::DataError => error_codes::BODY_DATA,
+            JsonBodyErrorType::SyntaxError => error_codes::BODY_SYNTAX,
+            JsonBodyErrorType::MissingJsonContentType => error_codes::BODY_CONTENT_TYPE,
+            JsonBodyErrorType::UnknownFields => error_codes::BODY_UNKNOWN_FIELDS,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum MsgPackBodyErrorType {
+    /// MessagePack data could not be deserialized to the target type.
+    DataError,
+    /// MessagePack syntax error. Invalid MessagePack.
+    SyntaxError,
+    /// Missing MessagePack content type.
+    MissingMsgPackContentType,
+    /// MessagePack contains unknown fields.
+    ///
+    /// Only returned if strict deserialization is enabled.
+    UnknownFields,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MsgPackBodyError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: MsgPackBodyErrorType,
+    reason: Option<String>,
+    expected_schema: Option<ExpectedSchema>,
+}
+
+impl MsgPackBodyError {
+    pub fn missing_content_type<T: JsonSchema>(verbosity: PrivateErrorVerbosity) -> ApiError {
+        Self::with_context::<T>(
+            verbosity,
+            MsgPackBodyErrorType::MissingMsgPackContentType,
+            String::from("Expected request with `Content-Type: application/msgpack`"),
+        )
+    }
+
+    pub fn from_decode_error<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        err: rmp_serde::decode::Error,
+    ) -> ApiError {
+        use rmp_serde::decode::Error;
+
+        let r#type = match err {
+            Error::InvalidMarkerRead(_)
+            | Error::InvalidDataRead(_)
+            | Error::Syntax(_)
+            | Error::DepthLimitExceeded => MsgPackBodyErrorType::SyntaxError,
+            Error::TypeMismatch(_)
+            | Error::OutOfRange
+            | Error::LengthMismatch(_)
+            | Error::Uncategorized(_)
+            | Error::Utf8Error(_) => MsgPackBodyErrorType::DataError,
+        };
+
+        Self::with_context::<T>(verbosity, r#type, err.to_string())
+    }
+
+    pub fn from_unknown_fields<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        unknown_fields: Vec<String>,
+    ) -> ApiError {
+        let (reason, expected_schema) = match unknown_fields_context::<T>(verbosity, unknown_fields)
+        {
+            Ok(context) => context,
+            Err(err) => return ApiError::from_generic_error(verbosity, err),
+        };
+
+        MsgPackBodyError {
+            verbosity,
+            r#type: MsgPackBodyErrorType::UnknownFields,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    fn with_context<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        r#type: MsgPackBodyErrorType,
+        reason: String,
+    ) -> ApiError {
+        let (reason, expected_schema) = match verbosity.should_generate_error_context() {
+            true => {
+                let expected_schema = match expected_schema::<T>() {
+                    Ok(schema) => schema,
+                    Err(err) => return ApiError::from_generic_error(verbosity, err),
+                };
+
+                (Some(reason), Some(expected_schema))
+            }
+            false => (None, None),
+        };
+
+        MsgPackBodyError {
+            verbosity,
+            r#type,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            MsgPackBodyErrorType::DataError | MsgPackBodyErrorType::UnknownFields => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            MsgPackBodyErrorType::SyntaxError => StatusCode::BAD_REQUEST,
+            MsgPackBodyErrorType::MissingMsgPackContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            MsgPackBodyErrorType::DataError => error_codes::BODY_DATA,
+            MsgPackBodyErrorType::SyntaxError => error_codes::BODY_SYNTAX,
+            MsgPackBodyErrorType::MissingMsgPackContentType => error_codes::BODY_CONTENT_TYPE,
+            MsgPackBodyErrorType::UnknownFields => error_codes::BODY_UNKNOWN_FIELDS,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum CborBodyErrorType {
+    /// CBOR data could not be deserialized to the target type.
+    DataError,
+    /// CBOR syntax error. Invalid CBOR.
+    SyntaxError,
+    /// Missing CBOR content type.
+    MissingCborContentType,
+    /// CBOR contains unknown fields.
+    ///
+    /// Only returned if strict deserialization is enabled.
+    UnknownFields,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CborBodyError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: CborBodyErrorType,
+    reason: Option<String>,
+    expected_schema: Option<ExpectedSchema>,
+}
+
+impl CborBodyError {
+    pub fn missing_content_type<T: JsonSchema>(verbosity: PrivateErrorVerbosity) -> ApiError {
+        Self::with_context::<T>(
+            verbosity,
+            CborBodyErrorType::MissingCborContentType,
+            String::from("Expected request with `Content-Type: application/cbor`"),
+        )
+    }
+
+    pub fn from_decode_error<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        err: ciborium::de::Error<std::io::Error>,
+    ) -> ApiError {
+        use ciborium::de::Error;
+
+        let r#type = match err {
+            Error::Io(_) | Error::Syntax(_) | Error::RecursionLimitExceeded => {
+                CborBodyErrorType::SyntaxError
+            }
+            Error::Semantic(_, _) => CborBodyErrorType::DataError,
+        };
+
+        Self::with_context::<T>(verbosity, r#type, err.to_string())
+    }
+
+    pub fn from_unknown_fields<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        unknown_fields: Vec<String>,
+    ) -> ApiError {
+        let (reason, expected_schema) = match unknown_fields_context::<T>(verbosity, unknown_fields)
+        {
+            Ok(context) => context,
+            Err(err) => return ApiError::from_generic_error(verbosity, err),
+        };
+
+        CborBodyError {
+            verbosity,
+            r#type: CborBodyErrorType::UnknownFields,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    fn with_context<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        r#type: CborBodyErrorType,
+        reason: String,
+    ) -> ApiError {
+        let (reason, expected_schema) = match verbosity.should_generate_error_context() {
+            true => {
+                let expected_schema = match expected_schema::<T>() {
+                    Ok(schema) => schema,
+                    Err(err) => return ApiError::from_generic_error(verbosity, err),
+                };
+
+                (Some(reason), Some(expected_schema))
+            }
+            false => (None, None),
+        };
+
+        CborBodyError {
+            verbosity,
+            r#type,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            CborBodyErrorType::DataError | CborBodyErrorType::UnknownFields => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            CborBodyErrorType::SyntaxError => StatusCode::BAD_REQUEST,
+            CborBodyErrorType::MissingCborContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            CborBodyErrorType::DataError => error_codes::BODY_DATA,
+            CborBodyErrorType::SyntaxError => error_codes::BODY_SYNTAX,
+            CborBodyErrorType::MissingCborContentType => error_codes::BODY_CONTENT_TYPE,
+            CborBodyErrorType::UnknownFields => error_codes::BODY_UNKNOWN_FIELDS,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum XmlBodyErrorType {
+    /// XML data could not be deserialized to the target type.
+    DataError,
+    /// XML syntax error. Invalid XML.
+    SyntaxError,
+    /// Missing XML content type.
+    MissingXmlContentType,
+    /// XML contains unknown fields.
+    ///
+    /// Only returned if strict deserialization is enabled.
+    UnknownFields,
+}
+
+#[derive(Debug, Serialize)]
+pub struct XmlBodyError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: XmlBodyErrorType,
+    reason: Option<String>,
+    expected_schema: Option<ExpectedSchema>,
+}
+
+impl XmlBodyError {
+    pub fn missing_content_type<T: JsonSchema>(verbosity: PrivateErrorVerbosity) -> ApiError {
+        Self::with_context::<T>(
+            verbosity,
+            XmlBodyErrorType::MissingXmlContentType,
+            String::from("Expected request with `Content-Type: application/xml`"),
+        )
+    }
+
+    pub fn from_utf8_error<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        err: std::str::Utf8Error,
+    ) -> ApiError {
+        Self::with_context::<T>(verbosity, XmlBodyErrorType::SyntaxError, err.to_string())
+    }
+
+    pub fn from_de_error<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        err: quick_xml::DeError,
+    ) -> ApiError {
+        let r#type = match err {
+            quick_xml::DeError::InvalidXml(_) | quick_xml::DeError::UnexpectedEof => {
+                XmlBodyErrorType::SyntaxError
+            }
+            _ => XmlBodyErrorType::DataError,
+        };
+
+        Self::with_context::<T>(verbosity, r#type, err.to_string())
+    }
+
+    pub fn from_unknown_fields<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        unknown_fields: Vec<String>,
+    ) -> ApiError {
+        let (reason, expected_schema) = match unknown_fields_context::<T>(verbosity, unknown_fields)
+        {
+            Ok(context) => context,
+            Err(err) => return ApiError::from_generic_error(verbosity, err),
+        };
+
+        XmlBodyError {
+            verbosity,
+            r#type: XmlBodyErrorType::UnknownFields,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    fn with_context<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        r#type: XmlBodyErrorType,
+        reason: String,
+    ) -> ApiError {
+        let (reason, expected_schema) = match verbosity.should_generate_error_context() {
+            true => {
+                let expected_schema = match expected_schema::<T>() {
+                    Ok(schema) => schema,
+                    Err(err) => return ApiError::from_generic_error(verbosity, err),
+                };
+
+                (Some(reason), Some(expected_schema))
+            }
+            false => (None, None),
+        };
+
+        XmlBodyError {
+            verbosity,
+            r#type,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            XmlBodyErrorType::DataError | XmlBodyErrorType::UnknownFields => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            XmlBodyErrorType::SyntaxError => StatusCode::BAD_REQUEST,
+            XmlBodyErrorType::MissingXmlContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            XmlBodyErrorType::DataError => error_codes::BODY_DATA,
+            XmlBodyErrorType::SyntaxError => error_codes::BODY_SYNTAX,
+            XmlBodyErrorType::MissingXmlContentType => error_codes::BODY_CONTENT_TYPE,
+            XmlBodyErrorType::UnknownFields => error_codes::BODY_UNKNOWN_FIELDS,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum FormBodyErrorType {
+    /// Form data could not be deserialized to the target type.
+    DeserializeError,
+    /// Invalid form content type.
+    InvalidFormContentType,
+    /// Form contains unknown fields.
+    ///
+    /// Only returned if strict deserialization is enabled.
+    UnknownFields,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FormBodyError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: FormBodyErrorType,
+    reason: Option<String>,
+    expected_schema: Option<ExpectedSchema>,
+}
+
+impl FormBodyError {
+    pub fn from_form_rejection<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        form_rejection: FormRejection,
+    ) -> ApiError {
+        let r#type = match form_rejection {
+            FormRejection::FailedToDeserializeForm(_)
+            | FormRejection::FailedToDeserializeFormBody(_) => FormBodyErrorType::DeserializeError,
+            FormRejection::InvalidFormContentType(_) => FormBodyErrorType::InvalidFormContentType,
+            _ => return ApiError::from_generic_error(verbosity, form_rejection),
+        };
+
+        let (reason, expected_schema) = match verbosity.should_generate_error_context() {
+            true => {
+                let reason = form_rejection.body_text();
+                let expected_schema = match expected_schema::<T>() {
+                    Ok(schema) => schema,
+                    Err(err) => return ApiError::from_generic_error(verbosity, err),
+                };
+
+                (Some(reason), Some(expected_schema))
+            }
+            false => (None, None),
+        };
+
+        FormBodyError {
+            verbosity,
+            r#type,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    pub fn from_unknown_fields<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        unknown_fields: Vec<String>,
+    ) -> ApiError {
+        let (reason, expected_schema) = match unknown_fields_context::<T>(verbosity, unknown_fields)
+        {
+            Ok(context) => context,
+            Err(err) => return ApiError::from_generic_error(verbosity, err),
+        };
+
+        FormBodyError {
+            verbosity,
+            r#type: FormBodyErrorType::UnknownFields,
+            reason,
+            expected_schema,
+        }
+        .into()
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            FormBodyErrorType::DeserializeError | FormBodyErrorType::UnknownFields => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            FormBodyErrorType::InvalidFormContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            FormBodyErrorType::DeserializeError => error_codes::BODY_DATA,
+            FormBodyErrorType::InvalidFormContentType => error_codes::BODY_CONTENT_TYPE,
+            FormBodyErrorType::UnknownFields => error_codes::BODY_UNKNOWN_FIELDS,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum MultipartErrorType {
+    /// Missing or invalid multipart boundary.
+    InvalidBoundary,
+    /// Multipart body could not be read.
+    Read {
+        #[serde(skip)]
+        err: AxumMultipartError,
+    },
+    /// A field exceeds the size limit.
+    FieldTooLarge {
+        #[serde(skip)]
+        name: String,
+        #[serde(skip)]
+        limit: usize,
+    },
+    /// The multipart body exceeds the size limit.
+    TotalTooLarge {
+        #[serde(skip)]
+        limit: usize,
+    },
+    /// A text field contains invalid UTF-8.
+    InvalidUtf8 {
+        #[serde(skip)]
+        name: String,
+        #[serde(skip)]
+        err: FromUtf8Error,
+    },
+    /// Text fields could not be deserialized to the target type.
+    DeserializeError {
+        #[serde(skip)]
+        err: serde_urlencoded::de::Error,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct MultipartError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: MultipartErrorType,
+    reason: Option<Cow<'static, str>>,
+    expected_schema: Option<ExpectedSchema>,
+}
+
+impl MultipartError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: MultipartErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| Self::reason(&r#type));
+
+        MultipartError {
+            verbosity,
+            r#type,
+            reason,
+            expected_schema: None,
+        }
+    }
+
+    pub fn from_deserialize_error<T: JsonSchema>(
+        verbosity: PrivateErrorVerbosity,
+        err: serde_urlencoded::de::Error,
+    ) -> ApiError {
+        let mut error = Self::new(verbosity, MultipartErrorType::DeserializeError { err });
+
+        if verbosity.should_generate_error_context() {
+            match expected_schema::<T>() {
+                Ok(schema) => error.expected_schema = Some(schema),
+                Err(err) => return ApiError::from_generic_error(verbosity, err),
+            }
+        }
+
+        error.into()
+    }
+
+    fn reason(r#type: &MultipartErrorType) -> Cow<'static, str> {
+        match r#type {
+            MultipartErrorType::InvalidBoundary => {
+                Cow::Borrowed("Missing or invalid multipart boundary")
+            }
+            MultipartErrorType::Read { err } => Cow::Owned(format!(
+                "Multipart body could not be read: {}",
+                err.body_text()
+            )),
+            MultipartErrorType::FieldTooLarge { name, limit } => {
+                Cow::Owned(format!("Field {name} exceeds the limit of {limit} bytes"))
+            }
+            MultipartErrorType::TotalTooLarge { limit } => {
+                Cow::Owned(format!("Multipart body exceeds the limit of {limit} bytes"))
+            }
+            MultipartErrorType::InvalidUtf8 { name, err } => {
+                Cow::Owned(format!("Field {name} contains invalid characters: {err}"))
+            }
+            MultipartErrorType::DeserializeError { err } => {
+                Cow::Owned(format!("Failed to deserialize fields: {err}"))
+            }
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match &self.r#type {
+            MultipartErrorType::InvalidBoundary | MultipartErrorType::InvalidUtf8 { .. } => {
+                StatusCode::BAD_REQUEST
+            }
+            MultipartErrorType::Read { err } => err.status(),
+            MultipartErrorType::FieldTooLarge { .. } | MultipartErrorType::TotalTooLarge { .. } => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            MultipartErrorType::DeserializeError { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            MultipartErrorType::InvalidBoundary => error_codes::MULTIPART_INVALID_BOUNDARY,
+            MultipartErrorType::Read { .. } => error_codes::MULTIPART_READ,
+            MultipartErrorType::FieldTooLarge { .. } => error_codes::MULTIPART_FIELD_TOO_LARGE,
+            MultipartErrorType::TotalTooLarge { .. } => error_codes::MULTIPART_TOTAL_TOO_LARGE,
+            MultipartErrorType::InvalidUtf8 { .. } => error_codes::MULTIPART_INVALID_UTF8,
+            MultipartErrorType::DeserializeError { .. } => error_codes::MULTIPART_DESERIALIZE,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum PathErrorType {
+    /// Path parameters deserialization failed.
+    DeserializeError,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PathError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: PathErrorType,
+    reason: Option<String>,
+}
+
+impl PathError {
+    pub fn from_path_rejection(
+        verbosity: PrivateErrorVerbosity,
+        path_rejection: PathRejection,
+    ) -> ApiError {
+        let r#type = match path_rejection {
+            PathRejection::FailedToDeserializePathParams(ref err) => match err.kind() {
+                PathErrorKind::Message(_)
+                | PathErrorKind::InvalidUtf8InPathParam { .. }
+                | PathErrorKind::ParseError { .. }
+                | PathErrorKind::ParseErrorAtIndex { .. }
+                | PathErrorKind::ParseErrorAtKey { .. } => PathErrorType::DeserializeError,
+                _ => return ApiError::from_generic_error(verbosity, path_rejection),
+            },
+            _ => return ApiError::from_generic_error(verbosity, path_rejection),
+        };
+
+        let reason = verbosity
+            .should_generate_error_context()
+            .then_some(path_rejection.body_text());
+
+        PathError {
+            verbosity,
+            r#type,
+            reason,
+        }
+        .into()
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            PathErrorType::DeserializeError => error_codes::PATH_DESERIALIZE,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MethodNotAllowedError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+}
+
+impl MethodNotAllowedError {
+    pub fn new(verbosity: PrivateErrorVerbosity) -> Self {
+        MethodNotAllowedError { verbosity }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::METHOD_NOT_ALLOWED
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum ClientIpErrorType {
+    /// Forwarding headers were sent by a peer that is not a trusted proxy.
+    UntrustedProxy,
+    /// A forwarding header contains an invalid address.
+    InvalidForwardingHeader {
+        #[serde(skip)]
+        header: &'static str,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClientIpError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: ClientIpErrorType,
+    reason: Option<Cow<'static, str>>,
+}
+
+impl ClientIpError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: ClientIpErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| match &r#type {
+                ClientIpErrorType::UntrustedProxy => {
+                    Cow::Borrowed("Forwarding headers are only accepted from trusted proxies")
+                }
+                ClientIpErrorType::InvalidForwardingHeader { header } => {
+                    Cow::Owned(format!("Header {header} contains an invalid address"))
+                }
+            });
+
+        ClientIpError {
+            verbosity,
+            r#type,
+            reason,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            ClientIpErrorType::UntrustedProxy => error_codes::CLIENT_IP_UNTRUSTED_PROXY,
+            ClientIpErrorType::InvalidForwardingHeader { .. } => {
+                error_codes::CLIENT_IP_INVALID_FORWARDING_HEADER
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ForbiddenError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    reason: Option<String>,
+}
+
+impl ForbiddenError {
+    pub fn from_missing_policy(verbosity: PrivateErrorVerbosity, policy: &str) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| format!("Policy {policy} is not granted"));
+
+        ForbiddenError { verbosity, reason }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum CsrfErrorType {
+    /// The request has no CSRF cookie.
+    MissingCookie,
+    /// The request has no CSRF header.
+    MissingHeader,
+    /// The tokens of the cookie and the header do not match.
+    Mismatch,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CsrfError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: CsrfErrorType,
+    reason: Option<&'static str>,
+}
+
+impl CsrfError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: CsrfErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then_some(match r#type {
+                CsrfErrorType::MissingCookie => "CSRF cookie not found",
+                CsrfErrorType::MissingHeader => "CSRF header not found",
+                CsrfErrorType::Mismatch => "CSRF header does not match the cookie",
+            });
+
+        CsrfError {
+            verbosity,
+            r#type,
+            reason,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            CsrfErrorType::MissingCookie => error_codes::CSRF_MISSING_COOKIE,
+            CsrfErrorType::MissingHeader => error_codes::CSRF_MISSING_HEADER,
+            CsrfErrorType::Mismatch => error_codes::CSRF_MISMATCH,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum IdempotencyErrorType {
+    /// The `Idempotency-Key` header is empty, too long or contains non visible ASCII characters.
+    InvalidKey,
+    /// A request with the same key is still being processed.
+    InProgress,
+    /// The key was already used for a different request.
+    KeyReused,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IdempotencyError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: IdempotencyErrorType,
+    reason: Option<&'static str>,
+}
+
+impl IdempotencyError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: IdempotencyErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then_some(match r#type {
+                IdempotencyErrorType::InvalidKey => "Idempotency-Key header is invalid",
+                IdempotencyErrorType::InProgress => {
+                    "A request with this Idempotency-Key is still being processed"
+                }
+                IdempotencyErrorType::KeyReused => {
+                    "Idempotency-Key was already used for a different request"
+                }
+            });
+
+        IdempotencyError {
+            verbosity,
+            r#type,
+            reason,
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            IdempotencyErrorType::InvalidKey => StatusCode::BAD_REQUEST,
+            IdempotencyErrorType::InProgress => StatusCode::CONFLICT,
+            IdempotencyErrorType::KeyReused => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            IdempotencyErrorType::InvalidKey => error_codes::IDEMPOTENCY_INVALID_KEY,
+            IdempotencyErrorType::InProgress => error_codes::IDEMPOTENCY_IN_PROGRESS,
+            IdempotencyErrorType::KeyReused => error_codes::IDEMPOTENCY_KEY_REUSED,
+        }
+    }
+}
+
+/// The part of the URL that failed to be extracted.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum UrlPart {
+    Path,
+    Query,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UrlPartsError {
+    part: UrlPart,
+    error: Box<ApiError>,
+}
+
+impl UrlPartsError {
+    /// Wraps a [`ApiError::Path`] or [`ApiError::Query`] error with the part that failed.
+    ///
+    /// Other errors are returned as they are.
+    pub fn wrap(part: UrlPart, error: ApiError) -> ApiError {
+        match error {
+            ApiError::Path(_) | ApiError::Query(_) => UrlPartsError {
+                part,
+                error: Box::new(error),
+            }
+            .into(),
+            _ => error,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum TenantErrorType {
+    /// The request does not identify a tenant.
+    Missing,
+    /// The identified tenant is not known.
+    Unknown {
+        #[serde(skip)]
+        id: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct TenantError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: TenantErrorType,
+    reason: Option<String>,
+}
+
+impl TenantError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: TenantErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| match &r#type {
+                TenantErrorType::Missing => String::from("The request does not identify a tenant"),
+                TenantErrorType::Unknown { id } => format!("Unknown tenant: {id}"),
+            });
+
+        TenantError {
+            verbosity,
+            r#type,
+            reason,
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            TenantErrorType::Missing => StatusCode::BAD_REQUEST,
+            TenantErrorType::Unknown { .. } => StatusCode::FORBIDDEN,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            TenantErrorType::Missing => error_codes::TENANT_MISSING,
+            TenantErrorType::Unknown { .. } => error_codes::TENANT_UNKNOWN,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum PaginationErrorType {
+    /// Pagination parameters could not be parsed.
+    InvalidParameters {
+        #[serde(skip)]
+        err: serde_urlencoded::de::Error,
+    },
+    /// `page`/`per_page` and `offset`/`limit` were mixed.
+    MixedStyles,
+    /// A pagination parameter is out of range.
+    OutOfRange {
+        #[serde(skip)]
+        field: &'static str,
+        #[serde(skip)]
+        min: u64,
+        #[serde(skip)]
+        max: u64,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaginationError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: PaginationErrorType,
+    reason: Option<String>,
+}
+
+impl PaginationError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: PaginationErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| match &r#type {
+                PaginationErrorType::InvalidParameters { err } => {
+                    format!("Invalid pagination parameters: {err}")
+                }
+                PaginationErrorType::MixedStyles => {
+                    String::from("Use either page and per_page or offset and limit, not both")
+                }
+                PaginationErrorType::OutOfRange { field, min, max } => {
+                    format!("{field} must be between {min} and {max}")
+                }
+            });
+
+        PaginationError {
+            verbosity,
+            r#type,
+            reason,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            PaginationErrorType::InvalidParameters { .. } => {
+                error_codes::PAGINATION_INVALID_PARAMETERS
+            }
+            PaginationErrorType::MixedStyles => error_codes::PAGINATION_MIXED_STYLES,
+            PaginationErrorType::OutOfRange { .. } => error_codes::PAGINATION_OUT_OF_RANGE,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreconditionFailedError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+}
+
+impl PreconditionFailedError {
+    pub fn new(verbosity: PrivateErrorVerbosity) -> Self {
+        PreconditionFailedError { verbosity }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeadlineExceededError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+}
+
+impl DeadlineExceededError {
+    pub fn new(verbosity: PrivateErrorVerbosity) -> Self {
+        DeadlineExceededError { verbosity }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RequestTimeoutError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    reason: Option<String>,
+}
+
+impl RequestTimeoutError {
+    pub fn new(verbosity: PrivateErrorVerbosity, timeout: Duration) -> Self {
+        let reason = verbosity.should_generate_error_context().then(|| {
+            format!(
+                "Request was not handled within {} milliseconds",
+                timeout.as_millis()
+            )
+        });
+
+        RequestTimeoutError { verbosity, reason }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PayloadTooLargeError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    reason: Option<String>,
+}
+
+impl PayloadTooLargeError {
+    pub fn new(verbosity: PrivateErrorVerbosity, limit: usize) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| format!("Body exceeds the limit of {limit} bytes"));
+
+        PayloadTooLargeError { verbosity, reason }
+    }
+
+    /// Creates the error for a body whose size is known, e.g. from the `Content-Length` header.
+    pub fn with_size(verbosity: PrivateErrorVerbosity, limit: usize, size: usize) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| format!("Body of {size} bytes exceeds the limit of {limit} bytes"));
+
+        PayloadTooLargeError { verbosity, reason }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TextBodyError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    reason: Option<String>,
+}
+
+impl TextBodyError {
+    pub fn new(verbosity: PrivateErrorVerbosity, err: FromUtf8Error) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| format!("Body is not valid UTF-8: {err}"));
+
+        TextBodyError { verbosity, reason }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotFoundError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+}
+
+impl NotFoundError {
+    pub fn new(verbosity: PrivateErrorVerbosity) -> Self {
+        NotFoundError { verbosity }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::NOT_FOUND
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum ApiKeyErrorType {
+    /// API key is missing.
+    Missing,
+    /// API key contains invalid characters.
+    InvalidChars {
+        #[serde(skip)]
+        err: ToStrError,
+    },
+    /// API key is invalid.
+    Invalid,
+    /// API key has expired.
+    Expired,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiKeyError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: ApiKeyErrorType,
+    reason: Option<Cow<'static, str>>,
+}
+
+impl ApiKeyError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: ApiKeyErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| Self::reason(&r#type));
+
+        ApiKeyError {
+            verbosity,
+            r#type,
+            reason,
+        }
+    }
+
+    fn reason(r#type: &ApiKeyErrorType) -> Cow<'static, str> {
+        match r#type {
+            ApiKeyErrorType::Missing => Cow::Borrowed("API key is missing"),
+            ApiKeyErrorType::InvalidChars { err } => {
+                Cow::Owned(format!("API key contains invalid characters: {err}"))
+            }
+            ApiKeyErrorType::Invalid => Cow::Borrowed("API key invalid"),
+            ApiKeyErrorType::Expired => Cow::Borrowed("API key expired"),
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            ApiKeyErrorType::Missing => StatusCode::UNAUTHORIZED,
+            ApiKeyErrorType::InvalidChars { .. } => StatusCode::UNAUTHORIZED,
+            ApiKeyErrorType::Invalid => StatusCode::FORBIDDEN,
+            ApiKeyErrorType::Expired => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            ApiKeyErrorType::Missing => error_codes::AUTH_API_KEY_MISSING,
+            ApiKeyErrorType::InvalidChars { .. } => error_codes::AUTH_API_KEY_INVALID_CHARS,
+            ApiKeyErrorType::Invalid => error_codes::AUTH_API_KEY_INVALID,
+            ApiKeyErrorType::Expired => error_codes::AUTH_API_KEY_EXPIRED,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum BasicAuthErrorType {
+    /// Authorization header is missing.
+    AuthMissing,
+    /// Authorization header contains invalid characters.
+    AuthInvalidChars {
+        #[serde(skip)]
+        err: ToStrError,
+    },
+    /// Authorization header could not be decoded.
+    Decode {
+        #[serde(skip)]
+        err: DecodeError,
+    },
+    /// Decoded authorization header contains invalid characters.
+    AuthInvalidUTF8 {
+        #[serde(skip)]
+        err: FromUtf8Error,
+    },
+    /// Authorization header is invalid Basic.
+    InvalidBasic,
+    /// Authentication failed.
+    Invalid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BasicAuthError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: BasicAuthErrorType,
+    reason: Option<Cow<'static, str>>,
+}
+
+impl BasicAuthError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: BasicAuthErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| Self::reason(&r#type));
+
+        BasicAuthError {
+            verbosity,
+            r#type,
+            reason,
+        }
+    }
+
+    fn reason(r#type: &BasicAuthErrorType) -> Cow<'static, str> {
+        match r#type {
+            BasicAuthErrorType::AuthMissing => Cow::Borrowed("Authorization header is missing"),
+            BasicAuthErrorType::AuthInvalidChars { err } => Cow::Owned(format!(
+                "Authorization header contains invalid characters: {err}"
+            )),
+            BasicAuthErrorType::Decode { err } => {
+                Cow::Owned(format!("Authorization header could not be decoded: {err}"))
+            }
+            BasicAuthErrorType::InvalidBasic => {
+                Cow::Borrowed("Authorization header is invalid Basic")
+            }
+            BasicAuthErrorType::AuthInvalidUTF8 { err } => Cow::Owned(format!(
+                "Decoded authorization header contains invalid characters: {err}"
+            )),
+            BasicAuthErrorType::Invalid => Cow::Borrowed("Basic auth is invalid"),
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::UNAUTHORIZED
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            BasicAuthErrorType::AuthMissing => error_codes::AUTH_BASIC_MISSING,
+            BasicAuthErrorType::AuthInvalidChars { .. } => error_codes::AUTH_BASIC_INVALID_CHARS,
+            BasicAuthErrorType::Decode { .. } => error_codes::AUTH_BASIC_DECODE,
+            BasicAuthErrorType::AuthInvalidUTF8 { .. } => error_codes::AUTH_BASIC_INVALID_UTF8,
+            BasicAuthErrorType::InvalidBasic => error_codes::AUTH_BASIC_MALFORMED,
+            BasicAuthErrorType::Invalid => error_codes::AUTH_BASIC_INVALID,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum DigestAuthErrorType {
+    /// Authorization header is missing.
+    AuthMissing,
+    /// Authorization header contains invalid characters.
+    AuthInvalidChars {
+        #[serde(skip)]
+        err: ToStrError,
+    },
+    /// Authorization header is invalid Digest.
+    InvalidDigest,
+    /// The digest algorithm is not supported.
+    UnsupportedAlgorithm {
+        #[serde(skip)]
+        algorithm: String,
+    },
+    /// The digest uri does not match the request.
+    UriMismatch,
+    /// The nonce was not issued by this server.
+    UnknownNonce,
+    /// The nonce has expired. The client should retry with the new nonce.
+    StaleNonce,
+    /// The nonce count was already used.
+    NonceReused,
+    /// Authentication failed.
+    Invalid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DigestAuthError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: DigestAuthErrorType,
+    reason: Option<Cow<'static, str>>,
+    /// The `WWW-Authenticate` challenges sent with the error.
+    #[serde(skip)]
+    challenges: Vec<HeaderValue>,
+}
+
+impl DigestAuthError {
+    pub fn new(
+        verbosity: PrivateErrorVerbosity,
+        r#type: DigestAuthErrorType,
+        challenges: Vec<HeaderValue>,
+    ) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| Self::reason(&r#type));
+
+        DigestAuthError {
+            verbosity,
+            r#type,
+            reason,
+            challenges,
+        }
+    }
+
+    fn reason(r#type: &DigestAuthErrorType) -> Cow<'static, str> {
+        match r#type {
+            DigestAuthErrorType::AuthMissing => Cow::Borrowed("Authorization header is missing"),
+            DigestAuthErrorType::AuthInvalidChars { err } => Cow::Owned(format!(
+                "Authorization header contains invalid characters: {err}"
+            )),
+            DigestAuthErrorType::InvalidDigest => {
+                Cow::Borrowed("Authorization header is invalid Digest")
+            }
+            DigestAuthErrorType::UnsupportedAlgorithm { algorithm } => {
+                Cow::Owned(format!("Digest algorithm is not supported: {algorithm}"))
+            }
+            DigestAuthErrorType::UriMismatch => {
+                Cow::Borrowed("Digest uri does not match the request")
+            }
+            DigestAuthErrorType::UnknownNonce => Cow::Borrowed("Digest nonce is unknown"),
+            DigestAuthErrorType::StaleNonce => Cow::Borrowed("Digest nonce is stale"),
+            DigestAuthErrorType::NonceReused => {
+                Cow::Borrowed("Digest nonce count was already used")
+            }
+            DigestAuthErrorType::Invalid => Cow::Borrowed("Digest auth is invalid"),
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            DigestAuthErrorType::AuthMissing => error_codes::AUTH_DIGEST_MISSING,
+            DigestAuthErrorType::AuthInvalidChars { .. } => error_codes::AUTH_DIGEST_INVALID_CHARS,
+            DigestAuthErrorType::InvalidDigest => error_codes::AUTH_DIGEST_MALFORMED,
+            DigestAuthErrorType::UnsupportedAlgorithm { .. } => {
+                error_codes::AUTH_DIGEST_UNSUPPORTED_ALGORITHM
+            }
+            DigestAuthErrorType::UriMismatch => error_codes::AUTH_DIGEST_URI_MISMATCH,
+            DigestAuthErrorType::UnknownNonce => error_codes::AUTH_DIGEST_UNKNOWN_NONCE,
+            DigestAuthErrorType::StaleNonce => error_codes::AUTH_DIGEST_STALE_NONCE,
+            DigestAuthErrorType::NonceReused => error_codes::AUTH_DIGEST_NONCE_REUSED,
+            DigestAuthErrorType::Invalid => error_codes::AUTH_DIGEST_INVALID,
+        }
+    }
 }
 
-impl MethodNotAllowedError {
-    pub fn new(verbosity: ErrorVerbosity) -> Self {
-        MethodNotAllowedError { verbosity }
+#[derive(Debug, Serialize)]
+pub enum BearerErrorType {
+    /// Authorization header is missing.
+    AuthMissing,
+    /// Authorization header contains invalid characters.
+    AuthInvalidChars {
+        #[serde(skip)]
+        err: ToStrError,
+    },
+    /// Authorization header is invalid Bearer.
+    InvalidBearer,
+    /// The introspected token is not active.
+    InactiveToken,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BearerError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: BearerErrorType,
+    reason: Option<Cow<'static, str>>,
+}
+
+impl BearerError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: BearerErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| Self::reason(&r#type));
+
+        BearerError {
+            verbosity,
+            r#type,
+            reason,
+        }
+    }
+
+    fn reason(r#type: &BearerErrorType) -> Cow<'static, str> {
+        match r#type {
+            BearerErrorType::AuthMissing => Cow::Borrowed("Authorization header is missing"),
+            BearerErrorType::AuthInvalidChars { err } => Cow::Owned(format!(
+                "Authorization header contains invalid characters: {err}"
+            )),
+            BearerErrorType::InvalidBearer => {
+                Cow::Borrowed("Authorization header is invalid Bearer")
+            }
+            BearerErrorType::InactiveToken => Cow::Borrowed("Bearer token is not active"),
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::UNAUTHORIZED
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            BearerErrorType::AuthMissing => error_codes::AUTH_BEARER_MISSING,
+            BearerErrorType::AuthInvalidChars { .. } => error_codes::AUTH_BEARER_INVALID_CHARS,
+            BearerErrorType::InvalidBearer => error_codes::AUTH_BEARER_MALFORMED,
+            BearerErrorType::InactiveToken => error_codes::AUTH_BEARER_INACTIVE_TOKEN,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum JwtErrorType {
+    /// JWT validation failed.
+    Invalid {
+        #[serde(skip)]
+        err: JwtValidationError,
+    },
+    /// ExpiredSignature is a special case of Invalid.
+    ///
+    /// Intentionally extracted from the Invalid variant to provide a more specific error message.
+    ExpiredSignature,
+    /// JWT is valid but was revoked before its expiry.
+    Revoked,
+    /// User does not have a valid role.
+    Forbidden,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JwtError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: JwtErrorType,
+    reason: Option<Cow<'static, str>>,
+}
+
+impl JwtError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: JwtErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| Self::reason(&r#type));
+
+        JwtError {
+            verbosity,
+            r#type,
+            reason,
+        }
+    }
+
+    fn reason(r#type: &JwtErrorType) -> Cow<'static, str> {
+        match r#type {
+            JwtErrorType::Invalid { err } => Cow::Owned(format!("JWT is invalid: {err}")),
+            JwtErrorType::ExpiredSignature => Cow::Borrowed("JWT has expired"),
+            JwtErrorType::Revoked => Cow::Borrowed("JWT has been revoked"),
+            JwtErrorType::Forbidden => Cow::Borrowed("User does not have a valid role"),
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            JwtErrorType::Invalid { .. }
+            | JwtErrorType::ExpiredSignature
+            | JwtErrorType::Revoked => StatusCode::UNAUTHORIZED,
+            JwtErrorType::Forbidden => StatusCode::FORBIDDEN,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            JwtErrorType::Invalid { .. } => error_codes::AUTH_JWT_INVALID,
+            JwtErrorType::ExpiredSignature => error_codes::AUTH_JWT_EXPIRED,
+            JwtErrorType::Revoked => error_codes::AUTH_JWT_REVOKED,
+            JwtErrorType::Forbidden => error_codes::AUTH_JWT_FORBIDDEN,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum PrincipalErrorType {
+    /// The request carries neither a bearer token, basic auth nor an API key.
+    Missing,
+    /// The claims of the JWT do not identify a subject.
+    Unmapped,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrincipalError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: PrincipalErrorType,
+    reason: Option<&'static str>,
+}
+
+impl PrincipalError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: PrincipalErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then_some(match r#type {
+                PrincipalErrorType::Missing => "No credentials found",
+                PrincipalErrorType::Unmapped => "JWT claims do not identify a subject",
+            });
+
+        PrincipalError {
+            verbosity,
+            r#type,
+            reason,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            PrincipalErrorType::Missing => error_codes::AUTH_PRINCIPAL_MISSING,
+            PrincipalErrorType::Unmapped => error_codes::AUTH_PRINCIPAL_UNMAPPED,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TooManyRequestsError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    reason: Option<String>,
+    /// `Retry-After` and the headers supplied by the rejecting middleware.
+    ///
+    /// Boxed to keep the size of [`ApiError`] small.
+    #[serde(skip)]
+    headers: Box<HeaderMap>,
+}
+
+impl TooManyRequestsError {
+    pub fn new(verbosity: PrivateErrorVerbosity, exceeded: RateLimitExceeded) -> Self {
+        let reason = verbosity.should_generate_error_context().then(|| {
+            format!(
+                "Rate limit of {} requests exceeded. Retry after {} seconds",
+                exceeded.status.limit,
+                ceil_secs(exceeded.retry_after)
+            )
+        });
+
+        TooManyRequestsError {
+            verbosity,
+            reason,
+            headers: Box::new(retry_after_headers(exceeded.retry_after)),
+        }
+    }
+
+    /// Attaches headers to the response, e.g. the `X-RateLimit-*` headers of the exceeded limit.
+    ///
+    /// Headers with the same name as an already attached header replace it.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+}
+
+fn retry_after_headers(retry_after: Duration) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, HeaderValue::from(ceil_secs(retry_after)));
+
+    headers
+}
+
+#[derive(Debug, Serialize)]
+pub enum ServiceUnavailableErrorType {
+    /// A concurrency limit was reached.
+    Overloaded {
+        #[serde(skip)]
+        limit: usize,
+    },
+    /// The server is in maintenance mode.
+    Maintenance,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ServiceUnavailableError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: ServiceUnavailableErrorType,
+    reason: Option<String>,
+    /// `Retry-After` and the headers supplied by the rejecting middleware.
+    ///
+    /// Boxed to keep the size of [`ApiError`] small.
+    #[serde(skip)]
+    headers: Box<HeaderMap>,
+}
+
+impl ServiceUnavailableError {
+    pub fn new(
+        verbosity: PrivateErrorVerbosity,
+        r#type: ServiceUnavailableErrorType,
+        retry_after: Duration,
+    ) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| match &r#type {
+                ServiceUnavailableErrorType::Overloaded { limit } => format!(
+                    "Limit of {limit} concurrent requests reached. Retry after {} seconds",
+                    ceil_secs(retry_after)
+                ),
+                ServiceUnavailableErrorType::Maintenance => format!(
+                    "Server is in maintenance mode. Retry after {} seconds",
+                    ceil_secs(retry_after)
+                ),
+            });
+
+        ServiceUnavailableError {
+            verbosity,
+            r#type,
+            reason,
+            headers: Box::new(retry_after_headers(retry_after)),
+        }
+    }
+
+    /// Attaches headers to the response.
+    ///
+    /// Headers with the same name as an already attached header replace it.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    pub fn overloaded(verbosity: PrivateErrorVerbosity, overloaded: Overloaded) -> Self {
+        Self::new(
+            verbosity,
+            ServiceUnavailableErrorType::Overloaded {
+                limit: overloaded.limit,
+            },
+            overloaded.retry_after,
+        )
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            ServiceUnavailableErrorType::Overloaded { .. } => error_codes::SERVICE_OVERLOADED,
+            ServiceUnavailableErrorType::Maintenance => error_codes::SERVICE_MAINTENANCE,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum LoginErrorType {
+    /// The `state` parameter is missing, unknown, expired or was issued to another browser.
+    InvalidState,
+    /// The `code` parameter is missing.
+    MissingCode,
+    /// The identity provider returned an error.
+    Provider {
+        #[serde(skip)]
+        error: String,
+        #[serde(skip)]
+        description: Option<String>,
+    },
+    /// The ID token is invalid.
+    InvalidIdToken {
+        #[serde(skip)]
+        err: JwtValidationError,
+    },
+    /// The `nonce` claim of the ID token does not match the login.
+    NonceMismatch,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoginError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: LoginErrorType,
+    reason: Option<String>,
+}
+
+impl LoginError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: LoginErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| match &r#type {
+                LoginErrorType::InvalidState => String::from(
+                    "Login state is missing, unknown, expired or was issued to another browser",
+                ),
+                LoginErrorType::MissingCode => String::from("Authorization code is missing"),
+                LoginErrorType::Provider {
+                    error,
+                    description: Some(description),
+                } => format!("Identity provider returned an error: {error}: {description}"),
+                LoginErrorType::Provider { error, .. } => {
+                    format!("Identity provider returned an error: {error}")
+                }
+                LoginErrorType::InvalidIdToken { err } => format!("ID token is invalid: {err}"),
+                LoginErrorType::NonceMismatch => String::from("ID token nonce does not match"),
+            });
+
+        LoginError {
+            verbosity,
+            r#type,
+            reason,
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            LoginErrorType::InvalidState | LoginErrorType::MissingCode => StatusCode::BAD_REQUEST,
+            LoginErrorType::Provider { .. }
+            | LoginErrorType::InvalidIdToken { .. }
+            | LoginErrorType::NonceMismatch => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            LoginErrorType::InvalidState => error_codes::LOGIN_INVALID_STATE,
+            LoginErrorType::MissingCode => error_codes::LOGIN_MISSING_CODE,
+            LoginErrorType::Provider { .. } => error_codes::LOGIN_PROVIDER,
+            LoginErrorType::InvalidIdToken { .. } => error_codes::LOGIN_INVALID_ID_TOKEN,
+            LoginErrorType::NonceMismatch => error_codes::LOGIN_NONCE_MISMATCH,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub enum SessionErrorType {
+    /// The request has no session or the session has expired.
+    Missing,
+    /// The session data can not be deserialized.
+    InvalidData {
+        #[serde(skip)]
+        err: serde_json::Error,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionError {
+    #[serde(skip)]
+    verbosity: PrivateErrorVerbosity,
+    r#type: SessionErrorType,
+    reason: Option<String>,
+}
+
+impl SessionError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: SessionErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| match &r#type {
+                SessionErrorType::Missing => String::from("No session found"),
+                SessionErrorType::InvalidData { err } => format!("Session data is invalid: {err}"),
+            });
+
+        SessionError {
+            verbosity,
+            r#type,
+            reason,
+        }
     }
 
-    fn status_code(&self) -> StatusCode {
-        StatusCode::METHOD_NOT_ALLOWED
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            SessionErrorType::Missing => error_codes::SESSION_MISSING,
+            SessionErrorType::InvalidData { .. } => error_codes::SESSION_INVALID_DATA,
+        }
     }
 }
 
 #[derive(Debug, Serialize)]
-pub struct NotFoundError {
+pub enum TokenGrantErrorType {
+    /// The grant type is not supported.
+    UnsupportedGrantType {
+        #[serde(skip)]
+        grant_type: String,
+    },
+    /// The refresh token is unknown or expired.
+    InvalidRefreshToken,
+    /// The refresh token was already used. The token family is revoked.
+    RefreshTokenReused,
+    /// The client is not allowed the requested scope.
+    InvalidScope {
+        #[serde(skip)]
+        scope: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenGrantError {
     #[serde(skip)]
-    verbosity: ErrorVerbosity,
+    verbosity: PrivateErrorVerbosity,
+    r#type: TokenGrantErrorType,
+    reason: Option<String>,
 }
 
-impl NotFoundError {
-    pub fn new(verbosity: ErrorVerbosity) -> Self {
-        NotFoundError { verbosity }
+impl TokenGrantError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: TokenGrantErrorType) -> Self {
+        let reason = verbosity
+            .should_generate_error_context()
+            .then(|| match &r#type {
+                TokenGrantErrorType::UnsupportedGrantType { grant_type } => {
+                    format!("Unsupported grant type: {grant_type}")
+                }
+                TokenGrantErrorType::InvalidRefreshToken => {
+                    String::from("Refresh token is unknown or expired")
+                }
+                TokenGrantErrorType::RefreshTokenReused => {
+                    String::from("Refresh token was already used")
+                }
+                TokenGrantErrorType::InvalidScope { scope } => {
+                    format!("Scope not allowed: {scope}")
+                }
+            });
+
+        TokenGrantError {
+            verbosity,
+            r#type,
+            reason,
+        }
     }
 
-    fn status_code(&self) -> StatusCode {
-        StatusCode::NOT_FOUND
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            TokenGrantErrorType::UnsupportedGrantType { .. } => {
+                error_codes::TOKEN_GRANT_UNSUPPORTED_GRANT_TYPE
+            }
+            TokenGrantErrorType::InvalidRefreshToken => {
+                error_codes::TOKEN_GRANT_INVALID_REFRESH_TOKEN
+            }
+            TokenGrantErrorType::RefreshTokenReused => {
+                error_codes::TOKEN_GRANT_REFRESH_TOKEN_REUSED
+            }
+            TokenGrantErrorType::InvalidScope { .. } => error_codes::TOKEN_GRANT_INVALID_SCOPE,
+        }
     }
 }
 
 #[derive(Debug, Serialize)]
-pub enum ApiKeyErrorType {
-    /// API key is missing.
-    Missing,
-    /// API key contains invalid characters.
-    InvalidChars {
+pub enum SignatureErrorType {
+    /// A signature header is missing.
+    MissingHeader {
         #[serde(skip)]
-        err: ToStrError,
+        header: &'static str,
     },
-    /// API key is invalid.
+    /// A signature header is malformed.
+    InvalidHeader {
+        #[serde(skip)]
+        header: &'static str,
+    },
+    /// The signature timestamp is outside of the allowed clock skew.
+    TimestampOutOfRange,
+    /// The signature key is not known.
+    UnknownKey {
+        #[serde(skip)]
+        key_id: String,
+    },
+    /// The signature does not match the request.
     Invalid,
 }
 
 #[derive(Debug, Serialize)]
-pub struct ApiKeyError {
+pub struct SignatureError {
     #[serde(skip)]
-    verbosity: ErrorVerbosity,
-    r#type: ApiKeyErrorType,
-    reason: Option<Cow<'static, str>>,
+    verbosity: PrivateErrorVerbosity,
+    r#type: SignatureErrorType,
+    reason: Option<String>,
 }
 
-impl ApiKeyError {
-    pub fn new(verbosity: ErrorVerbosity, r#type: ApiKeyErrorType) -> Self {
+impl SignatureError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: SignatureErrorType) -> Self {
         let reason = verbosity
             .should_generate_error_context()
-            .then(|| Self::reason(&r#type));
-
-        ApiKeyError {
+            .then(|| match &r#type {
+                SignatureErrorType::MissingHeader { header } => {
+                    format!("Signature header is missing: {header}")
+                }
+                SignatureErrorType::InvalidHeader { header } => {
+                    format!("Signature header is invalid: {header}")
+                }
+                SignatureErrorType::TimestampOutOfRange => {
+                    String::from("Signature timestamp is outside of the allowed skew")
+                }
+                SignatureErrorType::UnknownKey { key_id } => {
+                    format!("Unknown signature key: {key_id}")
+                }
+                SignatureErrorType::Invalid => String::from("Signature does not match the request"),
+            });
+
+        SignatureError {
             verbosity,
             r#type,
             reason,
         }
     }
 
-    fn reason(r#type: &ApiKeyErrorType) -> Cow<'static, str> {
-        match r#type {
-            ApiKeyErrorType::Missing => Cow::Borrowed("API key is missing"),
-            ApiKeyErrorType::InvalidChars { err } => {
-                Cow::Owned(format!("API key contains invalid characters: {err}"))
-            }
-            ApiKeyErrorType::Invalid => Cow::Borrowed("API key invalid"),
-        }
-    }
-
-    fn status_code(&self) -> StatusCode {
+    fn error_code(&self) -> &'static str {
         match self.r#type {
-            ApiKeyErrorType::Missing => StatusCode::UNAUTHORIZED,
-            ApiKeyErrorType::InvalidChars { .. } => StatusCode::UNAUTHORIZED,
-            ApiKeyErrorType::Invalid => StatusCode::FORBIDDEN,
+            SignatureErrorType::MissingHeader { .. } => error_codes::SIGNATURE_MISSING_HEADER,
+            SignatureErrorType::InvalidHeader { .. } => error_codes::SIGNATURE_INVALID_HEADER,
+            SignatureErrorType::TimestampOutOfRange => {
+                error_codes::SIGNATURE_TIMESTAMP_OUT_OF_RANGE
+            }
+            SignatureErrorType::UnknownKey { .. } => error_codes::SIGNATURE_UNKNOWN_KEY,
+            SignatureErrorType::Invalid => error_codes::SIGNATURE_INVALID,
         }
     }
 }
 
 #[derive(Debug, Serialize)]
-pub enum BasicAuthErrorType {
-    /// Authorization header is missing.
-    AuthMissing,
-    /// Authorization header contains invalid characters.
-    AuthInvalidChars {
-        #[serde(skip)]
-        err: ToStrError,
-    },
-    /// Authorization header could not be decoded.
-    Decode {
+pub enum ClientCertErrorType {
+    /// The connection did not present a client certificate.
+    Missing,
+    /// The client certificate can not be parsed.
+    Invalid {
         #[serde(skip)]
-        err: DecodeError,
+        err: x509_parser::nom::Err<x509_parser::error::X509Error>,
     },
-    /// Decoded authorization header contains invalid characters.
-    AuthInvalidUTF8 {
+    /// The client certificate is not allowed to access the API.
+    Unauthorized {
         #[serde(skip)]
-        err: FromUtf8Error,
+        subject: String,
     },
-    /// Authorization header is invalid Basic.
-    InvalidBasic,
-    /// Authentication failed.
-    Invalid,
 }
 
 #[derive(Debug, Serialize)]
-pub struct BasicAuthError {
+pub struct ClientCertError {
     #[serde(skip)]
-    verbosity: ErrorVerbosity,
-    r#type: BasicAuthErrorType,
-    reason: Option<Cow<'static, str>>,
+    verbosity: PrivateErrorVerbosity,
+    r#type: ClientCertErrorType,
+    reason: Option<String>,
 }
 
-impl BasicAuthError {
-    pub fn new(verbosity: ErrorVerbosity, r#type: BasicAuthErrorType) -> Self {
+impl ClientCertError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: ClientCertErrorType) -> Self {
         let reason = verbosity
             .should_generate_error_context()
-            .then(|| Self::reason(&r#type));
-
-        BasicAuthError {
+            .then(|| match &r#type {
+                ClientCertErrorType::Missing => String::from("No client certificate presented"),
+                ClientCertErrorType::Invalid { err } => {
+                    format!("Client certificate is invalid: {err}")
+                }
+                ClientCertErrorType::Unauthorized { subject } => {
+                    format!("Client certificate is not authorized: {subject}")
+                }
+            });
+
+        ClientCertError {
             verbosity,
             r#type,
             reason,
         }
     }
 
-    fn reason(r#type: &BasicAuthErrorType) -> Cow<'static, str> {
-        match r#type {
-            BasicAuthErrorType::AuthMissing => Cow::Borrowed("Authorization header is missing"),
-            BasicAuthErrorType::AuthInvalidChars { err } => Cow::Owned(format!(
-                "Authorization header contains invalid characters: {err}"
-            )),
-            BasicAuthErrorType::Decode { err } => {
-                Cow::Owned(format!("Authorization header could not be decoded: {err}"))
-            }
-            BasicAuthErrorType::InvalidBasic => {
-                Cow::Borrowed("Authorization header is invalid Basic")
+    fn status_code(&self) -> StatusCode {
+        match self.r#type {
+            ClientCertErrorType::Missing | ClientCertErrorType::Invalid { .. } => {
+                StatusCode::UNAUTHORIZED
             }
-            BasicAuthErrorType::AuthInvalidUTF8 { err } => Cow::Owned(format!(
-                "Decoded authorization header contains invalid characters: {err}"
-            )),
-            BasicAuthErrorType::Invalid => Cow::Borrowed("Basic auth is invalid"),
+            ClientCertErrorType::Unauthorized { .. } => StatusCode::FORBIDDEN,
         }
     }
 
-    fn status_code(&self) -> StatusCode {
-        StatusCode::UNAUTHORIZED
+    fn error_code(&self) -> &'static str {
+        match self.r#type {
+            ClientCertErrorType::Missing => error_codes::AUTH_CLIENT_CERT_MISSING,
+            ClientCertErrorType::Invalid { .. } => error_codes::AUTH_CLIENT_CERT_INVALID,
+            ClientCertErrorType::Unauthorized { .. } => error_codes::AUTH_CLIENT_CERT_UNAUTHORIZED,
+        }
     }
 }
 
+/// A single failed validation of a field.
 #[derive(Debug, Serialize)]
-pub enum BearerErrorType {
-    /// Authorization header is missing.
-    AuthMissing,
-    /// Authorization header contains invalid characters.
-    AuthInvalidChars {
-        #[serde(skip)]
-        err: ToStrError,
-    },
-    /// Authorization header is invalid Bearer.
-    InvalidBearer,
+pub struct FieldValidationError {
+    code: Cow<'static, str>,
+    /// Only set if the error verbosity is [`ErrorVerbosity::Full`].
+    message: Option<Cow<'static, str>>,
+    /// Only set if the error verbosity is [`ErrorVerbosity::Full`].
+    params: Option<HashMap<Cow<'static, str>, serde_json::Value>>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct BearerError {
+pub struct ValidationError {
     #[serde(skip)]
-    verbosity: ErrorVerbosity,
-    r#type: BearerErrorType,
-    reason: Option<Cow<'static, str>>,
+    verbosity: PrivateErrorVerbosity,
+    reason: Option<String>,
+    /// Failed validations by field path, e.g. `address.city` or `items[0].name`.
+    ///
+    /// Only set if the error verbosity is [`ErrorVerbosity::Type`] or [`ErrorVerbosity::Full`].
+    fields: Option<BTreeMap<String, Vec<FieldValidationError>>>,
 }
 
-impl BearerError {
-    pub fn new(verbosity: ErrorVerbosity, r#type: BearerErrorType) -> Self {
+impl ValidationError {
+    pub fn from_validation_errors(
+        verbosity: PrivateErrorVerbosity,
+        validation_errors: ValidationErrors,
+    ) -> Self {
         let reason = verbosity
             .should_generate_error_context()
-            .then(|| Self::reason(&r#type));
-
-        BearerError {
+            .then(|| validation_errors.to_string());
+
+        let fields =
+            matches!(verbosity.get(), ErrorVerbosity::Type | ErrorVerbosity::Full).then(|| {
+                let mut fields = BTreeMap::new();
+                Self::collect_fields(
+                    verbosity.should_generate_error_context(),
+                    "",
+                    &validation_errors,
+                    &mut fields,
+                );
+
+                fields
+            });
+
+        ValidationError {
             verbosity,
-            r#type,
             reason,
+            fields,
         }
     }
 
-    fn reason(r#type: &BearerErrorType) -> Cow<'static, str> {
-        match r#type {
-            BearerErrorType::AuthMissing => Cow::Borrowed("Authorization header is missing"),
-            BearerErrorType::AuthInvalidChars { err } => Cow::Owned(format!(
-                "Authorization header contains invalid characters: {err}"
-            )),
-            BearerErrorType::InvalidBearer => {
-                Cow::Borrowed("Authorization header is invalid Bearer")
+    fn collect_fields(
+        with_context: bool,
+        prefix: &str,
+        validation_errors: &ValidationErrors,
+        fields: &mut BTreeMap<String, Vec<FieldValidationError>>,
+    ) {
+        for (field, kind) in validation_errors.errors() {
+            let path = match prefix.is_empty() {
+                true => field.to_string(),
+                false => format!("{prefix}.{field}"),
+            };
+
+            match kind {
+                ValidationErrorsKind::Field(errors) => {
+                    let errors = errors.iter().map(|error| FieldValidationError {
+                        code: error.code.clone(),
+                        message: with_context.then(|| error.message.clone()).flatten(),
+                        params: with_context.then(|| error.params.clone()),
+                    });
+
+                    fields.entry(path).or_default().extend(errors);
+                }
+                ValidationErrorsKind::Struct(errors) => {
+                    Self::collect_fields(with_context, &path, errors, fields);
+                }
+                ValidationErrorsKind::List(list) => {
+                    for (index, errors) in list {
+                        Self::collect_fields(
+                            with_context,
+                            &format!("{path}[{index}]"),
+                            errors,
+                            fields,
+                        );
+                    }
+                }
             }
         }
     }
 
     fn status_code(&self) -> StatusCode {
-        StatusCode::UNAUTHORIZED
+        StatusCode::UNPROCESSABLE_ENTITY
     }
 }
 
 #[derive(Debug, Serialize)]
-pub enum JwtErrorType {
-    /// JWT validation failed.
-    Invalid {
-        #[serde(skip)]
-        err: JwtValidationError,
-    },
-    /// ExpiredSignature is a special case of Invalid.
-    ///
-    /// Intentionally extracted from the Invalid variant to provide a more specific error message.
-    ExpiredSignature,
-    /// User does not have a valid role.
-    Forbidden,
+pub enum GeoIpErrorType {
+    /// Client's country is blocked.
+    CountryBlocked,
 }
 
 #[derive(Debug, Serialize)]
-pub struct JwtError {
+pub struct GeoIpError {
     #[serde(skip)]
-    verbosity: ErrorVerbosity,
-    r#type: JwtErrorType,
+    verbosity: PrivateErrorVerbosity,
+    r#type: GeoIpErrorType,
     reason: Option<Cow<'static, str>>,
 }
 
-impl JwtError {
-    pub fn new(verbosity: ErrorVerbosity, r#type: JwtErrorType) -> Self {
+impl GeoIpError {
+    pub fn new(verbosity: PrivateErrorVerbosity, r#type: GeoIpErrorType) -> Self {
         let reason = verbosity
             .should_generate_error_context()
             .then(|| Self::reason(&r#type));
 
-        JwtError {
+        GeoIpError {
             verbosity,
             r#type,
             reason,
         }
     }
 
-    fn reason(r#type: &JwtErrorType) -> Cow<'static, str> {
+    fn reason(r#type: &GeoIpErrorType) -> Cow<'static, str> {
         match r#type {
-            JwtErrorType::Invalid { err } => Cow::Owned(format!("JWT is invalid: {err}")),
-            JwtErrorType::ExpiredSignature => Cow::Borrowed("JWT has expired"),
-            JwtErrorType::Forbidden => Cow::Borrowed("User does not have a valid role"),
+            GeoIpErrorType::CountryBlocked => Cow::Borrowed("Client's country is blocked"),
         }
     }
 
     fn status_code(&self) -> StatusCode {
+        StatusCode::FORBIDDEN
+    }
+
+    fn error_code(&self) -> &'static str {
         match self.r#type {
-            JwtErrorType::Invalid { .. } | JwtErrorType::ExpiredSignature => {
-                StatusCode::UNAUTHORIZED
-            }
-            JwtErrorType::Forbidden => StatusCode::FORBIDDEN,
+            GeoIpErrorType::CountryBlocked => error_codes::GEOIP_COUNTRY_BLOCKED,
         }
     }
 }
 
 #[derive(Debug, Serialize)]
-pub struct ValidationError {
+pub struct IpFilterError {
     #[serde(skip)]
-    verbosity: ErrorVerbosity,
+    verbosity: PrivateErrorVerbosity,
     reason: Option<String>,
 }
 
-impl ValidationError {
-    pub fn from_validation_errors(
-        verbosity: ErrorVerbosity,
-        validation_errors: ValidationErrors,
-    ) -> Self {
+impl IpFilterError {
+    pub fn new(verbosity: PrivateErrorVerbosity, ip: IpAddr, rule: IpFilterRule) -> Self {
         let reason = verbosity
             .should_generate_error_context()
-            .then_some(validation_errors.to_string());
-
-        ValidationError { verbosity, reason }
-    }
+            .then(|| format!("Client IP {ip} matches {rule}"));
 
-    fn status_code(&self) -> StatusCode {
-        StatusCode::UNPROCESSABLE_ENTITY
+        IpFilterError { verbosity, reason }
     }
 }
 
@@ -724,13 +3543,29 @@ impl ValidationError {
 struct ResourceErrorResponse<ET, C> {
     #[serde(flatten)]
     error: ResourceError<ET, C>,
-    message: &'static str,
+    /// The verbosity the error is rendered with.
+    #[serde(skip)]
+    verbosity: ErrorVerbosity,
+    /// The message of the error, localized if the request negotiated a locale.
+    message: Cow<'static, str>,
+    /// The stable code of the error.
+    ///
+    /// Only set if the error verbosity is [`ErrorVerbosity::Type`] or [`ErrorVerbosity::Full`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<&'static str>,
+    /// The id of the request the error occurred in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    /// When the error occurred.
+    timestamp: DateTime<Utc>,
 }
 
 impl<ET, C> From<ResourceErrorResponse<ET, C>> for ErrorMessage {
     fn from(response: ResourceErrorResponse<ET, C>) -> Self {
         ErrorMessage {
             message: response.message,
+            request_id: response.request_id,
+            timestamp: response.timestamp,
         }
     }
 }
@@ -742,13 +3577,32 @@ impl<ET, C> From<ResourceErrorResponse<ET, C>> for ErrorMessage {
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ResourceError<ET, C> {
     #[serde(skip)]
-    verbosity: ErrorVerbosity,
+    verbosity: PrivateErrorVerbosity,
     #[serde(flatten)]
     error_type: ET,
     #[serde(rename = "error")]
     context: Option<C>,
 }
 
+/// Derives [`ResourceErrorProvider`] from the `#[status(...)]`, `#[message(...)]`, `#[code(...)]` and `#[context(...)]` attributes.
+///
+/// ```rust,ignore
+/// #[derive(Debug, Serialize, ResourceError)]
+/// #[serde(tag = "error_type")]
+/// #[context(GetBookErrorContext)]
+/// pub enum GetBookErrorType {
+///     #[status(NOT_FOUND)]
+///     #[message("Book not found")]
+///     #[code(BOOK_NOT_FOUND)]
+///     #[context(GetBookErrorContext { context: format!("Book with id {id} not found") })]
+///     NotFound {
+///         #[serde(skip)]
+///         id: i64,
+///     },
+/// }
+/// ```
+pub use the_axum_derive::ResourceError;
+
 /// Must be implemented for a specific error type to be used in [`ResourceError`].
 pub trait ResourceErrorProvider {
     /// Resource specific context.
@@ -766,6 +3620,12 @@ pub trait ResourceErrorProvider {
     /// Message to be returned with the error.
     fn message(&self) -> &'static str;
 
+    /// Stable code to be returned with the error. See [`error_codes`].
+    fn error_code(&self) -> &'static str;
+
+    /// Name of the error type, used to count the error. See [`ErrorCounter`](crate::error_metrics::ErrorCounter).
+    fn error_type(&self) -> &'static str;
+
     /// Context to be returned with the error.
     fn context(&self) -> Self::Context;
 }
@@ -774,7 +3634,7 @@ impl<ET, C> ResourceError<ET, C>
 where
     ET: ResourceErrorProvider<Context = C>,
 {
-    pub fn new(verbosity: ErrorVerbosity, error_type: ET) -> Self {
+    pub fn new(verbosity: PrivateErrorVerbosity, error_type: ET) -> Self {
         let context = verbosity
             .should_generate_error_context()
             .then_some(error_type.context());
@@ -789,12 +3649,30 @@ where
 
 impl<ET, C> From<ResourceError<ET, C>> for ResourceErrorResponse<ET, C>
 where
-    ET: ResourceErrorProvider<Context = C>,
+    ET: ResourceErrorProvider<Context = C> + Serialize,
+    C: Serialize,
 {
     fn from(error: ResourceError<ET, C>) -> Self {
-        let message = error.error_type.message();
+        let verbosity = error.verbosity.get().for_category(ErrorCategory::Resource);
+
+        let message = localized_message(
+            error.error_type.error_code(),
+            error.error_type.message(),
+            &error,
+            verbosity,
+        );
+
+        let error_code = matches!(verbosity, ErrorVerbosity::Type | ErrorVerbosity::Full)
+            .then(|| error.error_type.error_code());
 
-        ResourceErrorResponse { error, message }
+        ResourceErrorResponse {
+            error,
+            verbosity,
+            message,
+            error_code,
+            request_id: RequestId::current().map(|request_id| request_id.to_string()),
+            timestamp: Utc::now(),
+        }
     }
 }
 
@@ -804,7 +3682,99 @@ where
     C: Serialize,
 {
     fn into_response(self) -> Response {
-        ResourceErrorResponse::from(self).into_response()
+        let status_code = self.error_type.status_code();
+
+        let reported = status_code.is_server_error().then(|| {
+            ReportedError::new(
+                false,
+                status_code,
+                serde_json::to_value(&self).ok(),
+                self.error_type.error_code(),
+                self.error_type.message(),
+                None,
+            )
+        });
+
+        let counted = CountedError {
+            error_type: self.error_type.error_type(),
+            status: status_code,
+        };
+
+        let mut response = ResourceErrorResponse::from(self).into_response();
+
+        response.extensions_mut().insert(counted);
+
+        if let Some(reported) = reported {
+            response.extensions_mut().insert(reported);
+        }
+
+        response
+    }
+}
+
+/// Error of a route that can fail with both an [`ApiError`] and a [`ResourceError`].
+///
+/// Both convert into it, so `?` can be used on either of them in the same handler.
+///
+/// ```rust,ignore
+/// async fn get_book(
+///     ApiDeadline(deadline): ApiDeadline,
+///     State(state): State<ApiState>,
+/// ) -> Result<GetBookResponse, RouteError<GetBookErrorType, GetBookErrorContext>> {
+///     let book = deadline.run(lookup).await?;
+///
+///     let book = book.ok_or_else(|| {
+///         ResourceError::new(state.error_verbosity(), GetBookErrorType::NotFound { id })
+///     })?;
+///
+///     Ok(GetBookResponse { book })
+/// }
+/// ```
+#[derive(Debug)]
+pub enum RouteError<ET, C> {
+    Api(ApiError),
+    Resource(ResourceError<ET, C>),
+}
+
+impl<ET, C> RouteError<ET, C> {
+    /// Creates the error from anything that converts into an [`ApiError`].
+    pub fn api(error: impl Into<ApiError>) -> Self {
+        Self::Api(error.into())
+    }
+}
+
+impl<ET, C> RouteError<ET, C>
+where
+    ET: ResourceErrorProvider<Context = C>,
+{
+    /// Creates a [`ResourceError`] with the error type.
+    pub fn resource(verbosity: PrivateErrorVerbosity, error_type: ET) -> Self {
+        Self::Resource(ResourceError::new(verbosity, error_type))
+    }
+}
+
+impl<ET, C> From<ApiError> for RouteError<ET, C> {
+    fn from(error: ApiError) -> Self {
+        Self::Api(error)
+    }
+}
+
+impl<ET, C> From<ResourceError<ET, C>> for RouteError<ET, C> {
+    fn from(error: ResourceError<ET, C>) -> Self {
+        Self::Resource(error)
+    }
+}
+
+impl<ET, C> IntoResponse for RouteError<ET, C>
+where
+    ET: ResourceErrorProvider<Context = C> + Serialize,
+    C: Serialize,
+{
+    fn into_response(self) -> Response {
+        match self {
+            Self::Api(error) => error.into_response(),
+            Self::Resource(error) => error.into_response(),
+        }
     }
 }
 
@@ -814,22 +3784,68 @@ where
     C: Serialize,
 {
     fn into_response(self) -> Response {
-        let headers = self.error.error_type.headers().unwrap_or_default();
+        let mut headers = self.error.error_type.headers().unwrap_or_default();
+        let lowered = self.verbosity < self.error.verbosity.get();
+        let error_code = self.error.error_type.error_code();
+        let localized = localized_messages(self.verbosity, &mut headers);
 
-        match self.error.verbosity {
+        match self.verbosity {
             ErrorVerbosity::None => StatusCode::NO_CONTENT.into_response(),
+            ErrorVerbosity::RandomStatus => random_status_code().into_response(),
             ErrorVerbosity::StatusCode => {
                 (self.error.error_type.status_code(), headers).into_response()
             }
+            verbosity @ (ErrorVerbosity::Message | ErrorVerbosity::Type | ErrorVerbosity::Full)
+                if is_problem_details() =>
+            {
+                let error = (!matches!(verbosity, ErrorVerbosity::Message))
+                    .then(|| serde_json::to_value(&self.error).ok())
+                    .flatten()
+                    .map(|mut error| {
+                        if lowered {
+                            clear_error_content(&mut error, &[]);
+                        }
+
+                        localize_reason(&mut error, error_code, localized.as_ref());
+
+                        error
+                    });
+
+                problem_details_response(
+                    self.error.error_type.status_code(),
+                    headers,
+                    self.message,
+                    error,
+                    self.error_code,
+                    self.request_id,
+                    self.timestamp,
+                )
+            }
             ErrorVerbosity::Message => (
                 self.error.error_type.status_code(),
                 headers,
                 Json(ErrorMessage::from(self)),
             )
                 .into_response(),
-            ErrorVerbosity::Type | ErrorVerbosity::Full => {
+            ErrorVerbosity::Type | ErrorVerbosity::Full if !lowered && localized.is_none() => {
                 (self.error.error_type.status_code(), headers, Json(self)).into_response()
             }
+            ErrorVerbosity::Type | ErrorVerbosity::Full => {
+                let status_code = self.error.error_type.status_code();
+
+                match serde_json::to_value(&self) {
+                    Ok(mut response) => {
+                        if lowered {
+                            clear_error_content(&mut response, &[]);
+                        }
+
+                        localize_reason(&mut response, error_code, localized.as_ref());
+
+                        (status_code, headers, Json(response)).into_response()
+                    }
+                    Err(_) => (status_code, headers).into_response(),
+                }
+            }
         }
     }
 }
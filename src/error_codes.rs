@@ -0,0 +1,223 @@
+//! Stable machine-readable codes of the errors.
+//!
+//! Emitted as the `error_code` of the error responses if the verbosity is [`ErrorVerbosity::Type`](crate::error::ErrorVerbosity::Type) or higher.
+//! Codes never change once released, even if the messages are reworded.
+//! The catalogue in `error_codes.md` is generated with `cargo run --bin error_codes > error_codes.md`.
+
+macro_rules! error_codes {
+    ($($(#[doc = $doc:literal])+ $code:ident,)+) => {
+        $(
+            $(#[doc = $doc])+
+            pub const $code: &str = stringify!($code);
+        )+
+
+        /// Every error code with its description.
+        pub const CATALOGUE: &[(&str, &str)] = &[$((stringify!($code), concat!($($doc),+))),+];
+    };
+}
+
+error_codes! {
+    /// An internal server error occurred.
+    INTERNAL_SERVER_ERROR,
+    /// The query parameters could not be deserialized.
+    QUERY_DESERIALIZE,
+    /// The query contains parameters that are not expected.
+    QUERY_UNKNOWN_FIELDS,
+    /// A header value contains invalid characters.
+    HEADER_INVALID_VALUE,
+    /// The headers could not be deserialized.
+    HEADER_DESERIALIZE,
+    /// The cookie header is malformed.
+    COOKIE_INVALID_HEADER,
+    /// The signature of a signed cookie is invalid.
+    COOKIE_INVALID_SIGNATURE,
+    /// The cookies could not be deserialized.
+    COOKIE_DESERIALIZE,
+    /// The path parameters could not be deserialized.
+    PATH_DESERIALIZE,
+    /// The body is well formed but does not match the expected structure.
+    BODY_DATA,
+    /// The body is malformed.
+    BODY_SYNTAX,
+    /// The content type of the body is missing or not supported.
+    BODY_CONTENT_TYPE,
+    /// The body contains fields that are not expected.
+    BODY_UNKNOWN_FIELDS,
+    /// The body exceeds the size limit.
+    BODY_TOO_LARGE,
+    /// The text body is not valid UTF-8.
+    BODY_INVALID_UTF8,
+    /// The multipart boundary is missing or invalid.
+    MULTIPART_INVALID_BOUNDARY,
+    /// The multipart body could not be read.
+    MULTIPART_READ,
+    /// A multipart field exceeds the field size limit.
+    MULTIPART_FIELD_TOO_LARGE,
+    /// The multipart body exceeds the total size limit.
+    MULTIPART_TOTAL_TOO_LARGE,
+    /// A multipart text field is not valid UTF-8.
+    MULTIPART_INVALID_UTF8,
+    /// A multipart field could not be deserialized.
+    MULTIPART_DESERIALIZE,
+    /// The request failed validation.
+    VALIDATION_FAILED,
+    /// The pagination parameters are invalid.
+    PAGINATION_INVALID_PARAMETERS,
+    /// Offset and cursor pagination parameters were mixed.
+    PAGINATION_MIXED_STYLES,
+    /// The page is out of range.
+    PAGINATION_OUT_OF_RANGE,
+    /// Forwarding headers were sent by a proxy that is not trusted.
+    CLIENT_IP_UNTRUSTED_PROXY,
+    /// A forwarding header is malformed.
+    CLIENT_IP_INVALID_FORWARDING_HEADER,
+    /// The tenant of the request is missing.
+    TENANT_MISSING,
+    /// The tenant of the request is unknown.
+    TENANT_UNKNOWN,
+    /// The method is not allowed for the path.
+    METHOD_NOT_ALLOWED,
+    /// No route matches the path.
+    NOT_FOUND,
+    /// The principal is not allowed to access the resource.
+    FORBIDDEN,
+    /// A conditional request header does not match.
+    PRECONDITION_FAILED,
+    /// The deadline of the request was exceeded.
+    DEADLINE_EXCEEDED,
+    /// The request was not handled in time.
+    REQUEST_TIMEOUT,
+    /// The rate limit was exceeded.
+    TOO_MANY_REQUESTS,
+    /// Requests from the IP of the client are blocked.
+    IP_BLOCKED,
+    /// Requests from the country of the client are blocked.
+    GEOIP_COUNTRY_BLOCKED,
+    /// The CSRF cookie is missing.
+    CSRF_MISSING_COOKIE,
+    /// The CSRF header is missing.
+    CSRF_MISSING_HEADER,
+    /// The CSRF header does not match the cookie.
+    CSRF_MISMATCH,
+    /// The idempotency key is invalid.
+    IDEMPOTENCY_INVALID_KEY,
+    /// A request with the same idempotency key is still in progress.
+    IDEMPOTENCY_IN_PROGRESS,
+    /// The idempotency key was used with a different request.
+    IDEMPOTENCY_KEY_REUSED,
+    /// The server is overloaded.
+    SERVICE_OVERLOADED,
+    /// The server is in maintenance mode.
+    SERVICE_MAINTENANCE,
+    /// The API key is missing.
+    AUTH_API_KEY_MISSING,
+    /// The API key header contains invalid characters.
+    AUTH_API_KEY_INVALID_CHARS,
+    /// The API key is invalid.
+    AUTH_API_KEY_INVALID,
+    /// The API key has expired.
+    AUTH_API_KEY_EXPIRED,
+    /// The basic auth credentials are missing.
+    AUTH_BASIC_MISSING,
+    /// The authorization header contains invalid characters.
+    AUTH_BASIC_INVALID_CHARS,
+    /// The basic auth credentials are not valid base64.
+    AUTH_BASIC_DECODE,
+    /// The basic auth credentials are not valid UTF-8.
+    AUTH_BASIC_INVALID_UTF8,
+    /// The authorization header is not a basic auth header.
+    AUTH_BASIC_MALFORMED,
+    /// The basic auth credentials are invalid.
+    AUTH_BASIC_INVALID,
+    /// The digest credentials are missing.
+    AUTH_DIGEST_MISSING,
+    /// The authorization header contains invalid characters.
+    AUTH_DIGEST_INVALID_CHARS,
+    /// The authorization header is not a valid digest header.
+    AUTH_DIGEST_MALFORMED,
+    /// The digest algorithm is not supported.
+    AUTH_DIGEST_UNSUPPORTED_ALGORITHM,
+    /// The digest uri does not match the request.
+    AUTH_DIGEST_URI_MISMATCH,
+    /// The digest nonce was not issued by the server.
+    AUTH_DIGEST_UNKNOWN_NONCE,
+    /// The digest nonce has expired.
+    AUTH_DIGEST_STALE_NONCE,
+    /// The digest nonce count was already used.
+    AUTH_DIGEST_NONCE_REUSED,
+    /// The digest credentials are invalid.
+    AUTH_DIGEST_INVALID,
+    /// The bearer token is missing.
+    AUTH_BEARER_MISSING,
+    /// The authorization header contains invalid characters.
+    AUTH_BEARER_INVALID_CHARS,
+    /// The authorization header is not a bearer header.
+    AUTH_BEARER_MALFORMED,
+    /// The bearer token is not active.
+    AUTH_BEARER_INACTIVE_TOKEN,
+    /// The JWT is invalid.
+    AUTH_JWT_INVALID,
+    /// The JWT has expired.
+    AUTH_JWT_EXPIRED,
+    /// The JWT was revoked.
+    AUTH_JWT_REVOKED,
+    /// The JWT does not grant the required roles.
+    AUTH_JWT_FORBIDDEN,
+    /// No credentials were sent.
+    AUTH_PRINCIPAL_MISSING,
+    /// The credentials could not be mapped to a principal.
+    AUTH_PRINCIPAL_UNMAPPED,
+    /// The client certificate is missing.
+    AUTH_CLIENT_CERT_MISSING,
+    /// The client certificate is invalid.
+    AUTH_CLIENT_CERT_INVALID,
+    /// The client certificate is not authorized.
+    AUTH_CLIENT_CERT_UNAUTHORIZED,
+    /// A signature header is missing.
+    SIGNATURE_MISSING_HEADER,
+    /// A signature header is malformed.
+    SIGNATURE_INVALID_HEADER,
+    /// The signature timestamp is too old or in the future.
+    SIGNATURE_TIMESTAMP_OUT_OF_RANGE,
+    /// The signing key is unknown.
+    SIGNATURE_UNKNOWN_KEY,
+    /// The signature is invalid.
+    SIGNATURE_INVALID,
+    /// The login state is invalid.
+    LOGIN_INVALID_STATE,
+    /// The authorization code is missing.
+    LOGIN_MISSING_CODE,
+    /// The identity provider returned an error.
+    LOGIN_PROVIDER,
+    /// The ID token is invalid.
+    LOGIN_INVALID_ID_TOKEN,
+    /// The nonce of the ID token does not match.
+    LOGIN_NONCE_MISMATCH,
+    /// The session is missing or has expired.
+    SESSION_MISSING,
+    /// The session data is invalid.
+    SESSION_INVALID_DATA,
+    /// The grant type is not supported.
+    TOKEN_GRANT_UNSUPPORTED_GRANT_TYPE,
+    /// The refresh token is invalid.
+    TOKEN_GRANT_INVALID_REFRESH_TOKEN,
+    /// The refresh token was already used.
+    TOKEN_GRANT_REFRESH_TOKEN_REUSED,
+    /// The requested scope is not allowed for the client.
+    TOKEN_GRANT_INVALID_SCOPE,
+    /// The book does not exist.
+    BOOK_NOT_FOUND,
+    /// The book id is too big.
+    BOOK_ID_TOO_BIG,
+}
+
+/// Renders the [`CATALOGUE`] as a markdown table.
+pub fn catalogue_markdown() -> String {
+    let mut markdown = String::from("# Error codes\n\n| Code | Description |\n| --- | --- |\n");
+
+    for (code, description) in CATALOGUE {
+        markdown.push_str(&format!("| `{code}` | {} |\n", description.trim()));
+    }
+
+    markdown
+}
@@ -0,0 +1,63 @@
+//! Counting of the error responses by error type and status.
+//!
+//! The error responses attach a [`CountedError`] to the response extensions,
+//! which is picked up by the [`error_metrics`](crate::middleware::error_metrics::error_metrics) middleware
+//! and counted by the [`ErrorCounter`].
+
+use std::{collections::HashMap, sync::Mutex};
+
+use axum::http::StatusCode;
+use serde::Serialize;
+
+/// An error as attached to the response extensions by the error responses.
+///
+/// The status is the status of the error, even if it is rendered with another one, e.g. by [`ErrorVerbosity::RandomStatus`](crate::error::ErrorVerbosity::RandomStatus).
+#[derive(Debug, Clone, Copy)]
+pub struct CountedError {
+    pub error_type: &'static str,
+    pub status: StatusCode,
+}
+
+/// The number of errors of a type and status.
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorCount {
+    pub error_type: &'static str,
+    pub status: u16,
+    pub count: u64,
+}
+
+/// Counts the errors by type and status.
+#[derive(Debug, Default)]
+pub struct ErrorCounter {
+    counts: Mutex<HashMap<(&'static str, StatusCode), u64>>,
+}
+
+impl ErrorCounter {
+    pub fn increment(&self, error: CountedError) {
+        let mut counts = self.counts.lock().expect("error counter mutex poisoned");
+
+        *counts.entry((error.error_type, error.status)).or_default() += 1;
+    }
+
+    /// Returns the counts sorted by error type and status.
+    pub fn counts(&self) -> Vec<ErrorCount> {
+        let counts = self.counts.lock().expect("error counter mutex poisoned");
+
+        let mut counts = counts
+            .iter()
+            .map(|(&(error_type, status), &count)| ErrorCount {
+                error_type,
+                status: status.as_u16(),
+                count,
+            })
+            .collect::<Vec<_>>();
+
+        counts.sort_by_key(|count| (count.error_type, count.status));
+
+        counts
+    }
+}
+
+pub trait ErrorCounterProvider {
+    fn error_counter(&self) -> &ErrorCounter;
+}
@@ -0,0 +1,101 @@
+//! HTML error pages for browser-facing deployments.
+//!
+//! Errors are rendered as `text/html` if the [`ErrorFormat`](crate::problem_details::ErrorFormat) of the config is
+//! [`ErrorFormat::Html`](crate::problem_details::ErrorFormat::Html) or HTML pages are enabled and the `Accept` header
+//! lists `text/html` before any JSON media type, as browsers do.
+//! The pages hold the same information as the [`ProblemDetails`](crate::problem_details::ProblemDetails) of the error,
+//! so they follow the verbosity of the error.
+//!
+//! Templates use the placeholders `{status}`, `{title}`, `{detail}`, `{error_code}`, `{request_id}` and `{timestamp}`.
+//! Values are HTML-escaped, placeholders without a value are replaced with an empty string.
+
+use serde::Deserialize;
+
+pub const HTML_CONTENT_TYPE: &str = "text/html; charset=utf-8";
+
+/// Used if no template is configured.
+pub const DEFAULT_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{status} {title}</title>
+<style>
+body { margin: 0; font-family: system-ui, sans-serif; background: #f6f7f9; color: #1f2328; }
+main { max-width: 40rem; margin: 15vh auto; padding: 2rem; background: #fff; border-radius: 8px; box-shadow: 0 1px 3px rgba(0, 0, 0, 0.1); }
+h1 { margin: 0 0 0.5rem; font-size: 1.5rem; }
+.status { color: #cf222e; }
+.detail { margin: 1rem 0; }
+dl { margin: 1.5rem 0 0; font-size: 0.85rem; color: #59636e; }
+dd { margin: 0 0 0.5rem; font-family: ui-monospace, monospace; }
+</style>
+</head>
+<body>
+<main>
+<h1><span class="status">{status}</span> {title}</h1>
+<p class="detail">{detail}</p>
+<dl>
+<dt>Error code</dt><dd>{error_code}</dd>
+<dt>Request id</dt><dd>{request_id}</dd>
+<dt>Time</dt><dd>{timestamp}</dd>
+</dl>
+</main>
+</body>
+</html>
+"#;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HtmlErrorPageConfig {
+    /// Replaces the [`DEFAULT_TEMPLATE`].
+    pub template: Option<String>,
+}
+
+impl HtmlErrorPageConfig {
+    pub fn template(&self) -> &str {
+        self.template.as_deref().unwrap_or(DEFAULT_TEMPLATE)
+    }
+}
+
+/// Returns whether the `Accept` header lists an HTML media type before any JSON media type.
+pub fn prefers_html(accept: &str) -> bool {
+    accept
+        .split(',')
+        .filter_map(|media_type| media_type.split(';').next())
+        .map(str::trim)
+        .find(|media_type| {
+            matches!(
+                *media_type,
+                "text/html" | "application/xhtml+xml" | "application/json"
+            ) || media_type.ends_with("+json")
+        })
+        .is_some_and(|media_type| matches!(media_type, "text/html" | "application/xhtml+xml"))
+}
+
+/// Replaces the placeholders of the template with the escaped values.
+pub(crate) fn render(template: &str, values: &[(&str, Option<&str>)]) -> String {
+    values
+        .iter()
+        .fold(template.to_owned(), |rendered, (placeholder, value)| {
+            rendered.replace(
+                &format!("{{{placeholder}}}"),
+                &escape(value.unwrap_or_default()),
+            )
+        })
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,175 @@
+//! Reporting of server errors.
+//!
+//! Every [`InternalServerError`](crate::error::InternalServerError), and optionally every other `5xx` error,
+//! is sent as an [`ErrorReport`] to the configured [`ErrorSink`].
+//! The error responses attach a [`ReportedError`] to the response extensions,
+//! which is picked up by the [`error_reporting`](crate::middleware::error_reporting::error_reporting) middleware.
+//! Reporting happens in the background, so a slow sink does not delay the responses.
+
+use std::future::Future;
+
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sink::{TracingErrorSink, WebhookErrorSink};
+
+pub mod sink;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ErrorReportingConfig {
+    #[serde(default)]
+    pub sink: ErrorSinkConfig,
+    /// Whether all `5xx` errors are reported, not only internal server errors.
+    #[serde(default)]
+    pub all_server_errors: bool,
+}
+
+/// Where the error reports are sent to.
+#[derive(Derivative, Clone, Default, Deserialize)]
+#[derivative(Debug)]
+#[serde(tag = "type")]
+pub enum ErrorSinkConfig {
+    /// Logged with the `error_report` target.
+    #[default]
+    Tracing,
+    /// Posted as JSON to a webhook.
+    Webhook { url: String },
+    /// Sent as events to Sentry.
+    #[cfg(feature = "sentry")]
+    Sentry {
+        #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+        dsn: String,
+        environment: Option<String>,
+    },
+}
+
+/// A server error as attached to the response extensions by the error responses.
+///
+/// Holds the full context of the error, regardless of the verbosity the error is rendered with.
+#[derive(Debug, Clone)]
+pub struct ReportedError {
+    /// Whether the error is an [`InternalServerError`](crate::error::InternalServerError).
+    pub internal: bool,
+    pub status: StatusCode,
+    pub error_type: Option<String>,
+    pub error_code: &'static str,
+    pub message: &'static str,
+    /// The serialized content of the error.
+    pub error: Option<Value>,
+    /// The cause of an internal server error.
+    pub cause: Option<String>,
+}
+
+impl ReportedError {
+    /// Splits a serialized error into its `error_type` tag and its `error` content.
+    pub(crate) fn new(
+        internal: bool,
+        status: StatusCode,
+        serialized: Option<Value>,
+        error_code: &'static str,
+        message: &'static str,
+        cause: Option<String>,
+    ) -> Self {
+        let (error_type, error) = match serialized {
+            Some(Value::Object(mut serialized)) => {
+                let error_type = match serialized.remove("error_type") {
+                    Some(Value::String(error_type)) => Some(error_type),
+                    _ => None,
+                };
+
+                (
+                    error_type,
+                    serialized.remove("error").filter(|error| !error.is_null()),
+                )
+            }
+            _ => (None, None),
+        };
+
+        Self {
+            internal,
+            status,
+            error_type,
+            error_code,
+            message,
+            error,
+            cause,
+        }
+    }
+}
+
+/// A reported server error with the request it occurred in.
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorReport {
+    pub timestamp: DateTime<Utc>,
+    pub request_id: Option<String>,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub error_type: Option<String>,
+    pub error_code: &'static str,
+    pub message: &'static str,
+    pub error: Option<Value>,
+    pub cause: Option<String>,
+}
+
+pub trait ErrorReportingProvider {
+    /// Returns whether the error is reported.
+    ///
+    /// Returns `false` if no sink is configured.
+    fn reports_error(&self, error: &ReportedError) -> bool;
+
+    /// Reports the error without blocking the response.
+    fn report_error(&self, report: ErrorReport);
+}
+
+/// Receives the error reports.
+pub trait ErrorSink {
+    type Error;
+
+    fn report(&self, report: &ErrorReport) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// The error sink selected by the [`ErrorSinkConfig`].
+///
+/// Created once and kept in the state, so the size difference between the variants does not matter.
+#[allow(clippy::large_enum_variant)]
+pub enum ConfiguredErrorSink {
+    Tracing(TracingErrorSink),
+    Webhook(WebhookErrorSink),
+    #[cfg(feature = "sentry")]
+    Sentry(sink::SentryErrorSink),
+}
+
+impl ConfiguredErrorSink {
+    pub fn from_config(
+        config: &ErrorSinkConfig,
+        http_client: reqwest::Client,
+    ) -> anyhow::Result<Self> {
+        match config {
+            ErrorSinkConfig::Tracing => Ok(Self::Tracing(TracingErrorSink)),
+            ErrorSinkConfig::Webhook { url } => Ok(Self::Webhook(WebhookErrorSink::new(
+                url.clone(),
+                http_client,
+            ))),
+            #[cfg(feature = "sentry")]
+            ErrorSinkConfig::Sentry { dsn, environment } => Ok(Self::Sentry(
+                sink::SentryErrorSink::new(dsn, environment.clone())?,
+            )),
+        }
+    }
+}
+
+impl ErrorSink for ConfiguredErrorSink {
+    type Error = anyhow::Error;
+
+    async fn report(&self, report: &ErrorReport) -> Result<(), Self::Error> {
+        match self {
+            Self::Tracing(sink) => sink.report(report).await.map_err(anyhow::Error::from),
+            Self::Webhook(sink) => sink.report(report).await.map_err(anyhow::Error::from),
+            #[cfg(feature = "sentry")]
+            Self::Sentry(sink) => sink.report(report).await.map_err(anyhow::Error::from),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::convert::Infallible;
+
+use super::{ErrorReport, ErrorSink};
+
+/// Logs the error reports with the `error_report` target.
+#[derive(Debug, Clone)]
+pub struct TracingErrorSink;
+
+impl ErrorSink for TracingErrorSink {
+    type Error = Infallible;
+
+    async fn report(&self, report: &ErrorReport) -> Result<(), Self::Error> {
+        tracing::error!(target: "error_report", ?report, "Server error");
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookErrorSinkError {
+    #[error("Failed to send error report to webhook: {0}")]
+    Send(#[source] reqwest::Error),
+    #[error("Webhook responded with an error: {0}")]
+    Status(#[source] reqwest::Error),
+}
+
+/// Posts the error reports as JSON to a webhook.
+#[derive(Debug, Clone)]
+pub struct WebhookErrorSink {
+    url: String,
+    http_client: reqwest::Client,
+}
+
+impl WebhookErrorSink {
+    pub fn new(url: String, http_client: reqwest::Client) -> Self {
+        Self { url, http_client }
+    }
+}
+
+impl ErrorSink for WebhookErrorSink {
+    type Error = WebhookErrorSinkError;
+
+    async fn report(&self, report: &ErrorReport) -> Result<(), Self::Error> {
+        self.http_client
+            .post(&self.url)
+            .json(report)
+            .send()
+            .await
+            .map_err(WebhookErrorSinkError::Send)?
+            .error_for_status()
+            .map_err(WebhookErrorSinkError::Status)?;
+
+        Ok(())
+    }
+}
+
+/// Sends the error reports as events to Sentry.
+#[cfg(feature = "sentry")]
+pub struct SentryErrorSink {
+    client: sentry::Client,
+}
+
+#[cfg(feature = "sentry")]
+impl SentryErrorSink {
+    pub fn new(dsn: &str, environment: Option<String>) -> anyhow::Result<Self> {
+        let options = sentry::ClientOptions {
+            dsn: Some(
+                dsn.parse()
+                    .map_err(|err| anyhow::anyhow!("Invalid Sentry DSN: {err}"))?,
+            ),
+            environment: environment.map(Into::into),
+            ..Default::default()
+        };
+
+        Ok(Self {
+            client: sentry::Client::from_config(sentry::apply_defaults(options)),
+        })
+    }
+}
+
+#[cfg(feature = "sentry")]
+impl ErrorSink for SentryErrorSink {
+    type Error = Infallible;
+
+    async fn report(&self, report: &ErrorReport) -> Result<(), Self::Error> {
+        use sentry::protocol::{Event, Level, Value};
+
+        let mut event = Event {
+            level: Level::Error,
+            message: Some(match &report.cause {
+                Some(cause) => format!("{}: {cause}", report.message),
+                None => report.message.to_owned(),
+            }),
+            transaction: Some(format!("{} {}", report.method, report.path)),
+            timestamp: report.timestamp.into(),
+            ..Default::default()
+        };
+
+        event
+            .tags
+            .insert(String::from("error_code"), report.error_code.to_owned());
+        event
+            .tags
+            .insert(String::from("status"), report.status.to_string());
+
+        if let Some(request_id) = &report.request_id {
+            event
+                .tags
+                .insert(String::from("request_id"), request_id.clone());
+        }
+
+        if let Some(error_type) = &report.error_type {
+            event
+                .tags
+                .insert(String::from("error_type"), error_type.clone());
+        }
+
+        if let Some(error) = &report.error {
+            event.extra.insert(String::from("error"), error.clone());
+        }
+
+        event
+            .extra
+            .insert(String::from("path"), Value::String(report.path.clone()));
+
+        self.client.capture_event(event, None);
+
+        Ok(())
+    }
+}
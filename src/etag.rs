@@ -0,0 +1,36 @@
+//! Strong ETags computed over the response bodies of configured routes.
+//!
+//! See [`etag`](crate::middleware::etag::etag).
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ETagConfig {
+    /// Responses to requests whose path starts with one of these prefixes get an ETag.
+    pub path_prefixes: Vec<String>,
+}
+
+impl ETagConfig {
+    pub fn applies_to(&self, path: &str) -> bool {
+        self.path_prefixes.iter().any(|prefix| {
+            let prefix = prefix.trim_end_matches('/');
+
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+}
+
+pub trait ETagProvider {
+    /// Returns the ETag config.
+    ///
+    /// Returns `None` if ETag generation is disabled.
+    fn etag_config(&self) -> Option<&ETagConfig>;
+}
+
+/// Returns the quoted strong ETag of the body, e.g. `"n4bQgYhMfWWaL-qgxVrQFaO_TxsrC4Is0V1sFbDwCgg"`.
+pub fn strong_etag(body: &[u8]) -> String {
+    format!("\"{}\"", URL_SAFE_NO_PAD.encode(Sha256::digest(body)))
+}
@@ -0,0 +1,51 @@
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+
+use crate::{error::ApiError, openapi::OperationInput};
+
+/// Extracts all inner extractors from the request.
+///
+/// Rejects with the [`ApiError`] of the first failing extractor.
+/// Inner extractors are run in order.
+///
+/// ```ignore
+/// async fn handler(All((api_key, basic_auth)): All<(ValidApiKey, ApiAuthenticatedBasicAuth)>) {}
+/// ```
+pub struct All<T>(pub T);
+
+impl<T> OperationInput for All<T> {}
+
+macro_rules! impl_all {
+    ($($ty:ident),+) => {
+        #[async_trait]
+        #[allow(non_snake_case)]
+        impl<S, $($ty,)+> FromRequestParts<S> for All<($($ty,)+)>
+        where
+            S: Send + Sync,
+            $($ty: FromRequestParts<S, Rejection = ApiError> + Send,)+
+        {
+            type Rejection = ApiError;
+
+            #[tracing::instrument(name = "all_extractor", skip_all)]
+            async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+                $(
+                    let $ty = $ty::from_request_parts(parts, state).await.map_err(|err| {
+                        tracing::warn!(extractor = std::any::type_name::<$ty>(), "Rejection");
+
+                        err
+                    })?;
+                )+
+
+                Ok(All(($($ty,)+)))
+            }
+        }
+    };
+}
+
+impl_all!(T1);
+impl_all!(T1, T2);
+impl_all!(T1, T2, T3);
+impl_all!(T1, T2, T3, T4);
+impl_all!(T1, T2, T3, T4, T5);
+impl_all!(T1, T2, T3, T4, T5, T6);
+impl_all!(T1, T2, T3, T4, T5, T6, T7);
+impl_all!(T1, T2, T3, T4, T5, T6, T7, T8);
@@ -1,10 +1,11 @@
-use std::future::Future;
+use std::{future::Future, net::IpAddr};
 
 use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
 
 use crate::{
     error::{ApiError, ApiKeyError, ApiKeyErrorType, ErrorVerbosityProvider},
-    types::used_api_key::UsedApiKey,
+    openapi::OperationInput,
+    types::used_api_key::{KeyInfo, UsedApiKey},
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -21,17 +22,26 @@ pub trait ApiKeyProvider {
     /// Returns the API key header name.
     fn header_name(&self) -> &str;
 
-    /// Validates the API key.
+    /// Validates the API key and returns its metadata.
+    ///
+    /// Expired keys may be returned. [`ValidApiKey`](super::valid_api_key::ValidApiKey) rejects them.
     fn validate(
         &self,
         key: &str,
-    ) -> impl Future<Output = Result<(), ApiKeyProviderError<Self::Error>>> + Send;
+    ) -> impl Future<Output = Result<KeyInfo, ApiKeyProviderError<Self::Error>>> + Send;
+
+    /// Called after an API key was rejected as invalid.
+    ///
+    /// Does nothing by default.
+    fn on_invalid(&self, _key: &str, _client_ip: Option<IpAddr>) {}
 }
 
 /// Extracts the API key from the request headers.
 #[derive(Debug, Clone)]
 pub struct ApiKey(pub UsedApiKey);
 
+impl OperationInput for ApiKey {}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for ApiKey
 where
@@ -0,0 +1,56 @@
+//! Helpers used by the code generated by [`ApiRequest`].
+
+use std::str::FromStr;
+
+use axum::http::request::Parts;
+
+use crate::{
+    error::{ApiError, HeaderError},
+    state::PrivateErrorVerbosity,
+};
+
+pub use the_axum_derive::ApiRequest;
+
+/// Parses the header with the given name. Returns `None` if the header is missing.
+pub fn header<T>(
+    parts: &Parts,
+    name: &str,
+    verbosity: PrivateErrorVerbosity,
+) -> Result<Option<T>, ApiError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    let Some(value) = parts.headers.get(name) else {
+        return Ok(None);
+    };
+
+    let value = value.to_str().map_err(|err| {
+        tracing::warn!(%name, %err, "Rejection. Invalid header value");
+
+        HeaderError::from_invalid_header_value(verbosity, name)
+    })?;
+
+    value.parse::<T>().map(Some).map_err(|err| {
+        tracing::warn!(%name, %err, "Rejection. Failed to parse header");
+
+        HeaderError::from_missing_or_invalid_header(verbosity, name, Some(&err))
+    })
+}
+
+/// Same as [`header`] but rejects if the header is missing.
+pub fn required_header<T>(
+    parts: &Parts,
+    name: &str,
+    verbosity: PrivateErrorVerbosity,
+) -> Result<T, ApiError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    header::<T>(parts, name, verbosity)?.ok_or_else(|| {
+        tracing::warn!(%name, "Rejection. Missing header");
+
+        HeaderError::from_missing_or_invalid_header(verbosity, name, None)
+    })
+}
@@ -5,6 +5,7 @@ use crate::{
         ApiError, BasicAuthError, BasicAuthErrorType, ErrorVerbosityProvider, InternalServerError,
     },
     extractor::basic_auth::BasicAuthProviderError,
+    openapi::OperationInput,
     types::used_basic_auth::UsedBasicAuth,
 };
 
@@ -14,6 +15,8 @@ use super::basic_auth::{ApiBasicAuth, BasicAuthProvider};
 #[derive(Debug, Clone)]
 pub struct ApiAuthenticatedBasicAuth(pub UsedBasicAuth);
 
+impl OperationInput for ApiAuthenticatedBasicAuth {}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for ApiAuthenticatedBasicAuth
 where
@@ -0,0 +1,174 @@
+use std::{collections::HashMap, fmt::Display, future::Future, marker::PhantomData};
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use serde::Deserialize;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, ForbiddenError, InternalServerError},
+    openapi::{ApiOperation, OperationInput},
+};
+
+use super::{
+    authenticated_basic_auth::ApiAuthenticatedBasicAuth, jwt::ApiJwt, jwt_roles::HasRoles,
+    valid_api_key::ValidApiKey, Extractor,
+};
+
+/// Derives [`Policy`] from the `#[policy("...")]` attribute.
+///
+/// ```rust,ignore
+/// #[derive(Policy)]
+/// #[policy("books:read")]
+/// pub struct BooksRead;
+/// ```
+pub use the_axum_derive::Policy;
+
+/// A named permission a route requires, e.g. `books:read`.
+pub trait Policy {
+    const NAME: &'static str;
+}
+
+/// The authenticated identity a policy is evaluated against.
+pub enum Subject<'a> {
+    Jwt(&'a (dyn HasRoles + Sync)),
+    ApiKey(&'a str),
+    BasicAuth { username: &'a str },
+}
+
+/// An extractor that authenticates an identity.
+pub trait PolicySubject {
+    fn subject(&self) -> Subject<'_>;
+}
+
+impl<C: HasRoles + Sync> PolicySubject for ApiJwt<C> {
+    fn subject(&self) -> Subject<'_> {
+        Subject::Jwt(&self.0)
+    }
+}
+
+impl PolicySubject for ValidApiKey {
+    fn subject(&self) -> Subject<'_> {
+        Subject::ApiKey(&self.0.value)
+    }
+}
+
+impl PolicySubject for ApiAuthenticatedBasicAuth {
+    fn subject(&self) -> Subject<'_> {
+        Subject::BasicAuth {
+            username: &self.0.username,
+        }
+    }
+}
+
+/// Identities that are granted a policy.
+///
+/// A JWT is also granted a policy if it carries the policy's name as a role or scope.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PolicyGrants {
+    /// JWT roles or scopes.
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub api_keys: Vec<String>,
+    #[serde(default)]
+    pub basic_auth_users: Vec<String>,
+}
+
+impl PolicyGrants {
+    /// Returns whether the subject is granted the policy with the given name.
+    pub fn grants(&self, policy: &str, subject: &Subject<'_>) -> bool {
+        match subject {
+            Subject::Jwt(claims) => {
+                claims.has_role(policy) || self.roles.iter().any(|role| claims.has_role(role))
+            }
+            Subject::ApiKey(key) => self.api_keys.iter().any(|granted| granted == key),
+            Subject::BasicAuth { username } => self
+                .basic_auth_users
+                .iter()
+                .any(|granted| granted == username),
+        }
+    }
+}
+
+/// Policy grants by policy name.
+pub type PolicyConfig = HashMap<String, PolicyGrants>;
+
+pub trait PolicyProvider {
+    type Error;
+
+    /// Returns whether the subject is granted the policy with the given name.
+    fn is_authorized(
+        &self,
+        policy: &str,
+        subject: &Subject<'_>,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+}
+
+/// Authenticates the identity using the extractor `X` and rejects if it is not granted the [`Policy`] `P`.
+///
+/// Rejects with [`ForbiddenError`] if the policy is not granted.
+///
+/// ```rust,ignore
+/// pub async fn list_books(
+///     Authorized(ApiJwt(claims), _): Authorized<BooksRead, ApiJwt<Claims>>,
+/// ) {}
+/// ```
+pub struct Authorized<P, X>(pub X, pub PhantomData<P>);
+
+impl<P, X: OperationInput> OperationInput for Authorized<P, X> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        X::describe(operation)
+    }
+}
+
+#[async_trait]
+impl<P, X, S> FromRequestParts<S> for Authorized<P, X>
+where
+    P: Policy,
+    X: FromRequestParts<S, Rejection = ApiError> + PolicySubject + Send,
+    S: Send + Sync + PolicyProvider + ErrorVerbosityProvider,
+    <S as PolicyProvider>::Error: Into<anyhow::Error> + Display,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "authorized_extractor", skip_all, fields(policy = P::NAME))]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let extractor = X::from_request_parts(parts, state).await?;
+
+        let authorized = state
+            .is_authorized(P::NAME, &extractor.subject())
+            .await
+            .map_err(|err| {
+                ApiError::InternalServerError(InternalServerError::from_generic_error(
+                    verbosity, err,
+                ))
+            })?;
+
+        if !authorized {
+            tracing::warn!("Rejection. Policy not granted");
+
+            return Err(ForbiddenError::from_missing_policy(verbosity, P::NAME).into());
+        }
+
+        tracing::trace!("Extracted");
+
+        Ok(Authorized(extractor, PhantomData))
+    }
+}
+
+impl<P, X> Extractor for Authorized<P, X> {
+    type Extracted = X;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
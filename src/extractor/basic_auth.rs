@@ -8,7 +8,9 @@ use axum::{
 use base64::Engine;
 
 use crate::{
-    error::{ApiError, BasicAuthError, BasicAuthErrorType, ErrorVerbosity, ErrorVerbosityProvider},
+    error::{ApiError, BasicAuthError, BasicAuthErrorType, ErrorVerbosityProvider},
+    openapi::OperationInput,
+    state::PrivateErrorVerbosity,
     types::used_basic_auth::UsedBasicAuth,
 };
 
@@ -35,8 +37,13 @@ pub trait BasicAuthProvider {
 #[derive(Debug, Clone)]
 pub struct ApiBasicAuth(pub UsedBasicAuth);
 
+impl OperationInput for ApiBasicAuth {}
+
 impl ApiBasicAuth {
-    fn extract_authorization(parts: &Parts, verbosity: ErrorVerbosity) -> Result<&str, ApiError> {
+    fn extract_authorization(
+        parts: &Parts,
+        verbosity: PrivateErrorVerbosity,
+    ) -> Result<&str, ApiError> {
         let authorization = parts
             .headers
             .get(AUTHORIZATION)
@@ -57,7 +64,7 @@ impl ApiBasicAuth {
 
     fn extract_encoded_basic(
         authorization: &str,
-        verbosity: ErrorVerbosity,
+        verbosity: PrivateErrorVerbosity,
     ) -> Result<&str, ApiError> {
         let split = authorization.split_once(' ');
         let encoded_basic = match split {
@@ -74,7 +81,7 @@ impl ApiBasicAuth {
         Ok(encoded_basic)
     }
 
-    fn decode(encoded_basic: &str, verbosity: ErrorVerbosity) -> Result<String, ApiError> {
+    fn decode(encoded_basic: &str, verbosity: PrivateErrorVerbosity) -> Result<String, ApiError> {
         let decoded = base64::engine::general_purpose::STANDARD
             .decode(encoded_basic)
             .map_err(|err| {
@@ -99,7 +106,10 @@ impl ApiBasicAuth {
         }
     }
 
-    pub fn from_req_parts(parts: &Parts, verbosity: ErrorVerbosity) -> Result<Self, ApiError> {
+    pub fn from_req_parts(
+        parts: &Parts,
+        verbosity: PrivateErrorVerbosity,
+    ) -> Result<Self, ApiError> {
         let authorization = Self::extract_authorization(parts, verbosity)?;
         let encoded_basic = Self::extract_encoded_basic(authorization, verbosity)?;
         let decoded = Self::decode(encoded_basic, verbosity)?;
@@ -5,7 +5,9 @@ use axum::{
 };
 
 use crate::{
-    error::{ApiError, BearerError, BearerErrorType, ErrorVerbosity, ErrorVerbosityProvider},
+    error::{ApiError, BearerError, BearerErrorType, ErrorVerbosityProvider},
+    openapi::OperationInput,
+    state::PrivateErrorVerbosity,
     types::used_bearer_token::UsedBearerToken,
 };
 
@@ -13,8 +15,13 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct ApiBearerToken(pub UsedBearerToken);
 
+impl OperationInput for ApiBearerToken {}
+
 impl ApiBearerToken {
-    fn extract_authorization(parts: &Parts, verbosity: ErrorVerbosity) -> Result<&str, ApiError> {
+    fn extract_authorization(
+        parts: &Parts,
+        verbosity: PrivateErrorVerbosity,
+    ) -> Result<&str, ApiError> {
         let authorization = parts
             .headers
             .get(AUTHORIZATION)
@@ -35,7 +42,7 @@ impl ApiBearerToken {
 
     fn extract_bearer_token(
         authorization: &str,
-        verbosity: ErrorVerbosity,
+        verbosity: PrivateErrorVerbosity,
     ) -> Result<&str, ApiError> {
         let split = authorization.split_once(' ');
         let bearer_token = match split {
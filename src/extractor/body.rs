@@ -0,0 +1,165 @@
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::header::CONTENT_LENGTH,
+};
+use http_body_util::LengthLimitError;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, PayloadTooLargeError, TextBodyError},
+    openapi::OperationInput,
+    state::PrivateErrorVerbosity,
+};
+
+use super::Extractor;
+
+pub trait BodyLimitProvider {
+    /// Returns the maximum size of the request body in bytes.
+    fn max_body_size_in_bytes(&self) -> usize;
+}
+
+/// The body size limit of the router, put as an extension by the [`BodyLimitLayer`](crate::middleware::body_limit::layer::BodyLimitLayer).
+#[derive(Debug, Clone, Copy)]
+pub struct RequestBodyLimit(pub usize);
+
+/// Reads the body rejecting with [`PayloadTooLargeError`] if it exceeds `limit`.
+///
+/// The limit of a [`RequestBodyLimit`] extension takes precedence over `limit`.
+pub(super) async fn read_body(
+    req: Request,
+    limit: usize,
+    verbosity: PrivateErrorVerbosity,
+) -> Result<Bytes, ApiError> {
+    let limit = req
+        .extensions()
+        .get::<RequestBodyLimit>()
+        .map_or(limit, |RequestBodyLimit(limit)| *limit);
+
+    let content_length = req
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+
+    if let Some(content_length) = content_length.filter(|length| *length > limit) {
+        tracing::warn!(
+            content_length,
+            limit,
+            "Rejection. Content length exceeds limit"
+        );
+
+        return Err(PayloadTooLargeError::with_size(verbosity, limit, content_length).into());
+    }
+
+    axum::body::to_bytes(req.into_body(), limit)
+        .await
+        .map_err(|err| {
+            let err = err.into_inner();
+
+            // Limits of outer layers, e.g. the decompressed size limit, fail with a nested error.
+            let exceeds_limit =
+                std::iter::successors(Some(&*err as &(dyn std::error::Error + 'static)), |err| {
+                    err.source()
+                })
+                .any(|err| err.downcast_ref::<LengthLimitError>().is_some());
+
+            if exceeds_limit {
+                tracing::warn!(limit, "Rejection. Body exceeds limit");
+
+                return PayloadTooLargeError::new(verbosity, limit).into();
+            }
+
+            tracing::warn!(%err, "Rejection. Failed to read body");
+
+            ApiError::from_generic_error(verbosity, anyhow::anyhow!(err))
+        })
+}
+
+/// Extracts the raw request body consuming the request.
+///
+/// Rejects if the body exceeds the configured limit.
+pub struct ApiBytes(pub Bytes);
+
+impl OperationInput for ApiBytes {}
+
+#[async_trait]
+impl<S> FromRequest<S> for ApiBytes
+where
+    S: Send + Sync + ErrorVerbosityProvider + BodyLimitProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "bytes_extractor", skip_all)]
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let bytes = read_body(req, state.max_body_size_in_bytes(), state.error_verbosity()).await?;
+
+        tracing::trace!(len = bytes.len(), "Extracted");
+
+        Ok(ApiBytes(bytes))
+    }
+}
+
+/// Extracts the request body as UTF-8 text consuming the request.
+///
+/// Rejects if the body exceeds the configured limit or is not valid UTF-8.
+pub struct ApiString(pub String);
+
+impl OperationInput for ApiString {}
+
+#[async_trait]
+impl<S> FromRequest<S> for ApiString
+where
+    S: Send + Sync + ErrorVerbosityProvider + BodyLimitProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "string_extractor", skip_all)]
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let bytes = read_body(req, state.max_body_size_in_bytes(), verbosity).await?;
+
+        let string = String::from_utf8(bytes.into()).map_err(|err| {
+            tracing::warn!(%err, "Rejection. Body is not valid UTF-8");
+
+            TextBodyError::new(verbosity, err)
+        })?;
+
+        tracing::trace!(len = string.len(), "Extracted");
+
+        Ok(ApiString(string))
+    }
+}
+
+impl Extractor for ApiBytes {
+    type Extracted = Bytes;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
+
+impl Extractor for ApiString {
+    type Extracted = String;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,123 @@
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
+};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+use crate::{
+    error::{ApiError, CborBodyError, ErrorVerbosityProvider},
+    openapi::{ApiOperation, OperationInput},
+};
+
+use super::{has_content_type, Extractor, StrictDeserializationProvider};
+
+pub const CBOR_CONTENT_TYPE: &str = "application/cbor";
+
+/// Extracts the request body as CBOR consuming the request.
+///
+/// Rejects with the same error structure as [`ApiJson`](super::json::ApiJson).
+/// Rejects unknown fields if strict deserialization is enabled.
+///
+/// Respond with [`Negotiated`](crate::response::Negotiated) to serialize the response as CBOR.
+pub struct ApiCbor<T>(pub T);
+
+impl<T: JsonSchema> OperationInput for ApiCbor<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.body::<T>(CBOR_CONTENT_TYPE)
+    }
+}
+
+impl<T> ApiCbor<T>
+where
+    T: DeserializeOwned + JsonSchema,
+{
+    /// Returns the paths of the fields that are not part of `T`.
+    ///
+    /// `ciborium` does not expose its deserializer, so the body is transcoded to a [`serde_json::Value`] first.
+    fn unknown_fields(bytes: &[u8]) -> Vec<String> {
+        let mut unknown_fields = Vec::new();
+
+        let Ok(value) = ciborium::from_reader::<ciborium::Value, _>(bytes) else {
+            return unknown_fields;
+        };
+
+        let Ok(value) = serde_json::to_value(value) else {
+            return unknown_fields;
+        };
+
+        let _: Result<T, _> =
+            serde_ignored::deserialize(value, |path| unknown_fields.push(path.to_string()));
+
+        unknown_fields
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiCbor<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Send,
+    S: Send + Sync + ErrorVerbosityProvider + StrictDeserializationProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "cbor_extractor", skip_all)]
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        if !has_content_type(req.headers(), &[CBOR_CONTENT_TYPE]) {
+            tracing::warn!("Rejection. Missing CBOR content type");
+
+            return Err(CborBodyError::missing_content_type::<T>(verbosity));
+        }
+
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(|bytes_rejection| {
+                tracing::warn!(rejection=?bytes_rejection, "Rejection");
+
+                ApiError::from_generic_error(verbosity, bytes_rejection)
+            })?;
+
+        let cbor = ciborium::from_reader::<T, _>(bytes.as_ref()).map_err(|err| {
+            tracing::warn!(%err, "Rejection");
+
+            CborBodyError::from_decode_error::<T>(verbosity, err)
+        })?;
+
+        if state.strict_deserialization() {
+            let unknown_fields = Self::unknown_fields(&bytes);
+
+            if !unknown_fields.is_empty() {
+                tracing::warn!(?unknown_fields, "Rejection. Unknown fields");
+
+                return Err(CborBodyError::from_unknown_fields::<T>(
+                    verbosity,
+                    unknown_fields,
+                ));
+            }
+        }
+
+        tracing::trace!(?cbor, "Extracted");
+
+        Ok(ApiCbor(cbor))
+    }
+}
+
+impl<T> Extractor for ApiCbor<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,197 @@
+use std::{fmt::Display, future::Future, sync::Arc};
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use x509_parser::{
+    certificate::X509Certificate, error::X509Error, extensions::GeneralName, nom, prelude::FromDer,
+};
+
+use crate::{
+    error::{
+        ApiError, ClientCertError, ClientCertErrorType, ErrorVerbosityProvider, InternalServerError,
+    },
+    openapi::OperationInput,
+};
+
+use super::Extractor;
+
+/// The DER encoded certificate chain presented by the client during the TLS handshake.
+///
+/// The leaf certificate comes first.
+/// Inserted into the request extensions by the TLS listener after the chain has been verified.
+#[derive(Debug, Clone)]
+pub struct PeerCertificates(pub Arc<Vec<Vec<u8>>>);
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ClientCertConfig {
+    /// Subjects or subject alternative names that are allowed. If empty, every verified certificate is accepted.
+    #[serde(default)]
+    pub allowed_identities: Vec<String>,
+}
+
+impl ClientCertConfig {
+    /// Returns whether one of the identities of the certificate is allowed.
+    pub fn allows(&self, cert: &ClientCert) -> bool {
+        self.allowed_identities.is_empty()
+            || cert.identities().any(|identity| {
+                self.allowed_identities
+                    .iter()
+                    .any(|allowed| allowed == identity)
+            })
+    }
+}
+
+pub trait CertAuthProvider {
+    type Error;
+
+    /// Returns whether the given verified client certificate is allowed to access the API.
+    fn authorize_client_cert(
+        &self,
+        cert: &ClientCert,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+}
+
+/// The identity of a verified client certificate.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientCert {
+    /// The subject distinguished name, e.g. `O=Acme, CN=client-1`.
+    pub subject: String,
+    /// The common name of the subject.
+    pub common_name: Option<String>,
+    /// DNS names, URIs, email addresses and IP addresses from the subject alternative name extension.
+    pub subject_alt_names: Vec<String>,
+    /// The hex encoded SHA-256 fingerprint of the certificate.
+    pub fingerprint: String,
+}
+
+impl ClientCert {
+    pub(crate) fn from_der(der: &[u8]) -> Result<Self, nom::Err<X509Error>> {
+        let (_, cert) = X509Certificate::from_der(der)?;
+
+        let subject = cert.subject().to_string();
+        let common_name = cert
+            .subject()
+            .iter_common_name()
+            .next()
+            .and_then(|cn| cn.as_str().ok())
+            .map(String::from);
+
+        let subject_alt_names = cert
+            .subject_alternative_name()
+            .ok()
+            .flatten()
+            .map(|san| {
+                san.value
+                    .general_names
+                    .iter()
+                    .filter_map(|name| match name {
+                        GeneralName::DNSName(name)
+                        | GeneralName::URI(name)
+                        | GeneralName::RFC822Name(name) => Some(String::from(*name)),
+                        GeneralName::IPAddress(ip) => match ip.len() {
+                            4 => <[u8; 4]>::try_from(*ip)
+                                .ok()
+                                .map(|ip| std::net::IpAddr::from(ip).to_string()),
+                            16 => <[u8; 16]>::try_from(*ip)
+                                .ok()
+                                .map(|ip| std::net::IpAddr::from(ip).to_string()),
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let fingerprint = hex::encode(Sha256::digest(der));
+
+        Ok(Self {
+            subject,
+            common_name,
+            subject_alt_names,
+            fingerprint,
+        })
+    }
+
+    /// Returns the subject, common name and subject alternative names of the certificate.
+    pub fn identities(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.subject.as_str())
+            .chain(self.common_name.as_deref())
+            .chain(self.subject_alt_names.iter().map(String::as_str))
+    }
+}
+
+/// Extracts the verified [`ClientCert`] of a mutual TLS connection and authorizes it using the state's [`CertAuthProvider`].
+///
+/// Rejects if the connection did not present a client certificate, the certificate can not be parsed or is not authorized.
+#[derive(Debug, Clone)]
+pub struct ApiClientCert(pub ClientCert);
+
+impl OperationInput for ApiClientCert {}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiClientCert
+where
+    S: Send + Sync + CertAuthProvider + ErrorVerbosityProvider,
+    <S as CertAuthProvider>::Error: Into<anyhow::Error> + Display,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "client_cert_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let Some(der) = parts
+            .extensions
+            .get::<PeerCertificates>()
+            .and_then(|certs| certs.0.first())
+        else {
+            tracing::warn!("Rejection. Missing client certificate");
+
+            return Err(ClientCertError::new(verbosity, ClientCertErrorType::Missing).into());
+        };
+
+        let cert = ClientCert::from_der(der).map_err(|err| {
+            tracing::warn!(%err, "Rejection. Invalid client certificate");
+
+            ClientCertError::new(verbosity, ClientCertErrorType::Invalid { err })
+        })?;
+
+        let authorized = state.authorize_client_cert(&cert).await.map_err(|err| {
+            ApiError::InternalServerError(InternalServerError::from_generic_error(verbosity, err))
+        })?;
+
+        if !authorized {
+            tracing::warn!(subject = %cert.subject, "Rejection. Client certificate not authorized");
+
+            return Err(ClientCertError::new(
+                verbosity,
+                ClientCertErrorType::Unauthorized {
+                    subject: cert.subject,
+                },
+            )
+            .into());
+        }
+
+        tracing::trace!(?cert, "Extracted");
+
+        Ok(ApiClientCert(cert))
+    }
+}
+
+impl Extractor for ApiClientCert {
+    type Extracted = ClientCert;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
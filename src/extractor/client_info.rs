@@ -0,0 +1,156 @@
+use std::convert::Infallible;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{
+        header::{ACCEPT_LANGUAGE, USER_AGENT},
+        request::Parts,
+    },
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::{
+    locale::parse_accept_language, openapi::OperationInput, request_id::REQUEST_ID_HEADER,
+};
+
+use super::Extractor;
+
+/// A product token of the `User-Agent` header, e.g. `Mozilla/5.0`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Product {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+/// Parsed `User-Agent` header.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct UserAgent {
+    pub raw: String,
+    /// Product tokens in order of appearance. Comments in parentheses are skipped.
+    pub products: Vec<Product>,
+}
+
+impl UserAgent {
+    fn parse(raw: &str) -> Self {
+        let mut products = Vec::new();
+        let mut depth = 0usize;
+        let mut token = String::new();
+
+        let mut push = |token: &mut String| {
+            if !token.is_empty() {
+                let (name, version) = match token.split_once('/') {
+                    Some((name, version)) => (name.to_string(), Some(version.to_string())),
+                    None => (token.clone(), None),
+                };
+
+                products.push(Product { name, version });
+                token.clear();
+            }
+        };
+
+        for c in raw.chars() {
+            match c {
+                '(' => {
+                    push(&mut token);
+                    depth += 1;
+                }
+                ')' => depth = depth.saturating_sub(1),
+                _ if depth > 0 => {}
+                c if c.is_whitespace() => push(&mut token),
+                c => token.push(c),
+            }
+        }
+
+        push(&mut token);
+
+        Self {
+            raw: raw.to_string(),
+            products,
+        }
+    }
+}
+
+/// Metadata about the client parsed from the request headers.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct ClientInfo {
+    pub user_agent: Option<UserAgent>,
+    /// Languages from the `Accept-Language` header ordered by preference.
+    pub languages: Vec<String>,
+    pub request_id: Option<String>,
+}
+
+/// Extracts the [`ClientInfo`] of the request.
+///
+/// The parsed [`ClientInfo`] is stored in the request extensions,
+/// so later extractors and middleware share the same data without parsing the headers again.
+///
+/// This extractor never fails.
+#[derive(Debug, Clone)]
+pub struct ApiClientInfo(pub ClientInfo);
+
+impl OperationInput for ApiClientInfo {}
+
+impl ApiClientInfo {
+    fn parse(parts: &Parts) -> ClientInfo {
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+
+        ClientInfo {
+            user_agent: header(USER_AGENT.as_str()).map(UserAgent::parse),
+            languages: header(ACCEPT_LANGUAGE.as_str())
+                .map(parse_accept_language)
+                .unwrap_or_default()
+                .into_iter()
+                .map(ToString::to_string)
+                .collect(),
+            request_id: header(REQUEST_ID_HEADER).map(ToString::to_string),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiClientInfo
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    #[tracing::instrument(name = "client_info_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(client_info) = parts.extensions.get::<ClientInfo>() {
+            tracing::trace!(?client_info, "Extracted from extensions");
+
+            return Ok(ApiClientInfo(client_info.clone()));
+        }
+
+        let client_info = Self::parse(parts);
+
+        parts.extensions.insert(client_info.clone());
+
+        tracing::trace!(?client_info, "Extracted");
+
+        Ok(ApiClientInfo(client_info))
+    }
+}
+
+impl Extractor for ApiClientInfo {
+    type Extracted = ClientInfo;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,181 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{request::Parts, HeaderMap},
+};
+use ipnet::IpNet;
+
+use crate::{
+    error::{ApiError, ClientIpError, ClientIpErrorType, ErrorVerbosityProvider},
+    openapi::OperationInput,
+};
+
+use super::Extractor;
+
+const FORWARDED: &str = "forwarded";
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+const X_REAL_IP: &str = "x-real-ip";
+
+pub trait TrustedProxiesProvider {
+    /// Returns the networks of the proxies that are trusted to set forwarding headers.
+    fn trusted_proxies(&self) -> &[IpNet];
+}
+
+/// Extracts the IP of the client.
+///
+/// If the peer is a trusted proxy, the IP is resolved from the `Forwarded`, `X-Forwarded-For` or `X-Real-IP` header (in that order).
+/// The right-most untrusted address of the chain is the client.
+/// Falls back to the peer address from [`ConnectInfo`].
+///
+/// Rejects if an untrusted peer sends forwarding headers.
+#[derive(Debug, Clone, Copy)]
+pub struct ApiClientIp(pub IpAddr);
+
+impl OperationInput for ApiClientIp {}
+
+impl ApiClientIp {
+    /// Returns the forwarded addresses from the first present forwarding header, client first.
+    ///
+    /// Returns `None` if no forwarding header is present.
+    fn forwarded_chain(headers: &HeaderMap) -> Option<Result<Vec<IpAddr>, &'static str>> {
+        let values = |name: &'static str| {
+            let mut values = headers.get_all(name).iter().peekable();
+
+            values.peek()?;
+
+            Some(
+                values
+                    .map(|value| value.to_str().map_err(|_| name))
+                    .collect::<Result<Vec<_>, _>>(),
+            )
+        };
+
+        if let Some(values) = values(FORWARDED) {
+            return Some(values.and_then(|values| {
+                values
+                    .iter()
+                    .flat_map(|value| value.split(','))
+                    .filter_map(|element| {
+                        element.split(';').find_map(|pair| {
+                            let (key, value) = pair.trim().split_once('=')?;
+
+                            key.eq_ignore_ascii_case("for").then_some(value)
+                        })
+                    })
+                    .map(|node| Self::parse_forwarded_node(node).ok_or(FORWARDED))
+                    .collect()
+            }));
+        }
+
+        if let Some(values) = values(X_FORWARDED_FOR) {
+            return Some(values.and_then(|values| {
+                values
+                    .iter()
+                    .flat_map(|value| value.split(','))
+                    .map(|ip| ip.trim().parse().map_err(|_| X_FORWARDED_FOR))
+                    .collect()
+            }));
+        }
+
+        values(X_REAL_IP).map(|values| {
+            values.and_then(|values| {
+                values
+                    .iter()
+                    .map(|ip| ip.trim().parse().map_err(|_| X_REAL_IP))
+                    .collect()
+            })
+        })
+    }
+
+    /// Parses a `Forwarded` node like `192.0.2.60`, `"[2001:db8:cafe::17]:4711"` or `192.0.2.60:80`.
+    fn parse_forwarded_node(node: &str) -> Option<IpAddr> {
+        let node = node.trim().trim_matches('"');
+
+        if let Some(rest) = node.strip_prefix('[') {
+            return rest.split_once(']')?.0.parse().ok();
+        }
+
+        node.parse()
+            .ok()
+            .or_else(|| node.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+    }
+
+    fn is_trusted(trusted_proxies: &[IpNet], ip: &IpAddr) -> bool {
+        trusted_proxies.iter().any(|net| net.contains(ip))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiClientIp
+where
+    S: Send + Sync + ErrorVerbosityProvider + TrustedProxiesProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "client_ip_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+        let trusted_proxies = state.trusted_proxies();
+
+        let ConnectInfo(peer) = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .copied()
+            .ok_or_else(|| {
+                ApiError::from_generic_error(verbosity, anyhow::anyhow!("ConnectInfo is missing"))
+            })?;
+
+        let peer = peer.ip();
+
+        let Some(chain) = Self::forwarded_chain(&parts.headers) else {
+            tracing::trace!(ip=%peer, "Extracted from peer");
+
+            return Ok(ApiClientIp(peer));
+        };
+
+        if !Self::is_trusted(trusted_proxies, &peer) {
+            tracing::warn!(%peer, "Rejection. Forwarding headers from untrusted peer");
+
+            return Err(ClientIpError::new(verbosity, ClientIpErrorType::UntrustedProxy).into());
+        }
+
+        let chain = chain.map_err(|header| {
+            tracing::warn!(%header, "Rejection. Invalid forwarding header");
+
+            ClientIpError::new(
+                verbosity,
+                ClientIpErrorType::InvalidForwardingHeader { header },
+            )
+        })?;
+
+        let ip = chain
+            .iter()
+            .rev()
+            .find(|ip| !Self::is_trusted(trusted_proxies, ip))
+            .or(chain.first())
+            .copied()
+            .unwrap_or(peer);
+
+        tracing::trace!(%ip, "Extracted from forwarding headers");
+
+        Ok(ApiClientIp(ip))
+    }
+}
+
+impl Extractor for ApiClientIp {
+    type Extracted = IpAddr;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,235 @@
+use std::convert::Infallible;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{
+        header::{
+            ETAG, IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_UNMODIFIED_SINCE, LAST_MODIFIED,
+        },
+        request::Parts,
+        HeaderMap, HeaderValue, Method, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, PreconditionFailedError},
+    openapi::OperationInput,
+    state::PrivateErrorVerbosity,
+};
+
+use super::Extractor;
+
+/// An `If-Match` or `If-None-Match` condition.
+#[derive(Debug, Clone)]
+pub enum EntityTagCondition {
+    /// `*`
+    Any,
+    /// The listed entity tags including their `W/` prefix and quotes.
+    Tags(Vec<String>),
+}
+
+impl EntityTagCondition {
+    pub(crate) fn parse(value: &str) -> Self {
+        if value.trim() == "*" {
+            return Self::Any;
+        }
+
+        Self::Tags(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(ToString::to_string)
+                .collect(),
+        )
+    }
+
+    fn opaque(tag: &str) -> &str {
+        tag.strip_prefix("W/").unwrap_or(tag)
+    }
+
+    /// Weak comparison used by `If-None-Match`.
+    pub(crate) fn matches_weak(&self, etag: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Tags(tags) => tags
+                .iter()
+                .any(|tag| Self::opaque(tag) == Self::opaque(etag)),
+        }
+    }
+
+    /// Strong comparison used by `If-Match`.
+    fn matches_strong(&self, etag: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Tags(tags) => {
+                !etag.starts_with("W/") && tags.iter().any(|tag| tag.as_str() == etag)
+            }
+        }
+    }
+}
+
+/// Preconditions of a conditional request.
+#[derive(Debug, Clone)]
+pub struct Preconditions {
+    method: Method,
+    verbosity: PrivateErrorVerbosity,
+    pub if_match: Option<EntityTagCondition>,
+    pub if_none_match: Option<EntityTagCondition>,
+    pub if_modified_since: Option<DateTime<Utc>>,
+    pub if_unmodified_since: Option<DateTime<Utc>>,
+}
+
+impl Preconditions {
+    fn is_safe_method(&self) -> bool {
+        self.method == Method::GET || self.method == Method::HEAD
+    }
+
+    /// Evaluates the preconditions in the order of RFC 9110 section 13.2.2.
+    ///
+    /// Returns `None` if the request should be processed.
+    fn evaluate(
+        &self,
+        etag: Option<&str>,
+        last_modified: Option<DateTime<Utc>>,
+    ) -> Option<StatusCode> {
+        // HTTP dates have a resolution of one second.
+        let last_modified = last_modified.map(|date| date.timestamp());
+
+        let precondition_failed = match (&self.if_match, &self.if_unmodified_since) {
+            (Some(if_match), _) => !etag.is_some_and(|etag| if_match.matches_strong(etag)),
+            (None, Some(since)) => {
+                last_modified.is_some_and(|modified| modified > since.timestamp())
+            }
+            (None, None) => false,
+        };
+
+        if precondition_failed {
+            return Some(StatusCode::PRECONDITION_FAILED);
+        }
+
+        let not_modified = match (&self.if_none_match, &self.if_modified_since) {
+            (Some(if_none_match), _) => {
+                matches!(if_none_match, EntityTagCondition::Any)
+                    || etag.is_some_and(|etag| if_none_match.matches_weak(etag))
+            }
+            (None, Some(since)) if self.is_safe_method() => {
+                last_modified.is_some_and(|modified| modified <= since.timestamp())
+            }
+            _ => false,
+        };
+
+        match (not_modified, self.is_safe_method()) {
+            (false, _) => None,
+            (true, true) => Some(StatusCode::NOT_MODIFIED),
+            (true, false) => Some(StatusCode::PRECONDITION_FAILED),
+        }
+    }
+
+    /// Returns the response if the preconditions pass, `304 Not Modified` or `412 Precondition Failed` otherwise.
+    ///
+    /// `etag` must include the quotes and the `W/` prefix for weak tags, e.g. `"abc"` or `W/"abc"`.
+    /// `ETag` and `Last-Modified` headers are added to the response.
+    pub fn respond(
+        &self,
+        etag: Option<&str>,
+        last_modified: Option<DateTime<Utc>>,
+        response: impl IntoResponse,
+    ) -> Response {
+        let mut headers = HeaderMap::new();
+
+        if let Some(etag) = etag.and_then(|etag| HeaderValue::from_str(etag).ok()) {
+            headers.insert(ETAG, etag);
+        }
+
+        if let Some(last_modified) = last_modified.and_then(|date| {
+            HeaderValue::from_str(&date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()).ok()
+        }) {
+            headers.insert(LAST_MODIFIED, last_modified);
+        }
+
+        match self.evaluate(etag, last_modified) {
+            None => (headers, response).into_response(),
+            Some(StatusCode::NOT_MODIFIED) => {
+                tracing::debug!("Not modified");
+
+                (StatusCode::NOT_MODIFIED, headers).into_response()
+            }
+            Some(_) => {
+                tracing::debug!("Precondition failed");
+
+                (
+                    headers,
+                    ApiError::from(PreconditionFailedError::new(self.verbosity)),
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
+/// Extracts the `If-Match`, `If-None-Match`, `If-Modified-Since` and `If-Unmodified-Since` preconditions.
+///
+/// Invalid dates are ignored as required by RFC 9110.
+///
+/// This extractor never fails.
+#[derive(Debug, Clone)]
+pub struct ApiConditional(pub Preconditions);
+
+impl OperationInput for ApiConditional {}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiConditional
+where
+    S: Send + Sync + ErrorVerbosityProvider,
+{
+    type Rejection = Infallible;
+
+    #[tracing::instrument(name = "conditional_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let header = |name| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+
+        let date = |name| {
+            header(name)
+                .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+                .map(|date| date.with_timezone(&Utc))
+        };
+
+        let preconditions = Preconditions {
+            method: parts.method.clone(),
+            verbosity: state.error_verbosity(),
+            if_match: header(IF_MATCH).map(EntityTagCondition::parse),
+            if_none_match: header(IF_NONE_MATCH).map(EntityTagCondition::parse),
+            if_modified_since: date(IF_MODIFIED_SINCE),
+            if_unmodified_since: date(IF_UNMODIFIED_SINCE),
+        };
+
+        tracing::trace!(?preconditions, "Extracted");
+
+        Ok(ApiConditional(preconditions))
+    }
+}
+
+impl Extractor for ApiConditional {
+    type Extracted = Preconditions;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,249 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::COOKIE, request::Parts},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use derivative::Derivative;
+use hmac::Mac;
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::fmt::Debug;
+
+use crate::{
+    error::{ApiError, CookieError, CookieErrorType, ErrorVerbosityProvider},
+    openapi::{ApiOperation, OperationInput},
+    signing::HmacSha256,
+    state::PrivateErrorVerbosity,
+};
+
+use super::Extractor;
+
+/// Length of a base64 url-safe encoded HMAC-SHA256 signature without padding.
+const SIGNATURE_LENGTH: usize = 43;
+
+pub trait CookieSigningKeyProvider {
+    /// Returns the key used to sign and verify cookies.
+    fn cookie_signing_key(&self) -> Option<&[u8]>;
+}
+
+/// Signs a cookie value.
+///
+/// Returns `<value>.<signature>` where the signature is the base64 url-safe encoded HMAC-SHA256 of `<name>=<value>`.
+pub fn sign_cookie_value(key: &[u8], name: &str, value: &str) -> String {
+    let signature = cookie_mac(key, name, value).finalize().into_bytes();
+
+    format!("{value}.{}", URL_SAFE_NO_PAD.encode(signature))
+}
+
+fn cookie_mac(key: &[u8], name: &str, value: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(name.as_bytes());
+    mac.update(b"=");
+    mac.update(value.as_bytes());
+
+    mac
+}
+
+/// Splits `<value>.<signature>` into the value and the decoded signature.
+fn split_signed_value(value: &str) -> Option<(&str, Vec<u8>)> {
+    let (value, signature) = value.rsplit_once('.')?;
+
+    if signature.len() != SIGNATURE_LENGTH {
+        return None;
+    }
+
+    let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+
+    Some((value, signature))
+}
+
+/// Parses the `Cookie` headers into name-value pairs.
+///
+/// Pairs without a name or without `=` are skipped, as browsers do.
+pub(crate) fn parse_cookies(
+    parts: &Parts,
+    verbosity: PrivateErrorVerbosity,
+) -> Result<Vec<(&str, &str)>, ApiError> {
+    let mut cookies = Vec::new();
+
+    for header in parts.headers.get_all(COOKIE) {
+        let header = header.to_str().map_err(|err| {
+            tracing::warn!(%err, "Rejection. Invalid cookie header");
+
+            CookieError::new(verbosity, CookieErrorType::InvalidCookieHeader)
+        })?;
+
+        for cookie in header.split(';').map(str::trim).filter(|c| !c.is_empty()) {
+            let Some((name, value)) = cookie.split_once('=') else {
+                tracing::debug!("Skipping cookie without value");
+
+                continue;
+            };
+
+            let name = name.trim();
+
+            if name.is_empty() {
+                tracing::debug!("Skipping cookie without name");
+
+                continue;
+            }
+
+            cookies.push((name, value.trim().trim_matches('"')));
+        }
+    }
+
+    Ok(cookies)
+}
+
+fn deserialize_cookies<T>(
+    cookies: &[(&str, &str)],
+    verbosity: PrivateErrorVerbosity,
+) -> Result<T, ApiError>
+where
+    T: DeserializeOwned + JsonSchema,
+{
+    let encoded = serde_urlencoded::to_string(cookies)
+        .map_err(|err| ApiError::from_generic_error(verbosity, err))?;
+
+    serde_urlencoded::from_str::<T>(&encoded).map_err(|err| {
+        tracing::warn!(%err, "Rejection. Failed to deserialize cookies");
+
+        CookieError::from_deserialize_error::<T>(verbosity, err)
+    })
+}
+
+/// Extracts the cookies from the `Cookie` header and deserializes them into `T`.
+pub struct ApiCookies<T>(pub T);
+
+impl<T: JsonSchema> OperationInput for ApiCookies<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.cookies::<T>()
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiCookies<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Send,
+    S: Send + Sync + ErrorVerbosityProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "cookies_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let cookies = parse_cookies(parts, verbosity)?;
+        let cookies = deserialize_cookies::<T>(&cookies, verbosity)?;
+
+        tracing::trace!(?cookies, "Extracted");
+
+        Ok(ApiCookies(cookies))
+    }
+}
+
+/// Extracts the cookies signed with [`sign_cookie_value`] and deserializes them into `T`.
+///
+/// Cookies without a signature are ignored.
+/// Rejects if a cookie's signature is invalid.
+pub struct ApiSignedCookies<T>(pub T);
+
+impl<T: JsonSchema> OperationInput for ApiSignedCookies<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.cookies::<T>()
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiSignedCookies<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Send,
+    S: Send + Sync + ErrorVerbosityProvider + CookieSigningKeyProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "signed_cookies_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let key = state.cookie_signing_key().ok_or_else(|| {
+            ApiError::from_generic_error(
+                verbosity,
+                anyhow::anyhow!("Cookie signing key is not configured"),
+            )
+        })?;
+
+        let mut verified = Vec::new();
+
+        for (name, value) in parse_cookies(parts, verbosity)? {
+            let Some((value, signature)) = split_signed_value(value) else {
+                tracing::debug!(%name, "Ignoring unsigned cookie");
+
+                continue;
+            };
+
+            if cookie_mac(key, name, value)
+                .verify_slice(&signature)
+                .is_err()
+            {
+                tracing::warn!(%name, "Rejection. Invalid cookie signature");
+
+                return Err(CookieError::new(
+                    verbosity,
+                    CookieErrorType::InvalidSignature {
+                        name: name.to_string(),
+                    },
+                )
+                .into());
+            }
+
+            verified.push((name, value));
+        }
+
+        let cookies = deserialize_cookies::<T>(&verified, verbosity)?;
+
+        tracing::trace!(?cookies, "Extracted");
+
+        Ok(ApiSignedCookies(cookies))
+    }
+}
+
+impl<T> Extractor for ApiCookies<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
+
+impl<T> Extractor for ApiSignedCookies<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
+
+#[derive(Derivative, Clone, Deserialize)]
+#[derivative(Debug)]
+pub struct CookieSigningConfig {
+    #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+    pub secret: String,
+}
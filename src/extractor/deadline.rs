@@ -0,0 +1,127 @@
+use std::{convert::Infallible, future::Future, time::Duration};
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use serde::Deserialize;
+use tokio::time::Instant;
+
+use crate::{
+    error::{ApiError, DeadlineExceededError, ErrorVerbosityProvider},
+    openapi::OperationInput,
+    state::PrivateErrorVerbosity,
+};
+
+use super::Extractor;
+
+/// Timeout of the request in milliseconds requested by the client.
+pub const REQUEST_TIMEOUT_HEADER: &str = "x-request-timeout";
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct DeadlineConfig {
+    /// Upper bound for the requested timeout. Also used if the client does not request a timeout.
+    pub max_request_timeout_in_millis: u64,
+}
+
+impl Default for DeadlineConfig {
+    fn default() -> Self {
+        Self {
+            max_request_timeout_in_millis: 30_000,
+        }
+    }
+}
+
+pub trait DeadlineConfigProvider {
+    fn deadline_config(&self) -> DeadlineConfig;
+}
+
+/// The point in time at which the request expires.
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    pub instant: Instant,
+    verbosity: PrivateErrorVerbosity,
+}
+
+impl Deadline {
+    /// Returns the time left until the deadline.
+    pub fn remaining(&self) -> Duration {
+        self.instant.saturating_duration_since(Instant::now())
+    }
+
+    /// Runs the future until the deadline.
+    ///
+    /// Rejects with [`DeadlineExceededError`] if the deadline is reached first.
+    pub async fn run<F: Future>(&self, future: F) -> Result<F::Output, ApiError> {
+        tokio::time::timeout_at(self.instant, future)
+            .await
+            .map_err(|_| {
+                tracing::warn!("Deadline exceeded");
+
+                DeadlineExceededError::new(self.verbosity).into()
+            })
+    }
+}
+
+/// Extracts the [`Deadline`] of the request from the `X-Request-Timeout` header.
+///
+/// The requested timeout is bounded by the configured maximum.
+/// Missing or invalid headers fall back to the maximum.
+///
+/// This extractor never fails.
+#[derive(Debug, Clone, Copy)]
+pub struct ApiDeadline(pub Deadline);
+
+impl OperationInput for ApiDeadline {}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiDeadline
+where
+    S: Send + Sync + ErrorVerbosityProvider + DeadlineConfigProvider,
+{
+    type Rejection = Infallible;
+
+    #[tracing::instrument(name = "deadline_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let max = state.deadline_config().max_request_timeout_in_millis;
+
+        let requested = parts.headers.get(REQUEST_TIMEOUT_HEADER).map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        });
+
+        let timeout = match requested {
+            None => max,
+            Some(Some(requested)) => requested.min(max),
+            Some(None) => {
+                tracing::warn!("Invalid request timeout header. Using maximum");
+
+                max
+            }
+        };
+
+        let deadline = Deadline {
+            instant: Instant::now() + Duration::from_millis(timeout),
+            verbosity: state.error_verbosity(),
+        };
+
+        tracing::trace!(timeout_in_millis = timeout, "Extracted");
+
+        Ok(ApiDeadline(deadline))
+    }
+}
+
+impl Extractor for ApiDeadline {
+    type Extracted = Deadline;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,508 @@
+//! HTTP Digest authentication as defined in [RFC 7616](https://datatracker.ietf.org/doc/html/rfc7616).
+//!
+//! Only the `auth` quality of protection with the `MD5` and `SHA-256` algorithms is supported.
+//! Nonces are issued with every challenge and every nonce count may only be used once.
+//! See [`DigestNonceStore`] for how the nonces are kept.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    future::Future,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, OriginalUri},
+    http::{header::AUTHORIZATION, request::Parts, HeaderValue},
+};
+use hmac::Mac;
+use md5::Md5;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::{
+    error::{
+        ApiError, DigestAuthError, DigestAuthErrorType, ErrorVerbosityProvider, InternalServerError,
+    },
+    openapi::OperationInput,
+    signing::HmacSha256,
+    state::PrivateErrorVerbosity,
+};
+
+use super::Extractor;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DigestAuthConfig {
+    #[serde(default = "default_realm")]
+    pub realm: String,
+    #[serde(default = "default_nonce_time_to_live_in_seconds")]
+    pub nonce_time_to_live_in_seconds: u64,
+}
+
+fn default_realm() -> String {
+    String::from("the-axum")
+}
+
+fn default_nonce_time_to_live_in_seconds() -> u64 {
+    300
+}
+
+impl Default for DigestAuthConfig {
+    fn default() -> Self {
+        Self {
+            realm: default_realm(),
+            nonce_time_to_live_in_seconds: default_nonce_time_to_live_in_seconds(),
+        }
+    }
+}
+
+pub trait DigestAuthProvider {
+    type Error;
+
+    /// Returns the realm the credentials are valid for.
+    fn digest_realm(&self) -> &str;
+
+    /// Returns the store that issues and checks the nonces.
+    fn digest_nonces(&self) -> &DigestNonceStore;
+
+    /// Returns the plaintext password of the given user.
+    ///
+    /// Returns `None` if the user is unknown or has no plaintext password.
+    fn digest_password(
+        &self,
+        username: &str,
+    ) -> impl Future<Output = Result<Option<String>, Self::Error>> + Send;
+}
+
+/// The hash algorithm of a digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum DigestAlgorithm {
+    Md5,
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "MD5" => Some(Self::Md5),
+            "SHA-256" => Some(Self::Sha256),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Sha256 => "SHA-256",
+        }
+    }
+
+    fn hash(&self, value: &str) -> String {
+        match self {
+            Self::Md5 => hex::encode(Md5::digest(value.as_bytes())),
+            Self::Sha256 => hex::encode(Sha256::digest(value.as_bytes())),
+        }
+    }
+}
+
+/// The outcome of checking a nonce and its nonce count.
+enum NonceCheck {
+    Valid,
+    Unknown,
+    Stale,
+    Reused,
+}
+
+/// The nonce counts of at most this many used nonces are kept.
+const MAX_USED_NONCES: usize = 10_000;
+
+struct UsedNonce {
+    issued_at: u64,
+    last_nonce_count: u32,
+}
+
+/// Issues nonces and keeps the last nonce count used with each of them.
+///
+/// A nonce is its issue time, a random salt and an HMAC over both, so issuing a nonce keeps no state.
+/// Only the nonces that were used to authenticate are kept, until they expire.
+pub struct DigestNonceStore {
+    key: [u8; 32],
+    time_to_live: Duration,
+    used: Mutex<HashMap<String, UsedNonce>>,
+}
+
+/// Returns the current unix time in milliseconds.
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+impl DigestNonceStore {
+    /// Signs the nonces with a random key, so nonces are only valid for this instance.
+    pub fn new(time_to_live: Duration) -> Self {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+
+        Self {
+            key,
+            time_to_live,
+            used: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn mac(&self, issued_at: &str, salt: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC can take key of any size");
+        mac.update(issued_at.as_bytes());
+        mac.update(b".");
+        mac.update(salt.as_bytes());
+
+        mac
+    }
+
+    /// Issues a new nonce in the form `{issued_at}.{salt}.{mac}`.
+    fn issue(&self) -> String {
+        let mut salt = [0u8; 8];
+        rand::thread_rng().fill_bytes(&mut salt);
+
+        let issued_at = format!("{:x}", now_millis());
+        let salt = hex::encode(salt);
+        let mac = hex::encode(self.mac(&issued_at, &salt).finalize().into_bytes());
+
+        format!("{issued_at}.{salt}.{mac}")
+    }
+
+    /// Returns the issue time of the nonce if it was issued by this store.
+    fn verify(&self, nonce: &str) -> Option<u64> {
+        let mut parts = nonce.splitn(3, '.');
+        let (issued_at, salt, mac) = (parts.next()?, parts.next()?, parts.next()?);
+
+        let mac = hex::decode(mac).ok()?;
+        self.mac(issued_at, salt).verify_slice(&mac).ok()?;
+
+        u64::from_str_radix(issued_at, 16).ok()
+    }
+
+    /// Checks the nonce and records the nonce count if it was not used before.
+    fn check(&self, nonce: &str, nonce_count: u32) -> NonceCheck {
+        let Some(issued_at) = self.verify(nonce) else {
+            return NonceCheck::Unknown;
+        };
+
+        let time_to_live = self.time_to_live.as_millis() as u64;
+        let now = now_millis();
+        let expired = |issued_at: u64| now.saturating_sub(issued_at) > time_to_live;
+
+        if expired(issued_at) {
+            return NonceCheck::Stale;
+        }
+
+        let mut used = self.used.lock().expect("digest nonces mutex poisoned");
+
+        if let Some(used) = used.get_mut(nonce) {
+            if nonce_count <= used.last_nonce_count {
+                return NonceCheck::Reused;
+            }
+
+            used.last_nonce_count = nonce_count;
+
+            return NonceCheck::Valid;
+        }
+
+        if used.len() >= MAX_USED_NONCES {
+            used.retain(|_, used| !expired(used.issued_at));
+        }
+
+        // The client retries with a fresh nonce, which is accepted once older nonces expired.
+        if used.len() >= MAX_USED_NONCES {
+            return NonceCheck::Stale;
+        }
+
+        used.insert(
+            nonce.to_owned(),
+            UsedNonce {
+                issued_at,
+                last_nonce_count: nonce_count,
+            },
+        );
+
+        NonceCheck::Valid
+    }
+}
+
+/// The parameters of the `Authorization` header the digest response is computed from.
+#[derive(Debug, Clone, Copy)]
+pub struct DigestParams<'a> {
+    pub username: &'a str,
+    pub realm: &'a str,
+    pub nonce: &'a str,
+    pub uri: &'a str,
+    pub nc: &'a str,
+    pub cnonce: &'a str,
+}
+
+impl DigestParams<'_> {
+    /// Returns the expected `response` for the `auth` quality of protection.
+    pub fn response(&self, algorithm: DigestAlgorithm, method: &str, password: &str) -> String {
+        let Self {
+            username,
+            realm,
+            nonce,
+            uri,
+            nc,
+            cnonce,
+        } = self;
+
+        let ha1 = algorithm.hash(&format!("{username}:{realm}:{password}"));
+        let ha2 = algorithm.hash(&format!("{method}:{uri}"));
+
+        algorithm.hash(&format!("{ha1}:{nonce}:{nc}:{cnonce}:auth:{ha2}"))
+    }
+}
+
+/// The authenticated user of a digest authentication.
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestAuth {
+    pub username: String,
+    pub algorithm: DigestAlgorithm,
+}
+
+/// Extracts and authenticates the digest credentials from the `Authorization` header.
+///
+/// Rejects with a fresh challenge in the `WWW-Authenticate` header if the credentials are missing or invalid.
+#[derive(Debug, Clone)]
+pub struct ApiDigestAuth(pub DigestAuth);
+
+impl OperationInput for ApiDigestAuth {}
+
+impl ApiDigestAuth {
+    /// Splits the comma separated `key=value` and `key="value"` parameters of the header.
+    fn parse_params(params: &str) -> Option<HashMap<&str, &str>> {
+        let mut parsed = HashMap::new();
+        let mut rest = params.trim();
+
+        while !rest.is_empty() {
+            let (key, value) = rest.split_once('=')?;
+            let key = key.trim();
+            let value = value.trim_start();
+
+            let (value, remaining) = match value.strip_prefix('"') {
+                Some(quoted) => {
+                    let end = quoted.find('"')?;
+                    (&quoted[..end], &quoted[end + 1..])
+                }
+                None => match value.find(',') {
+                    Some(end) => (value[..end].trim_end(), &value[end..]),
+                    None => (value.trim_end(), ""),
+                },
+            };
+
+            parsed.insert(key, value);
+
+            let remaining = remaining.trim_start();
+            rest = match remaining.strip_prefix(',') {
+                Some(remaining) => remaining.trim_start(),
+                None if remaining.is_empty() => remaining,
+                None => return None,
+            };
+        }
+
+        Some(parsed)
+    }
+
+    /// Returns the challenges for every supported algorithm with a freshly issued nonce.
+    fn challenges<S: DigestAuthProvider>(state: &S, stale: bool) -> Vec<HeaderValue> {
+        let nonce = state.digest_nonces().issue();
+        let realm = state.digest_realm();
+
+        [DigestAlgorithm::Sha256, DigestAlgorithm::Md5]
+            .iter()
+            .filter_map(|algorithm| {
+                let mut challenge = format!(
+                    "Digest realm=\"{realm}\", qop=\"auth\", algorithm={}, nonce=\"{nonce}\"",
+                    algorithm.name()
+                );
+
+                if stale {
+                    challenge.push_str(", stale=true");
+                }
+
+                HeaderValue::from_str(&challenge).ok()
+            })
+            .collect()
+    }
+
+    fn reject<S: DigestAuthProvider>(
+        state: &S,
+        verbosity: PrivateErrorVerbosity,
+        r#type: DigestAuthErrorType,
+    ) -> ApiError {
+        let stale = matches!(r#type, DigestAuthErrorType::StaleNonce);
+
+        DigestAuthError::new(verbosity, r#type, Self::challenges(state, stale)).into()
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiDigestAuth
+where
+    S: Send + Sync + DigestAuthProvider + ErrorVerbosityProvider,
+    <S as DigestAuthProvider>::Error: Into<anyhow::Error> + Display,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "digest_auth_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let Some(authorization) = parts.headers.get(AUTHORIZATION) else {
+            tracing::warn!("Rejection. Authorization header not found");
+
+            return Err(Self::reject(
+                state,
+                verbosity,
+                DigestAuthErrorType::AuthMissing,
+            ));
+        };
+
+        let authorization = authorization.to_str().map_err(|err| {
+            tracing::warn!(%err, "Rejection. Authorization header contains invalid characters");
+
+            Self::reject(
+                state,
+                verbosity,
+                DigestAuthErrorType::AuthInvalidChars { err },
+            )
+        })?;
+
+        let params = match authorization.split_once(' ') {
+            Some(("Digest", params)) => Self::parse_params(params),
+            _ => None,
+        };
+
+        let invalid_digest = || {
+            tracing::warn!("Rejection. Authorization header is invalid Digest");
+
+            Self::reject(state, verbosity, DigestAuthErrorType::InvalidDigest)
+        };
+
+        let params = params.ok_or_else(invalid_digest)?;
+
+        let param = |name: &str| params.get(name).copied().ok_or_else(invalid_digest);
+
+        let username = param("username")?;
+        let realm = param("realm")?;
+        let nonce = param("nonce")?;
+        let uri = param("uri")?;
+        let response = param("response")?;
+        let cnonce = param("cnonce")?;
+        let nc = param("nc")?;
+
+        if param("qop")? != "auth" || realm != state.digest_realm() {
+            return Err(invalid_digest());
+        }
+
+        let nonce_count = u32::from_str_radix(nc, 16).map_err(|_| invalid_digest())?;
+
+        let algorithm = params.get("algorithm").copied().unwrap_or("MD5");
+        let Some(algorithm) = DigestAlgorithm::parse(algorithm) else {
+            tracing::warn!(%algorithm, "Rejection. Unsupported digest algorithm");
+
+            return Err(Self::reject(
+                state,
+                verbosity,
+                DigestAuthErrorType::UnsupportedAlgorithm {
+                    algorithm: algorithm.to_owned(),
+                },
+            ));
+        };
+
+        let request_uri = parts
+            .extensions
+            .get::<OriginalUri>()
+            .map_or(&parts.uri, |OriginalUri(uri)| uri);
+        let request_uri = request_uri
+            .path_and_query()
+            .map_or(request_uri.path(), |path_and_query| path_and_query.as_str());
+
+        if uri != request_uri {
+            tracing::warn!(%uri, %request_uri, "Rejection. Digest uri does not match the request");
+
+            return Err(Self::reject(
+                state,
+                verbosity,
+                DigestAuthErrorType::UriMismatch,
+            ));
+        }
+
+        let password = state.digest_password(username).await.map_err(|err| {
+            ApiError::InternalServerError(InternalServerError::from_generic_error(verbosity, err))
+        })?;
+
+        let Some(password) = password else {
+            tracing::warn!(%username, "Rejection. Unknown digest user");
+
+            return Err(Self::reject(state, verbosity, DigestAuthErrorType::Invalid));
+        };
+
+        let expected = DigestParams {
+            username,
+            realm,
+            nonce,
+            uri,
+            nc,
+            cnonce,
+        }
+        .response(algorithm, parts.method.as_str(), &password);
+
+        if !bool::from(expected.as_bytes().ct_eq(response.as_bytes())) {
+            tracing::warn!(%username, "Rejection. Invalid digest response");
+
+            return Err(Self::reject(state, verbosity, DigestAuthErrorType::Invalid));
+        }
+
+        // The nonce is checked last so that invalid responses can not use up nonce counts.
+        let r#type = match state.digest_nonces().check(nonce, nonce_count) {
+            NonceCheck::Valid => None,
+            NonceCheck::Unknown => Some(DigestAuthErrorType::UnknownNonce),
+            NonceCheck::Stale => Some(DigestAuthErrorType::StaleNonce),
+            NonceCheck::Reused => Some(DigestAuthErrorType::NonceReused),
+        };
+
+        if let Some(r#type) = r#type {
+            tracing::warn!(%username, error_type = ?r#type, "Rejection. Invalid digest nonce");
+
+            return Err(Self::reject(state, verbosity, r#type));
+        }
+
+        let digest_auth = DigestAuth {
+            username: username.to_owned(),
+            algorithm,
+        };
+
+        tracing::trace!(?digest_auth, "Authenticated");
+
+        Ok(ApiDigestAuth(digest_auth))
+    }
+}
+
+impl Extractor for ApiDigestAuth {
+    type Extracted = DigestAuth;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
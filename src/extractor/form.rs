@@ -0,0 +1,110 @@
+use axum::{
+    async_trait,
+    body::{Body, Bytes},
+    extract::{rejection::FormRejection, Form as AxumForm, FromRequest, Request},
+    http::Method,
+};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, FormBodyError},
+    openapi::{ApiOperation, OperationInput},
+};
+
+use super::{unknown_urlencoded_fields, Extractor, StrictDeserializationProvider};
+
+/// A Wrapper around [`axum::extract::Form`] that rejects with an [`ApiError`].
+///
+/// Extracts the `application/x-www-form-urlencoded` request body consuming the request.
+/// `GET` and `HEAD` requests are extracted from the query string.
+/// Rejects unknown fields if strict deserialization is enabled.
+pub struct ApiForm<T>(pub T);
+
+impl<T: JsonSchema> OperationInput for ApiForm<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.form::<T>()
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiForm<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Send,
+    S: Send + Sync + ErrorVerbosityProvider + StrictDeserializationProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "form_extractor", skip_all)]
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let (parts, body) = req.into_parts();
+        let bytes = Bytes::from_request(Request::from_parts(parts.clone(), body), state)
+            .await
+            .map_err(|bytes_rejection| {
+                tracing::warn!(rejection=?bytes_rejection, "Rejection");
+
+                FormBodyError::from_form_rejection::<T>(
+                    verbosity,
+                    FormRejection::from(bytes_rejection),
+                )
+            })?;
+
+        let unknown_fields_input = match parts.method {
+            Method::GET | Method::HEAD => {
+                Bytes::copy_from_slice(parts.uri.query().unwrap_or_default().as_bytes())
+            }
+            _ => bytes.clone(),
+        };
+
+        let req = Request::from_parts(parts, Body::from(bytes));
+        let form = AxumForm::<T>::from_request(req, state).await;
+
+        match form {
+            Ok(form) => {
+                if state.strict_deserialization() {
+                    let unknown_fields = unknown_urlencoded_fields::<T>(&unknown_fields_input);
+
+                    if !unknown_fields.is_empty() {
+                        tracing::warn!(?unknown_fields, "Rejection. Unknown fields");
+
+                        return Err(FormBodyError::from_unknown_fields::<T>(
+                            verbosity,
+                            unknown_fields,
+                        ));
+                    }
+                }
+
+                tracing::trace!(form=?form.0, "Extracted");
+
+                Ok(ApiForm(form.0))
+            }
+            Err(form_rejection) => {
+                tracing::warn!(rejection=?form_rejection, "Rejection");
+
+                Err(FormBodyError::from_form_rejection::<T>(
+                    verbosity,
+                    form_rejection,
+                ))
+            }
+        }
+    }
+}
+
+impl<T> Extractor for ApiForm<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
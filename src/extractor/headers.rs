@@ -0,0 +1,100 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::COOKIE, request::Parts},
+};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, HeaderError},
+    openapi::{ApiOperation, OperationInput},
+};
+
+use super::{schema_fields, Extractor};
+
+/// Extracts the request headers and deserializes them into `T`.
+///
+/// Header names are lowercase, so use `#[serde(rename = "x-header-name")]` on the fields of `T`.
+/// Only the headers that are fields of `T` are read.
+/// Repeated headers are joined with `, `, repeated `Cookie` headers with `; `.
+pub struct ApiHeaders<T>(pub T);
+
+impl<T: JsonSchema> OperationInput for ApiHeaders<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.headers::<T>()
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiHeaders<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Send + 'static,
+    S: Send + Sync + ErrorVerbosityProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "headers_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let fields = schema_fields::<T>();
+
+        let mut headers: Vec<(&str, String)> = Vec::new();
+
+        for name in parts
+            .headers
+            .keys()
+            .filter(|name| fields.contains(name.as_str()))
+        {
+            let mut values = Vec::new();
+
+            for value in parts.headers.get_all(name) {
+                let value = value.to_str().map_err(|err| {
+                    tracing::warn!(%name, %err, "Rejection. Invalid header value");
+
+                    HeaderError::from_invalid_header_value(verbosity, name.as_str())
+                })?;
+
+                values.push(value);
+            }
+
+            let separator = match name == COOKIE {
+                true => "; ",
+                false => ", ",
+            };
+
+            headers.push((name.as_str(), values.join(separator)));
+        }
+
+        let encoded = serde_urlencoded::to_string(&headers)
+            .map_err(|err| ApiError::from_generic_error(verbosity, err))?;
+
+        let headers = serde_urlencoded::from_str::<T>(&encoded).map_err(|err| {
+            tracing::warn!(%err, "Rejection. Failed to deserialize headers");
+
+            HeaderError::from_deserialize_error::<T>(verbosity, err)
+        })?;
+
+        tracing::trace!(?headers, "Extracted");
+
+        Ok(ApiHeaders(headers))
+    }
+}
+
+impl<T> Extractor for ApiHeaders<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,99 @@
+use std::{fmt::Display, future::Future};
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{ApiError, BearerError, BearerErrorType, ErrorVerbosityProvider, InternalServerError},
+    openapi::OperationInput,
+    types::used_bearer_token::UsedBearerToken,
+};
+
+use super::{bearer_token::ApiBearerToken, Extractor};
+
+pub trait IntrospectionProvider {
+    type Error;
+
+    /// Introspects the opaque token, see [RFC 7662](https://datatracker.ietf.org/doc/html/rfc7662).
+    ///
+    /// Implementations may cache the result.
+    fn introspect(
+        &self,
+        token: &str,
+    ) -> impl Future<Output = Result<IntrospectedToken, Self::Error>> + Send;
+}
+
+/// The introspection response of a token as defined in [RFC 7662](https://datatracker.ietf.org/doc/html/rfc7662#section-2.2).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntrospectedToken {
+    pub active: bool,
+    pub scope: Option<String>,
+    pub client_id: Option<String>,
+    pub username: Option<String>,
+    pub token_type: Option<String>,
+    pub exp: Option<u64>,
+    pub iat: Option<u64>,
+    pub nbf: Option<u64>,
+    pub sub: Option<String>,
+    pub aud: Option<serde_json::Value>,
+    pub iss: Option<String>,
+    pub jti: Option<String>,
+    /// Extension fields of the introspection endpoint.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Extracts the bearer token and validates it using the state's [`IntrospectionProvider`].
+///
+/// Use this extractor for opaque tokens. Rejects if the token is not active.
+#[derive(Debug, Clone)]
+pub struct ApiIntrospectedToken(pub IntrospectedToken);
+
+impl OperationInput for ApiIntrospectedToken {}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiIntrospectedToken
+where
+    S: Send + Sync + IntrospectionProvider + ErrorVerbosityProvider,
+    <S as IntrospectionProvider>::Error: Into<anyhow::Error> + Display,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "introspected_token_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let ApiBearerToken(UsedBearerToken { value }) =
+            ApiBearerToken::from_request_parts(parts, state).await?;
+
+        let introspected_token = state.introspect(&value).await.map_err(|err| {
+            ApiError::InternalServerError(InternalServerError::from_generic_error(verbosity, err))
+        })?;
+
+        if !introspected_token.active {
+            tracing::warn!("Rejection. Token is not active");
+
+            return Err(BearerError::new(verbosity, BearerErrorType::InactiveToken).into());
+        }
+
+        tracing::trace!(?introspected_token, "Extracted");
+
+        Ok(ApiIntrospectedToken(introspected_token))
+    }
+}
+
+impl Extractor for ApiIntrospectedToken {
+    type Extracted = IntrospectedToken;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
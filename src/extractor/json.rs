@@ -1,34 +1,90 @@
 use axum::{
     async_trait,
-    extract::{FromRequest, Json as AxumJson, Request},
+    body::{Body, Bytes},
+    extract::{rejection::JsonRejection, FromRequest, Json as AxumJson, Request},
 };
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
 
-use crate::error::{ApiError, ErrorVerbosityProvider, JsonBodyError};
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, JsonBodyError},
+    openapi::{ApiOperation, OperationInput},
+};
 
-use super::Extractor;
+use super::{Extractor, StrictDeserializationProvider};
 
 /// A Wrapper around [`axum::extract::Json`] that rejects with an [`ApiError`].
 ///
 /// Extracts the request body as JSON consuming the request.
+/// Rejects unknown fields if strict deserialization is enabled.
 pub struct ApiJson<T>(pub T);
 
+impl<T: JsonSchema> OperationInput for ApiJson<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.json::<T>()
+    }
+}
+
+impl<T> ApiJson<T>
+where
+    T: DeserializeOwned + JsonSchema,
+{
+    /// Returns the paths of the fields that are not part of `T`.
+    pub(super) fn unknown_fields(bytes: &[u8]) -> Vec<String> {
+        let mut unknown_fields = Vec::new();
+
+        let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+        let _: Result<T, _> = serde_ignored::deserialize(&mut deserializer, |path| {
+            unknown_fields.push(path.to_string())
+        });
+
+        unknown_fields
+    }
+}
+
 #[async_trait]
 impl<T, S> FromRequest<S> for ApiJson<T>
 where
     T: DeserializeOwned + JsonSchema + Debug + Send,
-    S: Send + Sync + ErrorVerbosityProvider,
+    S: Send + Sync + ErrorVerbosityProvider + StrictDeserializationProvider,
 {
     type Rejection = ApiError;
 
     #[tracing::instrument(name = "json_extractor", skip_all)]
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let (parts, body) = req.into_parts();
+        let bytes = Bytes::from_request(Request::from_parts(parts.clone(), body), state)
+            .await
+            .map_err(|bytes_rejection| {
+                tracing::warn!(rejection=?bytes_rejection, "Rejection");
+
+                JsonBodyError::from_json_rejection::<T>(
+                    verbosity,
+                    JsonRejection::from(bytes_rejection),
+                )
+            })?;
+
+        let req = Request::from_parts(parts, Body::from(bytes.clone()));
         let json = AxumJson::<T>::from_request(req, state).await;
 
         match json {
             Ok(json) => {
+                if state.strict_deserialization() {
+                    let unknown_fields = Self::unknown_fields(&bytes);
+
+                    if !unknown_fields.is_empty() {
+                        tracing::warn!(?unknown_fields, "Rejection. Unknown fields");
+
+                        return Err(JsonBodyError::from_unknown_fields::<T>(
+                            verbosity,
+                            unknown_fields,
+                        ));
+                    }
+                }
+
                 tracing::trace!(json=?json.0, "Extracted");
 
                 Ok(ApiJson(json.0))
@@ -36,8 +92,6 @@ where
             Err(json_rejection) => {
                 tracing::warn!(rejection=?json_rejection, "Rejection");
 
-                let verbosity = state.error_verbosity();
-
                 Err(JsonBodyError::from_json_rejection::<T>(
                     verbosity,
                     json_rejection,
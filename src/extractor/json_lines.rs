@@ -0,0 +1,208 @@
+use std::marker::PhantomData;
+
+use axum::{
+    async_trait,
+    body::BodyDataStream,
+    extract::{FromRequest, Request},
+};
+use futures::{stream::BoxStream, StreamExt};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, JsonBodyError, PayloadTooLargeError},
+    openapi::{ApiOperation, OperationInput},
+    state::PrivateErrorVerbosity,
+};
+
+use super::{
+    body::BodyLimitProvider, has_content_type, json::ApiJson, Extractor,
+    StrictDeserializationProvider,
+};
+
+/// Accepted JSON lines content types.
+pub const JSON_LINES_CONTENT_TYPES: &[&str] = &[
+    "application/x-ndjson",
+    "application/jsonl",
+    "application/x-jsonlines",
+];
+
+/// Extracts the request body as a stream of newline-delimited JSON items consuming the request.
+///
+/// Items are deserialized one line at a time while the body is being received.
+/// Empty lines are skipped. A line must not be longer than the maximum body size.
+/// The stream ends after the first error.
+///
+/// Only the content type is checked on extraction. Item errors are yielded by the stream
+/// with the same error structure as [`ApiJson`].
+/// Rejects unknown fields if strict deserialization is enabled.
+pub struct ApiJsonLines<T>(pub BoxStream<'static, Result<T, ApiError>>);
+
+impl<T: JsonSchema> OperationInput for ApiJsonLines<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.body::<T>(JSON_LINES_CONTENT_TYPES[0])
+    }
+}
+
+struct JsonLines<T> {
+    body: BodyDataStream,
+    buffer: Vec<u8>,
+    line: usize,
+    eof: bool,
+    done: bool,
+    verbosity: PrivateErrorVerbosity,
+    strict_deserialization: bool,
+    max_line_size_in_bytes: usize,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T> JsonLines<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug,
+{
+    async fn next_item(&mut self) -> Option<Result<T, ApiError>> {
+        while !self.done {
+            if let Some(position) = self.buffer.iter().position(|byte| *byte == b'\n') {
+                let line = self.buffer.drain(..=position).collect::<Vec<_>>();
+                self.line += 1;
+
+                if line.trim_ascii().is_empty() {
+                    continue;
+                }
+
+                return Some(self.parse(&line));
+            }
+
+            if self.eof {
+                self.done = true;
+
+                let line = std::mem::take(&mut self.buffer);
+                self.line += 1;
+
+                if line.trim_ascii().is_empty() {
+                    return None;
+                }
+
+                return Some(self.parse(&line));
+            }
+
+            if self.buffer.len() > self.max_line_size_in_bytes {
+                tracing::warn!(line = self.line + 1, "Rejection. Line too large");
+
+                self.done = true;
+
+                return Some(Err(PayloadTooLargeError::new(
+                    self.verbosity,
+                    self.max_line_size_in_bytes,
+                )
+                .into()));
+            }
+
+            match self.body.next().await {
+                Some(Ok(chunk)) => self.buffer.extend_from_slice(&chunk),
+                Some(Err(err)) => {
+                    tracing::warn!(%err, "Rejection. Failed to read body");
+
+                    self.done = true;
+
+                    return Some(Err(ApiError::from_generic_error(self.verbosity, err)));
+                }
+                None => self.eof = true,
+            }
+        }
+
+        None
+    }
+
+    fn parse(&mut self, line: &[u8]) -> Result<T, ApiError> {
+        let item = serde_json::from_slice::<T>(line).map_err(|err| {
+            tracing::warn!(line = self.line, %err, "Rejection");
+
+            self.done = true;
+
+            JsonBodyError::from_json_lines_error::<T>(self.verbosity, self.line, err)
+        })?;
+
+        if self.strict_deserialization {
+            let unknown_fields = ApiJson::<T>::unknown_fields(line);
+
+            if !unknown_fields.is_empty() {
+                tracing::warn!(
+                    line = self.line,
+                    ?unknown_fields,
+                    "Rejection. Unknown fields"
+                );
+
+                self.done = true;
+
+                return Err(JsonBodyError::from_unknown_fields::<T>(
+                    self.verbosity,
+                    unknown_fields,
+                ));
+            }
+        }
+
+        tracing::trace!(line = self.line, ?item, "Extracted");
+
+        Ok(item)
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiJsonLines<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Send + 'static,
+    S: Send + Sync + ErrorVerbosityProvider + StrictDeserializationProvider + BodyLimitProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "json_lines_extractor", skip_all)]
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        if !has_content_type(req.headers(), JSON_LINES_CONTENT_TYPES) {
+            tracing::warn!("Rejection. Missing JSON lines content type");
+
+            return Err(JsonBodyError::missing_json_lines_content_type::<T>(
+                verbosity,
+            ));
+        }
+
+        let lines = JsonLines::<T> {
+            body: req.into_body().into_data_stream(),
+            buffer: Vec::new(),
+            line: 0,
+            eof: false,
+            done: false,
+            verbosity,
+            strict_deserialization: state.strict_deserialization(),
+            max_line_size_in_bytes: state.max_body_size_in_bytes(),
+            _item: PhantomData,
+        };
+
+        let stream = futures::stream::unfold(lines, |mut lines| async move {
+            let item = lines.next_item().await?;
+
+            Some((item, lines))
+        });
+
+        Ok(ApiJsonLines(stream.boxed()))
+    }
+}
+
+impl<T> Extractor for ApiJsonLines<T> {
+    type Extracted = BoxStream<'static, Result<T, ApiError>>;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -5,24 +5,71 @@ use std::{
 
 use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
 use serde::de::DeserializeOwned;
-use validation::JwtValidator;
+use validation::{JwtValidationError, JwtValidator};
 
 use crate::{
     error::{ApiError, ErrorVerbosityProvider, InternalServerError, JwtError, JwtErrorType},
     extractor::bearer_token::ApiBearerToken,
+    openapi::OperationInput,
+    revocation::{RevocableToken, TokenRevocationProvider},
     types::used_bearer_token::UsedBearerToken,
 };
 
 /// Extracts and validates the claims from the bearer JWT token.
+///
+/// If the token is signed with an unknown key, the JWK set is refreshed and the token is validated once more.
+/// Valid tokens are rejected if they were revoked through the [`TokenRevocationProvider`].
 #[derive(Debug)]
 pub struct ApiJwt<C>(pub C);
 
+impl<C> OperationInput for ApiJwt<C> {}
+
+impl<C> ApiJwt<C>
+where
+    C: DeserializeOwned,
+{
+    /// Validates the token against the current JWK set of the issuer.
+    ///
+    /// The outer error is an internal error, the inner error is a validation error.
+    async fn validate<S>(
+        jwt: &str,
+        issuer: &str,
+        state: &S,
+    ) -> Result<Result<C, JwtValidationError>, ApiError>
+    where
+        S: JwksProvider + ErrorVerbosityProvider,
+        <S as JwksProvider>::Error: Into<anyhow::Error>,
+    {
+        let jwks = state.jwks(issuer).await.map_err(|err| {
+            ApiError::InternalServerError(InternalServerError::from_generic_error(
+                state.error_verbosity(),
+                err,
+            ))
+        })?;
+
+        let Some(jwks) = jwks else {
+            return Ok(Err(JwtValidationError::UntrustedIssuer {
+                issuer: issuer.to_string(),
+            }));
+        };
+
+        Ok(JwtValidator::validate::<C, _, _>(
+            jwt,
+            jwks.as_ref(),
+            state.audience(issuer),
+            &[issuer],
+            state.validation_config(),
+        ))
+    }
+}
+
 #[async_trait]
 impl<C, S> FromRequestParts<S> for ApiJwt<C>
 where
     C: DeserializeOwned + Debug,
-    S: Send + Sync + JwksProvider + ErrorVerbosityProvider,
+    S: Send + Sync + JwksProvider + TokenRevocationProvider + ErrorVerbosityProvider,
     <S as JwksProvider>::Error: Into<anyhow::Error> + Display,
+    <S as TokenRevocationProvider>::Error: Into<anyhow::Error> + Display,
 {
     type Rejection = ApiError;
 
@@ -33,18 +80,7 @@ where
         let ApiBearerToken(UsedBearerToken { value }) =
             ApiBearerToken::from_request_parts(parts, state).await?;
 
-        let jwks = state.jwks().await.map_err(|err| {
-            ApiError::InternalServerError(InternalServerError::from_generic_error(verbosity, err))
-        })?;
-
-        let claims = JwtValidator::validate::<C, _, _>(
-            &value,
-            jwks.as_ref(),
-            state.audience(),
-            state.issuer(),
-            state.validate_nbf(),
-        )
-        .map_err(|err| {
+        let reject = |err: JwtValidationError| {
             tracing::warn!(%err, "Rejection");
 
             if err.is_expired() {
@@ -52,8 +88,45 @@ where
             }
 
             ApiError::Jwt(JwtError::new(verbosity, JwtErrorType::Invalid { err }))
+        };
+
+        let issuer = JwtValidator::unverified_issuer(&value).map_err(reject)?;
+
+        // Checked before the validation so that the claims are not held across an await.
+        let token = RevocableToken::from_jwt(&value);
+
+        let revoked = state.is_token_revoked(&token.id).await.map_err(|err| {
+            ApiError::InternalServerError(InternalServerError::from_generic_error(verbosity, err))
         })?;
 
+        if revoked {
+            tracing::warn!(token_id = %token.id, "Rejection. Token is revoked");
+
+            return Err(ApiError::Jwt(JwtError::new(
+                verbosity,
+                JwtErrorType::Revoked,
+            )));
+        }
+
+        let mut refreshed = false;
+        let claims = loop {
+            match Self::validate(&value, &issuer, state).await? {
+                Err(JwtValidationError::NoMatchingJWK { kid }) if !refreshed => {
+                    tracing::debug!(%kid, "Unknown kid. Refreshing Jwks");
+                }
+                result => break result,
+            }
+
+            refreshed = true;
+
+            state.refresh_jwks(&issuer).await.map_err(|err| {
+                ApiError::InternalServerError(InternalServerError::from_generic_error(
+                    verbosity, err,
+                ))
+            })?;
+        }
+        .map_err(reject)?;
+
         tracing::trace!(?claims, "Extracted");
 
         Ok(ApiJwt(claims))
@@ -63,22 +136,96 @@ where
 pub mod validation {
     use std::str::FromStr;
 
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
     use jsonwebtoken::{
         decode, decode_header,
-        jwk::{AlgorithmParameters, JwkSet},
+        jwk::{AlgorithmParameters, EllipticCurve, JwkSet},
         Algorithm, DecodingKey, Validation,
     };
-    use serde::de::DeserializeOwned;
+    use serde::{de::DeserializeOwned, Deserialize};
+
+    /// Tunes the validation of tokens, e.g. for deployments with clock skew or nonstandard claims.
+    #[derive(Debug, Clone, Deserialize)]
+    pub struct JwtValidationConfig {
+        /// Clock skew tolerated when validating the `exp` and `nbf` claims.
+        #[serde(default = "default_leeway_in_seconds")]
+        pub leeway_in_seconds: u64,
+        /// Claims that must be present in the token.
+        #[serde(default = "default_required_claims")]
+        pub required_claims: Vec<String>,
+        /// Algorithms tokens may be signed with. Every supported algorithm is allowed if empty.
+        #[serde(default)]
+        pub allowed_algorithms: Vec<Algorithm>,
+        #[serde(default = "default_true")]
+        pub validate_exp: bool,
+        #[serde(default = "default_true")]
+        pub validate_nbf: bool,
+    }
+
+    fn default_leeway_in_seconds() -> u64 {
+        60
+    }
+
+    fn default_required_claims() -> Vec<String> {
+        vec![String::from("exp")]
+    }
+
+    fn default_true() -> bool {
+        true
+    }
+
+    impl Default for JwtValidationConfig {
+        fn default() -> Self {
+            Self {
+                leeway_in_seconds: default_leeway_in_seconds(),
+                required_claims: default_required_claims(),
+                allowed_algorithms: Vec::new(),
+                validate_exp: default_true(),
+                validate_nbf: default_true(),
+            }
+        }
+    }
 
     pub struct JwtValidator;
 
+    #[derive(Deserialize)]
+    struct IssuerClaim {
+        iss: Option<String>,
+    }
+
     impl JwtValidator {
+        /// Returns the `iss` claim of the token without validating the token.
+        ///
+        /// Used to select the issuer the token is validated against.
+        pub fn unverified_issuer(jwt: &str) -> Result<String, JwtValidationError> {
+            let claim = Self::unverified_claims::<IssuerClaim>(jwt)?;
+
+            claim.iss.ok_or(JwtValidationError::NoIssuer)
+        }
+
+        /// Returns the claims of the token without validating the token.
+        pub fn unverified_claims<C>(jwt: &str) -> Result<C, JwtValidationError>
+        where
+            C: DeserializeOwned,
+        {
+            let payload = jwt
+                .split('.')
+                .nth(1)
+                .ok_or(JwtValidationError::MalformedPayload)?;
+
+            let payload = URL_SAFE_NO_PAD
+                .decode(payload)
+                .map_err(|_| JwtValidationError::MalformedPayload)?;
+
+            serde_json::from_slice::<C>(&payload).map_err(|_| JwtValidationError::MalformedPayload)
+        }
+
         pub fn validate<C, A, I>(
             jwt: &str,
             jwks: &JwkSet,
             audience: &[A],
             issuer: &[I],
-            validate_nbf: bool,
+            config: &JwtValidationConfig,
         ) -> Result<C, JwtValidationError>
         where
             C: DeserializeOwned,
@@ -91,32 +238,79 @@ pub mod validation {
             let jwk = jwks
                 .find(&kid)
                 .ok_or(JwtValidationError::NoMatchingJWK { kid })?;
-            let AlgorithmParameters::RSA(ref rsa) = jwk.algorithm else {
-                return Err(JwtValidationError::UnsupportedAlgorithm);
-            };
 
-            let decoding_key = DecodingKey::from_rsa_components(&rsa.n, &rsa.e)
-                .map_err(JwtValidationError::DecodingKey)?;
+            let decoding_key =
+                DecodingKey::from_jwk(jwk).map_err(JwtValidationError::DecodingKey)?;
 
-            let key_algorithm = jwk
-                .common
-                .key_algorithm
-                .ok_or(JwtValidationError::KeyAlgorithmNotFound)?;
+            let algorithm = match jwk.common.key_algorithm {
+                Some(key_algorithm) => {
+                    Algorithm::from_str(key_algorithm.to_string().as_str()).map_err(|err| {
+                        JwtValidationError::ValidationAlgorithm { key_algorithm, err }
+                    })?
+                }
+                None => Self::default_algorithm(&jwk.algorithm, header.alg)?,
+            };
 
-            let mut validation = Validation::new(
-                Algorithm::from_str(key_algorithm.to_string().as_str()).map_err(|err| {
-                    JwtValidationError::ValidationAlgorithm { key_algorithm, err }
-                })?,
-            );
+            if !config.allowed_algorithms.is_empty()
+                && !config.allowed_algorithms.contains(&algorithm)
+            {
+                return Err(JwtValidationError::AlgorithmNotAllowed { algorithm });
+            }
+
+            let mut validation = Validation::new(algorithm);
 
             validation.set_audience(audience);
             validation.set_issuer(issuer);
-            validation.validate_nbf = validate_nbf;
+            validation.set_required_spec_claims(&config.required_claims);
+            validation.leeway = config.leeway_in_seconds;
+            validation.validate_exp = config.validate_exp;
+            validation.validate_nbf = config.validate_nbf;
 
             let token_data = decode::<C>(jwt, &decoding_key, &validation)?;
 
+            // jsonwebtoken only checks the presence of the registered claims.
+            let claims =
+                Self::unverified_claims::<serde_json::Map<String, serde_json::Value>>(jwt)?;
+
+            if let Some(claim) = config
+                .required_claims
+                .iter()
+                .find(|claim| !claims.contains_key(claim.as_str()))
+            {
+                return Err(JwtValidationError::MissingClaim {
+                    claim: claim.clone(),
+                });
+            }
+
             Ok(token_data.claims)
         }
+
+        /// Returns the algorithm for a JWK without an `alg` field.
+        ///
+        /// EC and OKP keys imply their algorithm through the curve.
+        /// Symmetric keys use the HMAC algorithm of the token header.
+        /// RSA keys are ambiguous and must specify the algorithm.
+        fn default_algorithm(
+            parameters: &AlgorithmParameters,
+            header_algorithm: Algorithm,
+        ) -> Result<Algorithm, JwtValidationError> {
+            match parameters {
+                AlgorithmParameters::EllipticCurve(ec) => match ec.curve {
+                    EllipticCurve::P256 => Ok(Algorithm::ES256),
+                    EllipticCurve::P384 => Ok(Algorithm::ES384),
+                    _ => Err(JwtValidationError::UnsupportedAlgorithm),
+                },
+                AlgorithmParameters::OctetKeyPair(okp) => match okp.curve {
+                    EllipticCurve::Ed25519 => Ok(Algorithm::EdDSA),
+                    _ => Err(JwtValidationError::UnsupportedAlgorithm),
+                },
+                AlgorithmParameters::OctetKey(_) => match header_algorithm {
+                    Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => Ok(header_algorithm),
+                    _ => Err(JwtValidationError::UnsupportedAlgorithm),
+                },
+                AlgorithmParameters::RSA(_) => Err(JwtValidationError::KeyAlgorithmNotFound),
+            }
+        }
     }
 
     #[derive(Debug, thiserror::Error)]
@@ -125,10 +319,20 @@ pub mod validation {
         DecodeHeader(#[source] jsonwebtoken::errors::Error),
         #[error("Token doesn't have a kid header field")]
         NoKid,
+        #[error("Token payload is malformed")]
+        MalformedPayload,
+        #[error("Token doesn't have an iss claim")]
+        NoIssuer,
+        #[error("Token issuer is not trusted: {issuer}")]
+        UntrustedIssuer { issuer: String },
         #[error("No matching JWK found for the given kid: {kid}")]
         NoMatchingJWK { kid: String },
         #[error("JWK algorithm is not supported")]
         UnsupportedAlgorithm,
+        #[error("Algorithm is not allowed: {algorithm:?}")]
+        AlgorithmNotAllowed { algorithm: Algorithm },
+        #[error("Token doesn't have the required claim: {claim}")]
+        MissingClaim { claim: String },
         #[error("Error creating decoding key: {0}")]
         DecodingKey(#[source] jsonwebtoken::errors::Error),
         #[error("No key algorithm found in JWK")]
@@ -159,17 +363,22 @@ pub mod validation {
 pub trait JwksProvider {
     type Error;
 
-    /// Returns the JWK set.
+    /// Returns the JWK set of the issuer.
+    ///
+    /// Returns `None` if the issuer is not trusted.
     fn jwks(
         &self,
-    ) -> impl Future<Output = Result<impl AsRef<jsonwebtoken::jwk::JwkSet>, Self::Error>> + Send;
+        issuer: &str,
+    ) -> impl Future<Output = Result<Option<impl AsRef<jsonwebtoken::jwk::JwkSet>>, Self::Error>> + Send;
 
-    /// Returns the audience.
-    fn audience(&self) -> &[impl ToString];
+    /// Refreshes the JWK set of the issuer out of schedule, e.g. because a token is signed with an unknown key.
+    ///
+    /// Implementations should rate limit refreshes.
+    fn refresh_jwks(&self, issuer: &str) -> impl Future<Output = Result<(), Self::Error>> + Send;
 
-    /// Returns the issuer.
-    fn issuer(&self) -> &[impl ToString];
+    /// Returns the audience accepted for tokens of the issuer.
+    fn audience<'a>(&'a self, issuer: &str) -> &'a [impl ToString + 'a];
 
-    /// Returns whether to validate the nbf claim.
-    fn validate_nbf(&self) -> bool;
+    /// Returns how tokens are validated.
+    fn validation_config(&self) -> &validation::JwtValidationConfig;
 }
@@ -0,0 +1,94 @@
+use std::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+};
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, JwtError, JwtErrorType},
+    openapi::OperationInput,
+    revocation::TokenRevocationProvider,
+};
+
+use super::{
+    jwt::{ApiJwt, JwksProvider},
+    Extractor,
+};
+
+/// Claims that grant roles, e.g. through a `roles` or `scope` claim.
+pub trait HasRoles {
+    /// Returns whether the claims grant the role.
+    fn has_role(&self, role: &str) -> bool;
+}
+
+/// Roles required by a route.
+///
+/// ```rust,ignore
+/// pub struct Admin;
+///
+/// impl RequiredRoles for Admin {
+///     const ROLES: &'static [&'static str] = &["admin"];
+/// }
+/// ```
+pub trait RequiredRoles {
+    /// All of these roles must be granted.
+    const ROLES: &'static [&'static str];
+}
+
+/// Same as [`ApiJwt`] but also rejects if the claims do not grant all the [`RequiredRoles`] `R`.
+///
+/// Rejects with [`JwtErrorType::Forbidden`] if a role is missing.
+#[derive(Debug)]
+pub struct ApiJwtWithRoles<R, C>(pub C, pub PhantomData<R>);
+
+impl<R, C> OperationInput for ApiJwtWithRoles<R, C> {}
+
+#[async_trait]
+impl<R, C, S> FromRequestParts<S> for ApiJwtWithRoles<R, C>
+where
+    R: RequiredRoles,
+    C: DeserializeOwned + HasRoles + Debug,
+    S: Send + Sync + JwksProvider + TokenRevocationProvider + ErrorVerbosityProvider,
+    <S as JwksProvider>::Error: Into<anyhow::Error> + Display,
+    <S as TokenRevocationProvider>::Error: Into<anyhow::Error> + Display,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "jwt_with_roles_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ApiJwt(claims) = ApiJwt::<C>::from_request_parts(parts, state).await?;
+
+        let missing_roles = R::ROLES
+            .iter()
+            .filter(|role| !claims.has_role(role))
+            .collect::<Vec<_>>();
+
+        if !missing_roles.is_empty() {
+            tracing::warn!(?missing_roles, "Rejection. Missing roles");
+
+            return Err(JwtError::new(state.error_verbosity(), JwtErrorType::Forbidden).into());
+        }
+
+        tracing::trace!(roles = ?R::ROLES, "Extracted");
+
+        Ok(ApiJwtWithRoles(claims, PhantomData))
+    }
+}
+
+impl<R, C> Extractor for ApiJwtWithRoles<R, C> {
+    type Extracted = C;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,59 @@
+use std::convert::Infallible;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::ACCEPT_LANGUAGE, request::Parts},
+};
+
+use crate::{locale::LocaleCatalogProvider, openapi::OperationInput};
+
+use super::Extractor;
+
+/// Extracts the best supported locale for the `Accept-Language` header.
+///
+/// See [`LocaleCatalog::negotiate`](crate::locale::LocaleCatalog::negotiate).
+///
+/// This extractor never fails. Falls back to the default locale.
+#[derive(Debug, Clone)]
+pub struct ApiLocale(pub String);
+
+impl OperationInput for ApiLocale {}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiLocale
+where
+    S: Send + Sync + LocaleCatalogProvider,
+{
+    type Rejection = Infallible;
+
+    #[tracing::instrument(name = "locale_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let accept_language = parts
+            .headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok());
+
+        let locale = state.locale_catalog().negotiate(accept_language).to_owned();
+
+        tracing::trace!(%locale, "Extracted");
+
+        Ok(ApiLocale(locale))
+    }
+}
+
+impl Extractor for ApiLocale {
+    type Extracted = String;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -1,14 +1,145 @@
+pub mod all;
 pub mod api_key;
+pub mod api_request;
 pub mod authenticated_basic_auth;
+pub mod authorized;
 pub mod basic_auth;
 pub mod bearer_token;
+pub mod body;
+pub mod cbor;
+pub mod client_cert;
+pub mod client_info;
+pub mod client_ip;
+pub mod conditional;
+pub mod cookie;
+pub mod deadline;
+pub mod digest_auth;
+pub mod form;
+pub mod headers;
+pub mod introspected_token;
 pub mod json;
+pub mod json_lines;
 pub mod jwt;
+pub mod jwt_roles;
+pub mod locale;
+pub mod msgpack;
+pub mod multipart;
 pub mod optional;
+pub mod pagination;
 pub mod path;
+pub mod principal;
 pub mod query;
+pub mod query_extra;
+pub mod session;
+pub mod signed_request;
+pub mod sort_filter;
+pub mod tenant;
+pub mod url_parts;
 pub mod valid_api_key;
 pub mod validated;
+pub mod xml;
+
+use std::{
+    any::TypeId,
+    collections::{BTreeSet, HashMap},
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use axum::http::{header::CONTENT_TYPE, HeaderMap};
+use schemars::{
+    schema::{Schema, SchemaObject},
+    schema_for, JsonSchema, Map,
+};
+use serde::de::DeserializeOwned;
+
+/// Returns the names of the top level properties of `T`'s schema.
+///
+/// `$ref`s and `allOf`s, e.g. of flattened fields, are resolved. The names are computed once per type.
+fn schema_fields<T: JsonSchema + 'static>() -> Arc<BTreeSet<String>> {
+    static FIELDS: OnceLock<RwLock<HashMap<TypeId, Arc<BTreeSet<String>>>>> = OnceLock::new();
+
+    let cache = FIELDS.get_or_init(Default::default);
+
+    if let Some(fields) = cache
+        .read()
+        .expect("schema fields lock poisoned")
+        .get(&TypeId::of::<T>())
+    {
+        return fields.clone();
+    }
+
+    let root = schema_for!(T);
+    let mut fields = BTreeSet::new();
+    collect_schema_fields(&root.schema, &root.definitions, &mut fields);
+
+    cache
+        .write()
+        .expect("schema fields lock poisoned")
+        .entry(TypeId::of::<T>())
+        .or_insert_with(|| Arc::new(fields))
+        .clone()
+}
+
+fn collect_schema_fields(
+    schema: &SchemaObject,
+    definitions: &Map<String, Schema>,
+    fields: &mut BTreeSet<String>,
+) {
+    let referenced = schema
+        .reference
+        .as_deref()
+        .and_then(|reference| reference.strip_prefix("#/definitions/"))
+        .and_then(|name| definitions.get(name));
+
+    if let Some(Schema::Object(referenced)) = referenced {
+        collect_schema_fields(referenced, definitions, fields);
+    }
+
+    if let Some(object) = &schema.object {
+        fields.extend(object.properties.keys().cloned());
+    }
+
+    let all_of = schema
+        .subschemas
+        .as_ref()
+        .and_then(|subschemas| subschemas.all_of.as_ref());
+
+    for subschema in all_of.into_iter().flatten() {
+        if let Schema::Object(subschema) = subschema {
+            collect_schema_fields(subschema, definitions, fields);
+        }
+    }
+}
+
+/// Returns the paths of the url-encoded fields that are not part of `T`.
+fn unknown_urlencoded_fields<T: DeserializeOwned>(input: &[u8]) -> Vec<String> {
+    let mut unknown_fields = Vec::new();
+
+    let deserializer = serde_urlencoded::Deserializer::new(form_urlencoded::parse(input));
+    let _: Result<T, _> =
+        serde_ignored::deserialize(deserializer, |path| unknown_fields.push(path.to_string()));
+
+    unknown_fields
+}
+
+/// Returns whether the `Content-Type` header of the request is one of `content_types`, ignoring parameters.
+fn has_content_type(headers: &HeaderMap, content_types: &[&str]) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(|content_type| content_type.split(';').next())
+        .map(str::trim)
+        .is_some_and(|essence| {
+            content_types
+                .iter()
+                .any(|content_type| essence.eq_ignore_ascii_case(content_type))
+        })
+}
+
+pub trait StrictDeserializationProvider {
+    /// Returns whether the body and query extractors reject unknown fields.
+    fn strict_deserialization(&self) -> bool;
+}
 
 pub trait Extractor {
     type Extracted;
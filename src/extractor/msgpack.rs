@@ -0,0 +1,118 @@
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
+};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, MsgPackBodyError},
+    openapi::{ApiOperation, OperationInput},
+};
+
+use super::{has_content_type, Extractor, StrictDeserializationProvider};
+
+/// Accepted MessagePack content types.
+pub const MSGPACK_CONTENT_TYPES: &[&str] = &[
+    "application/msgpack",
+    "application/x-msgpack",
+    "application/vnd.msgpack",
+];
+
+/// Extracts the request body as MessagePack consuming the request.
+///
+/// Rejects with the same error structure as [`ApiJson`](super::json::ApiJson).
+/// Rejects unknown fields if strict deserialization is enabled.
+pub struct ApiMsgPack<T>(pub T);
+
+impl<T: JsonSchema> OperationInput for ApiMsgPack<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.body::<T>(MSGPACK_CONTENT_TYPES[0])
+    }
+}
+
+impl<T> ApiMsgPack<T>
+where
+    T: DeserializeOwned + JsonSchema,
+{
+    /// Returns the paths of the fields that are not part of `T`.
+    fn unknown_fields(bytes: &[u8]) -> Vec<String> {
+        let mut unknown_fields = Vec::new();
+
+        let mut deserializer = rmp_serde::Deserializer::from_read_ref(bytes);
+        let _: Result<T, _> = serde_ignored::deserialize(&mut deserializer, |path| {
+            unknown_fields.push(path.to_string())
+        });
+
+        unknown_fields
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiMsgPack<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Send,
+    S: Send + Sync + ErrorVerbosityProvider + StrictDeserializationProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "msgpack_extractor", skip_all)]
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        if !has_content_type(req.headers(), MSGPACK_CONTENT_TYPES) {
+            tracing::warn!("Rejection. Missing MessagePack content type");
+
+            return Err(MsgPackBodyError::missing_content_type::<T>(verbosity));
+        }
+
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(|bytes_rejection| {
+                tracing::warn!(rejection=?bytes_rejection, "Rejection");
+
+                ApiError::from_generic_error(verbosity, bytes_rejection)
+            })?;
+
+        let msgpack = rmp_serde::from_slice::<T>(&bytes).map_err(|err| {
+            tracing::warn!(%err, "Rejection");
+
+            MsgPackBodyError::from_decode_error::<T>(verbosity, err)
+        })?;
+
+        if state.strict_deserialization() {
+            let unknown_fields = Self::unknown_fields(&bytes);
+
+            if !unknown_fields.is_empty() {
+                tracing::warn!(?unknown_fields, "Rejection. Unknown fields");
+
+                return Err(MsgPackBodyError::from_unknown_fields::<T>(
+                    verbosity,
+                    unknown_fields,
+                ));
+            }
+        }
+
+        tracing::trace!(?msgpack, "Extracted");
+
+        Ok(ApiMsgPack(msgpack))
+    }
+}
+
+impl<T> Extractor for ApiMsgPack<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,195 @@
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Multipart as AxumMultipart, Request},
+};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::fmt::Debug;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, MultipartError, MultipartErrorType},
+    openapi::{ApiOperation, OperationInput},
+    state::PrivateErrorVerbosity,
+};
+
+use super::Extractor;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct MultipartLimits {
+    pub max_field_size_in_bytes: usize,
+    pub max_total_size_in_bytes: usize,
+}
+
+impl Default for MultipartLimits {
+    fn default() -> Self {
+        Self {
+            max_field_size_in_bytes: 1024 * 1024,
+            max_total_size_in_bytes: 2 * 1024 * 1024,
+        }
+    }
+}
+
+pub trait MultipartLimitsProvider {
+    /// Returns the size limits for multipart requests.
+    fn multipart_limits(&self) -> MultipartLimits;
+}
+
+/// A file field of a multipart request.
+#[derive(Debug, Clone)]
+pub struct MultipartFile {
+    pub name: String,
+    pub file_name: String,
+    pub content_type: Option<String>,
+    pub bytes: Bytes,
+}
+
+/// A Wrapper around [`axum::extract::Multipart`] that rejects with an [`ApiError`].
+///
+/// Streams the `multipart/form-data` request body consuming the request and enforces the [`MultipartLimits`].
+/// Text fields are deserialized into `T`, file fields are collected as [`MultipartFile`]s.
+///
+/// Implements [`Extractor`] for `T`, so the text fields can be validated using [`Validated`](super::validated::Validated).
+pub struct ApiMultipart<T> {
+    pub fields: T,
+    pub files: Vec<MultipartFile>,
+}
+
+impl<T: JsonSchema> OperationInput for ApiMultipart<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.body::<T>("multipart/form-data")
+    }
+}
+
+impl<T> ApiMultipart<T> {
+    fn read_error(
+        verbosity: PrivateErrorVerbosity,
+        err: axum::extract::multipart::MultipartError,
+    ) -> ApiError {
+        tracing::warn!(%err, "Rejection. Failed to read multipart");
+
+        MultipartError::new(verbosity, MultipartErrorType::Read { err }).into()
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiMultipart<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Send,
+    S: Send + Sync + ErrorVerbosityProvider + MultipartLimitsProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "multipart_extractor", skip_all)]
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+        let limits = state.multipart_limits();
+
+        let mut multipart =
+            AxumMultipart::from_request(req, state)
+                .await
+                .map_err(|multipart_rejection| {
+                    tracing::warn!(rejection=?multipart_rejection, "Rejection");
+
+                    MultipartError::new(verbosity, MultipartErrorType::InvalidBoundary)
+                })?;
+
+        let mut total_size = 0;
+        let mut text_fields = Vec::new();
+        let mut files = Vec::new();
+
+        while let Some(mut field) = multipart
+            .next_field()
+            .await
+            .map_err(|err| Self::read_error(verbosity, err))?
+        {
+            let name = field.name().unwrap_or_default().to_string();
+            let file_name = field.file_name().map(ToString::to_string);
+            let content_type = field.content_type().map(ToString::to_string);
+
+            let mut bytes = Vec::new();
+
+            while let Some(chunk) = field
+                .chunk()
+                .await
+                .map_err(|err| Self::read_error(verbosity, err))?
+            {
+                total_size += chunk.len();
+
+                if total_size > limits.max_total_size_in_bytes {
+                    tracing::warn!(%name, "Rejection. Multipart is too large");
+
+                    return Err(MultipartError::new(
+                        verbosity,
+                        MultipartErrorType::TotalTooLarge {
+                            limit: limits.max_total_size_in_bytes,
+                        },
+                    )
+                    .into());
+                }
+
+                if bytes.len() + chunk.len() > limits.max_field_size_in_bytes {
+                    tracing::warn!(%name, "Rejection. Multipart field is too large");
+
+                    return Err(MultipartError::new(
+                        verbosity,
+                        MultipartErrorType::FieldTooLarge {
+                            name,
+                            limit: limits.max_field_size_in_bytes,
+                        },
+                    )
+                    .into());
+                }
+
+                bytes.extend_from_slice(&chunk);
+            }
+
+            match file_name {
+                Some(file_name) => files.push(MultipartFile {
+                    name,
+                    file_name,
+                    content_type,
+                    bytes: Bytes::from(bytes),
+                }),
+                None => {
+                    let text = String::from_utf8(bytes).map_err(|err| {
+                        tracing::warn!(%name, %err, "Rejection. Multipart text field is not valid UTF-8");
+
+                        MultipartError::new(verbosity, MultipartErrorType::InvalidUtf8 { name: name.clone(), err })
+                    })?;
+
+                    text_fields.push((name, text));
+                }
+            }
+        }
+
+        let encoded = serde_urlencoded::to_string(&text_fields)
+            .map_err(|err| ApiError::from_generic_error(verbosity, err))?;
+
+        let fields = serde_urlencoded::from_str::<T>(&encoded).map_err(|err| {
+            tracing::warn!(%err, "Rejection. Failed to deserialize multipart fields");
+
+            MultipartError::from_deserialize_error::<T>(verbosity, err)
+        })?;
+
+        tracing::trace!(?fields, files = files.len(), "Extracted");
+
+        Ok(ApiMultipart { fields, files })
+    }
+}
+
+impl<T> Extractor for ApiMultipart<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.fields
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.fields
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.fields
+    }
+}
@@ -1,11 +1,22 @@
 use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
 use std::convert::Infallible;
 
+use crate::{
+    error::ApiError,
+    openapi::{ApiOperation, OperationInput},
+};
+
 /// Extracts an optional extractor from the request.
 ///
 /// This Extractors never fails, it will always return `None` if the inner extractor fails.
 pub struct Optional<X>(pub Option<X>);
 
+impl<X: OperationInput> OperationInput for Optional<X> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        X::describe(operation)
+    }
+}
+
 #[async_trait]
 impl<X, S> FromRequestParts<S> for Optional<X>
 where
@@ -21,3 +32,33 @@ where
         Ok(Optional(inner))
     }
 }
+
+/// Extracts an optional extractor from the request.
+///
+/// Returns `None` only if the credential is missing.
+/// Rejects with the inner [`ApiError`] if the credential is present but malformed or invalid.
+pub struct OptionalStrict<X>(pub Option<X>);
+
+impl<X: OperationInput> OperationInput for OptionalStrict<X> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        X::describe(operation)
+    }
+}
+
+#[async_trait]
+impl<X, S> FromRequestParts<S> for OptionalStrict<X>
+where
+    X: FromRequestParts<S, Rejection = ApiError>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "optional_strict_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match X::from_request_parts(parts, state).await {
+            Ok(inner) => Ok(OptionalStrict(Some(inner))),
+            Err(err) if err.is_missing() => Ok(OptionalStrict(None)),
+            Err(err) => Err(err),
+        }
+    }
+}
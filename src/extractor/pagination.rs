@@ -0,0 +1,266 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::LINK, request::Parts, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, PaginationError, PaginationErrorType},
+    openapi::{OperationInput, OperationOutput},
+};
+
+use super::Extractor;
+
+/// What to do with pagination values that are out of range.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub enum OutOfRangeBehavior {
+    /// Clamp the value into the allowed range.
+    #[default]
+    Clamp,
+    /// Reject the request with a [`PaginationError`].
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct PaginationConfig {
+    pub default_per_page: u64,
+    pub max_per_page: u64,
+    pub out_of_range: OutOfRangeBehavior,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            default_per_page: 20,
+            max_per_page: 100,
+            out_of_range: OutOfRangeBehavior::Clamp,
+        }
+    }
+}
+
+pub trait PaginationConfigProvider {
+    /// Returns the pagination bounds.
+    fn pagination_config(&self) -> PaginationConfig;
+}
+
+/// Query parameters of both pagination styles. Other parameters are ignored.
+#[derive(Debug, Deserialize)]
+struct PaginationQuery {
+    page: Option<u64>,
+    per_page: Option<u64>,
+    offset: Option<u64>,
+    limit: Option<u64>,
+}
+
+/// Extracts the pagination from the query string.
+///
+/// Accepts either `page`/`per_page` (pages start at 1) or `offset`/`limit`.
+/// Missing values fall back to the first page with the configured default size.
+#[derive(Debug, Clone)]
+pub struct ApiPagination(pub Pagination);
+
+impl OperationInput for ApiPagination {}
+
+#[derive(Debug, Clone)]
+pub struct Pagination {
+    pub offset: u64,
+    pub limit: u64,
+    path: String,
+    query: Vec<(String, String)>,
+}
+
+impl Pagination {
+    /// Returns the 1-based page. Rounded down if the offset is not a multiple of the limit.
+    pub fn page(&self) -> u64 {
+        self.offset / self.limit + 1
+    }
+
+    fn last_page(&self, total: u64) -> u64 {
+        total.div_ceil(self.limit).max(1)
+    }
+
+    fn page_uri(&self, page: u64) -> String {
+        let mut serializer = form_urlencoded::Serializer::new(String::new());
+
+        for (key, value) in &self.query {
+            serializer.append_pair(key, value);
+        }
+
+        serializer
+            .append_pair("page", &page.to_string())
+            .append_pair("per_page", &self.limit.to_string());
+
+        format!("{}?{}", self.path, serializer.finish())
+    }
+
+    /// Builds the `Link` header with the `first`, `prev`, `next` and `last` relations.
+    pub fn link_header(&self, total: u64) -> Option<HeaderValue> {
+        let page = self.page();
+        let last = self.last_page(total);
+
+        let mut links = vec![format!("<{}>; rel=\"first\"", self.page_uri(1))];
+
+        if page > 1 {
+            links.push(format!(
+                "<{}>; rel=\"prev\"",
+                self.page_uri((page - 1).min(last))
+            ));
+        }
+
+        if page < last {
+            links.push(format!("<{}>; rel=\"next\"", self.page_uri(page + 1)));
+        }
+
+        links.push(format!("<{}>; rel=\"last\"", self.page_uri(last)));
+
+        HeaderValue::from_str(&links.join(", ")).ok()
+    }
+
+    /// Wraps a page of items in a [`Paginated`] response.
+    pub fn paginate<T>(&self, items: Vec<T>, total: u64) -> Paginated<T> {
+        Paginated {
+            link: self.link_header(total),
+            body: PaginatedBody {
+                items,
+                page: self.page(),
+                per_page: self.limit,
+                offset: self.offset,
+                total,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiPagination
+where
+    S: Send + Sync + ErrorVerbosityProvider + PaginationConfigProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "pagination_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+        let config = state.pagination_config();
+
+        let raw_query = parts.uri.query().unwrap_or_default();
+
+        let query = serde_urlencoded::from_str::<PaginationQuery>(raw_query).map_err(|err| {
+            tracing::warn!(%err, "Rejection. Invalid pagination");
+
+            PaginationError::new(verbosity, PaginationErrorType::InvalidParameters { err })
+        })?;
+
+        let page_style = query.page.is_some() || query.per_page.is_some();
+        let offset_style = query.offset.is_some() || query.limit.is_some();
+
+        if page_style && offset_style {
+            tracing::warn!("Rejection. Mixed pagination styles");
+
+            return Err(PaginationError::new(verbosity, PaginationErrorType::MixedStyles).into());
+        }
+
+        let bounded =
+            |field: &'static str, value: u64, min: u64, max: u64| match config.out_of_range {
+                _ if (min..=max).contains(&value) => Ok(value),
+                OutOfRangeBehavior::Clamp => Ok(value.clamp(min, max)),
+                OutOfRangeBehavior::Reject => {
+                    tracing::warn!(%field, value, "Rejection. Pagination out of range");
+
+                    Err(PaginationError::new(
+                        verbosity,
+                        PaginationErrorType::OutOfRange { field, min, max },
+                    ))
+                }
+            };
+
+        let (offset, limit) = match page_style {
+            true => {
+                let per_page = bounded(
+                    "per_page",
+                    query.per_page.unwrap_or(config.default_per_page),
+                    1,
+                    config.max_per_page,
+                )?;
+                let page = bounded("page", query.page.unwrap_or(1), 1, u64::MAX / per_page)?;
+
+                ((page - 1) * per_page, per_page)
+            }
+            false => {
+                let limit = bounded(
+                    "limit",
+                    query.limit.unwrap_or(config.default_per_page),
+                    1,
+                    config.max_per_page,
+                )?;
+
+                (query.offset.unwrap_or(0), limit)
+            }
+        };
+
+        let query = form_urlencoded::parse(raw_query.as_bytes())
+            .filter(|(key, _)| !matches!(key.as_ref(), "page" | "per_page" | "offset" | "limit"))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+
+        let pagination = Pagination {
+            offset,
+            limit,
+            path: parts.uri.path().to_string(),
+            query,
+        };
+
+        tracing::trace!(?pagination, "Extracted");
+
+        Ok(ApiPagination(pagination))
+    }
+}
+
+impl Extractor for ApiPagination {
+    type Extracted = Pagination;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PaginatedBody<T> {
+    items: Vec<T>,
+    page: u64,
+    per_page: u64,
+    offset: u64,
+    total: u64,
+}
+
+/// Paginated response envelope with a `Link` header.
+///
+/// Created by [`Pagination::paginate`].
+#[derive(Debug)]
+pub struct Paginated<T> {
+    link: Option<HeaderValue>,
+    body: PaginatedBody<T>,
+}
+
+impl<T> OperationOutput for Paginated<T> {}
+
+impl<T: Serialize> IntoResponse for Paginated<T> {
+    fn into_response(self) -> Response {
+        match self.link {
+            Some(link) => (StatusCode::OK, [(LINK, link)], Json(self.body)).into_response(),
+            None => (StatusCode::OK, Json(self.body)).into_response(),
+        }
+    }
+}
@@ -7,7 +7,10 @@ use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
 
-use crate::error::{ApiError, ErrorVerbosityProvider, PathError};
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, PathError},
+    openapi::{ApiOperation, OperationInput},
+};
 
 use super::Extractor;
 
@@ -16,6 +19,12 @@ use super::Extractor;
 /// Extracts path parameters from the request.
 pub struct ApiPath<T>(pub T);
 
+impl<T: JsonSchema> OperationInput for ApiPath<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.path_params::<T>()
+    }
+}
+
 #[async_trait]
 impl<T, S> FromRequestParts<S> for ApiPath<T>
 where
@@ -0,0 +1,305 @@
+//! A normalized identity of the request, independent of how the request was authenticated.
+
+use std::{
+    fmt::Display,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request},
+    http::{header::AUTHORIZATION, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    credentials::api_key_digest,
+    error::{ApiError, ErrorVerbosityProvider, PrincipalError, PrincipalErrorType},
+    openapi::OperationInput,
+    revocation::TokenRevocationProvider,
+    types::{used_api_key::UsedApiKey, used_basic_auth::UsedBasicAuth},
+};
+
+use super::{
+    api_key::ApiKeyProvider,
+    authenticated_basic_auth::ApiAuthenticatedBasicAuth,
+    basic_auth::BasicAuthProvider,
+    jwt::{ApiJwt, JwksProvider},
+    jwt_roles::HasRoles,
+    valid_api_key::ValidApiKey,
+    Extractor,
+};
+
+/// The authenticated identity of a request.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Principal {
+    pub subject: String,
+    pub roles: Vec<String>,
+    pub tenant: Option<String>,
+    pub email: Option<String>,
+}
+
+impl HasRoles for Principal {
+    fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|granted| granted == role)
+    }
+}
+
+/// Records the [`Principal`] extracted by [`ApiPrincipal`] for the middlewares the request passed through.
+#[derive(Debug, Clone, Default)]
+struct PrincipalSlot(Arc<Mutex<Option<Principal>>>);
+
+impl PrincipalSlot {
+    fn record(&self, principal: &Principal) {
+        if let Ok(mut slot) = self.0.lock() {
+            *slot = Some(principal.clone());
+        }
+    }
+
+    fn take(&self) -> Option<Principal> {
+        self.0.lock().ok().and_then(|mut slot| slot.take())
+    }
+}
+
+/// Runs the request and puts the [`Principal`] extracted while handling it into the response extensions.
+///
+/// Middlewares read the principal from the response instead of authenticating the request themselves.
+pub async fn run_recording_principal(mut req: Request, next: Next) -> Response {
+    let slot = PrincipalSlot::default();
+    req.extensions_mut().insert(slot.clone());
+
+    let mut response = next.run(req).await;
+
+    if let Some(principal) = slot.take() {
+        response.extensions_mut().insert(principal);
+    }
+
+    response
+}
+
+pub trait ClaimsMapper {
+    /// Maps the validated claims of a JWT to a [`Principal`].
+    ///
+    /// Returns `None` if the claims do not identify a subject.
+    fn map_claims(&self, claims: &serde_json::Map<String, serde_json::Value>) -> Option<Principal>;
+}
+
+/// Names the claims a [`Principal`] is read from.
+///
+/// Nested claims are addressed with dots, e.g. `realm_access.roles`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClaimsMappingConfig {
+    #[serde(default = "default_subject_claim")]
+    pub subject_claim: String,
+    /// Claims holding roles, either as an array of strings or as a space separated string like `scope`.
+    #[serde(default = "default_roles_claims")]
+    pub roles_claims: Vec<String>,
+    #[serde(default)]
+    pub tenant_claim: Option<String>,
+    #[serde(default = "default_email_claim")]
+    pub email_claim: Option<String>,
+}
+
+fn default_subject_claim() -> String {
+    String::from("sub")
+}
+
+fn default_roles_claims() -> Vec<String> {
+    vec![
+        String::from("roles"),
+        String::from("realm_access.roles"),
+        String::from("scope"),
+    ]
+}
+
+fn default_email_claim() -> Option<String> {
+    Some(String::from("email"))
+}
+
+impl Default for ClaimsMappingConfig {
+    fn default() -> Self {
+        Self {
+            subject_claim: default_subject_claim(),
+            roles_claims: default_roles_claims(),
+            tenant_claim: None,
+            email_claim: default_email_claim(),
+        }
+    }
+}
+
+impl ClaimsMappingConfig {
+    fn claim<'a>(
+        claims: &'a serde_json::Map<String, serde_json::Value>,
+        path: &str,
+    ) -> Option<&'a serde_json::Value> {
+        let mut segments = path.split('.');
+        let first = claims.get(segments.next()?)?;
+
+        segments.try_fold(first, |value, segment| value.get(segment))
+    }
+
+    fn string_claim(
+        claims: &serde_json::Map<String, serde_json::Value>,
+        path: &str,
+    ) -> Option<String> {
+        Self::claim(claims, path)?.as_str().map(str::to_owned)
+    }
+}
+
+impl ClaimsMapper for ClaimsMappingConfig {
+    fn map_claims(&self, claims: &serde_json::Map<String, serde_json::Value>) -> Option<Principal> {
+        let subject = Self::string_claim(claims, &self.subject_claim)?;
+
+        let mut roles = Vec::new();
+        for value in self
+            .roles_claims
+            .iter()
+            .filter_map(|path| Self::claim(claims, path))
+        {
+            match value {
+                serde_json::Value::String(value) => {
+                    roles.extend(value.split_whitespace().map(str::to_owned))
+                }
+                serde_json::Value::Array(values) => roles.extend(
+                    values
+                        .iter()
+                        .filter_map(serde_json::Value::as_str)
+                        .map(str::to_owned),
+                ),
+                _ => {}
+            }
+        }
+
+        roles.sort();
+        roles.dedup();
+
+        Some(Principal {
+            subject,
+            roles,
+            tenant: self
+                .tenant_claim
+                .as_deref()
+                .and_then(|path| Self::string_claim(claims, path)),
+            email: self
+                .email_claim
+                .as_deref()
+                .and_then(|path| Self::string_claim(claims, path)),
+        })
+    }
+}
+
+/// Extracts the [`Principal`] of a request authenticated with a JWT, basic auth or an API key.
+///
+/// The `Authorization` header selects JWT or basic auth. Without it, the API key header is used.
+/// The claims of a JWT are mapped through the [`ClaimsMapper`].
+/// API keys are identified by their owner or, if they have none, by their digest and use their scopes as roles.
+///
+/// The principal is kept in the request extensions, so extracting it again does not authenticate again.
+/// It is recorded for [`run_recording_principal`].
+#[derive(Debug, Clone)]
+pub struct ApiPrincipal(pub Principal);
+
+impl OperationInput for ApiPrincipal {}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiPrincipal
+where
+    S: Send
+        + Sync
+        + ClaimsMapper
+        + JwksProvider
+        + TokenRevocationProvider
+        + ApiKeyProvider
+        + BasicAuthProvider
+        + ErrorVerbosityProvider,
+    <S as JwksProvider>::Error: Into<anyhow::Error> + Display,
+    <S as TokenRevocationProvider>::Error: Into<anyhow::Error> + Display,
+    <S as ApiKeyProvider>::Error: Into<anyhow::Error>,
+    <S as BasicAuthProvider>::Error: Into<anyhow::Error>,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "principal_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(principal) = parts.extensions.get::<Principal>() {
+            return Ok(ApiPrincipal(principal.clone()));
+        }
+
+        let verbosity = state.error_verbosity();
+
+        let scheme = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|authorization| authorization.to_str().ok())
+            .and_then(|authorization| authorization.split_once(' '))
+            .map(|(scheme, _)| scheme.to_owned());
+
+        let principal = match scheme.as_deref() {
+            Some("Bearer") => {
+                let ApiJwt(claims) =
+                    ApiJwt::<serde_json::Map<String, serde_json::Value>>::from_request_parts(
+                        parts, state,
+                    )
+                    .await?;
+
+                state.map_claims(&claims).ok_or_else(|| {
+                    tracing::warn!("Rejection. Claims can not be mapped to a principal");
+
+                    PrincipalError::new(verbosity, PrincipalErrorType::Unmapped)
+                })?
+            }
+            Some("Basic") => {
+                let ApiAuthenticatedBasicAuth(UsedBasicAuth { username, .. }) =
+                    ApiAuthenticatedBasicAuth::from_request_parts(parts, state).await?;
+
+                Principal {
+                    subject: username,
+                    ..Principal::default()
+                }
+            }
+            _ if parts.headers.contains_key(state.header_name()) => {
+                let ValidApiKey(UsedApiKey { value }, info) =
+                    ValidApiKey::from_request_parts(parts, state).await?;
+
+                Principal {
+                    subject: info.owner.unwrap_or_else(|| api_key_digest(&value)),
+                    roles: info.scopes,
+                    ..Principal::default()
+                }
+            }
+            _ => {
+                tracing::warn!("Rejection. No credentials found");
+
+                return Err(PrincipalError::new(verbosity, PrincipalErrorType::Missing).into());
+            }
+        };
+
+        tracing::trace!(?principal, "Extracted");
+
+        if let Some(slot) = parts.extensions.get::<PrincipalSlot>() {
+            slot.record(&principal);
+        }
+
+        parts.extensions.insert(principal.clone());
+
+        Ok(ApiPrincipal(principal))
+    }
+}
+
+impl Extractor for ApiPrincipal {
+    type Extracted = Principal;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -7,20 +7,30 @@ use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
 
-use crate::error::{ApiError, ErrorVerbosityProvider, QueryError};
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, QueryError},
+    openapi::{ApiOperation, OperationInput},
+};
 
-use super::Extractor;
+use super::{unknown_urlencoded_fields, Extractor, StrictDeserializationProvider};
 
 /// A Wrapper around [`axum::extract::Query`] that rejects with an [`ApiError`].
 ///
 /// Extracts query parameters from the request.
+/// Rejects unknown fields if strict deserialization is enabled.
 pub struct ApiQuery<T>(pub T);
 
+impl<T: JsonSchema> OperationInput for ApiQuery<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.query::<T>()
+    }
+}
+
 #[async_trait]
 impl<T, S> FromRequestParts<S> for ApiQuery<T>
 where
     T: DeserializeOwned + JsonSchema + Debug + Send,
-    S: Send + Sync + ErrorVerbosityProvider,
+    S: Send + Sync + ErrorVerbosityProvider + StrictDeserializationProvider,
 {
     type Rejection = ApiError;
 
@@ -30,6 +40,20 @@ where
 
         match query {
             Ok(query) => {
+                if state.strict_deserialization() {
+                    let query = parts.uri.query().unwrap_or_default();
+                    let unknown_fields = unknown_urlencoded_fields::<T>(query.as_bytes());
+
+                    if !unknown_fields.is_empty() {
+                        tracing::warn!(?unknown_fields, "Rejection. Unknown fields");
+
+                        return Err(QueryError::from_unknown_fields::<T>(
+                            state.error_verbosity(),
+                            unknown_fields,
+                        ));
+                    }
+                }
+
                 tracing::trace!(query=?query.0, "Extracted");
 
                 Ok(ApiQuery(query.0))
@@ -48,6 +72,55 @@ where
     }
 }
 
+/// Same as [`ApiQuery`] but returns `T::default()` if the request has no query string.
+///
+/// Still rejects if the query string is present but malformed.
+pub struct ApiQueryOrDefault<T>(pub T);
+
+impl<T: JsonSchema> OperationInput for ApiQueryOrDefault<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.query::<T>()
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiQueryOrDefault<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Default + Send,
+    S: Send + Sync + ErrorVerbosityProvider + StrictDeserializationProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "query_or_default_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if parts.uri.query().unwrap_or_default().is_empty() {
+            tracing::trace!("No query string. Using default");
+
+            return Ok(ApiQueryOrDefault(T::default()));
+        }
+
+        let ApiQuery(query) = ApiQuery::<T>::from_request_parts(parts, state).await?;
+
+        Ok(ApiQueryOrDefault(query))
+    }
+}
+
+impl<T> Extractor for ApiQueryOrDefault<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
+
 impl<T> Extractor for ApiQuery<T> {
     type Extracted = T;
 
@@ -0,0 +1,161 @@
+use std::{borrow::Cow, collections::HashMap, fmt::Debug};
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, QueryError},
+    openapi::{ApiOperation, OperationInput},
+};
+
+use super::{Extractor, StrictDeserializationProvider};
+
+/// Maximum nesting depth of the query parameters.
+const MAX_DEPTH: usize = 5;
+
+/// Extracts query parameters supporting arrays and nested structs.
+///
+/// Supports repeated keys `?tag=a&tag=b`, indexed brackets `?tag[0]=a&tag[1]=b`
+/// and nested structs `?filter[author]=a&filter[year]=1951`.
+/// Rejects unknown fields if strict deserialization is enabled.
+pub struct ApiQueryExtra<T>(pub T);
+
+impl<T: JsonSchema> OperationInput for ApiQueryExtra<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.query::<T>()
+    }
+}
+
+impl<T> ApiQueryExtra<T>
+where
+    T: DeserializeOwned,
+{
+    /// Rewrites repeated keys without brackets to indexed brackets.
+    ///
+    /// `tag=a&tag=b` becomes `tag[0]=a&tag[1]=b`.
+    fn index_repeated_keys(query: &str) -> Cow<'_, str> {
+        let key = |pair: &str| {
+            pair.split_once('=')
+                .map_or(pair, |(key, _)| key)
+                .to_string()
+        };
+        let has_brackets =
+            |key: &str| key.contains('[') || key.to_ascii_uppercase().contains("%5B");
+
+        let mut counts = HashMap::<String, usize>::new();
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            *counts.entry(key(pair)).or_default() += 1;
+        }
+
+        if counts
+            .iter()
+            .all(|(key, count)| *count == 1 || has_brackets(key))
+        {
+            return Cow::Borrowed(query);
+        }
+
+        let mut indices = HashMap::<String, usize>::new();
+
+        let pairs = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let key = key(pair);
+
+                if counts[&key] == 1 || has_brackets(&key) {
+                    return pair.to_string();
+                }
+
+                let index = indices.entry(key.clone()).or_default();
+                let value = pair.split_once('=').map_or("", |(_, value)| value);
+                let indexed = format!("{key}[{index}]={value}");
+
+                *index += 1;
+
+                indexed
+            })
+            .collect::<Vec<_>>();
+
+        Cow::Owned(pairs.join("&"))
+    }
+
+    fn deserializer_config() -> serde_qs::Config {
+        // Non strict mode accepts percent encoded brackets.
+        serde_qs::Config::new(MAX_DEPTH, false)
+    }
+
+    /// Returns the paths of the fields that are not part of `T`.
+    fn unknown_fields(query: &str) -> Vec<String> {
+        let mut unknown_fields = Vec::new();
+
+        let Ok(deserializer) =
+            serde_qs::Deserializer::with_config(&Self::deserializer_config(), query.as_bytes())
+        else {
+            return unknown_fields;
+        };
+
+        let _: Result<T, _> =
+            serde_ignored::deserialize(deserializer, |path| unknown_fields.push(path.to_string()));
+
+        unknown_fields
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiQueryExtra<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Send,
+    S: Send + Sync + ErrorVerbosityProvider + StrictDeserializationProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "query_extra_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let query = Self::index_repeated_keys(parts.uri.query().unwrap_or_default());
+
+        let extracted = Self::deserializer_config()
+            .deserialize_str::<T>(&query)
+            .map_err(|err| {
+                tracing::warn!(%err, "Rejection");
+
+                QueryError::from_deserialize_error::<T>(verbosity, err.to_string())
+            })?;
+
+        if state.strict_deserialization() {
+            let unknown_fields = Self::unknown_fields(&query);
+
+            if !unknown_fields.is_empty() {
+                tracing::warn!(?unknown_fields, "Rejection. Unknown fields");
+
+                return Err(QueryError::from_unknown_fields::<T>(
+                    verbosity,
+                    unknown_fields,
+                ));
+            }
+        }
+
+        tracing::trace!(query=?extracted, "Extracted");
+
+        Ok(ApiQueryExtra(extracted))
+    }
+}
+
+impl<T> Extractor for ApiQueryExtra<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
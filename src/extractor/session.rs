@@ -0,0 +1,94 @@
+use std::fmt::Debug;
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, SessionError, SessionErrorType},
+    openapi::OperationInput,
+    session::Session,
+};
+
+use super::Extractor;
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Session
+where
+    S: Send + Sync + ErrorVerbosityProvider,
+{
+    type Rejection = ApiError;
+
+    /// Extracts the [`Session`] put as an extension by the [`SessionLayer`](crate::middleware::session::SessionLayer).
+    ///
+    /// Returns an internal server error if the layer is missing.
+    #[tracing::instrument(name = "session_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let session = parts.extensions.get::<Session>().cloned().ok_or_else(|| {
+            ApiError::from_generic_error(
+                state.error_verbosity(),
+                anyhow::anyhow!("Session not found in extensions. Is the SessionLayer missing?"),
+            )
+        })?;
+
+        tracing::trace!("Extracted");
+
+        Ok(session)
+    }
+}
+
+/// Extracts the typed data of the current session.
+///
+/// Rejects if there is no session or its data can not be deserialized into `T`.
+/// Use [`Session`] to change the session.
+#[derive(Debug, Clone)]
+pub struct ApiSession<T>(pub T);
+
+impl<T> OperationInput for ApiSession<T> {}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiSession<T>
+where
+    T: DeserializeOwned + Debug,
+    S: Send + Sync + ErrorVerbosityProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "api_session_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let session = Session::from_request_parts(parts, state).await?;
+
+        let data = session.get::<T>().map_err(|err| {
+            tracing::warn!(%err, "Rejection. Invalid session data");
+
+            SessionError::new(verbosity, SessionErrorType::InvalidData { err })
+        })?;
+
+        let Some(data) = data else {
+            tracing::warn!("Rejection. Missing session");
+
+            return Err(SessionError::new(verbosity, SessionErrorType::Missing).into());
+        };
+
+        tracing::trace!(?data, "Extracted");
+
+        Ok(ApiSession(data))
+    }
+}
+
+impl<T> Extractor for ApiSession<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
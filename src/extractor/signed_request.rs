@@ -0,0 +1,223 @@
+use std::{
+    fmt::Display,
+    future::Future,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, OriginalUri, Request},
+    http::request::Parts,
+};
+use derivative::Derivative;
+use hmac::Mac;
+use serde::Deserialize;
+
+use crate::{
+    error::{
+        ApiError, ErrorVerbosityProvider, InternalServerError, SignatureError, SignatureErrorType,
+    },
+    openapi::OperationInput,
+    signing::{
+        canonical_request, hmac_sha256, HMAC_SHA256_PREFIX, SIGNATURE_HEADER,
+        SIGNATURE_KEY_ID_HEADER, SIGNATURE_TIMESTAMP_HEADER,
+    },
+    state::PrivateErrorVerbosity,
+};
+
+use super::{
+    body::{read_body, BodyLimitProvider},
+    Extractor,
+};
+
+pub trait SignatureKeyProvider {
+    type Error;
+
+    /// Returns the HMAC secret of the client's key or `None` if the key is unknown.
+    fn signature_secret(
+        &self,
+        key_id: &str,
+    ) -> impl Future<Output = Result<Option<Vec<u8>>, Self::Error>> + Send;
+
+    /// Returns how far the signature timestamp may deviate from the server's clock.
+    ///
+    /// Signed requests older than this are rejected to prevent replays.
+    fn max_signature_skew(&self) -> Duration;
+}
+
+#[derive(Derivative, Clone, Deserialize)]
+#[derivative(Debug)]
+pub struct SignatureKey {
+    pub key_id: String,
+    #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignatureVerificationConfig {
+    #[serde(default = "default_max_skew_in_seconds")]
+    pub max_skew_in_seconds: u64,
+    /// The per-client keys accepted for signed requests.
+    #[serde(default)]
+    pub keys: Vec<SignatureKey>,
+}
+
+fn default_max_skew_in_seconds() -> u64 {
+    5 * 60
+}
+
+impl Default for SignatureVerificationConfig {
+    fn default() -> Self {
+        Self {
+            max_skew_in_seconds: default_max_skew_in_seconds(),
+            keys: Vec::new(),
+        }
+    }
+}
+
+/// A request whose HMAC signature was verified.
+#[derive(Debug, Clone)]
+pub struct SignedRequest {
+    /// The id of the key the request was signed with.
+    pub key_id: String,
+    pub body: Bytes,
+}
+
+/// Verifies the HMAC-SHA256 signature of the request and extracts the body consuming the request.
+///
+/// The signature is computed over the canonical request, see [`crate::signing`].
+/// Rejects if a signature header is missing, the key is unknown,
+/// the timestamp is outside of the allowed skew or the signature does not match.
+pub struct ApiSignedRequest(pub SignedRequest);
+
+impl OperationInput for ApiSignedRequest {}
+
+impl ApiSignedRequest {
+    fn header<'a>(
+        parts: &'a Parts,
+        header: &'static str,
+        verbosity: PrivateErrorVerbosity,
+    ) -> Result<&'a str, ApiError> {
+        let value = parts.headers.get(header).ok_or_else(|| {
+            tracing::warn!(%header, "Rejection. Missing signature header");
+
+            SignatureError::new(verbosity, SignatureErrorType::MissingHeader { header })
+        })?;
+
+        value.to_str().map_err(|_| {
+            tracing::warn!(%header, "Rejection. Invalid signature header");
+
+            SignatureError::new(verbosity, SignatureErrorType::InvalidHeader { header }).into()
+        })
+    }
+
+    fn invalid_header(verbosity: PrivateErrorVerbosity, header: &'static str) -> ApiError {
+        tracing::warn!(%header, "Rejection. Invalid signature header");
+
+        SignatureError::new(verbosity, SignatureErrorType::InvalidHeader { header }).into()
+    }
+}
+
+#[async_trait]
+impl<S> FromRequest<S> for ApiSignedRequest
+where
+    S: Send + Sync + SignatureKeyProvider + BodyLimitProvider + ErrorVerbosityProvider,
+    <S as SignatureKeyProvider>::Error: Into<anyhow::Error> + Display,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "signed_request_extractor", skip_all)]
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let (parts, body) = req.into_parts();
+
+        let key_id = Self::header(&parts, SIGNATURE_KEY_ID_HEADER, verbosity)?.to_owned();
+
+        let timestamp = Self::header(&parts, SIGNATURE_TIMESTAMP_HEADER, verbosity)?
+            .parse::<u64>()
+            .map_err(|_| Self::invalid_header(verbosity, SIGNATURE_TIMESTAMP_HEADER))?;
+
+        let signature = Self::header(&parts, SIGNATURE_HEADER, verbosity)?
+            .strip_prefix(HMAC_SHA256_PREFIX)
+            .and_then(|signature| hex::decode(signature).ok())
+            .ok_or_else(|| Self::invalid_header(verbosity, SIGNATURE_HEADER))?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        if now.abs_diff(timestamp) > state.max_signature_skew().as_secs() {
+            tracing::warn!(%timestamp, %now, "Rejection. Signature timestamp outside of allowed skew");
+
+            return Err(
+                SignatureError::new(verbosity, SignatureErrorType::TimestampOutOfRange).into(),
+            );
+        }
+
+        let secret = state.signature_secret(&key_id).await.map_err(|err| {
+            ApiError::InternalServerError(InternalServerError::from_generic_error(verbosity, err))
+        })?;
+
+        let Some(secret) = secret else {
+            tracing::warn!(%key_id, "Rejection. Unknown signature key");
+
+            return Err(
+                SignatureError::new(verbosity, SignatureErrorType::UnknownKey { key_id }).into(),
+            );
+        };
+
+        // Nested routers strip their prefix from the URI, the signer signed the full path.
+        let uri = parts
+            .extensions
+            .get::<OriginalUri>()
+            .map_or(&parts.uri, |OriginalUri(uri)| uri);
+        let path_and_query = uri
+            .path_and_query()
+            .map_or_else(|| uri.path().to_owned(), ToString::to_string);
+        let method = parts.method.clone();
+
+        let body = read_body(
+            Request::from_parts(parts, body),
+            state.max_body_size_in_bytes(),
+            verbosity,
+        )
+        .await?;
+
+        let canonical_request =
+            canonical_request(method.as_str(), &path_and_query, timestamp, &body);
+
+        if hmac_sha256(&secret, &canonical_request)
+            .verify_slice(&signature)
+            .is_err()
+        {
+            tracing::warn!(%key_id, "Rejection. Invalid signature");
+
+            return Err(SignatureError::new(verbosity, SignatureErrorType::Invalid).into());
+        }
+
+        let signed_request = SignedRequest { key_id, body };
+
+        tracing::trace!(key_id = %signed_request.key_id, len = signed_request.body.len(), "Extracted");
+
+        Ok(ApiSignedRequest(signed_request))
+    }
+}
+
+impl Extractor for ApiSignedRequest {
+    type Extracted = SignedRequest;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,177 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, QueryError},
+    openapi::OperationInput,
+};
+
+use super::{schema_fields, Extractor};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SortField {
+    pub field: String,
+    pub direction: SortDirection,
+}
+
+/// Extracts the sort order from the `sort` query parameter.
+///
+/// `?sort=-year,title` sorts by `year` descending, then by `title` ascending.
+/// Only the fields of `T`'s schema are allowed.
+pub struct ApiSort<T>(pub Vec<SortField>, pub PhantomData<T>);
+
+impl<T> OperationInput for ApiSort<T> {}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiSort<T>
+where
+    T: JsonSchema + 'static,
+    S: Send + Sync + ErrorVerbosityProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "sort_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+        let allowed_fields = schema_fields::<T>();
+
+        let sort = form_urlencoded::parse(parts.uri.query().unwrap_or_default().as_bytes())
+            .find(|(key, _)| key == "sort")
+            .map(|(_, value)| value.into_owned())
+            .unwrap_or_default();
+
+        let mut fields = Vec::new();
+        let mut unknown_fields = Vec::new();
+
+        for field in sort.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            let (field, direction) = match field.strip_prefix('-') {
+                Some(field) => (field, SortDirection::Descending),
+                None => (field.trim_start_matches('+'), SortDirection::Ascending),
+            };
+
+            if !allowed_fields.contains(field) {
+                unknown_fields.push(field.to_string());
+
+                continue;
+            }
+
+            fields.push(SortField {
+                field: field.to_string(),
+                direction,
+            });
+        }
+
+        if !unknown_fields.is_empty() {
+            tracing::warn!(?unknown_fields, "Rejection. Unknown sort fields");
+
+            return Err(QueryError::from_unknown_fields::<T>(
+                verbosity,
+                unknown_fields,
+            ));
+        }
+
+        tracing::trace!(?fields, "Extracted");
+
+        Ok(ApiSort(fields, PhantomData))
+    }
+}
+
+/// Extracts the filter from the `filter[field]` query parameters and deserializes it into `T`.
+///
+/// `?filter[author]=foo&filter[year]=1951`.
+/// Only the fields of `T`'s schema are allowed.
+pub struct ApiFilter<T>(pub T);
+
+impl<T> OperationInput for ApiFilter<T> {}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiFilter<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Send + 'static,
+    S: Send + Sync + ErrorVerbosityProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "filter_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+        let allowed_fields = schema_fields::<T>();
+
+        let filters = form_urlencoded::parse(parts.uri.query().unwrap_or_default().as_bytes())
+            .filter_map(|(key, value)| {
+                let field = key.strip_prefix("filter[")?.strip_suffix(']')?.to_string();
+
+                Some((field, value.into_owned()))
+            })
+            .collect::<Vec<_>>();
+
+        let unknown_fields = filters
+            .iter()
+            .filter(|(field, _)| !allowed_fields.contains(field))
+            .map(|(field, _)| field.clone())
+            .collect::<Vec<_>>();
+
+        if !unknown_fields.is_empty() {
+            tracing::warn!(?unknown_fields, "Rejection. Unknown filter fields");
+
+            return Err(QueryError::from_unknown_fields::<T>(
+                verbosity,
+                unknown_fields,
+            ));
+        }
+
+        let encoded = serde_urlencoded::to_string(&filters)
+            .map_err(|err| ApiError::from_generic_error(verbosity, err))?;
+
+        let filter = serde_urlencoded::from_str::<T>(&encoded).map_err(|err| {
+            tracing::warn!(%err, "Rejection. Invalid filter");
+
+            QueryError::from_deserialize_error::<T>(verbosity, err.to_string())
+        })?;
+
+        tracing::trace!(?filter, "Extracted");
+
+        Ok(ApiFilter(filter))
+    }
+}
+
+impl<T> Extractor for ApiSort<T> {
+    type Extracted = Vec<SortField>;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
+
+impl<T> Extractor for ApiFilter<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,181 @@
+use std::{fmt::Display, future::Future};
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::HOST, request::Parts},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, InternalServerError, TenantError, TenantErrorType},
+    openapi::OperationInput,
+    revocation::TokenRevocationProvider,
+};
+
+use super::{
+    jwt::{ApiJwt, JwksProvider},
+    Extractor,
+};
+
+/// Where the tenant of a request is read from.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum TenantSource {
+    /// The value of the given header.
+    Header { name: String },
+    /// The subdomain left of the given base domain, e.g. `acme` for `acme.example.com`.
+    Subdomain { base_domain: String },
+    /// The given string claim of the validated bearer JWT.
+    Claim { name: String },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TenantConfig {
+    pub source: TenantSource,
+    /// Known tenants. If empty, every tenant is accepted.
+    #[serde(default)]
+    pub tenants: Vec<String>,
+}
+
+impl Default for TenantConfig {
+    fn default() -> Self {
+        Self {
+            source: TenantSource::Header {
+                name: String::from("x-tenant-id"),
+            },
+            tenants: Vec::new(),
+        }
+    }
+}
+
+pub trait TenantProvider {
+    type Error;
+
+    /// Returns where the tenant is read from.
+    fn tenant_source(&self) -> &TenantSource;
+
+    /// Resolves the tenant with the given id.
+    ///
+    /// Returns `None` if the tenant is unknown.
+    fn resolve_tenant(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = Result<Option<Tenant>, Self::Error>> + Send;
+}
+
+/// The tenant the request belongs to.
+#[derive(Debug, Clone, Serialize)]
+pub struct Tenant {
+    pub id: String,
+}
+
+/// Extracts the [`Tenant`] of the request using the state's [`TenantSource`].
+///
+/// Rejects if the request does not identify a tenant or the tenant is unknown.
+#[derive(Debug, Clone)]
+pub struct ApiTenant(pub Tenant);
+
+impl OperationInput for ApiTenant {}
+
+impl ApiTenant {
+    fn id_from_header(parts: &Parts, name: &str) -> Option<String> {
+        parts
+            .headers
+            .get(name)?
+            .to_str()
+            .ok()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(String::from)
+    }
+
+    fn id_from_subdomain(parts: &Parts, base_domain: &str) -> Option<String> {
+        let host = parts
+            .headers
+            .get(HOST)
+            .and_then(|host| host.to_str().ok())
+            .or_else(|| parts.uri.host())?;
+
+        let host = host.rsplit_once(':').map_or(host, |(host, _port)| host);
+
+        host.strip_suffix(base_domain)?
+            .strip_suffix('.')
+            .filter(|subdomain| !subdomain.is_empty())
+            .map(str::to_ascii_lowercase)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiTenant
+where
+    S: Send
+        + Sync
+        + TenantProvider
+        + JwksProvider
+        + TokenRevocationProvider
+        + ErrorVerbosityProvider,
+    <S as TenantProvider>::Error: Into<anyhow::Error> + Display,
+    <S as JwksProvider>::Error: Into<anyhow::Error> + Display,
+    <S as TokenRevocationProvider>::Error: Into<anyhow::Error> + Display,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "tenant_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        let id = match state.tenant_source() {
+            TenantSource::Header { name } => Self::id_from_header(parts, name),
+            TenantSource::Subdomain { base_domain } => Self::id_from_subdomain(parts, base_domain),
+            TenantSource::Claim { name } => {
+                let ApiJwt(mut claims) =
+                    ApiJwt::<serde_json::Map<String, serde_json::Value>>::from_request_parts(
+                        parts, state,
+                    )
+                    .await?;
+
+                match claims.remove(name) {
+                    Some(serde_json::Value::String(id)) if !id.is_empty() => Some(id),
+                    _ => None,
+                }
+            }
+        };
+
+        let Some(id) = id else {
+            tracing::warn!("Rejection. Missing tenant");
+
+            return Err(TenantError::new(verbosity, TenantErrorType::Missing).into());
+        };
+
+        let tenant = state.resolve_tenant(&id).await.map_err(|err| {
+            ApiError::InternalServerError(InternalServerError::from_generic_error(verbosity, err))
+        })?;
+
+        let Some(tenant) = tenant else {
+            tracing::warn!(%id, "Rejection. Unknown tenant");
+
+            return Err(TenantError::new(verbosity, TenantErrorType::Unknown { id }).into());
+        };
+
+        tracing::trace!(?tenant, "Extracted");
+
+        Ok(ApiTenant(tenant))
+    }
+}
+
+impl Extractor for ApiTenant {
+    type Extracted = Tenant;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,47 @@
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, UrlPart, UrlPartsError},
+    openapi::{ApiOperation, OperationInput},
+};
+
+use super::{path::ApiPath, query::ApiQuery, StrictDeserializationProvider};
+
+/// Extracts the path parameters `P` and the query parameters `Q` from the request.
+///
+/// Same as [`ApiPath`] followed by [`ApiQuery`], but the rejection says which part failed.
+pub struct ApiUrlParts<P, Q>(pub P, pub Q);
+
+impl<P: JsonSchema, Q: JsonSchema> OperationInput for ApiUrlParts<P, Q> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.path_params::<P>().query::<Q>()
+    }
+}
+
+#[async_trait]
+impl<P, Q, S> FromRequestParts<S> for ApiUrlParts<P, Q>
+where
+    P: DeserializeOwned + JsonSchema + Debug + Send,
+    Q: DeserializeOwned + JsonSchema + Debug + Send,
+    S: Send + Sync + ErrorVerbosityProvider + StrictDeserializationProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "url_parts_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ApiPath(path) = ApiPath::<P>::from_request_parts(parts, state)
+            .await
+            .map_err(|err| UrlPartsError::wrap(UrlPart::Path, err))?;
+
+        let ApiQuery(query) = ApiQuery::<Q>::from_request_parts(parts, state)
+            .await
+            .map_err(|err| UrlPartsError::wrap(UrlPart::Query, err))?;
+
+        tracing::trace!("Extracted");
+
+        Ok(ApiUrlParts(path, query))
+    }
+}
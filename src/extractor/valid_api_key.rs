@@ -1,16 +1,27 @@
-use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use std::net::SocketAddr;
+
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::request::Parts,
+};
 
 use crate::{
     error::{ApiError, ApiKeyError, ApiKeyErrorType, ErrorVerbosityProvider, InternalServerError},
     extractor::api_key::{ApiKey, ApiKeyProviderError},
-    types::used_api_key::UsedApiKey,
+    openapi::OperationInput,
+    types::used_api_key::{KeyInfo, UsedApiKey},
 };
 
 use super::api_key::ApiKeyProvider;
 
 /// Extracts and validates the API key from the request headers.
+///
+/// Rejects if the key is invalid or has expired.
 #[derive(Debug, Clone)]
-pub struct ValidApiKey(pub UsedApiKey);
+pub struct ValidApiKey(pub UsedApiKey, pub KeyInfo);
+
+impl OperationInput for ValidApiKey {}
 
 #[async_trait]
 impl<S> FromRequestParts<S> for ValidApiKey
@@ -27,11 +38,18 @@ where
         let ApiKey(UsedApiKey { value: api_key }) =
             ApiKey::from_request_parts(parts, state).await?;
 
-        state.validate(&api_key).await.map_err(|err| {
+        let info = state.validate(&api_key).await.map_err(|err| {
             tracing::warn!(%api_key, "Rejection. Invalid API key");
 
             match err {
                 ApiKeyProviderError::Invalid => {
+                    let client_ip = parts
+                        .extensions
+                        .get::<ConnectInfo<SocketAddr>>()
+                        .map(|ConnectInfo(addr)| addr.ip());
+
+                    state.on_invalid(&api_key, client_ip);
+
                     ApiError::ApiKey(ApiKeyError::new(verbosity, ApiKeyErrorType::Invalid))
                 }
                 ApiKeyProviderError::InternalServerError(err) => ApiError::InternalServerError(
@@ -40,8 +58,14 @@ where
             }
         })?;
 
-        tracing::trace!(%api_key, "Validated");
+        if info.is_expired() {
+            tracing::warn!(%api_key, "Rejection. Expired API key");
+
+            return Err(ApiKeyError::new(verbosity, ApiKeyErrorType::Expired).into());
+        }
+
+        tracing::trace!(%api_key, ?info, "Validated");
 
-        Ok(ValidApiKey(UsedApiKey { value: api_key }))
+        Ok(ValidApiKey(UsedApiKey { value: api_key }, info))
     }
 }
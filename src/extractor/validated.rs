@@ -3,15 +3,25 @@ use axum::{
     extract::{FromRequest, FromRequestParts, Request},
     http::request::Parts,
 };
-use validator::Validate;
+use std::{future::Future, marker::PhantomData};
+use validator::{Validate, ValidationErrors};
 
-use crate::error::{ApiError, ErrorVerbosityProvider, ValidationError};
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, ValidationError},
+    openapi::{ApiOperation, OperationInput},
+};
 
 use super::Extractor;
 
 /// An extractor that validates the extracted data by another extractor.
 pub struct Validated<X>(pub X);
 
+impl<X: OperationInput> OperationInput for Validated<X> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        X::describe(operation)
+    }
+}
+
 impl<X> Validated<X> {
     fn extract<S>(inner: X, state: &S) -> Result<Self, ApiError>
     where
@@ -38,6 +48,49 @@ impl<X> Validated<X> {
     }
 }
 
+/// An async, state-aware validation of `T`.
+///
+/// Used by [`ValidatedWith`] for checks that [`Validate`] can not express, like looking up a database.
+pub trait AsyncValidator<S, T> {
+    fn validate(value: &T, state: &S) -> impl Future<Output = Result<(), ValidationErrors>> + Send;
+}
+
+/// Same as [`Validated`] but additionally runs the [`AsyncValidator`] `V` after [`Validate`] succeeds.
+pub struct ValidatedWith<X, V>(pub X, pub PhantomData<V>);
+
+impl<X: OperationInput, V> OperationInput for ValidatedWith<X, V> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        X::describe(operation)
+    }
+}
+
+impl<X, V> ValidatedWith<X, V> {
+    async fn extract<S>(inner: X, state: &S) -> Result<Self, ApiError>
+    where
+        X: Extractor,
+        S: ErrorVerbosityProvider,
+        <X as Extractor>::Extracted: Validate,
+        V: AsyncValidator<S, <X as Extractor>::Extracted>,
+    {
+        let Validated(inner) = Validated::extract(inner, state)?;
+
+        match V::validate(inner.extracted(), state).await {
+            Ok(_) => {
+                tracing::trace!("Validated with async validator");
+
+                Ok(ValidatedWith(inner, PhantomData))
+            }
+            Err(errors) => {
+                tracing::warn!(?errors, "Async validation errors");
+
+                let verbosity = state.error_verbosity();
+
+                Err(ValidationError::from_validation_errors(verbosity, errors).into())
+            }
+        }
+    }
+}
+
 #[async_trait]
 impl<X, S> FromRequestParts<S> for Validated<X>
 where
@@ -73,3 +126,41 @@ where
         Self::extract(inner, state)
     }
 }
+
+#[async_trait]
+impl<X, V, S> FromRequestParts<S> for ValidatedWith<X, V>
+where
+    X: FromRequestParts<S, Rejection = ApiError>,
+    X: Extractor + Send,
+    <X as Extractor>::Extracted: Validate + Sync,
+    V: AsyncValidator<S, <X as Extractor>::Extracted>,
+    S: Send + Sync + ErrorVerbosityProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "validated_with_extractor", skip_all)]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let inner = X::from_request_parts(parts, state).await?;
+
+        Self::extract(inner, state).await
+    }
+}
+
+#[async_trait]
+impl<X, V, S> FromRequest<S> for ValidatedWith<X, V>
+where
+    X: FromRequest<S, Rejection = ApiError>,
+    X: Extractor + Send,
+    <X as Extractor>::Extracted: Validate + Sync,
+    V: AsyncValidator<S, <X as Extractor>::Extracted>,
+    S: Send + Sync + ErrorVerbosityProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "validated_with_extractor", skip_all)]
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let inner = X::from_request(req, state).await?;
+
+        Self::extract(inner, state).await
+    }
+}
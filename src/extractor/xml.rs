@@ -0,0 +1,120 @@
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
+};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, XmlBodyError},
+    openapi::{ApiOperation, OperationInput},
+};
+
+use super::{has_content_type, Extractor, StrictDeserializationProvider};
+
+/// Accepted XML content types.
+pub const XML_CONTENT_TYPES: &[&str] = &["application/xml", "text/xml"];
+
+/// Extracts the request body as XML consuming the request.
+///
+/// Rejects with the same error structure as [`ApiJson`](super::json::ApiJson).
+/// Rejects unknown fields if strict deserialization is enabled.
+pub struct ApiXml<T>(pub T);
+
+impl<T: JsonSchema> OperationInput for ApiXml<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.body::<T>(XML_CONTENT_TYPES[0])
+    }
+}
+
+impl<T> ApiXml<T>
+where
+    T: DeserializeOwned + JsonSchema,
+{
+    /// Returns the paths of the fields that are not part of `T`.
+    fn unknown_fields(xml: &str) -> Vec<String> {
+        let mut unknown_fields = Vec::new();
+
+        let mut deserializer = quick_xml::de::Deserializer::from_str(xml);
+        let _: Result<T, _> = serde_ignored::deserialize(&mut deserializer, |path| {
+            unknown_fields.push(path.to_string())
+        });
+
+        unknown_fields
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiXml<T>
+where
+    T: DeserializeOwned + JsonSchema + Debug + Send,
+    S: Send + Sync + ErrorVerbosityProvider + StrictDeserializationProvider,
+{
+    type Rejection = ApiError;
+
+    #[tracing::instrument(name = "xml_extractor", skip_all)]
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verbosity = state.error_verbosity();
+
+        if !has_content_type(req.headers(), XML_CONTENT_TYPES) {
+            tracing::warn!("Rejection. Missing XML content type");
+
+            return Err(XmlBodyError::missing_content_type::<T>(verbosity));
+        }
+
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(|bytes_rejection| {
+                tracing::warn!(rejection=?bytes_rejection, "Rejection");
+
+                ApiError::from_generic_error(verbosity, bytes_rejection)
+            })?;
+
+        let text = std::str::from_utf8(&bytes).map_err(|err| {
+            tracing::warn!(%err, "Rejection");
+
+            XmlBodyError::from_utf8_error::<T>(verbosity, err)
+        })?;
+
+        let xml = quick_xml::de::from_str::<T>(text).map_err(|err| {
+            tracing::warn!(%err, "Rejection");
+
+            XmlBodyError::from_de_error::<T>(verbosity, err)
+        })?;
+
+        if state.strict_deserialization() {
+            let unknown_fields = Self::unknown_fields(text);
+
+            if !unknown_fields.is_empty() {
+                tracing::warn!(?unknown_fields, "Rejection. Unknown fields");
+
+                return Err(XmlBodyError::from_unknown_fields::<T>(
+                    verbosity,
+                    unknown_fields,
+                ));
+            }
+        }
+
+        tracing::trace!(?xml, "Extracted");
+
+        Ok(ApiXml(xml))
+    }
+}
+
+impl<T> Extractor for ApiXml<T> {
+    type Extracted = T;
+
+    fn extracted(&self) -> &Self::Extracted {
+        &self.0
+    }
+
+    fn extracted_mut(&mut self) -> &mut Self::Extracted {
+        &mut self.0
+    }
+
+    fn into_extracted(self) -> Self::Extracted {
+        self.0
+    }
+}
@@ -0,0 +1,116 @@
+use std::net::IpAddr;
+
+use anyhow::Context;
+use maxminddb::{geoip2, MaxMindDBError, Reader};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeoIpConfig {
+    /// Path to the MaxMind country (or city) database.
+    pub country_database_path: String,
+    /// Path to the MaxMind ASN database.
+    pub asn_database_path: Option<String>,
+    /// ISO country codes of the countries that are not allowed to access the API.
+    #[serde(default)]
+    pub blocked_countries: Vec<String>,
+}
+
+/// GeoIP information about the client.
+///
+/// Put as an extension by the [`geoip`](crate::middleware::geoip::geoip) middleware.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GeoIpInfo {
+    /// ISO country code.
+    pub country: Option<String>,
+    /// Autonomous system number.
+    pub asn: Option<u32>,
+    /// Autonomous system organization.
+    pub as_organization: Option<String>,
+}
+
+pub trait GeoIpProvider {
+    /// Returns the GeoIP information of the given IP.
+    ///
+    /// Returns `None` if GeoIP enrichment is disabled.
+    fn lookup(&self, ip: IpAddr) -> Option<GeoIpInfo>;
+
+    /// Returns whether the given ISO country code is blocked.
+    fn is_country_blocked(&self, country: &str) -> bool;
+}
+
+/// Resolves IPs against MaxMind databases.
+pub struct GeoIpResolver {
+    country_reader: Reader<Vec<u8>>,
+    asn_reader: Option<Reader<Vec<u8>>>,
+    blocked_countries: Vec<String>,
+}
+
+impl GeoIpResolver {
+    pub fn from_config(config: GeoIpConfig) -> anyhow::Result<Self> {
+        let country_reader = Reader::open_readfile(&config.country_database_path)
+            .context("Failed to open GeoIP country database")?;
+
+        let asn_reader = config
+            .asn_database_path
+            .map(Reader::open_readfile)
+            .transpose()
+            .context("Failed to open GeoIP ASN database")?;
+
+        Ok(Self {
+            country_reader,
+            asn_reader,
+            blocked_countries: config.blocked_countries,
+        })
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn lookup(&self, ip: IpAddr) -> GeoIpInfo {
+        let country = match self.country_reader.lookup::<geoip2::Country>(ip) {
+            Ok(country) => country
+                .country
+                .and_then(|country| country.iso_code)
+                .map(ToString::to_string),
+            Err(err) => {
+                Self::trace_lookup_error(err);
+
+                None
+            }
+        };
+
+        let (asn, as_organization) = match self
+            .asn_reader
+            .as_ref()
+            .map(|asn_reader| asn_reader.lookup::<geoip2::Asn>(ip))
+        {
+            Some(Ok(asn)) => (
+                asn.autonomous_system_number,
+                asn.autonomous_system_organization.map(ToString::to_string),
+            ),
+            Some(Err(err)) => {
+                Self::trace_lookup_error(err);
+
+                (None, None)
+            }
+            None => (None, None),
+        };
+
+        GeoIpInfo {
+            country,
+            asn,
+            as_organization,
+        }
+    }
+
+    pub fn is_country_blocked(&self, country: &str) -> bool {
+        self.blocked_countries
+            .iter()
+            .any(|blocked| blocked.eq_ignore_ascii_case(country))
+    }
+
+    fn trace_lookup_error(err: MaxMindDBError) {
+        match err {
+            MaxMindDBError::AddressNotFoundError(_) => tracing::trace!("Address not found"),
+            err => tracing::warn!(%err, "GeoIP lookup failed"),
+        }
+    }
+}
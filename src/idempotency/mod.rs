@@ -0,0 +1,251 @@
+//! Replaying the responses of unsafe requests sent with an [`IDEMPOTENCY_KEY_HEADER`].
+//!
+//! The [`IdempotencyLayer`](crate::middleware::idempotency::layer::IdempotencyLayer) reserves the key of a request,
+//! stores its response and replays the stored response for retries with the same key.
+//! Retries of a request that is still being processed are rejected or wait for the first request to finish.
+
+use std::{future::Future, time::Duration};
+
+use axum::http::{HeaderName, HeaderValue, Method, StatusCode};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::store::{KeyValueStore, StoreError};
+
+const KEY_PREFIX: &str = "idempotency:";
+
+/// Header holding the idempotency key.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Header set on replayed responses.
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Longer keys are rejected.
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdempotencyConfig {
+    /// How long the responses are replayed.
+    #[serde(default = "default_time_to_live_in_seconds")]
+    pub time_to_live_in_seconds: u64,
+    /// How long retries wait for a request with the same key that is still being processed.
+    ///
+    /// Retries are rejected with a `409 Conflict` immediately if `0`.
+    #[serde(default)]
+    pub in_progress_wait_in_millis: u64,
+}
+
+fn default_time_to_live_in_seconds() -> u64 {
+    24 * 60 * 60
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            time_to_live_in_seconds: default_time_to_live_in_seconds(),
+            in_progress_wait_in_millis: 0,
+        }
+    }
+}
+
+/// Returns whether requests with the method are handled idempotently.
+pub fn is_idempotency_method(method: &Method) -> bool {
+    method == Method::POST || method == Method::PATCH
+}
+
+/// Returns whether the key is non-empty, not too long and only contains visible ASCII characters.
+pub fn is_valid_idempotency_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_IDEMPOTENCY_KEY_LENGTH
+        && key.bytes().all(|byte| byte.is_ascii_graphic())
+}
+
+/// Returns the hex encoded SHA-256 digest of the parts, separated so that different splits do not collide.
+pub fn idempotency_digest<'a>(parts: impl IntoIterator<Item = &'a [u8]>) -> String {
+    let mut hasher = Sha256::new();
+
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+
+    hex::encode(hasher.finalize())
+}
+
+/// A response stored for replaying.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    /// The base64 encoded body.
+    pub body: String,
+}
+
+impl StoredResponse {
+    /// Keeps the headers that are valid UTF-8.
+    pub fn new<'a>(
+        status: StatusCode,
+        headers: impl IntoIterator<Item = (&'a HeaderName, &'a HeaderValue)>,
+        body: &[u8],
+    ) -> Self {
+        Self {
+            status: status.as_u16(),
+            headers: headers
+                .into_iter()
+                .filter_map(|(name, value)| {
+                    Some((name.as_str().to_owned(), value.to_str().ok()?.to_owned()))
+                })
+                .collect(),
+            body: STANDARD.encode(body),
+        }
+    }
+
+    pub fn body_bytes(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        STANDARD.decode(&self.body)
+    }
+}
+
+/// The state of an idempotency key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "state")]
+pub enum IdempotencyRecord {
+    /// The first request with the key is still being processed.
+    InProgress { fingerprint: String },
+    /// The first request with the key was processed.
+    Completed {
+        fingerprint: String,
+        response: StoredResponse,
+    },
+}
+
+impl IdempotencyRecord {
+    /// The digest of the request the key was first used with.
+    pub fn fingerprint(&self) -> &str {
+        match self {
+            Self::InProgress { fingerprint } | Self::Completed { fingerprint, .. } => fingerprint,
+        }
+    }
+}
+
+/// Stores the [`IdempotencyRecord`]s by key.
+pub trait IdempotencyStore {
+    type Error;
+
+    /// Stores an [`IdempotencyRecord::InProgress`] if the key is unused.
+    ///
+    /// Returns the existing record if the key is already used.
+    fn reserve(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        time_to_live: Duration,
+    ) -> impl Future<Output = Result<Option<IdempotencyRecord>, Self::Error>> + Send;
+
+    /// Returns the record of the key or `None` if the key is unused or has expired.
+    fn load(
+        &self,
+        key: &str,
+    ) -> impl Future<Output = Result<Option<IdempotencyRecord>, Self::Error>> + Send;
+
+    /// Replaces the record of the key with an [`IdempotencyRecord::Completed`].
+    fn complete(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        response: StoredResponse,
+        time_to_live: Duration,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Removes the record of the key, so the request can be retried.
+    fn release(&self, key: &str) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+pub trait IdempotencyScopeProvider {
+    /// Returns the scope of the keys of the request, so different clients can not replay each other's responses.
+    ///
+    /// Requests without a scope, such as anonymous requests, are not handled idempotently.
+    fn idempotency_scope(&self, parts: &axum::http::request::Parts) -> Option<String>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IdempotencyStoreError {
+    #[error(transparent)]
+    Store(#[from] StoreError),
+    #[error("Failed to (de)serialize idempotency record: {0}")]
+    Data(#[from] serde_json::Error),
+}
+
+/// Keeps each record as JSON under the prefixed key.
+impl<K> IdempotencyStore for K
+where
+    K: KeyValueStore + Sync,
+    StoreError: From<K::Error>,
+{
+    type Error = IdempotencyStoreError;
+
+    async fn reserve(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        time_to_live: Duration,
+    ) -> Result<Option<IdempotencyRecord>, Self::Error> {
+        let record = serde_json::to_string(&IdempotencyRecord::InProgress {
+            fingerprint: fingerprint.to_owned(),
+        })?;
+
+        loop {
+            let reserved = self
+                .set_if_absent(&format!("{KEY_PREFIX}{key}"), record.clone(), time_to_live)
+                .await
+                .map_err(StoreError::from)?;
+
+            if reserved {
+                return Ok(None);
+            }
+
+            // The existing record may expire between the two commands.
+            if let Some(existing) = self.load(key).await? {
+                return Ok(Some(existing));
+            }
+        }
+    }
+
+    async fn load(&self, key: &str) -> Result<Option<IdempotencyRecord>, Self::Error> {
+        let record = self
+            .get(&format!("{KEY_PREFIX}{key}"))
+            .await
+            .map_err(StoreError::from)?;
+
+        Ok(record
+            .map(|record| serde_json::from_str(&record))
+            .transpose()?)
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        response: StoredResponse,
+        time_to_live: Duration,
+    ) -> Result<(), Self::Error> {
+        let record = serde_json::to_string(&IdempotencyRecord::Completed {
+            fingerprint: fingerprint.to_owned(),
+            response,
+        })?;
+
+        self.set(&format!("{KEY_PREFIX}{key}"), record, time_to_live)
+            .await
+            .map_err(StoreError::from)?;
+
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), Self::Error> {
+        self.remove(&format!("{KEY_PREFIX}{key}"))
+            .await
+            .map_err(StoreError::from)?;
+
+        Ok(())
+    }
+}
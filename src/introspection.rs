@@ -0,0 +1,131 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use derivative::Derivative;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+
+use crate::extractor::introspected_token::{IntrospectedToken, IntrospectionProvider};
+
+#[derive(Derivative, Clone, Deserialize)]
+#[derivative(Debug)]
+pub struct IntrospectionConfig {
+    /// The RFC 7662 token introspection endpoint.
+    pub introspection_endpoint: String,
+    pub client_id: String,
+    #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+    pub client_secret: String,
+    /// Introspection results are cached for this many seconds, but never beyond the token's `exp`.
+    #[serde(default = "default_cache_time_to_live_in_seconds")]
+    pub cache_time_to_live_in_seconds: u64,
+}
+
+fn default_cache_time_to_live_in_seconds() -> u64 {
+    60
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IntrospectionError {
+    #[error("Token introspection is not configured")]
+    NotConfigured,
+    #[error("Failed to request introspection: {0}")]
+    Request(#[source] reqwest::Error),
+    #[error("Introspection endpoint responded with an error: {0}")]
+    Status(#[source] reqwest::Error),
+    #[error("Failed to parse introspection response: {0}")]
+    Parse(#[source] reqwest::Error),
+}
+
+struct CachedIntrospection {
+    introspected_token: IntrospectedToken,
+    expires_at: Instant,
+}
+
+/// Validates opaque tokens using an RFC 7662 introspection endpoint.
+///
+/// Introspection results are cached per token.
+pub struct TokenIntrospector {
+    http_client: reqwest::Client,
+    config: IntrospectionConfig,
+    cache: RwLock<HashMap<String, CachedIntrospection>>,
+}
+
+impl TokenIntrospector {
+    pub fn new(http_client: reqwest::Client, config: IntrospectionConfig) -> Self {
+        Self {
+            http_client,
+            config,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn request_introspection(
+        &self,
+        token: &str,
+    ) -> Result<IntrospectedToken, IntrospectionError> {
+        tracing::debug!("Introspecting token");
+
+        self.http_client
+            .post(&self.config.introspection_endpoint)
+            .basic_auth(&self.config.client_id, Some(&self.config.client_secret))
+            .form(&[("token", token), ("token_type_hint", "access_token")])
+            .send()
+            .await
+            .map_err(IntrospectionError::Request)?
+            .error_for_status()
+            .map_err(IntrospectionError::Status)?
+            .json::<IntrospectedToken>()
+            .await
+            .map_err(IntrospectionError::Parse)
+    }
+
+    /// Returns how long the introspection result may be cached.
+    fn cache_time_to_live(&self, introspected_token: &IntrospectedToken) -> Duration {
+        let ttl = Duration::from_secs(self.config.cache_time_to_live_in_seconds);
+
+        let Some(exp) = introspected_token.exp else {
+            return ttl;
+        };
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        ttl.min(Duration::from_secs(exp.saturating_sub(now)))
+    }
+}
+
+impl IntrospectionProvider for TokenIntrospector {
+    type Error = IntrospectionError;
+
+    async fn introspect(&self, token: &str) -> Result<IntrospectedToken, Self::Error> {
+        if let Some(cached) = self.cache.read().await.get(token) {
+            if Instant::now() < cached.expires_at {
+                tracing::trace!("Using cached introspection");
+
+                return Ok(cached.introspected_token.clone());
+            }
+        }
+
+        let introspected_token = self.request_introspection(token).await?;
+        let expires_at = Instant::now() + self.cache_time_to_live(&introspected_token);
+
+        let mut cache = self.cache.write().await;
+
+        let now = Instant::now();
+        cache.retain(|_, cached| now < cached.expires_at);
+        cache.insert(
+            token.to_owned(),
+            CachedIntrospection {
+                introspected_token: introspected_token.clone(),
+                expires_at,
+            },
+        );
+
+        Ok(introspected_token)
+    }
+}
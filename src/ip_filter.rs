@@ -0,0 +1,51 @@
+//! Allows or denies requests by the client IP.
+//!
+//! See [`IpFilterLayer`](crate::middleware::ip_filter::layer::IpFilterLayer).
+
+use std::{fmt::Display, net::IpAddr};
+
+use ipnet::IpNet;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IpFilterConfig {
+    /// If not empty, only clients in one of these ranges are allowed.
+    #[serde(default)]
+    pub allow: Vec<IpNet>,
+    /// Clients in one of these ranges are denied, even if they are in an allowed range.
+    #[serde(default)]
+    pub deny: Vec<IpNet>,
+}
+
+/// The rule a denied client IP matched.
+#[derive(Debug, Clone, Copy)]
+pub enum IpFilterRule {
+    /// The IP is in a denied range.
+    Deny(IpNet),
+    /// The IP is in none of the allowed ranges.
+    NotAllowed,
+}
+
+impl Display for IpFilterRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Deny(net) => write!(f, "deny {net}"),
+            Self::NotAllowed => write!(f, "no allow rule"),
+        }
+    }
+}
+
+impl IpFilterConfig {
+    /// Returns the matched rule if the IP is denied.
+    pub fn check(&self, ip: &IpAddr) -> Result<(), IpFilterRule> {
+        if let Some(net) = self.deny.iter().find(|net| net.contains(ip)) {
+            return Err(IpFilterRule::Deny(*net));
+        }
+
+        if !self.allow.is_empty() && !self.allow.iter().any(|net| net.contains(ip)) {
+            return Err(IpFilterRule::NotAllowed);
+        }
+
+        Ok(())
+    }
+}
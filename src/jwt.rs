@@ -1,9 +1,15 @@
-use std::time::Instant;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
 
 use jsonwebtoken::jwk::JwkSet;
+use serde::Deserialize;
 use tokio::sync::RwLock;
 
-use crate::extractor::jwt::JwksProvider;
+use crate::extractor::jwt::{validation::JwtValidationConfig, JwksProvider};
 
 #[derive(Debug, thiserror::Error)]
 pub enum JwkError {
@@ -11,18 +17,44 @@ pub enum JwkError {
     Fetch(#[source] reqwest::Error),
     #[error("Failed to parse Jwk from the Jwks URI: {0}")]
     Parse(#[source] reqwest::Error),
+    #[error("Jwks are stale. Last refreshed {age_in_seconds} seconds ago")]
+    Stale { age_in_seconds: u64 },
 }
 
-pub struct JwkRefresher {
+/// An additional identity provider whose tokens are accepted.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdentityProviderConfig {
+    pub openid_configuration_url: String,
+    pub jwks_time_to_live_in_seconds: u64,
+    #[serde(default = "default_jwks_max_stale_in_seconds")]
+    pub jwks_max_stale_in_seconds: u64,
+    pub audience: Vec<String>,
+}
+
+pub fn default_jwks_max_stale_in_seconds() -> u64 {
+    3600
+}
+
+/// The JWK set of a single issuer.
+///
+/// Refreshed in the background every `time_to_live_in_seconds` (with jitter).
+/// If refreshing fails, the stale JWK set is served for up to `max_stale_in_seconds`.
+pub struct IssuerJwks {
     time_to_live_in_seconds: u64,
+    max_stale_in_seconds: u64,
     jwks_uri: String,
     http_client: reqwest::Client,
     holder: RwLock<JwkHolder>,
-    issuer: Vec<String>,
+    issuer: String,
     audience: Vec<String>,
+    last_refresh_error: Mutex<Option<String>>,
+    last_forced_refresh: tokio::sync::Mutex<Option<Instant>>,
 }
 
-impl JwkRefresher {
+/// Minimum time between two refreshes that are forced by an unknown `kid`.
+const MIN_FORCED_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+impl IssuerJwks {
     #[tracing::instrument(skip_all)]
     async fn obtain_jwks(
         jwks_uri: &str,
@@ -44,8 +76,9 @@ impl JwkRefresher {
 
     pub async fn new(
         time_to_live_in_seconds: u64,
+        max_stale_in_seconds: u64,
         jwks_uri: String,
-        issuer: Vec<String>,
+        issuer: String,
         audience: Vec<String>,
         http_client: reqwest::Client,
     ) -> Result<Self, JwkError> {
@@ -54,15 +87,18 @@ impl JwkRefresher {
 
         Ok(Self {
             time_to_live_in_seconds,
+            max_stale_in_seconds,
             jwks_uri,
             issuer,
             audience,
             http_client,
             holder: RwLock::new(JwkHolder { last_updated, jwks }),
+            last_refresh_error: Mutex::new(None),
+            last_forced_refresh: tokio::sync::Mutex::new(None),
         })
     }
 
-    #[tracing::instrument(skip_all)]
+    #[tracing::instrument(skip_all, fields(issuer = %self.issuer))]
     async fn refresh_jwks(&self) -> Result<(), JwkError> {
         tracing::debug!("Refreshing Jwks");
 
@@ -76,16 +112,139 @@ impl JwkRefresher {
         Ok(())
     }
 
+    /// Returns the JWK set unless it is older than `max_stale_in_seconds`.
+    ///
+    /// Never refreshes on the request path.
     #[tracing::instrument(skip_all)]
     async fn get(&self) -> Result<&RwLock<JwkHolder>, JwkError> {
-        let last_updated = self.holder.read().await.last_updated;
+        let age_in_seconds = self.holder.read().await.last_updated.elapsed().as_secs();
 
-        if last_updated.elapsed().as_secs() > self.time_to_live_in_seconds {
-            self.refresh_jwks().await?;
+        if age_in_seconds > self.max_stale_in_seconds {
+            return Err(JwkError::Stale { age_in_seconds });
         }
 
         Ok(&self.holder)
     }
+
+    /// Refreshes the JWK set unless it was force-refreshed within [`MIN_FORCED_REFRESH_INTERVAL`].
+    ///
+    /// Concurrent callers wait for the running refresh instead of starting their own.
+    async fn force_refresh_jwks(&self) -> Result<(), JwkError> {
+        let mut last_forced_refresh = self.last_forced_refresh.lock().await;
+
+        if last_forced_refresh.is_some_and(|last_forced_refresh| {
+            last_forced_refresh.elapsed() < MIN_FORCED_REFRESH_INTERVAL
+        }) {
+            tracing::debug!("Jwks were force-refreshed recently. Skipping");
+
+            return Ok(());
+        }
+
+        last_forced_refresh.replace(Instant::now());
+
+        self.refresh_jwks().await
+    }
+
+    /// Returns the error of the last refresh if it failed.
+    fn last_refresh_error(&self) -> Option<String> {
+        self.last_refresh_error
+            .lock()
+            .expect("Last refresh error mutex poisoned")
+            .clone()
+    }
+
+    /// Refreshes the JWK set until the [`IssuerJwks`] is dropped.
+    ///
+    /// Failed refreshes are retried after a tenth of the time to live.
+    async fn refresh_loop(issuer_jwks: Weak<IssuerJwks>, time_to_live_in_seconds: u64) {
+        let time_to_live = Duration::from_secs(time_to_live_in_seconds);
+        let retry_interval = (time_to_live / 10).max(Duration::from_secs(1));
+
+        let mut interval = with_jitter(time_to_live);
+
+        loop {
+            tokio::time::sleep(interval).await;
+
+            let Some(issuer_jwks) = issuer_jwks.upgrade() else {
+                break;
+            };
+
+            let result = issuer_jwks.refresh_jwks().await;
+
+            let mut last_refresh_error = issuer_jwks
+                .last_refresh_error
+                .lock()
+                .expect("Last refresh error mutex poisoned");
+
+            match result {
+                Ok(()) => {
+                    last_refresh_error.take();
+
+                    interval = with_jitter(time_to_live);
+                }
+                Err(err) => {
+                    tracing::warn!(issuer = %issuer_jwks.issuer, %err, "Failed to refresh Jwks. Serving stale Jwks");
+
+                    last_refresh_error.replace(err.to_string());
+
+                    interval = retry_interval;
+                }
+            }
+        }
+    }
+}
+
+/// Randomizes the duration by up to ±10% so that instances do not refresh at the same time.
+fn with_jitter(duration: Duration) -> Duration {
+    let random = RandomState::new().build_hasher().finish();
+    let factor = 0.9 + (random % 201) as f64 / 1000.0;
+
+    duration.mul_f64(factor)
+}
+
+/// Holds the JWK sets of all trusted issuers.
+///
+/// Tokens are validated against the issuer named in their `iss` claim.
+pub struct JwkRefresher {
+    issuers: Vec<Arc<IssuerJwks>>,
+    validation: JwtValidationConfig,
+}
+
+impl JwkRefresher {
+    /// Spawns a background refresh task for every issuer.
+    pub fn new(issuers: Vec<IssuerJwks>, validation: JwtValidationConfig) -> Self {
+        let issuers = issuers
+            .into_iter()
+            .map(|issuer_jwks| {
+                let time_to_live_in_seconds = issuer_jwks.time_to_live_in_seconds;
+                let issuer_jwks = Arc::new(issuer_jwks);
+
+                tokio::spawn(IssuerJwks::refresh_loop(
+                    Arc::downgrade(&issuer_jwks),
+                    time_to_live_in_seconds,
+                ));
+
+                issuer_jwks
+            })
+            .collect();
+
+        Self {
+            issuers,
+            validation,
+        }
+    }
+
+    fn find(&self, issuer: &str) -> Option<&IssuerJwks> {
+        self.issuers
+            .iter()
+            .map(AsRef::as_ref)
+            .find(|issuer_jwks| issuer_jwks.issuer == issuer)
+    }
+
+    /// Returns the error of the last background refresh of the issuer if it failed.
+    pub fn last_refresh_error(&self, issuer: &str) -> Option<String> {
+        self.find(issuer)?.last_refresh_error()
+    }
 }
 
 pub struct JwkHolder {
@@ -116,22 +275,34 @@ impl<'a> AsRef<JwkSet> for JwkReadGuard<'a> {
 impl JwksProvider for JwkRefresher {
     type Error = JwkError;
 
-    async fn jwks(&self) -> Result<impl AsRef<jsonwebtoken::jwk::JwkSet>, Self::Error> {
-        let jwks_guard = self.get().await?.read().await;
+    async fn jwks(
+        &self,
+        issuer: &str,
+    ) -> Result<Option<impl AsRef<jsonwebtoken::jwk::JwkSet>>, Self::Error> {
+        let Some(issuer_jwks) = self.find(issuer) else {
+            return Ok(None);
+        };
+
+        let jwks_guard = issuer_jwks.get().await?.read().await;
         let jwks_guard = JwkReadGuard::new(jwks_guard);
 
-        Ok(jwks_guard)
+        Ok(Some(jwks_guard))
     }
 
-    fn audience(&self) -> &[impl ToString] {
-        &self.audience
+    async fn refresh_jwks(&self, issuer: &str) -> Result<(), Self::Error> {
+        match self.find(issuer) {
+            Some(issuer_jwks) => issuer_jwks.force_refresh_jwks().await,
+            None => Ok(()),
+        }
     }
 
-    fn issuer(&self) -> &[impl ToString] {
-        &self.issuer
+    fn audience<'a>(&'a self, issuer: &str) -> &'a [impl ToString + 'a] {
+        self.find(issuer)
+            .map(|issuer_jwks| issuer_jwks.audience.as_slice())
+            .unwrap_or_default()
     }
 
-    fn validate_nbf(&self) -> bool {
-        true
+    fn validation_config(&self) -> &JwtValidationConfig {
+        &self.validation
     }
 }
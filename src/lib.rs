@@ -1,15 +1,59 @@
+// Lets `the_axum_derive` refer to this crate as `::the_axum` from within the crate itself.
+extern crate self as the_axum;
+
+pub mod alert;
+pub mod analytics;
+pub mod audit;
+pub mod body_trace;
+pub mod catch_panic;
 mod claims;
 pub mod cli_args;
+pub mod compression;
+pub mod concurrency_limit;
+pub mod cors;
+pub mod credentials;
+pub mod csrf;
+pub mod downstream;
 pub mod error;
+pub mod error_codes;
+pub mod error_metrics;
+pub mod error_page;
+pub mod error_sink;
+pub mod etag;
 mod extractor;
+pub mod geoip;
+pub mod idempotency;
+pub mod introspection;
+pub mod ip_filter;
 pub mod jwt;
+pub mod lifecycle;
+pub mod listener;
+pub mod locale;
+pub mod maintenance;
 mod middleware;
+pub mod oidc;
+pub mod openapi;
 mod openid_configuration;
+pub mod problem_details;
+pub mod rate_limit;
+pub mod request_id;
+pub mod response;
+pub mod response_schema;
+pub mod revocation;
 mod route;
+pub mod route_middleware;
 pub mod server;
+pub mod session;
+pub mod shutdown;
+pub mod signing;
+pub mod slow_request;
 pub mod state;
-mod types;
+pub mod store;
+pub mod tls;
+pub mod token_issuer;
+pub mod types;
 mod utils;
+pub mod verbosity_policy;
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle state of an endpoint.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum EndpointLifecycle {
+    /// Endpoint is stable.
+    Stable,
+    /// Endpoint is deprecated and might be removed at the sunset date.
+    Deprecated {
+        since: DateTime<Utc>,
+        sunset: Option<DateTime<Utc>>,
+        /// Link to the endpoint that replaces this one.
+        successor: Option<String>,
+    },
+}
+
+/// Lifecycle of the endpoint with the given path.
+///
+/// The path must match the route's path, e.g. `/books/get_book`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EndpointLifecycleEntry {
+    pub path: String,
+    #[serde(flatten)]
+    pub lifecycle: EndpointLifecycle,
+}
+
+pub trait EndpointLifecycleProvider {
+    /// Returns all registered endpoint lifecycles.
+    fn endpoint_lifecycles(&self) -> &[EndpointLifecycleEntry];
+
+    /// Returns the lifecycle of the endpoint with the given path.
+    fn endpoint_lifecycle(&self, path: &str) -> Option<&EndpointLifecycle> {
+        self.endpoint_lifecycles()
+            .iter()
+            .find(|entry| entry.path == path)
+            .map(|entry| &entry.lifecycle)
+    }
+}
+
+/// Formats a date as an HTTP-date. e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+pub fn fmt_http_date(date: &DateTime<Utc>) -> String {
+    date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
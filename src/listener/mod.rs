@@ -0,0 +1,128 @@
+//! The sockets the server accepts connections on.
+//!
+//! The same router is served on every listener.
+//! Unix domain sockets are meant for a proxy on the same host, e.g. a sidecar, and always serve plain HTTP.
+
+pub mod serve;
+
+use std::{
+    fmt::{self, Display},
+    future::Future,
+    net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::{TcpListener, TcpStream},
+};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum ListenerConfig {
+    /// Serves HTTPS if TLS is configured.
+    Tcp { socket_address: SocketAddr },
+    /// An existing socket file is replaced. The file is removed when the server stops.
+    #[cfg(unix)]
+    Unix { path: PathBuf },
+}
+
+impl Display for ListenerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp { socket_address } => write!(f, "tcp://{socket_address}"),
+            #[cfg(unix)]
+            Self::Unix { path } => write!(f, "unix://{}", path.display()),
+        }
+    }
+}
+
+impl ListenerConfig {
+    pub async fn bind(self) -> anyhow::Result<Listener> {
+        match self {
+            Self::Tcp { socket_address } => TcpListener::bind(socket_address)
+                .await
+                .map(Listener::Tcp)
+                .with_context(|| format!("Failed to bind {socket_address}")),
+            #[cfg(unix)]
+            Self::Unix { path } => UnixListener::bind(path).await.map(Listener::Unix),
+        }
+    }
+}
+
+/// A bound listener of a [`ListenerConfig`].
+#[derive(Debug)]
+pub enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+/// A listener that accepts connections for [`serve::serve`].
+pub trait Accept: Send {
+    type Io: AsyncRead + AsyncWrite + Unpin + Send + 'static;
+
+    /// Accepts the next connection and returns the address of the peer.
+    fn accept(&mut self) -> impl Future<Output = std::io::Result<(Self::Io, SocketAddr)>> + Send;
+}
+
+impl Accept for TcpListener {
+    type Io = TcpStream;
+
+    async fn accept(&mut self) -> std::io::Result<(Self::Io, SocketAddr)> {
+        TcpListener::accept(self).await
+    }
+}
+
+/// Removes its socket file when dropped.
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct UnixListener {
+    listener: tokio::net::UnixListener,
+    path: PathBuf,
+}
+
+#[cfg(unix)]
+impl UnixListener {
+    async fn bind(path: PathBuf) -> anyhow::Result<Self> {
+        use std::os::unix::fs::FileTypeExt;
+
+        // A socket file left behind by a previous run would make the bind fail.
+        if tokio::fs::symlink_metadata(&path)
+            .await
+            .is_ok_and(|metadata| metadata.file_type().is_socket())
+        {
+            tokio::fs::remove_file(&path)
+                .await
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+
+        let listener = tokio::net::UnixListener::bind(&path)
+            .with_context(|| format!("Failed to bind {}", path.display()))?;
+
+        Ok(Self { listener, path })
+    }
+}
+
+/// Peers of Unix domain sockets are processes on the same host, so they are reported as `127.0.0.1:0`.
+#[cfg(unix)]
+impl Accept for UnixListener {
+    type Io = tokio::net::UnixStream;
+
+    async fn accept(&mut self) -> std::io::Result<(Self::Io, SocketAddr)> {
+        let (stream, _) = self.listener.accept().await?;
+
+        Ok((stream, SocketAddr::from((Ipv4Addr::LOCALHOST, 0))))
+    }
+}
+
+#[cfg(unix)]
+impl Drop for UnixListener {
+    fn drop(&mut self) {
+        if let Err(err) = std::fs::remove_file(&self.path) {
+            tracing::warn!(%err, path = %self.path.display(), "Failed to remove socket file");
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::{convert::Infallible, future::Future, net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::{extract::ConnectInfo, middleware::AddExtension, Extension, Router};
+use http::{header::CONNECTION, HeaderValue, Request, Version};
+use hyper::body::Incoming;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto::Builder,
+    service::TowerToHyperService,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::watch,
+};
+use tokio_rustls::TlsAcceptor;
+use tower::{service_fn, Layer, ServiceExt};
+
+use crate::extractor::client_cert::PeerCertificates;
+
+use super::Accept;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Serving,
+    /// In-flight requests are completed. Responses close their HTTP/1 connection.
+    Draining,
+    /// The drain timeout elapsed. Remaining connections are dropped.
+    Closing,
+}
+
+/// Like [`axum::serve`] with graceful shutdown, but accepts connections from any [`Accept`] and terminates TLS if an
+/// acceptor is given.
+///
+/// Once `shutdown` completes, the connections are drained for up to `drain_timeout`:
+/// Idle connections are closed, HTTP/1 connections are closed after their in-flight request and HTTP/2 connections
+/// stop accepting new streams. Connections that are accepted during the drain are served with `Connection: close`.
+/// Connections that are still open after the timeout are dropped.
+///
+/// Like with [`Router::into_make_service_with_connect_info`], handlers can extract the [`ConnectInfo`] of the peer as
+/// [`SocketAddr`].
+/// The certificates of clients that authenticated with mutual TLS are inserted into the request extensions as
+/// [`PeerCertificates`].
+pub async fn serve<L, F>(
+    mut listener: L,
+    acceptor: Option<TlsAcceptor>,
+    router: Router,
+    shutdown: F,
+    drain_timeout: Duration,
+) where
+    L: Accept,
+    F: Future<Output = ()>,
+{
+    // Every connection holds a receiver, so the connections are drained once all receivers are dropped.
+    let (phase, _) = watch::channel(Phase::Serving);
+
+    let accept = |io: L::Io, addr: SocketAddr| {
+        let acceptor = acceptor.clone();
+        let service = Extension(ConnectInfo(addr)).layer(router.clone());
+        let phase = phase.subscribe();
+
+        tokio::spawn(async move {
+            let Some(acceptor) = acceptor else {
+                return serve_connection(io, service, None, phase, addr).await;
+            };
+
+            let stream = match acceptor.accept(io).await {
+                Ok(stream) => stream,
+                Err(err) => {
+                    tracing::debug!(%err, %addr, "TLS handshake failed");
+
+                    return;
+                }
+            };
+
+            let peer_certificates = stream.get_ref().1.peer_certificates().map(|certs| {
+                PeerCertificates(Arc::new(certs.iter().map(|cert| cert.to_vec()).collect()))
+            });
+
+            serve_connection(stream, service, peer_certificates, phase, addr).await;
+        });
+    };
+
+    tokio::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((io, addr)) => accept(io, addr),
+                Err(err) => tracing::warn!(%err, "Failed to accept connection"),
+            },
+            _ = &mut shutdown => break,
+        }
+    }
+
+    tracing::info!(
+        connections = phase.receiver_count(),
+        ?drain_timeout,
+        "Draining connections"
+    );
+
+    phase.send_replace(Phase::Draining);
+
+    let drained = async {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((io, addr)) => accept(io, addr),
+                    Err(err) => tracing::warn!(%err, "Failed to accept connection"),
+                },
+                _ = phase.closed() => break,
+            }
+        }
+    };
+
+    if tokio::time::timeout(drain_timeout, drained).await.is_err() {
+        tracing::warn!(
+            connections = phase.receiver_count(),
+            "Drain timeout elapsed. Closing remaining connections"
+        );
+
+        phase.send_replace(Phase::Closing);
+        phase.closed().await;
+    }
+}
+
+async fn serve_connection<I>(
+    io: I,
+    service: AddExtension<Router, ConnectInfo<SocketAddr>>,
+    peer_certificates: Option<PeerCertificates>,
+    mut phase: watch::Receiver<Phase>,
+    addr: SocketAddr,
+) where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let service = service_fn({
+        let phase = phase.clone();
+
+        move |mut request: Request<Incoming>| {
+            if let Some(peer_certificates) = &peer_certificates {
+                request.extensions_mut().insert(peer_certificates.clone());
+            }
+
+            // HTTP/2 has no `Connection` header. Its connections are closed with a GOAWAY frame instead.
+            let close = request.version() <= Version::HTTP_11 && *phase.borrow() != Phase::Serving;
+            let response = service.clone().oneshot(request);
+
+            async move {
+                let mut response = response.await?;
+
+                if close {
+                    response
+                        .headers_mut()
+                        .insert(CONNECTION, HeaderValue::from_static("close"));
+                }
+
+                Ok::<_, Infallible>(response)
+            }
+        }
+    });
+
+    let builder = Builder::new(TokioExecutor::new());
+    let connection =
+        builder.serve_connection_with_upgrades(TokioIo::new(io), TowerToHyperService::new(service));
+
+    tokio::pin!(connection);
+
+    // Connections accepted during the drain are closed by the `Connection: close` of their response.
+    let mut shutting_down = *phase.borrow() != Phase::Serving;
+
+    loop {
+        tokio::select! {
+            result = connection.as_mut() => {
+                if let Err(err) = result {
+                    tracing::debug!(%err, %addr, "Failed to serve connection");
+                }
+
+                return;
+            }
+            changed = phase.changed() => {
+                let current = *phase.borrow_and_update();
+
+                if changed.is_err() || current == Phase::Closing {
+                    tracing::debug!(%addr, "Connection closed after drain timeout");
+
+                    return;
+                }
+
+                if current == Phase::Draining && !shutting_down {
+                    connection.as_mut().graceful_shutdown();
+                    shutting_down = true;
+                }
+            }
+        }
+    }
+}
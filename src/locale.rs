@@ -0,0 +1,236 @@
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+tokio::task_local! {
+    static CURRENT_ERROR_MESSAGES: LocalizedMessages;
+}
+
+/// Localized messages by locale and message key.
+///
+/// The error responses look their `message` up by their stable [`error_codes`](crate::error_codes)
+/// and their `reason` by the error code with a `.reason` suffix.
+/// Placeholders like `{reason}` are replaced with the fields of the error content.
+/// Errors without a message in the negotiated locale or the default locale keep their English message.
+///
+/// ```yaml
+/// default_locale: en
+/// supported_locales:
+///   - en
+///   - de
+/// messages:
+///   en:
+///     book_found: Book found
+///   de:
+///     book_found: Buch gefunden
+///     REQUEST_TIMEOUT: Zeitüberschreitung der Anfrage
+///     REQUEST_TIMEOUT.reason: "Die Anfrage wurde nicht rechtzeitig bearbeitet: {reason}"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct LocaleCatalog {
+    #[serde(default = "default_locale")]
+    pub default_locale: String,
+    /// Locales offered during negotiation. If empty, the locales of the messages are offered.
+    #[serde(default)]
+    pub supported_locales: Vec<String>,
+    #[serde(default)]
+    pub messages: HashMap<String, HashMap<String, String>>,
+}
+
+fn default_locale() -> String {
+    String::from("en")
+}
+
+impl Default for LocaleCatalog {
+    fn default() -> Self {
+        Self {
+            default_locale: default_locale(),
+            supported_locales: Vec::new(),
+            messages: HashMap::new(),
+        }
+    }
+}
+
+impl LocaleCatalog {
+    /// Resolves the message with the given key for the first matching locale of the `Accept-Language` header.
+    ///
+    /// Falls back to the default locale and finally to the key itself.
+    pub fn resolve<'a>(&'a self, accept_language: Option<&str>, key: &'a str) -> &'a str {
+        accept_language
+            .map(parse_accept_language)
+            .unwrap_or_default()
+            .into_iter()
+            .chain(std::iter::once(self.default_locale.as_str()))
+            .find_map(|locale| self.lookup(locale, key))
+            .unwrap_or(key)
+    }
+
+    /// Negotiates the best supported locale for the `Accept-Language` header.
+    ///
+    /// Locales are tried in order of their quality, falling back from e.g. `de-DE` to `de`.
+    /// Returns the default locale if no supported locale matches.
+    pub fn negotiate(&self, accept_language: Option<&str>) -> &str {
+        let supported = |locale: &str| {
+            if self.supported_locales.is_empty() {
+                return self
+                    .messages
+                    .keys()
+                    .find(|supported| supported.eq_ignore_ascii_case(locale))
+                    .map(String::as_str);
+            }
+
+            self.supported_locales
+                .iter()
+                .find(|supported| supported.eq_ignore_ascii_case(locale))
+                .map(String::as_str)
+        };
+
+        accept_language
+            .map(parse_accept_language)
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|locale| {
+                supported(locale).or_else(|| {
+                    let (language, _) = locale.split_once('-')?;
+
+                    supported(language)
+                })
+            })
+            .unwrap_or(&self.default_locale)
+    }
+
+    /// Resolves the message with the given key for a negotiated locale.
+    ///
+    /// Falls back to the default locale and finally to the key itself.
+    pub fn message<'a>(&'a self, locale: &str, key: &'a str) -> &'a str {
+        self.find_message(locale, key).unwrap_or(key)
+    }
+
+    /// Resolves the message with the given key for a negotiated locale, falling back to the default locale.
+    ///
+    /// Returns `None` if neither locale has the message.
+    pub fn find_message(&self, locale: &str, key: &str) -> Option<&str> {
+        self.lookup(locale, key)
+            .or_else(|| self.lookup(&self.default_locale, key))
+    }
+
+    /// Looks up the message for the locale, falling back from e.g. `de-DE` to `de`.
+    fn lookup(&self, locale: &str, key: &str) -> Option<&str> {
+        let exact = self
+            .messages
+            .get(locale)
+            .and_then(|messages| messages.get(key));
+
+        let language = || {
+            let (language, _) = locale.split_once('-')?;
+
+            self.messages
+                .get(language)
+                .and_then(|messages| messages.get(key))
+        };
+
+        exact.or_else(language).map(String::as_str)
+    }
+}
+
+/// Parses the `Accept-Language` header into locales ordered by their quality.
+///
+/// Locales with a quality of `0` are not acceptable and are skipped.
+pub fn parse_accept_language(accept_language: &str) -> Vec<&str> {
+    let mut locales = accept_language
+        .split(',')
+        .filter_map(|part| {
+            let mut split = part.trim().split(';');
+            let locale = split.next()?.trim();
+
+            let quality = split
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .and_then(|quality| quality.parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            (!locale.is_empty() && locale != "*" && quality > 0.0).then_some((locale, quality))
+        })
+        .collect::<Vec<_>>();
+
+    locales.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    locales.into_iter().map(|(locale, _)| locale).collect()
+}
+
+pub trait LocaleCatalogProvider {
+    /// Returns the locale catalog.
+    fn locale_catalog(&self) -> &Arc<LocaleCatalog>;
+}
+
+/// The messages of the locale negotiated for the errors of a request.
+///
+/// Negotiated by the [`error_locale`](crate::middleware::error_locale::error_locale) middleware
+/// and read by the error responses through [`LocalizedMessages::current`].
+#[derive(Debug, Clone)]
+pub struct LocalizedMessages {
+    locale: String,
+    catalog: Arc<LocaleCatalog>,
+}
+
+impl LocalizedMessages {
+    /// Negotiates the locale for the `Accept-Language` header.
+    pub fn negotiate(catalog: &Arc<LocaleCatalog>, accept_language: Option<&str>) -> Self {
+        Self {
+            locale: catalog.negotiate(accept_language).to_owned(),
+            catalog: catalog.clone(),
+        }
+    }
+
+    /// Returns the negotiated locale, used as the `Content-Language` of the error responses.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Returns the localized message of the error with the code.
+    ///
+    /// `content` is the serialized error content the placeholders are replaced with.
+    pub fn message(&self, error_code: &str, content: Option<&Value>) -> Option<String> {
+        self.render(error_code, content)
+    }
+
+    /// Returns the localized reason of the error with the code.
+    pub fn reason(&self, error_code: &str, content: Option<&Value>) -> Option<String> {
+        self.render(&format!("{error_code}.reason"), content)
+    }
+
+    fn render(&self, key: &str, content: Option<&Value>) -> Option<String> {
+        let template = self.catalog.find_message(&self.locale, key)?;
+
+        let Some(Value::Object(fields)) = content else {
+            return Some(template.to_owned());
+        };
+
+        let rendered = fields
+            .iter()
+            .fold(template.to_owned(), |rendered, (key, value)| {
+                let value = match value {
+                    Value::String(value) => value.clone(),
+                    Value::Number(value) => value.to_string(),
+                    Value::Bool(value) => value.to_string(),
+                    _ => return rendered,
+                };
+
+                rendered.replace(&format!("{{{key}}}"), &value)
+            });
+
+        Some(rendered)
+    }
+
+    /// Returns the messages of the locale negotiated for the request that is currently handled.
+    ///
+    /// Returns `None` outside of [`LocalizedMessages::scope`].
+    pub fn current() -> Option<Self> {
+        CURRENT_ERROR_MESSAGES.try_with(Clone::clone).ok()
+    }
+
+    /// Runs the future with these messages as the [`LocalizedMessages::current`] messages.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT_ERROR_MESSAGES.scope(self, future).await
+    }
+}
@@ -0,0 +1,97 @@
+//! Maintenance mode that can be toggled at runtime to drain traffic, e.g. during deploys.
+//!
+//! While enabled, new requests are rejected with a `503 Service Unavailable`, except for the allowed paths.
+//! Requests already in flight are not affected.
+//! Toggled through the `/admin/maintenance` route or, on unix, by sending `SIGHUP` to the server.
+
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MaintenanceConfig {
+    /// Whether the server starts in maintenance mode.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Sent in the `Retry-After` header of rejected requests.
+    #[serde(default = "default_retry_after_in_seconds")]
+    pub retry_after_in_seconds: u64,
+    /// Requests whose path starts with one of these prefixes are still handled.
+    #[serde(default = "default_allowed_path_prefixes")]
+    pub allowed_path_prefixes: Vec<String>,
+}
+
+fn default_retry_after_in_seconds() -> u64 {
+    60
+}
+
+fn default_allowed_path_prefixes() -> Vec<String> {
+    vec![String::from("/health"), String::from("/admin")]
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retry_after_in_seconds: default_retry_after_in_seconds(),
+            allowed_path_prefixes: default_allowed_path_prefixes(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MaintenanceMode {
+    enabled: AtomicBool,
+    retry_after: Duration,
+    allowed_path_prefixes: Vec<String>,
+}
+
+impl MaintenanceMode {
+    pub fn new(config: MaintenanceConfig) -> Self {
+        Self {
+            enabled: AtomicBool::new(config.enabled),
+            retry_after: Duration::from_secs(config.retry_after_in_seconds),
+            allowed_path_prefixes: config.allowed_path_prefixes,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+
+        tracing::info!(enabled, "Maintenance mode changed");
+    }
+
+    /// Toggles the maintenance mode and returns whether it is enabled now.
+    pub fn toggle(&self) -> bool {
+        let enabled = !self.enabled.fetch_xor(true, Ordering::Relaxed);
+
+        tracing::info!(enabled, "Maintenance mode toggled");
+
+        enabled
+    }
+
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after
+    }
+
+    /// Returns whether requests to the path are handled during maintenance.
+    pub fn allows(&self, path: &str) -> bool {
+        self.allowed_path_prefixes.iter().any(|prefix| {
+            let prefix = prefix.trim_end_matches('/');
+
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+}
+
+pub trait MaintenanceModeProvider {
+    fn maintenance_mode(&self) -> &MaintenanceMode;
+}
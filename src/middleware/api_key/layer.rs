@@ -0,0 +1,25 @@
+use tower::Layer;
+
+use super::service::ApiKeyAuth;
+
+/// Validates the API key of requests before they reach the inner service.
+///
+/// The [`ValidApiKey`](crate::extractor::valid_api_key::ValidApiKey) is inserted into the request extensions.
+#[derive(Debug, Clone)]
+pub struct ApiKeyLayer<P> {
+    provider: P,
+}
+
+impl<P> ApiKeyLayer<P> {
+    pub const fn new(provider: P) -> Self {
+        ApiKeyLayer { provider }
+    }
+}
+
+impl<S, P: Clone> Layer<S> for ApiKeyLayer<P> {
+    type Service = ApiKeyAuth<S, P>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        ApiKeyAuth::new(service, self.provider.clone())
+    }
+}
@@ -0,0 +1,56 @@
+use std::task::{Context, Poll};
+
+use axum::{body::Body as AxumBody, extract::FromRequestParts};
+use http::{Request, Response};
+use tower::Service;
+
+use crate::{
+    error::ErrorVerbosityProvider,
+    extractor::{api_key::ApiKeyProvider, valid_api_key::ValidApiKey},
+    middleware::auth_future::ResponseFuture,
+};
+
+/// Validates the API key of the request and puts the [`ValidApiKey`] as an extension for the inner service.
+#[derive(Debug, Clone)]
+pub struct ApiKeyAuth<T, P> {
+    inner: T,
+    provider: P,
+}
+
+impl<T, P> ApiKeyAuth<T, P> {
+    pub const fn new(inner: T, provider: P) -> Self {
+        ApiKeyAuth { inner, provider }
+    }
+}
+
+impl<S, ReqBody, P> Service<Request<ReqBody>> for ApiKeyAuth<S, P>
+where
+    P: ApiKeyProvider + ErrorVerbosityProvider + Send + Sync + Clone + 'static,
+    <P as ApiKeyProvider>::Error: Into<anyhow::Error>,
+    S: Service<Request<ReqBody>, Response = Response<AxumBody>> + Clone + Send,
+{
+    type Response = Response<AxumBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S, ReqBody, ValidApiKey>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // The inner service is called after the validation, so the service that was polled ready is moved into the future.
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+
+        let (mut parts, body) = request.into_parts();
+        let provider = self.provider.clone();
+
+        let authentication = Box::pin(async move {
+            let valid_api_key = ValidApiKey::from_request_parts(&mut parts, &provider).await;
+
+            (parts, valid_api_key)
+        });
+
+        ResponseFuture::new(authentication, inner, body)
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    convert::Infallible,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use axum::{
+    extract::{FromRequestParts, MatchedPath, OriginalUri, Request},
+    response::Response,
+};
+use chrono::Utc;
+use futures::future::BoxFuture;
+use tower::{Layer, Service};
+
+use crate::{
+    audit::{AuditConfig, AuditEvent, AuditProvider},
+    error::ErrorVerbosityProvider,
+    extractor::client_ip::{ApiClientIp, TrustedProxiesProvider},
+    request_id::RequestId,
+};
+
+/// Records an [`AuditEvent`] for every handled request through the [`AuditProvider`].
+///
+/// Requests to the excluded paths and, if configured, anonymous requests are not recorded.
+#[derive(Debug, Clone)]
+pub struct AuditLayer<S> {
+    state: S,
+    config: Arc<AuditConfig>,
+}
+
+impl<S> AuditLayer<S> {
+    pub fn new(state: S, config: AuditConfig) -> Self {
+        AuditLayer {
+            state,
+            config: Arc::new(config),
+        }
+    }
+}
+
+impl<I, S: Clone> Layer<I> for AuditLayer<S> {
+    type Service = AuditService<I, S>;
+
+    fn layer(&self, inner: I) -> Self::Service {
+        AuditService {
+            inner,
+            state: self.state.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditService<I, S> {
+    inner: I,
+    state: S,
+    config: Arc<AuditConfig>,
+}
+
+impl<I, S> Service<Request> for AuditService<I, S>
+where
+    I: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    I::Future: Send,
+    S: AuditProvider
+        + TrustedProxiesProvider
+        + ErrorVerbosityProvider
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Take the service that was driven to readiness.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let state = self.state.clone();
+        let config = self.config.clone();
+
+        Box::pin(async move {
+            let (mut parts, body) = request.into_parts();
+
+            let path = parts
+                .extensions
+                .get::<OriginalUri>()
+                .map_or(&parts.uri, |OriginalUri(uri)| uri)
+                .path()
+                .to_owned();
+
+            let identities = state.audit_identities(&parts);
+
+            if !config.applies_to(&path) || (config.authenticated_only && identities.is_empty()) {
+                return inner.call(Request::from_parts(parts, body)).await;
+            }
+
+            // Requests with invalid forwarding headers are still recorded, just without the client IP.
+            let client_ip = ApiClientIp::from_request_parts(&mut parts, &state)
+                .await
+                .ok()
+                .map(|ApiClientIp(ip)| ip);
+
+            let request_id = parts.extensions.get::<RequestId>().map(ToString::to_string);
+
+            let route = parts
+                .extensions
+                .get::<MatchedPath>()
+                .map(|matched_path| matched_path.as_str().to_string())
+                .unwrap_or_else(|| String::from("unmatched"));
+
+            let method = parts.method.to_string();
+
+            let timestamp = Utc::now();
+            let start = Instant::now();
+            let response = inner.call(Request::from_parts(parts, body)).await?;
+
+            state.record_audit_event(AuditEvent {
+                timestamp,
+                request_id,
+                principal: identities.first().map(ToString::to_string),
+                identities,
+                client_ip,
+                method,
+                route,
+                path,
+                status: response.status().as_u16(),
+                latency_in_millis: start.elapsed().as_secs_f64() * 1000.0,
+            });
+
+            Ok(response)
+        })
+    }
+}
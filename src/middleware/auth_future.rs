@@ -0,0 +1,104 @@
+//! The response future of the authentication layers.
+//!
+//! Authenticates the request before the inner service is called and puts the authenticated value as an extension.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use axum::{body::Body as AxumBody, response::IntoResponse};
+use http::{request::Parts, Request, Response};
+use pin_project_lite::pin_project;
+use tower::Service;
+
+use crate::error::ApiError;
+
+/// Returns the request parts with the authenticated value or the rejection.
+pub type Authentication<E> =
+    Pin<Box<dyn Future<Output = (Parts, Result<E, ApiError>)> + Send + 'static>>;
+
+pin_project! {
+    pub struct ResponseFuture<S, ReqBody, E>
+    where
+        S: Service<Request<ReqBody>>,
+    {
+        #[pin]
+        kind: Kind<S, ReqBody, E>,
+    }
+}
+
+impl<S, ReqBody, E> ResponseFuture<S, ReqBody, E>
+where
+    S: Service<Request<ReqBody>>,
+{
+    pub fn new(authentication: Authentication<E>, inner: S, body: ReqBody) -> Self {
+        Self {
+            kind: Kind::Authenticating {
+                authentication,
+                inner: Some(inner),
+                body: Some(body),
+            },
+        }
+    }
+}
+
+pin_project! {
+    #[project = KindProj]
+    enum Kind<S, ReqBody, E>
+    where
+        S: Service<Request<ReqBody>>,
+    {
+        Authenticating {
+            authentication: Authentication<E>,
+            inner: Option<S>,
+            body: Option<ReqBody>,
+        },
+        Calling {
+            #[pin]
+            future: S::Future,
+        },
+    }
+}
+
+impl<S, ReqBody, E> Future for ResponseFuture<S, ReqBody, E>
+where
+    S: Service<Request<ReqBody>, Response = Response<AxumBody>>,
+    E: Clone + Send + Sync + 'static,
+{
+    type Output = Result<Response<AxumBody>, S::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            match this.kind.as_mut().project() {
+                KindProj::Authenticating {
+                    authentication,
+                    inner,
+                    body,
+                } => {
+                    let (parts, authenticated) = ready!(authentication.as_mut().poll(cx));
+
+                    let authenticated = match authenticated {
+                        Ok(authenticated) => authenticated,
+                        Err(api_error) => return Poll::Ready(Ok(api_error.into_response())),
+                    };
+
+                    let body = body.take().expect("future polled after completion");
+                    let mut request = Request::from_parts(parts, body);
+                    request.extensions_mut().insert(authenticated);
+
+                    let future = inner
+                        .take()
+                        .expect("future polled after completion")
+                        .call(request);
+
+                    this.kind.set(Kind::Calling { future });
+                }
+                KindProj::Calling { future } => return future.poll(cx),
+            }
+        }
+    }
+}
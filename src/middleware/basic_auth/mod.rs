@@ -1,4 +1,3 @@
-pub mod future;
 pub mod layer;
 pub mod provider;
 pub mod service;
@@ -1,5 +1,7 @@
 use std::future::Future;
 
+use crate::{error::ErrorVerbosityProvider, state::PrivateErrorVerbosity};
+
 pub trait BasicAuthProvider {
     fn authenticate(
         &self,
@@ -8,8 +10,15 @@ pub trait BasicAuthProvider {
     ) -> impl Future<Output = bool> + Send;
 }
 
+/// Renders errors with the verbosity it was created with.
 #[derive(Debug, Clone)]
-pub struct DummyAuthProvider;
+pub struct DummyAuthProvider(pub PrivateErrorVerbosity);
+
+impl ErrorVerbosityProvider for DummyAuthProvider {
+    fn error_verbosity(&self) -> PrivateErrorVerbosity {
+        self.0
+    }
+}
 
 impl BasicAuthProvider for DummyAuthProvider {
     async fn authenticate(&self, username: &str, _passowrd: Option<&str>) -> bool {
@@ -1,6 +1,11 @@
-use crate::extractor::basic_auth::ApiBasicAuth;
+use crate::{
+    error::{ApiError, BasicAuthError, BasicAuthErrorType, ErrorVerbosityProvider},
+    extractor::basic_auth::ApiBasicAuth,
+    middleware::auth_future::ResponseFuture,
+    types::used_basic_auth::UsedBasicAuth,
+};
 
-use super::{future::ResponseFuture, provider::BasicAuthProvider};
+use super::provider::BasicAuthProvider;
 use axum::body::Body as AxumBody;
 
 use http::{Request, Response};
@@ -31,12 +36,12 @@ pub struct BasicAuthToken;
 
 impl<S, ReqBody, P> Service<Request<ReqBody>> for BasicAuth<S, P>
 where
-    P: BasicAuthProvider + Send + Clone + 'static,
-    S: Service<Request<ReqBody>, Response = Response<AxumBody>> + Send,
+    P: BasicAuthProvider + ErrorVerbosityProvider + Send + Sync + Clone + 'static,
+    S: Service<Request<ReqBody>, Response = Response<AxumBody>> + Clone + Send,
 {
     type Response = Response<AxumBody>;
     type Error = S::Error;
-    type Future = ResponseFuture<S::Future>;
+    type Future = ResponseFuture<S, ReqBody, UsedBasicAuth>;
 
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         self.inner.poll_ready(cx)
@@ -45,28 +50,42 @@ where
     fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
         request.extensions_mut().insert(BasicAuthToken {});
 
+        // The inner service is called after the authentication, so the service that was polled ready is moved into the future.
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+
         let (parts, body) = request.into_parts();
+        let provider = self.provider.clone();
+
+        let authentication = Box::pin(async move {
+            let verbosity = provider.error_verbosity();
+
+            let used_basic_auth = match ApiBasicAuth::from_req_parts(&parts, verbosity) {
+                Ok(ApiBasicAuth(used_basic_auth)) => used_basic_auth,
+                Err(err) => return (parts, Err(err)),
+            };
 
-        match ApiBasicAuth::from_req_parts(&parts, crate::error::ErrorVerbosity::Full) {
-            Ok(ApiBasicAuth(used_basic_auth)) => {
-                let request = Request::from_parts(parts, body);
-                let future = self.inner.call(request);
+            let authenticated = provider
+                .authenticate(
+                    &used_basic_auth.username,
+                    used_basic_auth.password.as_deref(),
+                )
+                .await;
 
-                let provider = self.provider.clone();
+            if !authenticated {
+                tracing::warn!(username = %used_basic_auth.username, "Rejection. Invalid basic auth");
 
-                let boxed = Box::pin(async move {
-                    provider
-                        .authenticate(
-                            &used_basic_auth.username,
-                            used_basic_auth.password.as_deref(),
-                        )
-                        .await
-                });
+                let err = ApiError::BasicAuth(BasicAuthError::new(
+                    verbosity,
+                    BasicAuthErrorType::Invalid,
+                ));
 
-                // TODO: how do I get to the request to insert extensions?
-                ResponseFuture::future(boxed, future)
+                return (parts, Err(err));
             }
-            Err(err) => ResponseFuture::api_error(err),
-        }
+
+            (parts, Ok(used_basic_auth))
+        });
+
+        ResponseFuture::new(authentication, inner, body)
     }
 }
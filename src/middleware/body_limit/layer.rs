@@ -0,0 +1,47 @@
+use tower::Layer;
+
+use super::service::BodyLimit;
+
+/// Limits the size of the request bodies of a router.
+///
+/// Overrides the limit of the [`BodyLimitProvider`](crate::extractor::body::BodyLimitProvider) for the body extractors.
+#[derive(Clone)]
+pub struct BodyLimitLayer<P> {
+    provider: P,
+    limit: usize,
+    overrides_extractor_limit: bool,
+}
+
+impl<P> BodyLimitLayer<P> {
+    pub fn new(provider: P, limit_in_bytes: usize) -> Self {
+        BodyLimitLayer {
+            provider,
+            limit: limit_in_bytes,
+            overrides_extractor_limit: true,
+        }
+    }
+
+    /// Limits the size of the decompressed request bodies without overriding the limit of the body extractors.
+    ///
+    /// Applied inside the request decompression, so compressed bodies can not expand beyond the limit.
+    pub fn decompressed(provider: P, limit_in_bytes: usize) -> Self {
+        BodyLimitLayer {
+            provider,
+            limit: limit_in_bytes,
+            overrides_extractor_limit: false,
+        }
+    }
+}
+
+impl<S, P: Clone> Layer<S> for BodyLimitLayer<P> {
+    type Service = BodyLimit<S, P>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        BodyLimit::new(
+            service,
+            self.provider.clone(),
+            self.limit,
+            self.overrides_extractor_limit,
+        )
+    }
+}
@@ -0,0 +1,88 @@
+use std::task::{Context, Poll};
+
+use axum::{
+    body::{Body as AxumBody, Bytes, HttpBody},
+    http::header::CONTENT_LENGTH,
+    response::IntoResponse,
+    BoxError,
+};
+use futures::future::{ready, Either, Ready};
+use http::{Request, Response};
+use http_body_util::Limited;
+use tower::Service;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, PayloadTooLargeError},
+    extractor::body::RequestBodyLimit,
+};
+
+/// Rejects requests whose `Content-Length` exceeds the limit and limits the bodies of the others.
+///
+/// Bodies exceeding the limit while they are read are rejected by the body extractors.
+#[derive(Clone)]
+pub struct BodyLimit<T, P> {
+    inner: T,
+    provider: P,
+    limit: usize,
+    overrides_extractor_limit: bool,
+}
+
+impl<T, P> BodyLimit<T, P> {
+    pub fn new(inner: T, provider: P, limit: usize, overrides_extractor_limit: bool) -> Self {
+        BodyLimit {
+            inner,
+            provider,
+            limit,
+            overrides_extractor_limit,
+        }
+    }
+}
+
+impl<S, P, B> Service<Request<B>> for BodyLimit<S, P>
+where
+    P: ErrorVerbosityProvider,
+    S: Service<Request<AxumBody>, Response = Response<AxumBody>>,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response<AxumBody>;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response<AxumBody>, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let content_length = request
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<usize>().ok());
+
+        if let Some(content_length) = content_length.filter(|length| *length > self.limit) {
+            tracing::warn!(
+                content_length,
+                limit = self.limit,
+                "Rejection. Content length exceeds limit"
+            );
+
+            let api_error = ApiError::from(PayloadTooLargeError::with_size(
+                self.provider.error_verbosity(),
+                self.limit,
+                content_length,
+            ));
+
+            return Either::Left(ready(Ok(api_error.into_response())));
+        }
+
+        let (mut parts, body) = request.into_parts();
+        if self.overrides_extractor_limit {
+            parts.extensions.insert(RequestBodyLimit(self.limit));
+        }
+
+        let body = AxumBody::new(Limited::new(body, self.limit));
+
+        Either::Right(self.inner.call(Request::from_parts(parts, body)))
+    }
+}
@@ -0,0 +1,33 @@
+use axum::{body::Bytes, http::response::Parts, response::Response};
+use http_body_util::BodyExt;
+
+use crate::{error::ApiError, state::PrivateErrorVerbosity};
+
+/// The buffered body of a response, kept as an extension so the next middlewares do not buffer it again.
+#[derive(Debug, Clone)]
+struct BufferedBody(Bytes);
+
+/// Reads the entire response body.
+///
+/// Returns the body buffered by a previous middleware if there is one.
+/// The body must be put back into the response unchanged, see [`Response::from_parts`].
+pub async fn buffer_body(
+    response: Response,
+    verbosity: PrivateErrorVerbosity,
+) -> Result<(Parts, Bytes), ApiError> {
+    let (mut parts, body) = response.into_parts();
+
+    if let Some(BufferedBody(bytes)) = parts.extensions.get::<BufferedBody>().cloned() {
+        return Ok((parts, bytes));
+    }
+
+    let bytes = body
+        .collect()
+        .await
+        .map_err(|err| ApiError::from_generic_error(verbosity, err))?
+        .to_bytes();
+
+    parts.extensions.insert(BufferedBody(bytes.clone()));
+
+    Ok((parts, bytes))
+}
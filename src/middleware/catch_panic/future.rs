@@ -0,0 +1,95 @@
+use std::{
+    any::Any,
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::{body::Body as AxumBody, response::IntoResponse};
+use http::Response;
+use pin_project_lite::pin_project;
+
+use crate::{
+    catch_panic::{panic_message, PanicCounterProvider},
+    error::{ApiError, ErrorVerbosityProvider, InternalServerError},
+};
+
+pin_project! {
+    pub struct ResponseFuture<F, P> {
+        #[pin]
+        kind: Kind<F, P>,
+    }
+}
+
+pin_project! {
+    #[project = KindProj]
+    enum Kind<F, P> {
+        Future {
+            #[pin]
+            future: F,
+            provider: P,
+        },
+        Panicked {
+            response: Option<Response<AxumBody>>,
+        },
+    }
+}
+
+impl<F, P> ResponseFuture<F, P>
+where
+    P: ErrorVerbosityProvider + PanicCounterProvider,
+{
+    pub fn future(future: F, provider: P) -> Self {
+        Self {
+            kind: Kind::Future { future, provider },
+        }
+    }
+
+    pub fn panicked(provider: &P, payload: Box<dyn Any + Send>) -> Self {
+        Self {
+            kind: Kind::Panicked {
+                response: Some(panic_response(provider, payload)),
+            },
+        }
+    }
+}
+
+fn panic_response<P>(provider: &P, payload: Box<dyn Any + Send>) -> Response<AxumBody>
+where
+    P: ErrorVerbosityProvider + PanicCounterProvider,
+{
+    let message = panic_message(payload.as_ref());
+
+    tracing::error!(message, "Handler panicked");
+
+    provider.panic_counter().increment();
+
+    ApiError::from(InternalServerError::from_panic(
+        provider.error_verbosity(),
+        message,
+    ))
+    .into_response()
+}
+
+impl<F, P, E> Future for ResponseFuture<F, P>
+where
+    F: Future<Output = Result<Response<AxumBody>, E>>,
+    P: ErrorVerbosityProvider + PanicCounterProvider,
+{
+    type Output = Result<Response<AxumBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().kind.project() {
+            KindProj::Future { future, provider } => {
+                match catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+                    Ok(poll) => poll,
+                    Err(payload) => Poll::Ready(Ok(panic_response(provider, payload))),
+                }
+            }
+            KindProj::Panicked { response } => Poll::Ready(Ok(response
+                .take()
+                .expect("response future polled after completion"))),
+        }
+    }
+}
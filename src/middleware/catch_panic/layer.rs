@@ -0,0 +1,26 @@
+use tower::Layer;
+
+use super::service::CatchPanic;
+
+/// Converts panics of the inner service into a `500 Internal Server Error`.
+///
+/// The panic message is only part of the response if the error verbosity is full.
+/// Every caught panic is logged and counted by the [`PanicCounter`](crate::catch_panic::PanicCounter).
+#[derive(Debug, Clone)]
+pub struct CatchPanicLayer<P> {
+    provider: P,
+}
+
+impl<P> CatchPanicLayer<P> {
+    pub fn new(provider: P) -> Self {
+        CatchPanicLayer { provider }
+    }
+}
+
+impl<S, P: Clone> Layer<S> for CatchPanicLayer<P> {
+    type Service = CatchPanic<S, P>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        CatchPanic::new(service, self.provider.clone())
+    }
+}
@@ -0,0 +1,46 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    task::{Context, Poll},
+};
+
+use axum::body::Body as AxumBody;
+use http::{Request, Response};
+use tower::Service;
+
+use crate::{catch_panic::PanicCounterProvider, error::ErrorVerbosityProvider};
+
+use super::future::ResponseFuture;
+
+#[derive(Debug, Clone)]
+pub struct CatchPanic<T, P> {
+    inner: T,
+    provider: P,
+}
+
+impl<T, P> CatchPanic<T, P> {
+    pub fn new(inner: T, provider: P) -> Self {
+        CatchPanic { inner, provider }
+    }
+}
+
+impl<S, ReqBody, P> Service<Request<ReqBody>> for CatchPanic<S, P>
+where
+    P: ErrorVerbosityProvider + PanicCounterProvider + Clone,
+    S: Service<Request<ReqBody>, Response = Response<AxumBody>>,
+{
+    type Response = Response<AxumBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future, P>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // Services may panic before returning their future.
+        match catch_unwind(AssertUnwindSafe(|| self.inner.call(request))) {
+            Ok(future) => ResponseFuture::future(future, self.provider.clone()),
+            Err(payload) => ResponseFuture::panicked(&self.provider, payload),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use axum::body::Body as AxumBody;
+use http::Response;
+use pin_project_lite::pin_project;
+
+use crate::concurrency_limit::ConcurrencyPermit;
+
+pin_project! {
+    pub struct ResponseFuture<F> {
+        #[pin]
+        kind: Kind<F>,
+    }
+}
+
+impl<F> ResponseFuture<F> {
+    pub fn called(future: F, permit: ConcurrencyPermit) -> Self {
+        Self {
+            kind: Kind::Called {
+                future,
+                permit: Some(permit),
+            },
+        }
+    }
+
+    pub fn rejected(response: Response<AxumBody>) -> Self {
+        Self {
+            kind: Kind::Rejected {
+                response: Some(response),
+            },
+        }
+    }
+}
+
+pin_project! {
+    #[project = KindProj]
+    enum Kind<F> {
+        Called {
+            #[pin]
+            future: F,
+            permit: Option<ConcurrencyPermit>,
+        },
+        Rejected {
+            response: Option<Response<AxumBody>>,
+        },
+    }
+}
+
+impl<F, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response<AxumBody>, E>>,
+{
+    type Output = Result<Response<AxumBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().kind.project() {
+            KindProj::Called { future, permit } => {
+                let response = ready!(future.poll(cx));
+
+                // The slots are released as soon as the inner service responded.
+                permit.take();
+
+                Poll::Ready(response)
+            }
+            KindProj::Rejected { response } => {
+                Poll::Ready(Ok(response.take().expect("future polled after completion")))
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use tower::Layer;
+
+use crate::concurrency_limit::ConcurrencyLimiter;
+
+use super::service::ConcurrencyLimit;
+
+/// Sheds requests with a `503 Service Unavailable` once a concurrency limit is reached.
+#[derive(Clone)]
+pub struct ConcurrencyLimitLayer<P> {
+    provider: P,
+    limiter: Arc<ConcurrencyLimiter>,
+}
+
+impl<P> ConcurrencyLimitLayer<P> {
+    pub fn new(provider: P, limiter: ConcurrencyLimiter) -> Self {
+        ConcurrencyLimitLayer {
+            provider,
+            limiter: Arc::new(limiter),
+        }
+    }
+}
+
+impl<S, P: Clone> Layer<S> for ConcurrencyLimitLayer<P> {
+    type Service = ConcurrencyLimit<S, P>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        ConcurrencyLimit::new(service, self.provider.clone(), self.limiter.clone())
+    }
+}
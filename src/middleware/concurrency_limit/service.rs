@@ -0,0 +1,63 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{body::Body as AxumBody, response::IntoResponse};
+use http::{Request, Response};
+use tower::Service;
+
+use crate::{
+    concurrency_limit::ConcurrencyLimiter,
+    error::{ApiError, ErrorVerbosityProvider, ServiceUnavailableError},
+};
+
+use super::future::ResponseFuture;
+
+/// Takes the slots of the request before calling the inner service and releases them once it responded.
+#[derive(Clone)]
+pub struct ConcurrencyLimit<T, P> {
+    inner: T,
+    provider: P,
+    limiter: Arc<ConcurrencyLimiter>,
+}
+
+impl<T, P> ConcurrencyLimit<T, P> {
+    pub fn new(inner: T, provider: P, limiter: Arc<ConcurrencyLimiter>) -> Self {
+        ConcurrencyLimit {
+            inner,
+            provider,
+            limiter,
+        }
+    }
+}
+
+impl<S, ReqBody, P> Service<Request<ReqBody>> for ConcurrencyLimit<S, P>
+where
+    P: ErrorVerbosityProvider,
+    S: Service<Request<ReqBody>, Response = Response<AxumBody>>,
+{
+    type Response = Response<AxumBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        match self.limiter.try_acquire(request.uri().path()) {
+            Ok(permit) => ResponseFuture::called(self.inner.call(request), permit),
+            Err(overloaded) => {
+                tracing::warn!(path = %request.uri().path(), limit = overloaded.limit, "Rejection. Concurrency limit reached");
+
+                let api_error = ApiError::from(ServiceUnavailableError::overloaded(
+                    self.provider.error_verbosity(),
+                    overloaded,
+                ));
+
+                ResponseFuture::rejected(api_error.into_response())
+            }
+        }
+    }
+}
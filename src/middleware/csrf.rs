@@ -0,0 +1,86 @@
+use axum::{
+    extract::{Request, State},
+    http::header::{AUTHORIZATION, SET_COOKIE},
+    middleware::Next,
+    response::IntoResponse,
+};
+
+use crate::{
+    csrf::{is_unsafe_method, CsrfProvider, CsrfToken},
+    error::{ApiError, CsrfError, CsrfErrorType, ErrorVerbosityProvider},
+    extractor::cookie::parse_cookies,
+};
+
+/// Middleware to protect cookie authenticated requests against cross-site request forgery.
+///
+/// Issues a [`CsrfToken`] cookie if the request has none and puts the token as an extension for the next layers.
+/// Requests with unsafe methods must send the token of the cookie in the configured header.
+///
+/// Requests with a bearer token and requests to exempt paths are not checked,
+/// since browsers do not attach bearer tokens on their own.
+/// If CSRF protection is disabled, the request is passed through.
+pub async fn csrf<S: CsrfProvider + ErrorVerbosityProvider>(
+    State(state): State<S>,
+    mut req: Request,
+    next: Next,
+) -> Result<impl IntoResponse, ApiError> {
+    let Some(config) = state.csrf_config() else {
+        return Ok(next.run(req).await);
+    };
+
+    let verbosity = state.error_verbosity();
+
+    let (parts, body) = req.into_parts();
+    // An unreadable cookie header counts as a missing cookie, so it is only rejected for unsafe methods.
+    let cookie_token = parse_cookies(&parts, verbosity)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|(name, _)| *name == config.cookie_name)
+        .map(|(_, value)| CsrfToken::new(value));
+    req = Request::from_parts(parts, body);
+
+    let is_bearer = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("Bearer "));
+
+    if is_unsafe_method(req.method()) && !is_bearer && !config.is_exempt(req.uri().path()) {
+        let header_token = req
+            .headers()
+            .get(&config.header_name)
+            .and_then(|value| value.to_str().ok());
+
+        let r#type = match (&cookie_token, header_token) {
+            (None, _) => Some(CsrfErrorType::MissingCookie),
+            (Some(_), None) => Some(CsrfErrorType::MissingHeader),
+            (Some(cookie_token), Some(header_token)) if !cookie_token.matches(header_token) => {
+                Some(CsrfErrorType::Mismatch)
+            }
+            _ => None,
+        };
+
+        if let Some(r#type) = r#type {
+            tracing::warn!(error_type = ?r#type, "Rejection. CSRF check failed");
+
+            return Err(CsrfError::new(verbosity, r#type).into());
+        }
+    }
+
+    let (token, issued) = match cookie_token {
+        Some(token) => (token, false),
+        None => (CsrfToken::generate(), true),
+    };
+
+    req.extensions_mut().insert(token.clone());
+
+    let mut response = next.run(req).await;
+
+    if issued {
+        if let Some(cookie) = config.cookie(&token) {
+            response.headers_mut().append(SET_COOKIE, cookie);
+        }
+    }
+
+    Ok(response)
+}
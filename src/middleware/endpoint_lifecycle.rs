@@ -0,0 +1,62 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::lifecycle::{fmt_http_date, EndpointLifecycle, EndpointLifecycleProvider};
+
+/// Middleware to emit the `Deprecation`, `Sunset` and `Link` headers for deprecated endpoints.
+///
+/// See [RFC 9745](https://www.rfc-editor.org/rfc/rfc9745) and [RFC 8594](https://www.rfc-editor.org/rfc/rfc8594).
+pub async fn endpoint_lifecycle<S: EndpointLifecycleProvider>(
+    State(state): State<S>,
+    matched_path: Option<MatchedPath>,
+    req: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let mut response = next.run(req).await;
+
+    let Some(matched_path) = matched_path else {
+        return response;
+    };
+
+    if let Some(EndpointLifecycle::Deprecated {
+        since,
+        sunset,
+        successor,
+    }) = state.endpoint_lifecycle(matched_path.as_str())
+    {
+        tracing::debug!(path = %matched_path.as_str(), "Deprecated endpoint called");
+
+        insert_header(
+            &mut response,
+            "Deprecation",
+            format!("@{}", since.timestamp()),
+        );
+
+        if let Some(sunset) = sunset {
+            insert_header(&mut response, "Sunset", fmt_http_date(sunset));
+        }
+
+        if let Some(successor) = successor {
+            insert_header(
+                &mut response,
+                "Link",
+                format!("<{successor}>; rel=\"successor-version\""),
+            );
+        }
+    }
+
+    response
+}
+
+fn insert_header(response: &mut Response, name: &'static str, value: String) {
+    match HeaderValue::from_str(&value) {
+        Ok(value) => {
+            response.headers_mut().append(name, value);
+        }
+        Err(err) => tracing::warn!(%err, %name, "Invalid header value"),
+    }
+}
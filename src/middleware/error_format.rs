@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::header::ACCEPT,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::{
+    error_page::DEFAULT_TEMPLATE,
+    problem_details::{ErrorFormat, ErrorFormatContext, ErrorFormatProvider},
+};
+
+/// Middleware to negotiate the [`ErrorFormat`](crate::problem_details::ErrorFormat) of the error responses.
+///
+/// The errors of the inner services are rendered in the negotiated format.
+pub async fn error_format<S: ErrorFormatProvider>(
+    State(state): State<S>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let config = state.error_format_config();
+
+    let accept = req
+        .headers()
+        .get(ACCEPT)
+        .and_then(|value| value.to_str().ok());
+
+    let format = config.negotiate(accept);
+
+    let html_template = (format == ErrorFormat::Html).then(|| {
+        let template = config
+            .html
+            .as_ref()
+            .map_or(DEFAULT_TEMPLATE, |html| html.template());
+
+        Arc::from(template)
+    });
+
+    let context = ErrorFormatContext {
+        format,
+        problem_type_base_uri: Arc::from(config.problem_type_base_uri.as_str()),
+        instance: req.uri().path().to_owned(),
+        html_template,
+        expected_schema: config.expected_schema.clone(),
+    };
+
+    context.scope(next.run(req)).await
+}
@@ -0,0 +1,26 @@
+use axum::{
+    extract::{Request, State},
+    http::header::ACCEPT_LANGUAGE,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::locale::{LocaleCatalogProvider, LocalizedMessages};
+
+/// Middleware to negotiate the [`LocalizedMessages`] of the request.
+///
+/// The errors of the inner services are rendered with the messages of the negotiated locale.
+pub async fn error_locale<S: LocaleCatalogProvider>(
+    State(state): State<S>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let accept_language = req
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok());
+
+    LocalizedMessages::negotiate(state.locale_catalog(), accept_language)
+        .scope(next.run(req))
+        .await
+}
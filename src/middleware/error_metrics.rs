@@ -0,0 +1,22 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::error_metrics::{CountedError, ErrorCounterProvider};
+
+/// Middleware to count the errors of the inner services with the [`ErrorCounter`](crate::error_metrics::ErrorCounter).
+pub async fn error_metrics<S: ErrorCounterProvider>(
+    State(state): State<S>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(req).await;
+
+    if let Some(error) = response.extensions_mut().remove::<CountedError>() {
+        state.error_counter().increment(error);
+    }
+
+    response
+}
@@ -0,0 +1,45 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use chrono::Utc;
+
+use crate::{
+    error_sink::{ErrorReport, ErrorReportingProvider, ReportedError},
+    request_id::RequestId,
+};
+
+/// Middleware to report the server errors of the inner services to the [`ErrorReportingProvider`].
+pub async fn error_reporting<S: ErrorReportingProvider>(
+    State(state): State<S>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().to_string();
+    let path = req.uri().path().to_owned();
+    let request_id = req.extensions().get::<RequestId>().map(ToString::to_string);
+
+    let mut response = next.run(req).await;
+
+    let Some(error) = response.extensions_mut().remove::<ReportedError>() else {
+        return response;
+    };
+
+    if state.reports_error(&error) {
+        state.report_error(ErrorReport {
+            timestamp: Utc::now(),
+            request_id,
+            method,
+            path,
+            status: error.status.as_u16(),
+            error_type: error.error_type,
+            error_code: error.error_code,
+            message: error.message,
+            error: error.error,
+            cause: error.cause,
+        });
+    }
+
+    response
+}
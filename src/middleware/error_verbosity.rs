@@ -0,0 +1,20 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::verbosity_policy::VerbosityPolicyProvider;
+
+/// Middleware to resolve the [`RouteVerbosity`](crate::verbosity_policy::RouteVerbosity) of the request.
+///
+/// The errors of the inner services are created and rendered with the verbosity of the route.
+pub async fn error_verbosity<S: VerbosityPolicyProvider>(
+    State(state): State<S>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let route_verbosity = state.verbosity_policy().for_path(req.uri().path());
+
+    route_verbosity.scope(next.run(req)).await
+}
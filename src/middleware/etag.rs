@@ -0,0 +1,80 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_LOCATION, DATE, ETAG, EXPIRES, IF_NONE_MATCH, VARY},
+        HeaderMap, HeaderValue, Method, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider},
+    etag::{strong_etag, ETagProvider},
+    extractor::conditional::EntityTagCondition,
+};
+
+use super::buffered_body::buffer_body;
+
+/// Middleware to add a strong ETag to successful responses of the configured routes.
+///
+/// Answers requests whose `If-None-Match` matches the ETag with `304 Not Modified`.
+/// Responses that already have an ETag are passed through.
+///
+/// This middleware reads the entire response body.
+/// The body is shared with the other middlewares reading it, e.g. [`response_schema_validation`](super::response_schema_validation::response_schema_validation).
+pub async fn etag<S: ETagProvider + ErrorVerbosityProvider>(
+    State(state): State<S>,
+    req: Request,
+    next: Next,
+) -> Result<impl IntoResponse, ApiError> {
+    let applies = state
+        .etag_config()
+        .is_some_and(|config| config.applies_to(req.uri().path()));
+
+    if !applies || !(req.method() == Method::GET || req.method() == Method::HEAD) {
+        return Ok(next.run(req).await);
+    }
+
+    let if_none_match = req
+        .headers()
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(EntityTagCondition::parse);
+
+    let response = next.run(req).await;
+
+    if response.status() != StatusCode::OK || response.headers().contains_key(ETAG) {
+        return Ok(response);
+    }
+
+    let (mut parts, bytes) = buffer_body(response, state.error_verbosity()).await?;
+
+    let etag = strong_etag(&bytes);
+    parts.headers.insert(
+        ETAG,
+        HeaderValue::from_str(&etag).expect("etag is a valid header value"),
+    );
+
+    if if_none_match.is_some_and(|condition| condition.matches_weak(&etag)) {
+        tracing::trace!(%etag, "Not modified");
+
+        return Ok(not_modified(&parts.headers));
+    }
+
+    Ok(Response::from_parts(parts, Body::from(bytes)))
+}
+
+/// Returns a `304 Not Modified` with the headers RFC 9110 section 15.4.5 requires.
+fn not_modified(headers: &HeaderMap) -> Response {
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+
+    for name in [ETAG, CACHE_CONTROL, CONTENT_LOCATION, DATE, EXPIRES, VARY] {
+        if let Some(value) = headers.get(&name) {
+            response.headers_mut().insert(name, value.clone());
+        }
+    }
+
+    response
+}
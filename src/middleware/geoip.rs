@@ -0,0 +1,67 @@
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts, Request, State},
+    middleware::Next,
+    response::IntoResponse,
+};
+use tracing::Instrument;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, GeoIpError, GeoIpErrorType},
+    extractor::client_ip::{ApiClientIp, TrustedProxiesProvider},
+    geoip::GeoIpProvider,
+};
+
+/// Resolves the client IP and puts the [`GeoIpInfo`](crate::geoip::GeoIpInfo) as an extension for the next layers.
+///
+/// The client IP is taken from the forwarding headers of trusted proxies, see [`ApiClientIp`],
+/// falling back to the peer address.
+/// Requests from blocked countries are rejected.
+/// If GeoIP enrichment is disabled, the request is passed through.
+pub async fn geoip<S>(
+    State(state): State<S>,
+    req: Request,
+    next: Next,
+) -> Result<impl IntoResponse, ApiError>
+where
+    S: GeoIpProvider + TrustedProxiesProvider + ErrorVerbosityProvider + Send + Sync,
+{
+    let (mut parts, body) = req.into_parts();
+
+    let peer = parts
+        .extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+
+    let ip = match ApiClientIp::from_request_parts(&mut parts, &state).await {
+        Ok(ApiClientIp(ip)) => Some(ip),
+        Err(_) => peer,
+    };
+
+    let mut req = Request::from_parts(parts, body);
+
+    let Some((ip, geoip_info)) = ip.and_then(|ip| Some((ip, state.lookup(ip)?))) else {
+        return Ok(next.run(req).await);
+    };
+
+    let span = tracing::info_span!(
+        "geoip",
+        country = geoip_info.country.as_deref(),
+        asn = geoip_info.asn
+    );
+
+    if let Some(country) = geoip_info.country.as_deref() {
+        if state.is_country_blocked(country) {
+            span.in_scope(|| tracing::warn!(%ip, "Rejection. Country is blocked"));
+
+            return Err(
+                GeoIpError::new(state.error_verbosity(), GeoIpErrorType::CountryBlocked).into(),
+            );
+        }
+    }
+
+    req.extensions_mut().insert(geoip_info);
+
+    Ok(next.run(req).instrument(span).await)
+}
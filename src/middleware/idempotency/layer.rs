@@ -0,0 +1,53 @@
+use std::{sync::Arc, time::Duration};
+
+use tower::Layer;
+
+use super::service::IdempotencyService;
+
+/// Stores the responses of `POST` and `PATCH` requests with an `Idempotency-Key` and replays them for retries.
+///
+/// Responses with a server error are not stored, so the request can be retried.
+/// See [`idempotency`](crate::idempotency).
+#[derive(Debug)]
+pub struct IdempotencyLayer<P, St> {
+    provider: P,
+    store: Arc<St>,
+    time_to_live: Duration,
+    in_progress_wait: Duration,
+}
+
+impl<P, St> IdempotencyLayer<P, St> {
+    pub fn new(provider: P, store: St, time_to_live: Duration, in_progress_wait: Duration) -> Self {
+        IdempotencyLayer {
+            provider,
+            store: Arc::new(store),
+            time_to_live,
+            in_progress_wait,
+        }
+    }
+}
+
+impl<P: Clone, St> Clone for IdempotencyLayer<P, St> {
+    fn clone(&self) -> Self {
+        IdempotencyLayer {
+            provider: self.provider.clone(),
+            store: self.store.clone(),
+            time_to_live: self.time_to_live,
+            in_progress_wait: self.in_progress_wait,
+        }
+    }
+}
+
+impl<I, P: Clone, St> Layer<I> for IdempotencyLayer<P, St> {
+    type Service = IdempotencyService<I, P, St>;
+
+    fn layer(&self, inner: I) -> Self::Service {
+        IdempotencyService::new(
+            inner,
+            self.provider.clone(),
+            self.store.clone(),
+            self.time_to_live,
+            self.in_progress_wait,
+        )
+    }
+}
@@ -0,0 +1,298 @@
+use std::{
+    convert::Infallible,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use futures::future::BoxFuture;
+use tokio::time::Instant;
+use tower::Service;
+
+use crate::{
+    error::{
+        ApiError, ErrorVerbosityProvider, IdempotencyError, IdempotencyErrorType,
+        PayloadTooLargeError,
+    },
+    extractor::body::BodyLimitProvider,
+    idempotency::{
+        idempotency_digest, is_idempotency_method, is_valid_idempotency_key, IdempotencyRecord,
+        IdempotencyScopeProvider, IdempotencyStore, StoredResponse, IDEMPOTENCY_KEY_HEADER,
+        IDEMPOTENT_REPLAYED_HEADER,
+    },
+    middleware::buffered_body::buffer_body,
+    state::PrivateErrorVerbosity,
+};
+
+/// How often a retry checks whether the request with the same key has finished.
+const IN_PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug)]
+pub struct IdempotencyService<I, P, St> {
+    inner: I,
+    provider: P,
+    store: Arc<St>,
+    time_to_live: Duration,
+    in_progress_wait: Duration,
+}
+
+impl<I, P, St> IdempotencyService<I, P, St> {
+    pub fn new(
+        inner: I,
+        provider: P,
+        store: Arc<St>,
+        time_to_live: Duration,
+        in_progress_wait: Duration,
+    ) -> Self {
+        IdempotencyService {
+            inner,
+            provider,
+            store,
+            time_to_live,
+            in_progress_wait,
+        }
+    }
+}
+
+impl<I: Clone, P: Clone, St> Clone for IdempotencyService<I, P, St> {
+    fn clone(&self) -> Self {
+        IdempotencyService {
+            inner: self.inner.clone(),
+            provider: self.provider.clone(),
+            store: self.store.clone(),
+            time_to_live: self.time_to_live,
+            in_progress_wait: self.in_progress_wait,
+        }
+    }
+}
+
+/// Releases the reserved key if the request is dropped before its response is stored, e.g. if the client disconnects.
+struct Reservation<St: IdempotencyStore + Send + Sync + 'static> {
+    store: Arc<St>,
+    key: Option<String>,
+}
+
+impl<St: IdempotencyStore + Send + Sync + 'static> Reservation<St> {
+    fn disarm(mut self) -> String {
+        self.key.take().expect("reservation is only disarmed once")
+    }
+}
+
+impl<St: IdempotencyStore + Send + Sync + 'static> Drop for Reservation<St> {
+    fn drop(&mut self) {
+        let Some(key) = self.key.take() else {
+            return;
+        };
+
+        let store = self.store.clone();
+
+        tokio::spawn(async move {
+            if store.release(&key).await.is_err() {
+                tracing::error!("Failed to release idempotency key");
+            }
+        });
+    }
+}
+
+fn replay(response: &StoredResponse, verbosity: PrivateErrorVerbosity) -> Response {
+    let body = match response.body_bytes() {
+        Ok(body) => body,
+        Err(err) => return ApiError::from_generic_error(verbosity, err).into_response(),
+    };
+
+    let mut replayed = Response::new(Body::from(body));
+    *replayed.status_mut() = StatusCode::from_u16(response.status).unwrap_or(StatusCode::OK);
+
+    for (name, value) in &response.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::try_from(name.as_str()),
+            HeaderValue::try_from(value.as_str()),
+        ) {
+            replayed.headers_mut().append(name, value);
+        }
+    }
+
+    replayed
+        .headers_mut()
+        .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+
+    replayed
+}
+
+impl<I, P, St> Service<Request> for IdempotencyService<I, P, St>
+where
+    I: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    I::Future: Send,
+    P: ErrorVerbosityProvider
+        + BodyLimitProvider
+        + IdempotencyScopeProvider
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    St: IdempotencyStore + Send + Sync + 'static,
+    St::Error: Into<anyhow::Error> + Send,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Take the service that was driven to readiness.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let provider = self.provider.clone();
+        let store = self.store.clone();
+        let time_to_live = self.time_to_live;
+        let in_progress_wait = self.in_progress_wait;
+
+        Box::pin(async move {
+            let key = request.headers().get(IDEMPOTENCY_KEY_HEADER).cloned();
+
+            let Some(key) = key.filter(|_| is_idempotency_method(request.method())) else {
+                return inner.call(request).await;
+            };
+
+            let verbosity = provider.error_verbosity();
+
+            let Some(key) = key
+                .to_str()
+                .ok()
+                .filter(|key| is_valid_idempotency_key(key))
+            else {
+                tracing::warn!("Rejection. Invalid Idempotency-Key header");
+
+                return Ok(ApiError::from(IdempotencyError::new(
+                    verbosity,
+                    IdempotencyErrorType::InvalidKey,
+                ))
+                .into_response());
+            };
+
+            let (parts, body) = request.into_parts();
+
+            let Some(scope) = provider.idempotency_scope(&parts) else {
+                tracing::debug!("Request without idempotency scope is not handled idempotently");
+
+                return inner.call(Request::from_parts(parts, body)).await;
+            };
+
+            let limit = provider.max_body_size_in_bytes();
+            let Ok(body) = axum::body::to_bytes(body, limit).await else {
+                return Ok(
+                    ApiError::from(PayloadTooLargeError::new(verbosity, limit)).into_response()
+                );
+            };
+
+            let path = parts
+                .uri
+                .path_and_query()
+                .map_or(parts.uri.path(), |path_and_query| path_and_query.as_str());
+
+            let fingerprint =
+                idempotency_digest([parts.method.as_str().as_bytes(), path.as_bytes(), &body]);
+            let key = idempotency_digest([scope.as_bytes(), key.as_bytes()]);
+
+            let internal_error =
+                |err: St::Error| Ok(ApiError::from_generic_error(verbosity, err).into_response());
+
+            let deadline = Instant::now() + in_progress_wait;
+
+            let mut existing = match store.reserve(&key, &fingerprint, time_to_live).await {
+                Ok(existing) => existing,
+                Err(err) => return internal_error(err),
+            };
+
+            while let Some(record) = existing {
+                if record.fingerprint() != fingerprint {
+                    tracing::warn!("Rejection. Idempotency-Key reused for a different request");
+
+                    return Ok(ApiError::from(IdempotencyError::new(
+                        verbosity,
+                        IdempotencyErrorType::KeyReused,
+                    ))
+                    .into_response());
+                }
+
+                match record {
+                    IdempotencyRecord::Completed { response, .. } => {
+                        tracing::debug!("Replaying stored response");
+
+                        return Ok(replay(&response, verbosity));
+                    }
+                    IdempotencyRecord::InProgress { .. } if Instant::now() < deadline => {
+                        tokio::time::sleep(IN_PROGRESS_POLL_INTERVAL).await;
+
+                        // The key is reserved again if the first request was released.
+                        existing = match store.load(&key).await {
+                            Ok(Some(record)) => Some(record),
+                            Ok(None) => match store.reserve(&key, &fingerprint, time_to_live).await
+                            {
+                                Ok(existing) => existing,
+                                Err(err) => return internal_error(err),
+                            },
+                            Err(err) => return internal_error(err),
+                        };
+                    }
+                    IdempotencyRecord::InProgress { .. } => {
+                        tracing::warn!("Rejection. Idempotency-Key is in progress");
+
+                        return Ok(ApiError::from(IdempotencyError::new(
+                            verbosity,
+                            IdempotencyErrorType::InProgress,
+                        ))
+                        .into_response());
+                    }
+                }
+            }
+
+            let reservation = Reservation {
+                store: store.clone(),
+                key: Some(key),
+            };
+
+            let response = inner
+                .call(Request::from_parts(parts, Body::from(body)))
+                .await?;
+
+            // Server errors are not stored, so the request can be retried. The reservation is released on drop.
+            if response.status().is_server_error() {
+                return Ok(response);
+            }
+
+            let (parts, body) = match buffer_body(response, verbosity).await {
+                Ok(buffered) => buffered,
+                Err(api_error) => return Ok(api_error.into_response()),
+            };
+
+            let key = reservation.disarm();
+            let stored = StoredResponse::new(parts.status, &parts.headers, &body);
+
+            if let Err(err) = store
+                .complete(&key, &fingerprint, stored, time_to_live)
+                .await
+            {
+                let err: anyhow::Error = err.into();
+                tracing::error!(%err, "Failed to store idempotent response");
+
+                // Retries are processed again instead of waiting for a response that is never stored.
+                if let Err(err) = store.release(&key).await {
+                    let err: anyhow::Error = err.into();
+                    tracing::error!(%err, "Failed to release idempotency key");
+                }
+            }
+
+            Ok(Response::from_parts(parts, Body::from(body)))
+        })
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use tower::Layer;
+
+use crate::ip_filter::IpFilterConfig;
+
+use super::service::IpFilter;
+
+/// Rejects requests whose client IP is denied with a `403 Forbidden`.
+///
+/// The client IP is resolved like [`ApiClientIp`](crate::extractor::client_ip::ApiClientIp)
+/// and inserted into the request extensions.
+#[derive(Debug, Clone)]
+pub struct IpFilterLayer<P> {
+    provider: P,
+    config: Arc<IpFilterConfig>,
+}
+
+impl<P> IpFilterLayer<P> {
+    pub fn new(provider: P, config: IpFilterConfig) -> Self {
+        IpFilterLayer {
+            provider,
+            config: Arc::new(config),
+        }
+    }
+}
+
+impl<S, P: Clone> Layer<S> for IpFilterLayer<P> {
+    type Service = IpFilter<S, P>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        IpFilter::new(service, self.provider.clone(), self.config.clone())
+    }
+}
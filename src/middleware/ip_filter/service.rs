@@ -0,0 +1,77 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{body::Body as AxumBody, extract::FromRequestParts};
+use http::{Request, Response};
+use tower::Service;
+
+use crate::{
+    error::{ErrorVerbosityProvider, IpFilterError},
+    extractor::client_ip::{ApiClientIp, TrustedProxiesProvider},
+    ip_filter::IpFilterConfig,
+    middleware::auth_future::ResponseFuture,
+};
+
+/// Resolves the client IP and checks it against the allow and deny lists.
+#[derive(Debug, Clone)]
+pub struct IpFilter<T, P> {
+    inner: T,
+    provider: P,
+    config: Arc<IpFilterConfig>,
+}
+
+impl<T, P> IpFilter<T, P> {
+    pub fn new(inner: T, provider: P, config: Arc<IpFilterConfig>) -> Self {
+        IpFilter {
+            inner,
+            provider,
+            config,
+        }
+    }
+}
+
+impl<S, ReqBody, P> Service<Request<ReqBody>> for IpFilter<S, P>
+where
+    P: TrustedProxiesProvider + ErrorVerbosityProvider + Send + Sync + Clone + 'static,
+    S: Service<Request<ReqBody>, Response = Response<AxumBody>> + Clone + Send,
+{
+    type Response = Response<AxumBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S, ReqBody, ApiClientIp>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // The inner service is called after the check, so the service that was polled ready is moved into the future.
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+
+        let (mut parts, body) = request.into_parts();
+        let provider = self.provider.clone();
+        let config = self.config.clone();
+
+        let filtering = Box::pin(async move {
+            let client_ip = match ApiClientIp::from_request_parts(&mut parts, &provider).await {
+                Ok(client_ip) => client_ip,
+                Err(api_error) => return (parts, Err(api_error)),
+            };
+
+            let checked = config
+                .check(&client_ip.0)
+                .map(|_| client_ip)
+                .map_err(|rule| {
+                    tracing::warn!(ip = %client_ip.0, %rule, "Rejection. Client IP is denied");
+
+                    IpFilterError::new(provider.error_verbosity(), client_ip.0, rule).into()
+                });
+
+            (parts, checked)
+        });
+
+        ResponseFuture::new(filtering, inner, body)
+    }
+}
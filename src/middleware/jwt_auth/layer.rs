@@ -0,0 +1,54 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use tower::Layer;
+
+use super::service::JwtAuth;
+
+/// Validates the bearer JWT of requests before they reach the inner service.
+///
+/// The claims `C` are inserted into the request extensions, so handlers can use `Extension<C>`.
+/// Nested layers reuse the claims of an outer layer instead of validating the token again.
+#[derive(Debug)]
+pub struct JwtAuthLayer<P, C> {
+    provider: P,
+    required_roles: Arc<[String]>,
+    _claims: PhantomData<fn() -> C>,
+}
+
+impl<P, C> JwtAuthLayer<P, C> {
+    pub fn new(provider: P) -> Self {
+        JwtAuthLayer {
+            provider,
+            required_roles: Arc::from([]),
+            _claims: PhantomData,
+        }
+    }
+
+    /// Rejects requests whose claims do not grant all the roles or scopes.
+    pub fn require_roles<I, R>(mut self, roles: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<String>,
+    {
+        self.required_roles = roles.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+impl<P: Clone, C> Clone for JwtAuthLayer<P, C> {
+    fn clone(&self) -> Self {
+        JwtAuthLayer {
+            provider: self.provider.clone(),
+            required_roles: self.required_roles.clone(),
+            _claims: PhantomData,
+        }
+    }
+}
+
+impl<S, P: Clone, C> Layer<S> for JwtAuthLayer<P, C> {
+    type Service = JwtAuth<S, P, C>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        JwtAuth::new(service, self.provider.clone(), self.required_roles.clone())
+    }
+}
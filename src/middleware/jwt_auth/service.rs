@@ -0,0 +1,116 @@
+use std::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{body::Body as AxumBody, extract::FromRequestParts};
+use http::{Request, Response};
+use serde::de::DeserializeOwned;
+use tower::Service;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, JwtError, JwtErrorType},
+    extractor::{
+        jwt::{ApiJwt, JwksProvider},
+        jwt_roles::HasRoles,
+    },
+    middleware::auth_future::ResponseFuture,
+    revocation::TokenRevocationProvider,
+};
+
+/// Validates the bearer JWT of the request, enforces the required roles and puts the claims as an extension for the inner service.
+#[derive(Debug)]
+pub struct JwtAuth<T, P, C> {
+    inner: T,
+    provider: P,
+    required_roles: Arc<[String]>,
+    _claims: PhantomData<fn() -> C>,
+}
+
+impl<T, P, C> JwtAuth<T, P, C> {
+    pub fn new(inner: T, provider: P, required_roles: Arc<[String]>) -> Self {
+        JwtAuth {
+            inner,
+            provider,
+            required_roles,
+            _claims: PhantomData,
+        }
+    }
+}
+
+impl<T: Clone, P: Clone, C> Clone for JwtAuth<T, P, C> {
+    fn clone(&self) -> Self {
+        JwtAuth {
+            inner: self.inner.clone(),
+            provider: self.provider.clone(),
+            required_roles: self.required_roles.clone(),
+            _claims: PhantomData,
+        }
+    }
+}
+
+impl<S, ReqBody, P, C> Service<Request<ReqBody>> for JwtAuth<S, P, C>
+where
+    P: JwksProvider
+        + TokenRevocationProvider
+        + ErrorVerbosityProvider
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+    <P as JwksProvider>::Error: Into<anyhow::Error> + Display,
+    <P as TokenRevocationProvider>::Error: Into<anyhow::Error> + Display,
+    C: DeserializeOwned + HasRoles + Debug + Clone + Send + Sync + 'static,
+    S: Service<Request<ReqBody>, Response = Response<AxumBody>> + Clone + Send,
+{
+    type Response = Response<AxumBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S, ReqBody, C>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // The inner service is called after the validation, so the service that was polled ready is moved into the future.
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+
+        let (mut parts, body) = request.into_parts();
+        let provider = self.provider.clone();
+        let required_roles = self.required_roles.clone();
+
+        let authentication = Box::pin(async move {
+            let claims = match parts.extensions.get::<C>() {
+                Some(claims) => Ok(claims.clone()),
+                None => ApiJwt::<C>::from_request_parts(&mut parts, &provider)
+                    .await
+                    .map(|ApiJwt(claims)| claims),
+            };
+
+            let claims = claims.and_then(|claims| {
+                let missing_roles = required_roles
+                    .iter()
+                    .filter(|role| !claims.has_role(role))
+                    .collect::<Vec<_>>();
+
+                if !missing_roles.is_empty() {
+                    tracing::warn!(?missing_roles, "Rejection. Missing roles");
+
+                    return Err(ApiError::from(JwtError::new(
+                        provider.error_verbosity(),
+                        JwtErrorType::Forbidden,
+                    )));
+                }
+
+                Ok(claims)
+            });
+
+            (parts, claims)
+        });
+
+        ResponseFuture::new(authentication, inner, body)
+    }
+}
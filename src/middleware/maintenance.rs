@@ -0,0 +1,36 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::IntoResponse,
+};
+
+use crate::{
+    error::{
+        ApiError, ErrorVerbosityProvider, ServiceUnavailableError, ServiceUnavailableErrorType,
+    },
+    maintenance::MaintenanceModeProvider,
+};
+
+/// Middleware to reject requests with a `503 Service Unavailable` while the maintenance mode is enabled.
+///
+/// Requests to the allowed paths are passed through.
+pub async fn maintenance<S: MaintenanceModeProvider + ErrorVerbosityProvider>(
+    State(state): State<S>,
+    req: Request,
+    next: Next,
+) -> Result<impl IntoResponse, ApiError> {
+    let maintenance_mode = state.maintenance_mode();
+
+    if maintenance_mode.is_enabled() && !maintenance_mode.allows(req.uri().path()) {
+        tracing::warn!(path = %req.uri().path(), "Rejection. Maintenance mode is enabled");
+
+        return Err(ServiceUnavailableError::new(
+            state.error_verbosity(),
+            ServiceUnavailableErrorType::Maintenance,
+            maintenance_mode.retry_after(),
+        )
+        .into());
+    }
+
+    Ok(next.run(req).await)
+}
@@ -1,6 +1,34 @@
+pub mod api_key;
+pub mod audit;
+pub mod auth_future;
 pub mod basic_auth;
+pub mod body_limit;
+pub mod buffered_body;
+pub mod catch_panic;
+pub mod concurrency_limit;
+pub mod csrf;
+pub mod endpoint_lifecycle;
+pub mod error_format;
+pub mod error_locale;
+pub mod error_metrics;
+pub mod error_reporting;
+pub mod error_verbosity;
+pub mod etag;
+pub mod geoip;
+pub mod idempotency;
+pub mod ip_filter;
+pub mod jwt_auth;
+pub mod maintenance;
 pub mod method_not_allowed;
 pub mod not_found;
+pub mod path_prefix;
+pub mod rate_limit;
+pub mod request_id;
+pub mod response_body_trace;
+pub mod response_schema_validation;
+pub mod session;
+pub mod slow_request;
+pub mod timeout;
 pub mod trace_headers;
-pub mod trace_response_body;
-pub mod validate_api_key_and_put_as_extension;
+pub mod trace_request_body;
+pub mod usage_analytics;
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use tower::Layer;
+
+use super::service::PathPrefix;
+
+/// Applies the given layer only to the requests whose path starts with the prefix.
+///
+/// Other requests are passed to the inner service directly.
+#[derive(Debug, Clone)]
+pub struct PathPrefixLayer<L> {
+    prefix: Arc<str>,
+    layer: L,
+}
+
+impl<L> PathPrefixLayer<L> {
+    pub fn new(prefix: &str, layer: L) -> Self {
+        PathPrefixLayer {
+            prefix: Arc::from(prefix.trim_end_matches('/')),
+            layer,
+        }
+    }
+}
+
+impl<S: Clone, L: Layer<S>> Layer<S> for PathPrefixLayer<L> {
+    type Service = PathPrefix<S, L::Service>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        PathPrefix::new(
+            service.clone(),
+            self.layer.layer(service),
+            self.prefix.clone(),
+        )
+    }
+}
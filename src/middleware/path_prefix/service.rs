@@ -0,0 +1,64 @@
+use std::{
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+
+use axum::extract::OriginalUri;
+use futures::future::Either;
+use http::Request;
+use tower::Service;
+
+/// Calls the `matching` service for the requests whose path starts with the prefix and the `inner` service for the others.
+#[derive(Debug, Clone)]
+pub struct PathPrefix<S, T> {
+    inner: S,
+    matching: T,
+    prefix: Arc<str>,
+}
+
+impl<S, T> PathPrefix<S, T> {
+    pub fn new(inner: S, matching: T, prefix: Arc<str>) -> Self {
+        PathPrefix {
+            inner,
+            matching,
+            prefix,
+        }
+    }
+
+    /// Whether the path is the prefix itself or below it.
+    fn matches(&self, path: &str) -> bool {
+        path.strip_prefix(&*self.prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    }
+}
+
+impl<S, T, ReqBody> Service<Request<ReqBody>> for PathPrefix<S, T>
+where
+    S: Service<Request<ReqBody>>,
+    T: Service<Request<ReqBody>, Response = S::Response, Error = S::Error>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Either<T::Future, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.inner.poll_ready(cx))?;
+
+        self.matching.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // Nested routers strip their prefix from the uri.
+        let path = request
+            .extensions()
+            .get::<OriginalUri>()
+            .map_or(request.uri(), |OriginalUri(uri)| uri)
+            .path();
+
+        if self.matches(path) {
+            return Either::Left(self.matching.call(request));
+        }
+
+        Either::Right(self.inner.call(request))
+    }
+}
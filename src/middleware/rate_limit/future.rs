@@ -0,0 +1,107 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use axum::{body::Body as AxumBody, response::IntoResponse};
+use http::{request::Parts, Request, Response};
+use pin_project_lite::pin_project;
+use tower::Service;
+
+use crate::{error::ApiError, rate_limit::RateLimitStatus};
+
+/// Returns the request parts with the status of the limit, `None` if the request is not limited, or the rejection.
+type Limiting = Pin<
+    Box<dyn Future<Output = (Parts, Result<Option<RateLimitStatus>, ApiError>)> + Send + 'static>,
+>;
+
+pin_project! {
+    pub struct ResponseFuture<S, ReqBody>
+    where
+        S: Service<Request<ReqBody>>,
+    {
+        #[pin]
+        kind: Kind<S, ReqBody>,
+    }
+}
+
+impl<S, ReqBody> ResponseFuture<S, ReqBody>
+where
+    S: Service<Request<ReqBody>>,
+{
+    pub fn new(limiting: Limiting, inner: S, body: ReqBody) -> Self {
+        Self {
+            kind: Kind::Limiting {
+                limiting,
+                inner: Some(inner),
+                body: Some(body),
+            },
+        }
+    }
+}
+
+pin_project! {
+    #[project = KindProj]
+    enum Kind<S, ReqBody>
+    where
+        S: Service<Request<ReqBody>>,
+    {
+        Limiting {
+            limiting: Limiting,
+            inner: Option<S>,
+            body: Option<ReqBody>,
+        },
+        Calling {
+            #[pin]
+            future: S::Future,
+            status: Option<RateLimitStatus>,
+        },
+    }
+}
+
+impl<S, ReqBody> Future for ResponseFuture<S, ReqBody>
+where
+    S: Service<Request<ReqBody>, Response = Response<AxumBody>>,
+{
+    type Output = Result<Response<AxumBody>, S::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            match this.kind.as_mut().project() {
+                KindProj::Limiting {
+                    limiting,
+                    inner,
+                    body,
+                } => {
+                    let (parts, status) = ready!(limiting.as_mut().poll(cx));
+
+                    let status = match status {
+                        Ok(status) => status,
+                        Err(api_error) => return Poll::Ready(Ok(api_error.into_response())),
+                    };
+
+                    let body = body.take().expect("future polled after completion");
+
+                    let future = inner
+                        .take()
+                        .expect("future polled after completion")
+                        .call(Request::from_parts(parts, body));
+
+                    this.kind.set(Kind::Calling { future, status });
+                }
+                KindProj::Calling { future, status } => {
+                    let mut response = ready!(future.poll(cx))?;
+
+                    if let Some(status) = status {
+                        status.insert_headers(response.headers_mut());
+                    }
+
+                    return Poll::Ready(Ok(response));
+                }
+            }
+        }
+    }
+}
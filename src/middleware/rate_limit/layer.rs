@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use tower::Layer;
+
+use crate::rate_limit::RateLimiter;
+
+use super::service::RateLimit;
+
+/// Limits the request rate per key before requests reach the inner service.
+///
+/// The key of a request is resolved by the [`RateLimitKeyExtractor`](crate::rate_limit::RateLimitKeyExtractor) `K`.
+#[derive(Clone)]
+pub struct RateLimitLayer<P, K> {
+    provider: P,
+    limiter: Arc<RateLimiter>,
+    key_extractor: K,
+}
+
+impl<P, K> RateLimitLayer<P, K> {
+    pub fn new(provider: P, limiter: RateLimiter, key_extractor: K) -> Self {
+        RateLimitLayer {
+            provider,
+            limiter: Arc::new(limiter),
+            key_extractor,
+        }
+    }
+}
+
+impl<S, P: Clone, K: Clone> Layer<S> for RateLimitLayer<P, K> {
+    type Service = RateLimit<S, P, K>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        RateLimit::new(
+            service,
+            self.provider.clone(),
+            self.limiter.clone(),
+            self.key_extractor.clone(),
+        )
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::body::Body as AxumBody;
+use http::{HeaderMap, Request, Response};
+use tower::Service;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, TooManyRequestsError},
+    rate_limit::{RateLimitKeyExtractor, RateLimiter},
+};
+
+use super::future::ResponseFuture;
+
+/// Counts the request against the limit of its key and rejects it if the limit is exceeded.
+#[derive(Clone)]
+pub struct RateLimit<T, P, K> {
+    inner: T,
+    provider: P,
+    limiter: Arc<RateLimiter>,
+    key_extractor: K,
+}
+
+impl<T, P, K> RateLimit<T, P, K> {
+    pub fn new(inner: T, provider: P, limiter: Arc<RateLimiter>, key_extractor: K) -> Self {
+        RateLimit {
+            inner,
+            provider,
+            limiter,
+            key_extractor,
+        }
+    }
+}
+
+impl<S, ReqBody, P, K> Service<Request<ReqBody>> for RateLimit<S, P, K>
+where
+    P: ErrorVerbosityProvider + Send + Sync + Clone + 'static,
+    K: RateLimitKeyExtractor<P> + Send + Sync + Clone + 'static,
+    S: Service<Request<ReqBody>, Response = Response<AxumBody>> + Clone + Send,
+{
+    type Response = Response<AxumBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S, ReqBody>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // The inner service is called after the limit is checked, so the service that was polled ready is moved into the future.
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+
+        let (mut parts, body) = request.into_parts();
+        let provider = self.provider.clone();
+        let limiter = self.limiter.clone();
+        let key_extractor = self.key_extractor.clone();
+
+        let limiting = Box::pin(async move {
+            let Some(key) = key_extractor.rate_limit_key(&mut parts, &provider).await else {
+                return (parts, Ok(None));
+            };
+
+            let status = limiter.check(&key).map(Some).map_err(|exceeded| {
+                tracing::warn!(%key, retry_after = ?exceeded.retry_after, "Rejection. Rate limit exceeded");
+
+                let mut headers = HeaderMap::new();
+                exceeded.status.insert_headers(&mut headers);
+
+                ApiError::from(
+                    TooManyRequestsError::new(provider.error_verbosity(), exceeded)
+                        .with_headers(headers),
+                )
+            });
+
+            (parts, status)
+        });
+
+        ResponseFuture::new(limiting, inner, body)
+    }
+}
@@ -0,0 +1,22 @@
+use axum::{extract::Request, middleware::Next, response::Response};
+
+use crate::request_id::{RequestId, REQUEST_ID_HEADER};
+
+/// Middleware to propagate the `X-Request-Id` header or generate a new one.
+///
+/// The id is stored in the request extensions, set on the request header for the handlers and echoed in the response.
+pub async fn request_id(mut req: Request, next: Next) -> Response {
+    let request_id = RequestId::from_header(req.headers().get(REQUEST_ID_HEADER));
+
+    req.headers_mut()
+        .insert(REQUEST_ID_HEADER, request_id.header_value());
+    req.extensions_mut().insert(request_id.clone());
+
+    let mut response = request_id.clone().scope(next.run(req)).await;
+
+    response
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, request_id.header_value());
+
+    response
+}
@@ -0,0 +1,121 @@
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+
+use axum::{body::Bytes, http::HeaderMap};
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+use crate::body_trace::BodyTraceConfig;
+
+pin_project! {
+    /// Passes the frames of the inner body through and captures the beginning of the data for tracing.
+    ///
+    /// More than `max_logged_bytes` are captured, so that JSON bodies can be redacted before they are truncated.
+    ///
+    /// The captured data is logged once, when the body ends, fails or is dropped.
+    #[derive(Debug)]
+    pub struct TracedBody<B> {
+        #[pin]
+        inner: B,
+        config: Arc<BodyTraceConfig>,
+        headers: HeaderMap,
+        captured: Vec<u8>,
+        total_bytes: usize,
+        logged: bool,
+    }
+
+    impl<B> PinnedDrop for TracedBody<B> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+
+            if !*this.logged {
+                log(this.config, this.headers, this.captured, *this.total_bytes, false);
+            }
+        }
+    }
+}
+
+impl<B> TracedBody<B> {
+    pub fn new(inner: B, config: Arc<BodyTraceConfig>, headers: HeaderMap) -> Self {
+        Self {
+            inner,
+            config,
+            headers,
+            captured: Vec::new(),
+            total_bytes: 0,
+            logged: false,
+        }
+    }
+}
+
+fn log(
+    config: &BodyTraceConfig,
+    headers: &HeaderMap,
+    captured: &[u8],
+    total_bytes: usize,
+    completed: bool,
+) {
+    let body = config.render_captured(headers, captured, total_bytes);
+
+    tracing::trace!(body, total_bytes, completed, "Response body");
+}
+
+impl<B: Body<Data = Bytes>> Body for TracedBody<B> {
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        let frame = ready!(this.inner.as_mut().poll_frame(cx));
+
+        // Bodies like `Full` end without yielding `None`, if they are not polled after their last frame.
+        let ended = match &frame {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    *this.total_bytes += data.len();
+
+                    let remaining = this
+                        .config
+                        .max_captured_bytes()
+                        .saturating_sub(this.captured.len());
+                    this.captured
+                        .extend_from_slice(&data[..data.len().min(remaining)]);
+                }
+
+                this.inner.is_end_stream().then_some(true)
+            }
+            Some(Err(_)) => Some(false),
+            None => Some(true),
+        };
+
+        if let Some(completed) = ended {
+            if !*this.logged {
+                *this.logged = true;
+
+                log(
+                    this.config,
+                    this.headers,
+                    this.captured,
+                    *this.total_bytes,
+                    completed,
+                );
+            }
+        }
+
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+
+use axum::{
+    body::{Body as AxumBody, Bytes},
+    http::Response,
+    BoxError,
+};
+use http_body::Body;
+use pin_project_lite::pin_project;
+
+use crate::body_trace::BodyTraceConfig;
+
+use super::body::TracedBody;
+
+pin_project! {
+    /// Response future of [`ResponseBodyTraceLayer`](super::layer::ResponseBodyTraceLayer).
+    #[derive(Debug)]
+    pub struct ResponseFuture<F> {
+        #[pin]
+        inner: F,
+        // `None` if the request is not sampled.
+        config: Option<Arc<BodyTraceConfig>>,
+    }
+}
+
+impl<F> ResponseFuture<F> {
+    pub fn new(inner: F, config: Option<Arc<BodyTraceConfig>>) -> Self {
+        Self { inner, config }
+    }
+}
+
+impl<F, B, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+    B: Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Output = Result<Response<AxumBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let response = ready!(this.inner.poll(cx)?);
+
+        let config = this
+            .config
+            .take()
+            .filter(|config| config.traces_content_type(response.headers()));
+
+        let Some(config) = config else {
+            return Poll::Ready(Ok(response.map(AxumBody::new)));
+        };
+
+        let (parts, body) = response.into_parts();
+        let body = TracedBody::new(body, config, parts.headers.clone());
+
+        Poll::Ready(Ok(Response::from_parts(parts, AxumBody::new(body))))
+    }
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use tower::Layer;
+
+use crate::body_trace::BodyTraceConfig;
+
+use super::service::ResponseBodyTraceService;
+
+/// Traces the response body while it is streamed to the client.
+///
+/// Unlike buffering the whole body, streamed and long-lived responses like server-sent events are passed through unchanged.
+/// Up to [`BodyTraceConfig::max_logged_bytes`] are captured and logged once the body ends or is dropped.
+#[derive(Debug, Clone)]
+pub struct ResponseBodyTraceLayer {
+    config: Arc<BodyTraceConfig>,
+}
+
+impl ResponseBodyTraceLayer {
+    pub fn new(config: BodyTraceConfig) -> Self {
+        ResponseBodyTraceLayer {
+            config: Arc::new(config),
+        }
+    }
+}
+
+impl<S> Layer<S> for ResponseBodyTraceLayer {
+    type Service = ResponseBodyTraceService<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        ResponseBodyTraceService::new(service, self.config.clone())
+    }
+}
@@ -0,0 +1,4 @@
+pub mod body;
+pub mod future;
+pub mod layer;
+pub mod service;
@@ -0,0 +1,52 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::{Body as AxumBody, Bytes},
+    extract::Request,
+    http::Response,
+    BoxError,
+};
+use http_body::Body;
+use tower::Service;
+
+use crate::body_trace::BodyTraceConfig;
+
+use super::future::ResponseFuture;
+
+#[derive(Debug, Clone)]
+pub struct ResponseBodyTraceService<S> {
+    inner: S,
+    config: Arc<BodyTraceConfig>,
+}
+
+impl<S> ResponseBodyTraceService<S> {
+    pub fn new(inner: S, config: Arc<BodyTraceConfig>) -> Self {
+        ResponseBodyTraceService { inner, config }
+    }
+}
+
+impl<ReqBody, ResBody, S> Service<Request<ReqBody>> for ResponseBodyTraceService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    ResBody: Body<Data = Bytes> + Send + 'static,
+    ResBody::Error: Into<BoxError>,
+{
+    type Response = Response<AxumBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        let config = (self.config.trace_responses
+            && self.config.is_sampled(request.extensions_mut()))
+        .then(|| self.config.clone());
+
+        ResponseFuture::new(self.inner.call(request), config)
+    }
+}
@@ -0,0 +1,87 @@
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request, State},
+    http::header::CONTENT_TYPE,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use jsonschema::JSONSchema;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider},
+    response_schema::{ResponseSchema, ResponseSchemaValidationProvider},
+};
+
+use super::buffered_body::buffer_body;
+
+/// Middleware to validate JSON responses against their schema.
+///
+/// The schema is taken from the response's [`ResponseSchema`] or registered for the route.
+/// Mismatches are logged and, if configured, replaced with an internal server error.
+///
+/// This is a very expensive middleware, since it reads the entire response body. Use it for debugging only.
+pub async fn response_schema_validation<S>(
+    State(state): State<S>,
+    matched_path: Option<MatchedPath>,
+    req: Request,
+    next: Next,
+) -> Result<impl IntoResponse, ApiError>
+where
+    S: ResponseSchemaValidationProvider + ErrorVerbosityProvider,
+{
+    let response = next.run(req).await;
+
+    let Some(config) = state.response_schema_validation() else {
+        return Ok(response);
+    };
+
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+
+    if !response.status().is_success() || !is_json {
+        return Ok(response);
+    }
+
+    let schema = response
+        .extensions()
+        .get::<ResponseSchema>()
+        .copied()
+        .or_else(|| {
+            matched_path
+                .and_then(|matched_path| state.registered_response_schema(matched_path.as_str()))
+        });
+
+    let Some(ResponseSchema(schema)) = schema else {
+        return Ok(response);
+    };
+
+    let verbosity = state.error_verbosity();
+
+    let (parts, bytes) = buffer_body(response, verbosity).await?;
+
+    let schema = serde_json::to_value(schema())
+        .map_err(|err| ApiError::from_generic_error(verbosity, err))?;
+    let instance = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .map_err(|err| ApiError::from_generic_error(verbosity, err))?;
+
+    let compiled = JSONSchema::compile(&schema)
+        .map_err(|err| ApiError::from_generic_error(verbosity, anyhow::anyhow!("{err}")))?;
+
+    if let Err(errors) = compiled.validate(&instance) {
+        let errors = errors.map(|err| err.to_string()).collect::<Vec<_>>();
+
+        tracing::error!(?errors, "Response does not match its schema");
+
+        if config.fail_on_mismatch {
+            return Err(ApiError::from_generic_error(
+                verbosity,
+                anyhow::anyhow!("Response does not match its schema: {}", errors.join(", ")),
+            ));
+        }
+    }
+
+    Ok(Response::from_parts(parts, Body::from(bytes)))
+}
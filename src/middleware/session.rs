@@ -0,0 +1,183 @@
+use std::{
+    convert::Infallible,
+    fmt::Display,
+    task::{Context, Poll},
+};
+
+use axum::{
+    extract::Request,
+    http::{header::SET_COOKIE, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use futures::future::BoxFuture;
+use tower::{Layer, Service};
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider},
+    extractor::cookie::parse_cookies,
+    session::{Session, SessionChange, SessionConfigProvider, SessionStore, SESSION_COOKIE_NAME},
+};
+
+/// Loads the session of the request into a [`Session`] extension and persists its changes after the response.
+///
+/// The expiry of an existing session is extended on every request.
+#[derive(Debug, Clone)]
+pub struct SessionLayer<S> {
+    state: S,
+}
+
+impl<S> SessionLayer<S> {
+    pub const fn new(state: S) -> Self {
+        SessionLayer { state }
+    }
+}
+
+impl<I, S: Clone> Layer<I> for SessionLayer<S> {
+    type Service = SessionService<I, S>;
+
+    fn layer(&self, inner: I) -> Self::Service {
+        SessionService {
+            inner,
+            state: self.state.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionService<I, S> {
+    inner: I,
+    state: S,
+}
+
+fn session_cookie(value: &str, max_age_in_seconds: u64) -> Option<HeaderValue> {
+    HeaderValue::from_str(&format!(
+        "{SESSION_COOKIE_NAME}={value}; HttpOnly; Secure; SameSite=Lax; Path=/; Max-Age={max_age_in_seconds}"
+    ))
+    .ok()
+}
+
+impl<I, S> SessionService<I, S>
+where
+    S: SessionStore + SessionConfigProvider + ErrorVerbosityProvider,
+    <S as SessionStore>::Error: Into<anyhow::Error> + Display,
+{
+    async fn load(state: &S, request: Request) -> Result<(Session, Request), ApiError> {
+        let (parts, body) = request.into_parts();
+
+        let id = parse_cookies(&parts, state.error_verbosity())
+            .ok()
+            .and_then(|cookies| {
+                cookies
+                    .into_iter()
+                    .find(|(name, _)| *name == SESSION_COOKIE_NAME)
+                    .map(|(_, value)| value.to_owned())
+            });
+
+        let data = match &id {
+            Some(id) => state
+                .load_session(id)
+                .await
+                .map_err(|err| ApiError::from_generic_error(state.error_verbosity(), err))?,
+            None => None,
+        };
+
+        // An unknown or expired id is not reused.
+        let id = id.filter(|_| data.is_some());
+
+        Ok((Session::new(id, data), Request::from_parts(parts, body)))
+    }
+
+    /// Persists the session changes and returns the `Set-Cookie` header if the cookie has to be updated.
+    async fn save(state: &S, session: &Session) -> Result<Option<HeaderValue>, ApiError> {
+        let time_to_live = state.session_time_to_live();
+        let server_error = |err| ApiError::from_generic_error(state.error_verbosity(), err);
+
+        match session.change() {
+            SessionChange::Unchanged { id: None } => Ok(None),
+            SessionChange::Unchanged { id: Some(id) } => {
+                state
+                    .touch_session(&id, time_to_live)
+                    .await
+                    .map_err(server_error)?;
+
+                Ok(session_cookie(&id, time_to_live.as_secs()))
+            }
+            SessionChange::Stored {
+                id,
+                previous_id,
+                data,
+            } => {
+                if let Some(previous_id) = previous_id {
+                    state
+                        .remove_session(&previous_id)
+                        .await
+                        .map_err(server_error)?;
+                }
+
+                state
+                    .store_session(&id, data, time_to_live)
+                    .await
+                    .map_err(server_error)?;
+
+                Ok(session_cookie(&id, time_to_live.as_secs()))
+            }
+            SessionChange::Removed { id } => {
+                if let Some(id) = id {
+                    state.remove_session(&id).await.map_err(server_error)?;
+                }
+
+                Ok(session_cookie("", 0))
+            }
+        }
+    }
+}
+
+impl<I, S> Service<Request> for SessionService<I, S>
+where
+    I: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    I::Future: Send,
+    S: SessionStore
+        + SessionConfigProvider
+        + ErrorVerbosityProvider
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    <S as SessionStore>::Error: Into<anyhow::Error> + Display,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Take the service that was driven to readiness.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let state = self.state.clone();
+
+        Box::pin(async move {
+            let (session, mut request) = match Self::load(&state, request).await {
+                Ok(loaded) => loaded,
+                Err(err) => return Ok(err.into_response()),
+            };
+
+            request.extensions_mut().insert(session.clone());
+
+            let mut response = inner.call(request).await?;
+
+            match Self::save(&state, &session).await {
+                Ok(Some(cookie)) => {
+                    response.headers_mut().append(SET_COOKIE, cookie);
+                }
+                Ok(None) => {}
+                Err(err) => return Ok(err.into_response()),
+            }
+
+            Ok(response)
+        })
+    }
+}
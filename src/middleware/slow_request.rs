@@ -0,0 +1,56 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::{
+    extractor::principal::{run_recording_principal, Principal},
+    request_id::RequestId,
+    slow_request::SlowRequestProvider,
+};
+
+/// Middleware to log and count the requests exceeding the latency threshold of the [`SlowRequestProvider`].
+pub async fn slow_request<S: SlowRequestProvider>(
+    State(state): State<S>,
+    matched_path: Option<MatchedPath>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(threshold) = state.slow_request_threshold() else {
+        return next.run(req).await;
+    };
+
+    let method = req.method().clone();
+    let request_id = req.extensions().get::<RequestId>().map(ToString::to_string);
+
+    let start = Instant::now();
+    let response = run_recording_principal(req, next).await;
+    let latency = start.elapsed();
+
+    if latency > threshold {
+        state.slow_request_counter().increment();
+
+        let route = matched_path
+            .as_ref()
+            .map_or("unmatched", |matched_path| matched_path.as_str());
+
+        tracing::warn!(
+            %method,
+            route,
+            principal = response
+                .extensions()
+                .get::<Principal>()
+                .map_or("anonymous", |principal| principal.subject.as_str()),
+            request_id,
+            status = response.status().as_u16(),
+            latency_in_millis = latency.as_secs_f64() * 1000.0,
+            threshold_in_millis = threshold.as_millis() as u64,
+            "Slow request"
+        );
+    }
+
+    response
+}
@@ -0,0 +1,106 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::{body::Body as AxumBody, response::IntoResponse};
+use http::Response;
+use pin_project_lite::pin_project;
+use tokio::time::{sleep_until, Sleep};
+
+use crate::{
+    error::{ApiError, RequestTimeoutError},
+    state::PrivateErrorVerbosity,
+};
+
+use super::service::RequestTimeout;
+
+pin_project! {
+    pub struct ResponseFuture<F> {
+        #[pin]
+        kind: Kind<F>,
+    }
+}
+
+impl<F> ResponseFuture<F> {
+    pub fn timed(
+        future: F,
+        request_timeout: RequestTimeout,
+        verbosity: PrivateErrorVerbosity,
+    ) -> Self {
+        Self {
+            kind: Kind::Timed {
+                future,
+                sleep: sleep_until(request_timeout.deadline()),
+                request_timeout,
+                verbosity,
+            },
+        }
+    }
+
+    pub fn overriding(future: F) -> Self {
+        Self {
+            kind: Kind::Overriding { future },
+        }
+    }
+}
+
+pin_project! {
+    #[project = KindProj]
+    enum Kind<F> {
+        Timed {
+            #[pin]
+            future: F,
+            #[pin]
+            sleep: Sleep,
+            request_timeout: RequestTimeout,
+            verbosity: PrivateErrorVerbosity,
+        },
+        Overriding {
+            #[pin]
+            future: F,
+        },
+    }
+}
+
+impl<F, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response<AxumBody>, E>>,
+{
+    type Output = Result<Response<AxumBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().kind.project() {
+            KindProj::Timed {
+                future,
+                mut sleep,
+                request_timeout,
+                verbosity,
+            } => {
+                if let Poll::Ready(response) = future.poll(cx) {
+                    return Poll::Ready(response);
+                }
+
+                // A nested layer may have overridden the timeout while the inner service was polled.
+                let deadline = request_timeout.deadline();
+                if sleep.deadline() != deadline {
+                    sleep.as_mut().reset(deadline);
+                }
+
+                if sleep.poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+
+                let timeout = request_timeout.timeout();
+
+                tracing::warn!(?timeout, "Rejection. Request timed out");
+
+                let api_error = ApiError::from(RequestTimeoutError::new(*verbosity, timeout));
+
+                Poll::Ready(Ok(api_error.into_response()))
+            }
+            KindProj::Overriding { future } => future.poll(cx),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use tower::Layer;
+
+use super::service::Timeout;
+
+/// Rejects requests with a `504 Gateway Timeout` if the inner service does not respond in time.
+///
+/// Nested layers override the timeout of the outermost layer,
+/// so a router can be given a longer or shorter timeout than the whole server.
+/// The overriding timeout is measured from the moment the outermost layer received the request.
+#[derive(Clone)]
+pub struct TimeoutLayer<P> {
+    provider: P,
+    timeout: Duration,
+}
+
+impl<P> TimeoutLayer<P> {
+    pub fn new(provider: P, timeout: Duration) -> Self {
+        TimeoutLayer { provider, timeout }
+    }
+}
+
+impl<S, P: Clone> Layer<S> for TimeoutLayer<P> {
+    type Service = Timeout<S, P>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        Timeout::new(service, self.provider.clone(), self.timeout)
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use axum::body::Body as AxumBody;
+use http::{Request, Response};
+use tokio::time::Instant;
+use tower::Service;
+
+use crate::error::ErrorVerbosityProvider;
+
+use super::future::ResponseFuture;
+
+/// The timeout of a request, shared between the outermost [`Timeout`] and the nested ones overriding it.
+#[derive(Debug, Clone)]
+pub struct RequestTimeout {
+    started: Instant,
+    timeout: Arc<Mutex<Duration>>,
+}
+
+impl RequestTimeout {
+    fn new(timeout: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            timeout: Arc::new(Mutex::new(timeout)),
+        }
+    }
+
+    /// Returns the current timeout of the request.
+    pub fn timeout(&self) -> Duration {
+        *self.timeout.lock().expect("request timeout mutex poisoned")
+    }
+
+    pub fn deadline(&self) -> Instant {
+        self.started + self.timeout()
+    }
+
+    fn set_timeout(&self, timeout: Duration) {
+        *self.timeout.lock().expect("request timeout mutex poisoned") = timeout;
+    }
+}
+
+/// Runs the inner service until the timeout of the request expires.
+///
+/// If the request already has a [`RequestTimeout`], the timeout is overridden instead.
+#[derive(Clone)]
+pub struct Timeout<T, P> {
+    inner: T,
+    provider: P,
+    timeout: Duration,
+}
+
+impl<T, P> Timeout<T, P> {
+    pub fn new(inner: T, provider: P, timeout: Duration) -> Self {
+        Timeout {
+            inner,
+            provider,
+            timeout,
+        }
+    }
+}
+
+impl<S, ReqBody, P> Service<Request<ReqBody>> for Timeout<S, P>
+where
+    P: ErrorVerbosityProvider,
+    S: Service<Request<ReqBody>, Response = Response<AxumBody>>,
+{
+    type Response = Response<AxumBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        if let Some(request_timeout) = request.extensions().get::<RequestTimeout>() {
+            tracing::trace!(timeout = ?self.timeout, "Overriding request timeout");
+
+            request_timeout.set_timeout(self.timeout);
+
+            return ResponseFuture::overriding(self.inner.call(request));
+        }
+
+        let request_timeout = RequestTimeout::new(self.timeout);
+        request.extensions_mut().insert(request_timeout.clone());
+
+        ResponseFuture::timed(
+            self.inner.call(request),
+            request_timeout,
+            self.provider.error_verbosity(),
+        )
+    }
+}
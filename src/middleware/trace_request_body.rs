@@ -0,0 +1,56 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::header::CONTENT_LENGTH,
+    middleware::Next,
+    response::IntoResponse,
+};
+
+use crate::{
+    body_trace::BodyTraceProvider,
+    error::{ApiError, ErrorVerbosityProvider},
+    extractor::body::BodyLimitProvider,
+};
+
+/// Middlware to trace the request body.
+///
+/// The body is only read if the request is sampled, the content type is traced
+/// and the `Content-Length` is within the body size limit, so streamed bodies are never buffered.
+pub async fn trace_request_body<
+    S: ErrorVerbosityProvider + BodyTraceProvider + BodyLimitProvider,
+>(
+    State(state): State<S>,
+    mut req: Request,
+    next: Next,
+) -> Result<impl IntoResponse, ApiError> {
+    let config = state.body_trace_config();
+
+    let content_length = req
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|content_length| content_length.to_str().ok())
+        .and_then(|content_length| content_length.parse::<usize>().ok());
+
+    let traced = config.trace_requests
+        && content_length.is_some_and(|length| length <= state.max_body_size_in_bytes())
+        && config.traces_content_type(req.headers())
+        && config.is_sampled(req.extensions_mut());
+
+    if !traced {
+        return Ok(next.run(req).await);
+    }
+
+    let (parts, body) = req.into_parts();
+
+    let bytes = axum::body::to_bytes(body, state.max_body_size_in_bytes())
+        .await
+        .map_err(|err| ApiError::from_generic_error(state.error_verbosity(), err))?;
+
+    if let Some(body) = config.render(&parts.headers, &bytes) {
+        tracing::trace!(%body, "Request body");
+    }
+
+    let req = Request::from_parts(parts, Body::from(bytes));
+
+    Ok(next.run(req).await)
+}
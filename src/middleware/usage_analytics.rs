@@ -0,0 +1,38 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::IntoResponse,
+};
+
+use crate::{
+    analytics::UsageAnalyticsProvider,
+    extractor::principal::{run_recording_principal, Principal},
+};
+
+/// Middleware to record the request count and latency per principal and route.
+///
+/// The principal is the one extracted by the handler, requests that did not extract one are anonymous.
+pub async fn usage_analytics<S: UsageAnalyticsProvider>(
+    State(state): State<S>,
+    matched_path: Option<MatchedPath>,
+    req: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let route = matched_path
+        .map(|matched_path| matched_path.as_str().to_string())
+        .unwrap_or_else(|| String::from("unmatched"));
+
+    let start = Instant::now();
+    let response = run_recording_principal(req, next).await;
+
+    let principal = response
+        .extensions()
+        .get::<Principal>()
+        .map(|principal| principal.subject.clone());
+
+    state.record_usage(principal, route, start.elapsed());
+
+    response
+}
@@ -0,0 +1,204 @@
+use anyhow::Context;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use derivative::Derivative;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::token_client::TokenError;
+
+#[derive(Derivative, Clone, Deserialize)]
+#[derivative(Debug)]
+pub struct OidcLoginConfig {
+    pub client_id: String,
+    #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+    pub client_secret: String,
+    /// The URL of the `/auth/callback` route as registered at the identity provider.
+    pub redirect_uri: String,
+    #[serde(default = "default_scope")]
+    pub scope: String,
+    /// Where the identity provider redirects to after the logout.
+    pub post_logout_redirect_uri: Option<String>,
+}
+
+fn default_scope() -> String {
+    String::from("openid profile email")
+}
+
+/// A PKCE code verifier and its `S256` code challenge.
+pub struct Pkce {
+    pub code_verifier: String,
+    pub code_challenge: String,
+}
+
+impl Pkce {
+    /// Generates a random code verifier.
+    pub fn generate() -> Self {
+        let code_verifier = URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>());
+        let code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()));
+
+        Self {
+            code_verifier,
+            code_challenge,
+        }
+    }
+}
+
+/// A login that was started but not yet completed by the callback.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PendingLogin {
+    pub code_verifier: String,
+    pub nonce: String,
+    /// Where to redirect to after the login.
+    pub return_to: String,
+}
+
+/// The tokens returned by the token endpoint for an authorization code.
+#[derive(Deserialize)]
+pub struct LoginTokens {
+    pub access_token: String,
+    pub id_token: String,
+    pub refresh_token: Option<String>,
+}
+
+/// The claims of the ID token used by the login.
+#[derive(Debug, Deserialize)]
+pub struct IdTokenClaims {
+    pub sub: String,
+    pub nonce: Option<String>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+/// The session of a logged in user.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoginSession {
+    pub sub: String,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub id_token: String,
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+}
+
+/// Authorization code flow with PKCE against the OpenID provider.
+pub struct OidcLogin {
+    http_client: reqwest::Client,
+    issuer: String,
+    authorization_endpoint: Url,
+    token_endpoint: String,
+    end_session_endpoint: Option<Url>,
+    config: OidcLoginConfig,
+}
+
+impl OidcLogin {
+    pub fn new(
+        http_client: reqwest::Client,
+        issuer: String,
+        authorization_endpoint: String,
+        token_endpoint: String,
+        end_session_endpoint: Option<String>,
+        config: OidcLoginConfig,
+    ) -> anyhow::Result<Self> {
+        let authorization_endpoint = Url::parse(&authorization_endpoint)
+            .context("Failed to parse authorization endpoint")?;
+
+        let end_session_endpoint = end_session_endpoint
+            .map(|end_session_endpoint| Url::parse(&end_session_endpoint))
+            .transpose()
+            .context("Failed to parse end session endpoint")?;
+
+        Ok(Self {
+            http_client,
+            issuer,
+            authorization_endpoint,
+            token_endpoint,
+            end_session_endpoint,
+            config,
+        })
+    }
+
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    pub fn config(&self) -> &OidcLoginConfig {
+        &self.config
+    }
+
+    /// Returns the URL of the authorization endpoint the user is redirected to.
+    pub fn authorization_url(&self, state: &str, nonce: &str, code_challenge: &str) -> Url {
+        let mut url = self.authorization_endpoint.clone();
+
+        url.query_pairs_mut().extend_pairs([
+            ("response_type", "code"),
+            ("client_id", self.config.client_id.as_str()),
+            ("redirect_uri", self.config.redirect_uri.as_str()),
+            ("scope", self.config.scope.as_str()),
+            ("state", state),
+            ("nonce", nonce),
+            ("code_challenge", code_challenge),
+            ("code_challenge_method", "S256"),
+        ]);
+
+        url
+    }
+
+    /// Exchanges the authorization code for tokens.
+    #[tracing::instrument(skip_all)]
+    pub async fn exchange_code(
+        &self,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<LoginTokens, TokenError> {
+        tracing::debug!("Exchanging authorization code");
+
+        self.http_client
+            .post(&self.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", self.config.redirect_uri.as_str()),
+                ("client_id", self.config.client_id.as_str()),
+                ("client_secret", self.config.client_secret.as_str()),
+                ("code_verifier", code_verifier),
+            ])
+            .send()
+            .await
+            .map_err(TokenError::Request)?
+            .error_for_status()
+            .map_err(TokenError::Status)?
+            .json::<LoginTokens>()
+            .await
+            .map_err(TokenError::Parse)
+    }
+
+    /// Returns the URL of the identity provider's `end_session_endpoint` if it supports RP-initiated logout.
+    ///
+    /// The given `post_logout_redirect_uri` overrides the configured one.
+    pub fn logout_url(
+        &self,
+        id_token_hint: Option<&str>,
+        post_logout_redirect_uri: Option<&str>,
+    ) -> Option<Url> {
+        let mut url = self.end_session_endpoint.clone()?;
+
+        {
+            let mut query = url.query_pairs_mut();
+
+            query.append_pair("client_id", &self.config.client_id);
+
+            if let Some(id_token_hint) = id_token_hint {
+                query.append_pair("id_token_hint", id_token_hint);
+            }
+
+            if let Some(post_logout_redirect_uri) =
+                post_logout_redirect_uri.or(self.config.post_logout_redirect_uri.as_deref())
+            {
+                query.append_pair("post_logout_redirect_uri", post_logout_redirect_uri);
+            }
+        }
+
+        Some(url)
+    }
+}
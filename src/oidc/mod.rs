@@ -0,0 +1,4 @@
+//! Outbound OpenID Connect clients using the endpoints of the discovered [`OpenIdConfiguration`](crate::openid_configuration::OpenIdConfiguration).
+
+pub mod login;
+pub mod token_client;
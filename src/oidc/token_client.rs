@@ -0,0 +1,141 @@
+use std::time::{Duration, Instant};
+
+use derivative::Derivative;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+
+#[derive(Derivative, Clone, Deserialize)]
+#[derivative(Debug)]
+pub struct TokenClientConfig {
+    pub client_id: String,
+    #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+    pub client_secret: String,
+    pub scope: Option<String>,
+    /// Tokens are refreshed this many seconds before they expire.
+    #[serde(default = "default_refresh_before_expiry_in_seconds")]
+    pub refresh_before_expiry_in_seconds: u64,
+}
+
+fn default_refresh_before_expiry_in_seconds() -> u64 {
+    30
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TokenError {
+    #[error("Failed to request token: {0}")]
+    Request(#[source] reqwest::Error),
+    #[error("Token endpoint responded with an error: {0}")]
+    Status(#[source] reqwest::Error),
+    #[error("Failed to parse token response: {0}")]
+    Parse(#[source] reqwest::Error),
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Clone)]
+pub struct CachedToken {
+    pub access_token: String,
+    refresh_at: Instant,
+}
+
+impl CachedToken {
+    pub fn is_fresh(&self) -> bool {
+        Instant::now() < self.refresh_at
+    }
+}
+
+/// Obtains tokens from the token endpoint of the OpenID provider.
+///
+/// The client-credentials token is cached and refreshed before it expires.
+pub struct TokenClient {
+    http_client: reqwest::Client,
+    token_endpoint: String,
+    config: TokenClientConfig,
+    client_credentials_token: RwLock<Option<CachedToken>>,
+}
+
+impl TokenClient {
+    pub fn new(
+        http_client: reqwest::Client,
+        token_endpoint: String,
+        config: TokenClientConfig,
+    ) -> Self {
+        Self {
+            http_client,
+            token_endpoint,
+            config,
+            client_credentials_token: RwLock::new(None),
+        }
+    }
+
+    /// Requests a token using the client's credentials and the given grant parameters.
+    #[tracing::instrument(skip_all)]
+    pub async fn obtain_token(&self, params: &[(&str, &str)]) -> Result<CachedToken, TokenError> {
+        tracing::debug!("Obtaining token");
+
+        let mut form = vec![
+            ("client_id", self.config.client_id.as_str()),
+            ("client_secret", self.config.client_secret.as_str()),
+        ];
+        form.extend_from_slice(params);
+
+        if let Some(scope) = self.config.scope.as_deref() {
+            form.push(("scope", scope));
+        }
+
+        let token = self
+            .http_client
+            .post(&self.token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .map_err(TokenError::Request)?
+            .error_for_status()
+            .map_err(TokenError::Status)?
+            .json::<TokenResponse>()
+            .await
+            .map_err(TokenError::Parse)?;
+
+        let lifetime = Duration::from_secs(
+            token
+                .expires_in
+                .saturating_sub(self.config.refresh_before_expiry_in_seconds),
+        );
+
+        Ok(CachedToken {
+            access_token: token.access_token,
+            refresh_at: Instant::now() + lifetime,
+        })
+    }
+
+    /// Returns a cached client-credentials token or obtains a new one.
+    pub async fn client_credentials_token(&self) -> Result<String, TokenError> {
+        if let Some(token) = self.client_credentials_token.read().await.as_ref() {
+            if token.is_fresh() {
+                return Ok(token.access_token.clone());
+            }
+        }
+
+        let mut cached = self.client_credentials_token.write().await;
+
+        // Another request might have refreshed the token while we were waiting for the lock.
+        if let Some(token) = cached.as_ref() {
+            if token.is_fresh() {
+                return Ok(token.access_token.clone());
+            }
+        }
+
+        let token = self
+            .obtain_token(&[("grant_type", "client_credentials")])
+            .await?;
+        let access_token = token.access_token.clone();
+
+        *cached = Some(token);
+
+        Ok(access_token)
+    }
+}
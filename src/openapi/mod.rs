@@ -0,0 +1,565 @@
+//! OpenAPI document of the bundled routes.
+//!
+//! The apps record the operations of their routes in an [`ApiRouter`](router::ApiRouter), which are nested with the
+//! routers in [`Server::run`](crate::server::Server::run).
+//! Request and response types are described by their [`JsonSchema`] and converted to utoipa schemas.
+//! Every operation responds with an [`ApiError`](crate::error::ApiError) or [`ResourceError`](crate::error::ResourceError)
+//! as its `default` response.
+//! The shape of the error body depends on the [`ErrorVerbosity`], so the document describes the errors as rendered with
+//! the configured verbosity.
+
+use anyhow::Context;
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{header::CONTENT_TYPE, HeaderValue, StatusCode},
+    response::{Redirect, Response as AxumResponse},
+    routing::get,
+    Extension, Json, Router,
+};
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    schema::Schema as JsonSchemaSchema,
+    JsonSchema,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::openapi::{
+    path::{OperationBuilder, ParameterBuilder, ParameterIn, PathItemType},
+    request_body::RequestBodyBuilder,
+    schema::{KnownFormat, SchemaFormat, SchemaType},
+    ComponentsBuilder, Content, Info, ObjectBuilder, OpenApi, OpenApiBuilder, PathItem,
+    PathsBuilder, Ref, RefOr, Required, Response, ResponseBuilder, Schema,
+};
+use utoipa_rapidoc::RapiDoc;
+use utoipa_swagger_ui::{Config, SwaggerUi};
+
+use crate::{error::ErrorVerbosity, problem_details::PROBLEM_JSON_CONTENT_TYPE};
+
+pub mod router;
+
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+const JSON_CONTENT_TYPE: &str = "application/json";
+
+const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OpenApiConfig {
+    /// Serves Swagger UI at `/swagger-ui`.
+    #[serde(default)]
+    pub swagger_ui: bool,
+    /// Serves RapiDoc at `/rapidoc`.
+    #[serde(default)]
+    pub rapidoc: bool,
+}
+
+type SchemaFn = fn(&mut SchemaGenerator) -> JsonSchemaSchema;
+
+/// An operation of a route.
+pub struct ApiOperation {
+    method: PathItemType,
+    path: String,
+    summary: Option<&'static str>,
+    tag: Option<String>,
+    parameters: Vec<(ParameterIn, SchemaFn)>,
+    body: Option<(&'static str, SchemaFn)>,
+    response: Option<(&'static str, SchemaFn)>,
+}
+
+impl ApiOperation {
+    fn new(method: PathItemType, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            summary: None,
+            tag: None,
+            parameters: Vec::new(),
+            body: None,
+            response: None,
+        }
+    }
+
+    pub fn get(path: impl Into<String>) -> Self {
+        Self::new(PathItemType::Get, path)
+    }
+
+    pub fn post(path: impl Into<String>) -> Self {
+        Self::new(PathItemType::Post, path)
+    }
+
+    pub fn put(path: impl Into<String>) -> Self {
+        Self::new(PathItemType::Put, path)
+    }
+
+    pub fn summary(mut self, summary: &'static str) -> Self {
+        self.summary = Some(summary);
+
+        self
+    }
+
+    /// Describes the path parameters with the fields of `T`.
+    pub fn path_params<T: JsonSchema>(mut self) -> Self {
+        self.parameters.push((ParameterIn::Path, T::json_schema));
+
+        self
+    }
+
+    /// Describes the query parameters with the fields of `T`.
+    pub fn query<T: JsonSchema>(mut self) -> Self {
+        self.parameters.push((ParameterIn::Query, T::json_schema));
+
+        self
+    }
+
+    /// Describes the headers with the fields of `T`.
+    pub fn headers<T: JsonSchema>(mut self) -> Self {
+        self.parameters.push((ParameterIn::Header, T::json_schema));
+
+        self
+    }
+
+    /// Describes the cookies with the fields of `T`.
+    pub fn cookies<T: JsonSchema>(mut self) -> Self {
+        self.parameters.push((ParameterIn::Cookie, T::json_schema));
+
+        self
+    }
+
+    /// Describes the request body with the given content type.
+    pub fn body<T: JsonSchema>(mut self, content_type: &'static str) -> Self {
+        self.body = Some((content_type, SchemaGenerator::subschema_for::<T>));
+
+        self
+    }
+
+    /// Describes a JSON request body.
+    pub fn json<T: JsonSchema>(self) -> Self {
+        self.body::<T>(JSON_CONTENT_TYPE)
+    }
+
+    /// Describes a URL-encoded form request body.
+    pub fn form<T: JsonSchema>(self) -> Self {
+        self.body::<T>(FORM_CONTENT_TYPE)
+    }
+
+    /// Describes a JSON response body.
+    pub fn response<T: JsonSchema>(self) -> Self {
+        self.response_as::<T>(JSON_CONTENT_TYPE)
+    }
+
+    /// Describes the response body with the given content type.
+    pub fn response_as<T: JsonSchema>(mut self, content_type: &'static str) -> Self {
+        self.response = Some((content_type, SchemaGenerator::subschema_for::<T>));
+
+        self
+    }
+
+    fn build(
+        &self,
+        generator: &mut SchemaGenerator,
+        error_response: &Response,
+    ) -> utoipa::openapi::path::Operation {
+        let parameters = self
+            .parameters
+            .iter()
+            .flat_map(|(parameter_in, schema)| parameters(schema(generator), parameter_in));
+
+        let operation = parameters.fold(
+            OperationBuilder::new()
+                .summary(self.summary)
+                .tags(self.tag.clone().map(|tag| [tag])),
+            OperationBuilder::parameter,
+        );
+
+        let operation = match self.body {
+            Some((content_type, schema)) => operation.request_body(Some(
+                RequestBodyBuilder::new()
+                    .content(content_type, Content::new(to_utoipa(schema(generator))))
+                    .required(Some(Required::True))
+                    .build(),
+            )),
+            None => operation,
+        };
+
+        let success = match self.response {
+            Some((content_type, schema)) => ResponseBuilder::new()
+                .description("Success")
+                .content(content_type, Content::new(to_utoipa(schema(generator)))),
+            None => ResponseBuilder::new().description("Success"),
+        };
+
+        operation
+            .response("200", success)
+            .response("default", error_response.clone())
+            .build()
+    }
+}
+
+/// Describes what an extractor reads from the request.
+///
+/// Extractors that neither read parameters nor the body keep the default implementation.
+pub trait OperationInput {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation
+    }
+}
+
+/// Describes the successful response of a handler.
+///
+/// Errors are described by the `default` response of every operation.
+/// Responses without a [`JsonSchema`] keep the default implementation.
+pub trait OperationOutput {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation
+    }
+}
+
+impl<S> OperationInput for State<S> {}
+
+impl<T> OperationInput for Extension<T> {}
+
+impl<T: JsonSchema> OperationOutput for Json<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.response::<T>()
+    }
+}
+
+impl<T: OperationOutput, E> OperationOutput for Result<T, E> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        T::describe(operation)
+    }
+}
+
+impl OperationOutput for () {}
+
+impl OperationOutput for &'static str {}
+
+impl OperationOutput for String {}
+
+impl OperationOutput for Bytes {}
+
+impl OperationOutput for StatusCode {}
+
+impl OperationOutput for Redirect {}
+
+impl OperationOutput for AxumResponse {}
+
+/// Operations registered per route.
+#[derive(Default)]
+pub struct OpenApiRegistry {
+    operations: Vec<ApiOperation>,
+}
+
+impl OpenApiRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn operation(mut self, operation: ApiOperation) -> Self {
+        self.operations.push(operation);
+
+        self
+    }
+
+    /// Registers the operations of `other` below the path prefix, like [`Router::nest`](axum::Router::nest).
+    ///
+    /// The operations are tagged with the prefix, so the outermost prefix wins.
+    pub fn nest(mut self, prefix: &str, other: OpenApiRegistry) -> Self {
+        let tag = prefix.trim_matches('/');
+
+        self.operations
+            .extend(other.operations.into_iter().map(|mut operation| {
+                operation.path = match operation.path.as_str() {
+                    "/" => prefix.to_owned(),
+                    path => format!("{}{path}", prefix.trim_end_matches('/')),
+                };
+
+                if !tag.is_empty() {
+                    operation.tag = Some(tag.to_owned());
+                }
+
+                operation
+            }));
+
+        self
+    }
+
+    /// Builds the document with the error responses rendered with the verbosity.
+    pub fn build(&self, verbosity: ErrorVerbosity) -> OpenApi {
+        let mut generator = SchemaSettings::openapi3().into_generator();
+        let error_response = error_response(verbosity);
+
+        let paths = self
+            .operations
+            .iter()
+            .fold(PathsBuilder::new(), |paths, operation| {
+                paths.path(
+                    openapi_path(&operation.path),
+                    PathItem::new(
+                        operation.method.clone(),
+                        operation.build(&mut generator, &error_response),
+                    ),
+                )
+            });
+
+        let components = generator
+            .take_definitions()
+            .into_iter()
+            .fold(ComponentsBuilder::new(), |components, (name, schema)| {
+                components.schema(name, to_utoipa(schema))
+            });
+
+        let components = error_schemas(verbosity)
+            .into_iter()
+            .fold(components, |components, (_, name, schema)| {
+                components.schema(name, schema)
+            });
+
+        OpenApiBuilder::new()
+            .info(Info::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")))
+            .paths(paths)
+            .components(Some(components.build()))
+            .build()
+    }
+}
+
+/// Serves the document at [`OPENAPI_PATH`] and the UIs enabled in the config.
+///
+/// The document is serialized once.
+pub fn router<S>(config: &OpenApiConfig, openapi: &OpenApi) -> anyhow::Result<Router<S>>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let json = Bytes::from(
+        openapi
+            .to_json()
+            .context("Failed to serialize OpenAPI document")?,
+    );
+
+    let router = Router::new().route(
+        OPENAPI_PATH,
+        get(|| async move {
+            (
+                [(CONTENT_TYPE, HeaderValue::from_static(JSON_CONTENT_TYPE))],
+                json,
+            )
+        }),
+    );
+
+    let router = if config.swagger_ui {
+        router.merge(SwaggerUi::new("/swagger-ui").config(Config::new([OPENAPI_PATH])))
+    } else {
+        router
+    };
+
+    let router = if config.rapidoc {
+        router.merge(RapiDoc::new(OPENAPI_PATH).path("/rapidoc"))
+    } else {
+        router
+    };
+
+    Ok(router)
+}
+
+/// Converts the axum path parameters `:name` and `*name` to `{name}`.
+fn openapi_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix([':', '*']) {
+            Some(name) => format!("{{{name}}}"),
+            None => segment.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Describes every property of the object schema as a parameter.
+fn parameters(
+    schema: JsonSchemaSchema,
+    parameter_in: &ParameterIn,
+) -> Vec<utoipa::openapi::path::Parameter> {
+    let Some(object) = schema.into_object().object else {
+        return Vec::new();
+    };
+
+    object
+        .properties
+        .into_iter()
+        .map(|(name, property)| {
+            let required =
+                matches!(parameter_in, ParameterIn::Path) || object.required.contains(&name);
+            let description = match &property {
+                JsonSchemaSchema::Object(property) => property
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.description.clone()),
+                JsonSchemaSchema::Bool(_) => None,
+            };
+
+            ParameterBuilder::new()
+                .name(name)
+                .parameter_in(parameter_in.clone())
+                .required(if required {
+                    Required::True
+                } else {
+                    Required::False
+                })
+                .description(description)
+                .schema(Some(to_utoipa(property)))
+                .build()
+        })
+        .collect()
+}
+
+/// Converts a schemars schema to a utoipa schema.
+///
+/// Falls back to a schema accepting any value if utoipa can not represent the schema.
+fn to_utoipa(schema: impl Serialize) -> RefOr<Schema> {
+    serde_json::to_value(schema)
+        .and_then(serde_json::from_value)
+        .unwrap_or_else(|_| ObjectBuilder::new().schema_type(SchemaType::Value).into())
+}
+
+fn string_schema() -> ObjectBuilder {
+    ObjectBuilder::new().schema_type(SchemaType::String)
+}
+
+fn timestamp_schema() -> ObjectBuilder {
+    string_schema().format(Some(SchemaFormat::KnownFormat(KnownFormat::DateTime)))
+}
+
+/// Name of the component schema for the verbosity, e.g. `ApiErrorResponseFull`.
+fn error_schema_name(name: &str, verbosity: ErrorVerbosity) -> String {
+    format!("{name}{verbosity:?}")
+}
+
+/// Response of an [`ApiError`](crate::error::ApiError) or [`ResourceError`](crate::error::ResourceError) rendered with the verbosity.
+fn error_response(verbosity: ErrorVerbosity) -> Response {
+    let description = match verbosity {
+        ErrorVerbosity::None => "Error, responded with `204 No Content` without a body",
+        ErrorVerbosity::RandomStatus => "Error, responded with a random status code without a body",
+        ErrorVerbosity::StatusCode => {
+            "Error, responded with the status code of the error without a body"
+        }
+        ErrorVerbosity::Message | ErrorVerbosity::Type | ErrorVerbosity::Full => {
+            "Error, the content type depends on the error format"
+        }
+    };
+
+    error_schemas(verbosity)
+        .into_iter()
+        .fold(
+            ResponseBuilder::new().description(description),
+            |response, (content_type, name, _)| {
+                response.content(content_type, Content::new(Ref::from_schema_name(name)))
+            },
+        )
+        .build()
+}
+
+/// Content types and component schemas of the error bodies rendered with the verbosity.
+///
+/// Errors have no body below [`ErrorVerbosity::Message`].
+fn error_schemas(verbosity: ErrorVerbosity) -> Vec<(&'static str, String, ObjectBuilder)> {
+    if verbosity < ErrorVerbosity::Message {
+        return Vec::new();
+    }
+
+    vec![
+        (
+            JSON_CONTENT_TYPE,
+            error_schema_name("ApiErrorResponse", verbosity),
+            api_error_response_schema(verbosity),
+        ),
+        (
+            PROBLEM_JSON_CONTENT_TYPE,
+            error_schema_name("ProblemDetails", verbosity),
+            problem_details_schema(verbosity),
+        ),
+    ]
+}
+
+/// The content of the error, cleared for [`ErrorVerbosity::Type`].
+fn error_content_schema(verbosity: ErrorVerbosity) -> ObjectBuilder {
+    match verbosity {
+        ErrorVerbosity::Full => ObjectBuilder::new()
+            .nullable(true)
+            .description(Some("The content of the error, depends on the error type")),
+        _ => ObjectBuilder::new()
+            .nullable(true)
+            .description(Some("The cleared content of the error")),
+    }
+}
+
+/// JSON body of an [`ApiError`](crate::error::ApiError) or [`ResourceError`](crate::error::ResourceError).
+fn api_error_response_schema(verbosity: ErrorVerbosity) -> ObjectBuilder {
+    let schema = ObjectBuilder::new()
+        .property(
+            "message",
+            string_schema().description(Some("The message of the error")),
+        )
+        .required("message");
+
+    let schema = if verbosity >= ErrorVerbosity::Type {
+        schema
+            .property(
+                "error_type",
+                string_schema().description(Some("The type of the error")),
+            )
+            .required("error_type")
+            .property("error", error_content_schema(verbosity))
+            .property(
+                "error_code",
+                string_schema().description(Some("The stable code of the error")),
+            )
+    } else {
+        schema
+    };
+
+    schema
+        .property(
+            "request_id",
+            string_schema().description(Some("The id of the request the error occurred in")),
+        )
+        .property(
+            "timestamp",
+            timestamp_schema().description(Some("When the error occurred")),
+        )
+        .required("timestamp")
+}
+
+/// [`ProblemDetails`](crate::problem_details::ProblemDetails) of an error.
+fn problem_details_schema(verbosity: ErrorVerbosity) -> ObjectBuilder {
+    let schema = ObjectBuilder::new()
+        .description(Some("A problem as defined in RFC 9457"))
+        .property(
+            "type",
+            string_schema().description(Some(match verbosity {
+                ErrorVerbosity::Message => "Always `about:blank`",
+                _ => "URI of the error type",
+            })),
+        )
+        .required("type")
+        .property("title", string_schema())
+        .required("title")
+        .property(
+            "status",
+            ObjectBuilder::new().schema_type(SchemaType::Integer),
+        )
+        .required("status")
+        .property("detail", string_schema())
+        .property("instance", string_schema())
+        .required("instance");
+
+    let schema = if verbosity >= ErrorVerbosity::Type {
+        schema
+            .property("error", error_content_schema(verbosity))
+            .property("error_code", string_schema())
+    } else {
+        schema
+    };
+
+    schema
+        .property("request_id", string_schema())
+        .property("timestamp", timestamp_schema())
+        .required("timestamp")
+}
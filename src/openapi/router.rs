@@ -0,0 +1,251 @@
+//! Routers that record the operations of their handlers.
+//!
+//! ```rust,ignore
+//! ApiRouter::new()
+//!     .api_route("/get_book", get(get_book))
+//!     .api_route("/maintenance", get(get_maintenance).put(set_maintenance))
+//! ```
+//!
+//! The parameters and bodies of an operation are described by the [`OperationInput`] of the extractors,
+//! the response by the [`OperationOutput`] of the returned type.
+
+use std::{convert::Infallible, future::Future};
+
+use axum::{
+    extract::Request,
+    handler::Handler,
+    response::IntoResponse,
+    routing::{MethodRouter, Route},
+    Router,
+};
+use tower::{Layer, Service};
+
+use super::{ApiOperation, OpenApiRegistry, OperationInput, OperationOutput};
+
+/// Describes the operation of a handler from its extractors and its response.
+///
+/// Implemented for async functions whose extractors implement [`OperationInput`] and whose response implements
+/// [`OperationOutput`].
+pub trait OperationHandler<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation;
+}
+
+impl<F, Fut, Res> OperationHandler<()> for F
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Res>,
+    Res: OperationOutput,
+{
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        Res::describe(operation)
+    }
+}
+
+macro_rules! impl_operation_handler {
+    ($($ty:ident),+) => {
+        impl<F, Fut, Res, $($ty,)+> OperationHandler<($($ty,)+)> for F
+        where
+            F: FnOnce($($ty,)+) -> Fut,
+            Fut: Future<Output = Res>,
+            Res: OperationOutput,
+            $($ty: OperationInput,)+
+        {
+            fn describe(operation: ApiOperation) -> ApiOperation {
+                let operation = Res::describe(operation);
+                $(let operation = $ty::describe(operation);)+
+
+                operation
+            }
+        }
+    };
+}
+
+impl_operation_handler!(T1);
+impl_operation_handler!(T1, T2);
+impl_operation_handler!(T1, T2, T3);
+impl_operation_handler!(T1, T2, T3, T4);
+impl_operation_handler!(T1, T2, T3, T4, T5);
+impl_operation_handler!(T1, T2, T3, T4, T5, T6);
+impl_operation_handler!(T1, T2, T3, T4, T5, T6, T7);
+impl_operation_handler!(T1, T2, T3, T4, T5, T6, T7, T8);
+
+/// A [`MethodRouter`] with the operations of its handlers.
+pub struct ApiMethodRouter<S = ()> {
+    router: MethodRouter<S>,
+    operations: Vec<ApiOperation>,
+}
+
+macro_rules! api_method {
+    ($method:ident, $method_with:ident) => {
+        impl<S> ApiMethodRouter<S>
+        where
+            S: Clone + Send + Sync + 'static,
+        {
+            pub fn $method<H, T, I>(self, handler: H) -> Self
+            where
+                H: Handler<T, S> + OperationHandler<I>,
+                T: 'static,
+            {
+                self.$method_with(handler, |operation| operation)
+            }
+
+            /// Same as
+            #[doc = concat!("[`", stringify!($method), "`](Self::", stringify!($method), ")")]
+            /// but lets `describe` amend the recorded operation, e.g. with a summary.
+            pub fn $method_with<H, T, I>(
+                mut self,
+                handler: H,
+                describe: impl FnOnce(ApiOperation) -> ApiOperation,
+            ) -> Self
+            where
+                H: Handler<T, S> + OperationHandler<I>,
+                T: 'static,
+            {
+                self.operations
+                    .push(describe(H::describe(ApiOperation::$method(""))));
+                self.router = self.router.$method(handler);
+
+                self
+            }
+        }
+
+        pub fn $method<H, T, I, S>(handler: H) -> ApiMethodRouter<S>
+        where
+            H: Handler<T, S> + OperationHandler<I>,
+            T: 'static,
+            S: Clone + Send + Sync + 'static,
+        {
+            $method_with(handler, |operation| operation)
+        }
+
+        /// Same as
+        #[doc = concat!("[`", stringify!($method), "`]")]
+        /// but lets `describe` amend the recorded operation, e.g. with a summary.
+        pub fn $method_with<H, T, I, S>(
+            handler: H,
+            describe: impl FnOnce(ApiOperation) -> ApiOperation,
+        ) -> ApiMethodRouter<S>
+        where
+            H: Handler<T, S> + OperationHandler<I>,
+            T: 'static,
+            S: Clone + Send + Sync + 'static,
+        {
+            ApiMethodRouter {
+                router: MethodRouter::new(),
+                operations: Vec::new(),
+            }
+            .$method_with(handler, describe)
+        }
+    };
+}
+
+api_method!(get, get_with);
+api_method!(post, post_with);
+api_method!(put, put_with);
+
+impl<S> ApiMethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// See [`MethodRouter::layer`].
+    pub fn layer<L>(self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + 'static,
+        L::Service: Service<Request> + Clone + Send + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        Self {
+            router: self.router.layer(layer),
+            operations: self.operations,
+        }
+    }
+}
+
+/// A [`Router`] that records the operations of the routes added with [`api_route`](Self::api_route).
+pub struct ApiRouter<S = ()> {
+    router: Router<S>,
+    openapi: OpenApiRegistry,
+}
+
+impl<S> Default for ApiRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> ApiRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            router: Router::new(),
+            openapi: OpenApiRegistry::new(),
+        }
+    }
+
+    /// Adds the route and records its operations.
+    pub fn api_route(self, path: &str, method_router: ApiMethodRouter<S>) -> Self {
+        let openapi =
+            method_router
+                .operations
+                .into_iter()
+                .fold(self.openapi, |openapi, mut operation| {
+                    operation.path = path.to_owned();
+
+                    openapi.operation(operation)
+                });
+
+        Self {
+            router: self.router.route(path, method_router.router),
+            openapi,
+        }
+    }
+
+    /// Adds the route without recording its operations.
+    pub fn route(self, path: &str, method_router: MethodRouter<S>) -> Self {
+        Self {
+            router: self.router.route(path, method_router),
+            openapi: self.openapi,
+        }
+    }
+
+    /// See [`Router::nest`] and [`OpenApiRegistry::nest`].
+    pub fn nest(self, path: &str, other: ApiRouter<S>) -> Self {
+        Self {
+            router: self.router.nest(path, other.router),
+            openapi: self.openapi.nest(path, other.openapi),
+        }
+    }
+
+    /// See [`Router::layer`].
+    pub fn layer<L>(self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + 'static,
+        L::Service: Service<Request> + Clone + Send + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        Self {
+            router: self.router.layer(layer),
+            openapi: self.openapi,
+        }
+    }
+
+    /// Returns the router and the recorded operations.
+    pub fn into_parts(self) -> (Router<S>, OpenApiRegistry) {
+        (self.router, self.openapi)
+    }
+}
+
+impl<S> From<ApiRouter<S>> for Router<S> {
+    fn from(router: ApiRouter<S>) -> Self {
+        router.router
+    }
+}
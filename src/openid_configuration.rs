@@ -7,6 +7,7 @@ pub struct OpenIdConfiguration {
     pub authorization_endpoint: String,
     pub token_endpoint: String,
     pub userinfo_endpoint: String,
+    pub end_session_endpoint: Option<String>,
     pub request_parameter_supported: bool,
     pub request_uri_parameter_supported: bool,
     pub id_token_signing_alg_values_supported: Vec<String>,
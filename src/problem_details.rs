@@ -0,0 +1,255 @@
+//! Problem Details for HTTP APIs as defined in [RFC 9457](https://datatracker.ietf.org/doc/html/rfc9457).
+//!
+//! Errors are rendered as `application/problem+json` if the [`ErrorFormat`] of the config is [`ErrorFormat::ProblemDetails`]
+//! or the `Accept` header of the request asks for it.
+//! The format is negotiated by the [`error_format`](crate::middleware::error_format::error_format) middleware
+//! and read by the error responses through [`ErrorFormatContext::current`].
+
+use std::{borrow::Cow, future::Future, sync::Arc};
+
+use axum::{
+    http::{header::CONTENT_TYPE, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error_page::{self, prefers_html, HtmlErrorPageConfig, HTML_CONTENT_TYPE};
+
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+
+tokio::task_local! {
+    static CURRENT_ERROR_FORMAT: ErrorFormatContext;
+}
+
+/// Format of the error response bodies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum ErrorFormat {
+    /// The [`ApiError`](crate::error::ApiError) serialized as `application/json`.
+    #[default]
+    Json,
+    /// Problem Details serialized as `application/problem+json`.
+    ProblemDetails,
+    /// An HTML page rendered from the Problem Details. See [`error_page`](crate::error_page).
+    Html,
+}
+
+/// Format of the `expected_schema` of the errors of invalid request parts, e.g. [`QueryError`](crate::error::QueryError).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type")]
+pub enum ExpectedSchemaFormat {
+    /// The JSON schema as a YAML string.
+    #[default]
+    Yaml,
+    /// The JSON schema as a JSON object.
+    Json,
+    /// A URL made of the base URL and the name of the schema, e.g. pointing at the served OpenAPI component.
+    Url {
+        #[serde(default = "default_schema_base_url")]
+        base_url: String,
+    },
+}
+
+fn default_schema_base_url() -> String {
+    String::from("/openapi.json#/components/schemas/")
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ErrorFormatConfig {
+    /// Used unless the `Accept` header asks for `application/problem+json`.
+    #[serde(default)]
+    pub default: ErrorFormat,
+    /// Prepended to the error type to build the `type` of a problem.
+    #[serde(default = "default_problem_type_base_uri")]
+    pub problem_type_base_uri: String,
+    /// Serves HTML error pages to clients preferring `text/html`. Disabled if not set.
+    #[serde(default)]
+    pub html: Option<HtmlErrorPageConfig>,
+    #[serde(default)]
+    pub expected_schema: ExpectedSchemaFormat,
+}
+
+fn default_problem_type_base_uri() -> String {
+    String::from("urn:the-axum:problem:")
+}
+
+impl Default for ErrorFormatConfig {
+    fn default() -> Self {
+        Self {
+            default: ErrorFormat::default(),
+            problem_type_base_uri: default_problem_type_base_uri(),
+            html: None,
+            expected_schema: ExpectedSchemaFormat::default(),
+        }
+    }
+}
+
+impl ErrorFormatConfig {
+    /// Chooses [`ErrorFormat::ProblemDetails`] if the `Accept` header asks for it,
+    /// [`ErrorFormat::Html`] if HTML pages are enabled and the `Accept` header prefers them
+    /// and the default format otherwise.
+    pub fn negotiate(&self, accept: Option<&str>) -> ErrorFormat {
+        let accepts_problem_details = accept.is_some_and(|accept| {
+            accept
+                .split(',')
+                .any(|media_type| media_type.trim().starts_with(PROBLEM_JSON_CONTENT_TYPE))
+        });
+
+        if accepts_problem_details {
+            return ErrorFormat::ProblemDetails;
+        }
+
+        if self.html.is_some() && accept.is_some_and(prefers_html) {
+            return ErrorFormat::Html;
+        }
+
+        self.default
+    }
+}
+
+pub trait ErrorFormatProvider {
+    fn error_format_config(&self) -> &ErrorFormatConfig;
+}
+
+/// The error format of the request that is currently handled.
+#[derive(Debug, Clone)]
+pub struct ErrorFormatContext {
+    pub format: ErrorFormat,
+    pub problem_type_base_uri: Arc<str>,
+    /// The path of the request, used as the `instance` of a problem.
+    pub instance: String,
+    /// The template of the HTML error pages. Only set if the format is [`ErrorFormat::Html`].
+    pub html_template: Option<Arc<str>>,
+    pub expected_schema: ExpectedSchemaFormat,
+}
+
+impl ErrorFormatContext {
+    /// Returns the context of the request that is currently handled.
+    ///
+    /// Returns `None` outside of [`ErrorFormatContext::scope`].
+    pub fn current() -> Option<Self> {
+        CURRENT_ERROR_FORMAT.try_with(Clone::clone).ok()
+    }
+
+    /// Runs the future with this context as the [`ErrorFormatContext::current`] context.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT_ERROR_FORMAT.scope(self, future).await
+    }
+}
+
+/// A problem as defined in [RFC 9457](https://datatracker.ietf.org/doc/html/rfc9457#section-3).
+#[derive(Debug, Serialize)]
+pub struct ProblemDetails {
+    r#type: String,
+    title: Cow<'static, str>,
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    instance: String,
+    /// The content of the error, only set if the verbosity includes the error type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    timestamp: DateTime<Utc>,
+}
+
+impl ProblemDetails {
+    /// Maps a serialized error with its `error_type` tag and `error` content to a problem.
+    ///
+    /// Without the serialized error, e.g. for [`ErrorVerbosity::Message`](crate::error::ErrorVerbosity::Message),
+    /// the `type` is `about:blank`.
+    pub(crate) fn new(
+        context: &ErrorFormatContext,
+        status: StatusCode,
+        title: Cow<'static, str>,
+        error: Option<Value>,
+        error_code: Option<&'static str>,
+        request_id: Option<String>,
+        timestamp: DateTime<Utc>,
+    ) -> Self {
+        let (r#type, error) = match error {
+            Some(Value::Object(mut error)) => {
+                let r#type = match error.remove("error_type") {
+                    Some(Value::String(error_type)) => {
+                        format!("{}{error_type}", context.problem_type_base_uri)
+                    }
+                    _ => String::from("about:blank"),
+                };
+
+                (
+                    r#type,
+                    error.remove("error").filter(|error| !error.is_null()),
+                )
+            }
+            _ => (String::from("about:blank"), None),
+        };
+
+        let detail = error
+            .as_ref()
+            .and_then(|error| error.get("reason")?.as_str().map(ToOwned::to_owned));
+
+        ProblemDetails {
+            r#type,
+            title,
+            status: status.as_u16(),
+            detail,
+            instance: context.instance.clone(),
+            error,
+            error_code,
+            request_id,
+            timestamp,
+        }
+    }
+
+    /// Renders the problem as an HTML page with the template.
+    pub(crate) fn into_html_response(self, headers: HeaderMap, template: &str) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let timestamp = self.timestamp.to_rfc3339();
+
+        let html = error_page::render(
+            template,
+            &[
+                ("status", Some(&self.status.to_string())),
+                ("title", Some(self.title.as_ref())),
+                ("detail", self.detail.as_deref()),
+                ("error_code", self.error_code),
+                ("request_id", self.request_id.as_deref()),
+                ("timestamp", Some(&timestamp)),
+            ],
+        );
+
+        (
+            status,
+            headers,
+            [(CONTENT_TYPE, HeaderValue::from_static(HTML_CONTENT_TYPE))],
+            html,
+        )
+            .into_response()
+    }
+
+    pub(crate) fn into_response(self, headers: HeaderMap) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+        match serde_json::to_vec(&self) {
+            Ok(json) => (
+                status,
+                headers,
+                [(
+                    CONTENT_TYPE,
+                    HeaderValue::from_static(PROBLEM_JSON_CONTENT_TYPE),
+                )],
+                json,
+            )
+                .into_response(),
+            Err(err) => {
+                tracing::error!(%err, "Failed to serialize problem details");
+
+                (status, headers).into_response()
+            }
+        }
+    }
+}
@@ -0,0 +1,417 @@
+//! Limits the request rate per key, e.g. per client IP, API key or JWT subject.
+//!
+//! The limits are kept in memory and are not shared between instances.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{Debug, Display},
+    future::Future,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{request::Parts, HeaderMap, HeaderValue},
+};
+use serde::Deserialize;
+
+use crate::{
+    credentials::api_key_digest,
+    error::ErrorVerbosityProvider,
+    extractor::{
+        api_key::ApiKeyProvider,
+        client_ip::{ApiClientIp, TrustedProxiesProvider},
+        jwt::{ApiJwt, JwksProvider},
+    },
+    revocation::TokenRevocationProvider,
+};
+
+/// Stale keys are only pruned once this many keys are tracked.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Stale keys are pruned at most this often.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Requests of new keys share the [`OVERFLOW_KEY`] once this many keys are tracked.
+pub(crate) const MAX_TRACKED_KEYS: usize = 100_000;
+
+const OVERFLOW_KEY: &str = "overflow";
+
+/// How long an API key found valid gets its own limit without being validated again.
+const VALIDATED_API_KEY_TIME_TO_LIVE: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum RateLimitAlgorithm {
+    /// Allows bursts of up to `capacity` requests, refilled at `refill_per_second`.
+    TokenBucket {
+        capacity: u32,
+        refill_per_second: f64,
+    },
+    /// Allows `requests` within any window of `window_in_seconds`.
+    SlidingWindow {
+        requests: u32,
+        window_in_seconds: u64,
+    },
+}
+
+/// What requests are limited by.
+///
+/// Requests without a valid API key or a valid JWT are limited by their client IP.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub enum RateLimitKey {
+    #[default]
+    ClientIp,
+    ApiKey,
+    JwtSubject,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitConfig {
+    pub algorithm: RateLimitAlgorithm,
+    #[serde(default)]
+    pub key: RateLimitKey,
+}
+
+/// The state of a key's limit after a request.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitStatus {
+    pub limit: u32,
+    pub remaining: u32,
+    /// Time until the limit is fully replenished.
+    pub reset_after: Duration,
+}
+
+impl RateLimitStatus {
+    /// Inserts the `X-RateLimit-Limit`, `X-RateLimit-Remaining` and `X-RateLimit-Reset` headers.
+    pub fn insert_headers(&self, headers: &mut HeaderMap) {
+        headers.insert("X-RateLimit-Limit", HeaderValue::from(self.limit));
+        headers.insert("X-RateLimit-Remaining", HeaderValue::from(self.remaining));
+        headers.insert(
+            "X-RateLimit-Reset",
+            HeaderValue::from(ceil_secs(self.reset_after)),
+        );
+    }
+}
+
+/// A request that exceeded its key's limit.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitExceeded {
+    pub status: RateLimitStatus,
+    pub retry_after: Duration,
+}
+
+/// Returns the duration in whole seconds, rounded up.
+pub fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+enum Bucket {
+    TokenBucket { tokens: f64, refilled: Instant },
+    SlidingWindow { requests: VecDeque<Instant> },
+}
+
+impl Bucket {
+    fn last_used(&self) -> Option<Instant> {
+        match self {
+            Self::TokenBucket { refilled, .. } => Some(*refilled),
+            Self::SlidingWindow { requests } => requests.back().copied(),
+        }
+    }
+}
+
+struct Buckets {
+    buckets: HashMap<String, Bucket>,
+    pruned_at: Instant,
+}
+
+pub struct RateLimiter {
+    algorithm: RateLimitAlgorithm,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(algorithm: RateLimitAlgorithm) -> Self {
+        Self {
+            algorithm,
+            buckets: Mutex::new(Buckets {
+                buckets: HashMap::new(),
+                pruned_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Time after which an unused key is back at its full limit.
+    fn idle_time(&self) -> Duration {
+        match self.algorithm {
+            RateLimitAlgorithm::TokenBucket {
+                capacity,
+                refill_per_second,
+            } => Duration::from_secs_f64(f64::from(capacity) / refill_per_second.max(f64::EPSILON)),
+            RateLimitAlgorithm::SlidingWindow {
+                window_in_seconds, ..
+            } => Duration::from_secs(window_in_seconds),
+        }
+    }
+
+    /// Counts a request against the key's limit.
+    pub fn check(&self, key: &str) -> Result<RateLimitStatus, RateLimitExceeded> {
+        let now = Instant::now();
+        let mut guard = self
+            .buckets
+            .lock()
+            .expect("rate limit buckets mutex poisoned");
+        let Buckets { buckets, pruned_at } = &mut *guard;
+
+        if buckets.len() >= PRUNE_THRESHOLD && now.duration_since(*pruned_at) >= PRUNE_INTERVAL {
+            let idle_time = self.idle_time();
+            *pruned_at = now;
+
+            buckets.retain(|_, bucket| {
+                bucket
+                    .last_used()
+                    .is_some_and(|last_used| now.duration_since(last_used) < idle_time)
+            });
+        }
+
+        let key = if buckets.len() >= MAX_TRACKED_KEYS && !buckets.contains_key(key) {
+            OVERFLOW_KEY
+        } else {
+            key
+        };
+
+        match self.algorithm {
+            RateLimitAlgorithm::TokenBucket {
+                capacity,
+                refill_per_second,
+            } => {
+                let bucket = buckets
+                    .entry(key.to_owned())
+                    .or_insert_with(|| Bucket::TokenBucket {
+                        tokens: f64::from(capacity),
+                        refilled: now,
+                    });
+
+                let Bucket::TokenBucket { tokens, refilled } = bucket else {
+                    unreachable!("the algorithm of a limiter does not change");
+                };
+
+                let elapsed = now.duration_since(*refilled).as_secs_f64();
+                *tokens = (*tokens + elapsed * refill_per_second).min(f64::from(capacity));
+                *refilled = now;
+
+                let refill_time = |missing: f64| {
+                    Duration::from_secs_f64(missing.max(0.0) / refill_per_second.max(f64::EPSILON))
+                };
+
+                if *tokens < 1.0 {
+                    return Err(RateLimitExceeded {
+                        status: RateLimitStatus {
+                            limit: capacity,
+                            remaining: 0,
+                            reset_after: refill_time(f64::from(capacity) - *tokens),
+                        },
+                        retry_after: refill_time(1.0 - *tokens),
+                    });
+                }
+
+                *tokens -= 1.0;
+
+                Ok(RateLimitStatus {
+                    limit: capacity,
+                    remaining: *tokens as u32,
+                    reset_after: refill_time(f64::from(capacity) - *tokens),
+                })
+            }
+            RateLimitAlgorithm::SlidingWindow {
+                requests: limit,
+                window_in_seconds,
+            } => {
+                let window = Duration::from_secs(window_in_seconds);
+
+                let bucket =
+                    buckets
+                        .entry(key.to_owned())
+                        .or_insert_with(|| Bucket::SlidingWindow {
+                            requests: VecDeque::new(),
+                        });
+
+                let Bucket::SlidingWindow { requests } = bucket else {
+                    unreachable!("the algorithm of a limiter does not change");
+                };
+
+                while requests
+                    .front()
+                    .is_some_and(|request| now.duration_since(*request) >= window)
+                {
+                    requests.pop_front();
+                }
+
+                let expires_after = |request: Option<&Instant>| {
+                    request.map_or(Duration::ZERO, |request| {
+                        window.saturating_sub(now.duration_since(*request))
+                    })
+                };
+
+                if requests.len() >= limit as usize {
+                    return Err(RateLimitExceeded {
+                        status: RateLimitStatus {
+                            limit,
+                            remaining: 0,
+                            reset_after: expires_after(requests.back()),
+                        },
+                        retry_after: expires_after(requests.front()),
+                    });
+                }
+
+                requests.push_back(now);
+
+                Ok(RateLimitStatus {
+                    limit,
+                    remaining: limit - requests.len() as u32,
+                    reset_after: expires_after(requests.back()),
+                })
+            }
+        }
+    }
+}
+
+pub trait RateLimitKeyExtractor<S> {
+    /// Returns the key the request is limited by.
+    ///
+    /// Returns `None` if the request is not limited.
+    fn rate_limit_key(
+        &self,
+        parts: &mut Parts,
+        state: &S,
+    ) -> impl Future<Output = Option<String>> + Send;
+}
+
+#[derive(Debug, Deserialize)]
+struct SubjectClaim {
+    sub: Option<String>,
+}
+
+impl RateLimitKey {
+    /// Returns the client IP, ignoring forwarding headers that can not be trusted.
+    async fn client_ip<S>(parts: &mut Parts, state: &S) -> Option<String>
+    where
+        S: TrustedProxiesProvider + ErrorVerbosityProvider + Send + Sync,
+    {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+
+        let ip = match ApiClientIp::from_request_parts(parts, state).await {
+            Ok(ApiClientIp(ip)) => Some(ip),
+            Err(_) => peer,
+        };
+
+        ip.map(|ip| format!("ip:{ip}"))
+    }
+}
+
+/// Digests of the API keys found valid, with the time until they are trusted without validating them again.
+#[derive(Default)]
+struct ValidatedApiKeys {
+    valid_until: Mutex<HashMap<String, Instant>>,
+}
+
+impl ValidatedApiKeys {
+    fn contains(&self, digest: &str) -> bool {
+        let now = Instant::now();
+
+        self.valid_until
+            .lock()
+            .expect("validated API keys mutex poisoned")
+            .get(digest)
+            .is_some_and(|valid_until| *valid_until > now)
+    }
+
+    /// Keys found valid while [`MAX_TRACKED_KEYS`] keys are remembered are validated on every request.
+    fn insert(&self, digest: String) {
+        let now = Instant::now();
+        let mut valid_until = self
+            .valid_until
+            .lock()
+            .expect("validated API keys mutex poisoned");
+
+        if valid_until.len() >= MAX_TRACKED_KEYS {
+            valid_until.retain(|_, valid_until| *valid_until > now);
+        }
+
+        if valid_until.len() < MAX_TRACKED_KEYS {
+            valid_until.insert(digest, now + VALIDATED_API_KEY_TIME_TO_LIVE);
+        }
+    }
+}
+
+/// Extracts the [`RateLimitKey`] of requests.
+///
+/// API keys found valid are remembered by their digest, so that counting a request does not validate its key every time.
+#[derive(Clone)]
+pub struct RateLimitKeyResolver {
+    key: RateLimitKey,
+    validated_api_keys: Arc<ValidatedApiKeys>,
+}
+
+impl From<RateLimitKey> for RateLimitKeyResolver {
+    fn from(key: RateLimitKey) -> Self {
+        Self {
+            key,
+            validated_api_keys: Arc::default(),
+        }
+    }
+}
+
+impl<S> RateLimitKeyExtractor<S> for RateLimitKeyResolver
+where
+    S: TrustedProxiesProvider
+        + ApiKeyProvider
+        + JwksProvider
+        + TokenRevocationProvider
+        + ErrorVerbosityProvider
+        + Send
+        + Sync,
+    <S as JwksProvider>::Error: Into<anyhow::Error> + Display,
+    <S as TokenRevocationProvider>::Error: Into<anyhow::Error> + Display,
+{
+    async fn rate_limit_key(&self, parts: &mut Parts, state: &S) -> Option<String> {
+        match self.key {
+            RateLimitKey::ClientIp => {}
+            RateLimitKey::ApiKey => {
+                // Only valid keys get their own limit, so made up keys can not evade the limit of the client IP.
+                let api_key = parts
+                    .headers
+                    .get(state.header_name())
+                    .and_then(|api_key| api_key.to_str().ok());
+
+                if let Some(api_key) = api_key {
+                    let digest = api_key_digest(api_key);
+
+                    if self.validated_api_keys.contains(&digest) {
+                        return Some(format!("api_key:{digest}"));
+                    }
+
+                    if state.validate(api_key).await.is_ok() {
+                        self.validated_api_keys.insert(digest.clone());
+
+                        return Some(format!("api_key:{digest}"));
+                    }
+                }
+            }
+            RateLimitKey::JwtSubject => {
+                if let Ok(ApiJwt(SubjectClaim { sub: Some(sub) })) =
+                    ApiJwt::<SubjectClaim>::from_request_parts(parts, state).await
+                {
+                    return Some(format!("sub:{sub}"));
+                }
+            }
+        }
+
+        RateLimitKey::client_ip(parts, state).await
+    }
+}
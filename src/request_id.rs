@@ -0,0 +1,87 @@
+//! Identifies requests, so error reports of users can be correlated with the logs.
+//!
+//! The id of the current request is available through [`RequestId::current`] while the request is handled.
+
+use std::{fmt::Display, future::Future};
+
+use axum::http::{HeaderValue, Request};
+use tracing::Span;
+use uuid::Uuid;
+
+/// Header holding the request id.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Propagated ids longer than this are replaced with a generated one.
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: RequestId;
+}
+
+/// The id of a request, stored in the request extensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// Generates a new time ordered id.
+    pub fn generate() -> Self {
+        Self(Uuid::now_v7().to_string())
+    }
+
+    /// Uses the id of the header if it is valid, otherwise generates a new one.
+    pub fn from_header(header: Option<&HeaderValue>) -> Self {
+        header
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| {
+                !value.is_empty()
+                    && value.len() <= MAX_REQUEST_ID_LENGTH
+                    && value.bytes().all(|byte| byte.is_ascii_graphic())
+            })
+            .map(|value| Self(value.to_owned()))
+            .unwrap_or_else(Self::generate)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn header_value(&self) -> HeaderValue {
+        HeaderValue::from_str(&self.0).expect("request id is a valid header value")
+    }
+
+    /// Returns the id of the request that is currently handled.
+    ///
+    /// Returns `None` outside of [`RequestId::scope`].
+    pub fn current() -> Option<Self> {
+        CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+    }
+
+    /// Runs the future with this id as the [`RequestId::current`] id.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT_REQUEST_ID.scope(self, future).await
+    }
+}
+
+impl Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Creates the span of a request with its [`RequestId`].
+///
+/// Used with [`TraceLayer::make_span_with`](tower_http::trace::TraceLayer::make_span_with).
+pub fn make_span<B>(request: &Request<B>) -> Span {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(ToString::to_string);
+
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        version = ?request.version(),
+        request_id,
+    )
+}
@@ -0,0 +1,308 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{
+        header::{ACCEPT, ACCEPT_LANGUAGE, CONTENT_TYPE},
+        request::Parts,
+        HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::convert::Infallible;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider},
+    extractor::{cbor::CBOR_CONTENT_TYPE, msgpack::MSGPACK_CONTENT_TYPES},
+    locale::LocaleCatalogProvider,
+    openapi::{ApiOperation, OperationInput, OperationOutput},
+    request_id::REQUEST_ID_HEADER,
+    state::PrivateErrorVerbosity,
+};
+
+/// Format of the response body chosen from the `Accept` header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResponseFormat {
+    #[default]
+    Json,
+    Yaml,
+    MsgPack,
+    Cbor,
+}
+
+impl ResponseFormat {
+    /// Selects the supported format with the highest quality in the `Accept` header.
+    /// Media ranges of equal quality are preferred in the order of the header.
+    ///
+    /// Falls back to JSON if the `Accept` header is missing or accepts none of the supported formats.
+    fn from_accept(accept: Option<&str>) -> Self {
+        let Some(accept) = accept else {
+            return Self::Json;
+        };
+
+        let mut media_ranges = accept
+            .split(',')
+            .filter_map(|part| {
+                let mut split = part.trim().split(';');
+                let media_range = split.next()?.trim().to_ascii_lowercase();
+
+                let quality = split
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .and_then(|quality| quality.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+
+                (quality > 0.0).then_some((media_range, quality))
+            })
+            .collect::<Vec<_>>();
+
+        // The sort is stable, so the order of the header is kept for equal qualities.
+        media_ranges.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        media_ranges
+            .iter()
+            .find_map(|(media_range, _)| Self::from_media_range(media_range))
+            .unwrap_or(Self::Json)
+    }
+
+    fn from_media_range(media_range: &str) -> Option<Self> {
+        match media_range {
+            "application/json" | "application/*" | "*/*" => Some(Self::Json),
+            "application/yaml" | "text/yaml" => Some(Self::Yaml),
+            CBOR_CONTENT_TYPE => Some(Self::Cbor),
+            _ if MSGPACK_CONTENT_TYPES.contains(&media_range) => Some(Self::MsgPack),
+            _ => None,
+        }
+    }
+
+    /// Serializes `body` in this format.
+    ///
+    /// Serialization failures are returned as [`ApiError`]s with the given verbosity.
+    fn into_response<T: Serialize>(
+        self,
+        status_code: StatusCode,
+        body: &T,
+        verbosity: PrivateErrorVerbosity,
+    ) -> Response {
+        match self {
+            ResponseFormat::Json => match serde_json::to_vec(body) {
+                Ok(json) => (
+                    status_code,
+                    [(CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+                    json,
+                )
+                    .into_response(),
+                Err(err) => ApiError::from_generic_error(verbosity, err).into_response(),
+            },
+            ResponseFormat::Yaml => match serde_yaml::to_string(body) {
+                Ok(yaml) => (
+                    status_code,
+                    [(CONTENT_TYPE, HeaderValue::from_static("application/yaml"))],
+                    yaml,
+                )
+                    .into_response(),
+                Err(err) => ApiError::from_generic_error(verbosity, err).into_response(),
+            },
+            ResponseFormat::MsgPack => match rmp_serde::to_vec_named(body) {
+                Ok(msgpack) => (
+                    status_code,
+                    [(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static("application/msgpack"),
+                    )],
+                    msgpack,
+                )
+                    .into_response(),
+                Err(err) => ApiError::from_generic_error(verbosity, err).into_response(),
+            },
+            ResponseFormat::Cbor => {
+                let mut cbor = Vec::new();
+
+                match ciborium::into_writer(body, &mut cbor) {
+                    Ok(()) => (
+                        status_code,
+                        [(CONTENT_TYPE, HeaderValue::from_static(CBOR_CONTENT_TYPE))],
+                        cbor,
+                    )
+                        .into_response(),
+                    Err(err) => ApiError::from_generic_error(verbosity, err).into_response(),
+                }
+            }
+        }
+    }
+}
+
+/// Everything needed to build an [`ApiResponse`] for the current request.
+///
+/// This extractor never fails.
+#[derive(Debug, Clone)]
+pub struct ResponseContext {
+    accept_language: Option<String>,
+    request_id: Option<String>,
+    format: ResponseFormat,
+}
+
+impl OperationInput for ResponseContext {}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ResponseContext
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(ToString::to_string)
+        };
+
+        Ok(ResponseContext {
+            accept_language: header(ACCEPT_LANGUAGE.as_str()),
+            request_id: header(REQUEST_ID_HEADER),
+            format: ResponseFormat::from_accept(header(ACCEPT.as_str()).as_deref()),
+        })
+    }
+}
+
+impl ResponseContext {
+    /// Wraps the data in an [`ApiResponse`] with the message resolved from the locale catalog.
+    pub fn respond<T, S>(&self, state: &S, data: T, message_key: &str) -> ApiResponse<T>
+    where
+        S: LocaleCatalogProvider + ErrorVerbosityProvider,
+    {
+        let message = state
+            .locale_catalog()
+            .resolve(self.accept_language.as_deref(), message_key)
+            .to_string();
+
+        ApiResponse {
+            status_code: StatusCode::OK,
+            body: ApiResponseBody {
+                data,
+                message,
+                request_id: self.request_id.clone(),
+            },
+            format: self.format,
+            verbosity: state.error_verbosity(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ApiResponseBody<T> {
+    data: T,
+    message: String,
+    request_id: Option<String>,
+}
+
+/// Success response envelope holding the data, a localized message and the request id.
+///
+/// Created by [`ResponseContext::respond`].
+#[derive(Debug)]
+pub struct ApiResponse<T> {
+    status_code: StatusCode,
+    body: ApiResponseBody<T>,
+    format: ResponseFormat,
+    /// Used if the body can not be serialized.
+    verbosity: PrivateErrorVerbosity,
+}
+
+impl<T> OperationOutput for ApiResponse<T> {}
+
+impl<T> ApiResponse<T> {
+    pub fn with_status_code(mut self, status_code: StatusCode) -> Self {
+        self.status_code = status_code;
+
+        self
+    }
+}
+
+impl<T: Serialize> IntoResponse for ApiResponse<T> {
+    fn into_response(self) -> Response {
+        self.format
+            .into_response(self.status_code, &self.body, self.verbosity)
+    }
+}
+
+/// Negotiates the response format of the current request from the `Accept` header.
+///
+/// This extractor never fails.
+#[derive(Debug, Clone, Copy)]
+pub struct Negotiator {
+    format: ResponseFormat,
+    verbosity: PrivateErrorVerbosity,
+}
+
+impl OperationInput for Negotiator {}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Negotiator
+where
+    S: Send + Sync + ErrorVerbosityProvider,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let accept = parts
+            .headers
+            .get(ACCEPT)
+            .and_then(|value| value.to_str().ok());
+
+        Ok(Negotiator {
+            format: ResponseFormat::from_accept(accept),
+            verbosity: state.error_verbosity(),
+        })
+    }
+}
+
+impl Negotiator {
+    pub fn format(&self) -> ResponseFormat {
+        self.format
+    }
+
+    /// Wraps the data in a [`Negotiated`] response.
+    pub fn negotiate<T>(&self, data: T) -> Negotiated<T> {
+        Negotiated {
+            status_code: StatusCode::OK,
+            data,
+            format: self.format,
+            verbosity: self.verbosity,
+        }
+    }
+}
+
+/// Response serialized as JSON, YAML, MessagePack or CBOR depending on the `Accept` header.
+///
+/// Created by [`Negotiator::negotiate`].
+#[derive(Debug)]
+pub struct Negotiated<T> {
+    status_code: StatusCode,
+    data: T,
+    format: ResponseFormat,
+    verbosity: PrivateErrorVerbosity,
+}
+
+impl<T: JsonSchema> OperationOutput for Negotiated<T> {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.response::<T>()
+    }
+}
+
+impl<T> Negotiated<T> {
+    pub fn with_status_code(mut self, status_code: StatusCode) -> Self {
+        self.status_code = status_code;
+
+        self
+    }
+}
+
+impl<T: Serialize> IntoResponse for Negotiated<T> {
+    fn into_response(self) -> Response {
+        self.format
+            .into_response(self.status_code, &self.data, self.verbosity)
+    }
+}
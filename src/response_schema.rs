@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use axum::response::{IntoResponseParts, ResponseParts};
+use schemars::{schema::RootSchema, schema_for, JsonSchema};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResponseSchemaValidationConfig {
+    /// Replace mismatching responses with an internal server error instead of only logging them.
+    #[serde(default)]
+    pub fail_on_mismatch: bool,
+}
+
+/// Schema of a JSON response.
+///
+/// Can be returned as a part of the response to validate the response body against the schema of `T`,
+/// see [`response_schema_validation`](crate::middleware::response_schema_validation::response_schema_validation).
+/// The schema is only generated if response schema validation is enabled.
+#[derive(Debug, Clone, Copy)]
+pub struct ResponseSchema(pub fn() -> RootSchema);
+
+impl ResponseSchema {
+    pub fn of<T: JsonSchema>() -> Self {
+        Self(|| schema_for!(T))
+    }
+}
+
+impl IntoResponseParts for ResponseSchema {
+    type Error = std::convert::Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        res.extensions_mut().insert(self);
+
+        Ok(res)
+    }
+}
+
+/// Response schemas registered per route.
+#[derive(Debug, Default)]
+pub struct ResponseSchemaRegistry {
+    schemas: HashMap<String, ResponseSchema>,
+}
+
+impl ResponseSchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `T` as the response schema of the route with the given path.
+    pub fn register<T: JsonSchema>(mut self, path: impl Into<String>) -> Self {
+        self.schemas.insert(path.into(), ResponseSchema::of::<T>());
+
+        self
+    }
+
+    pub fn get(&self, path: &str) -> Option<ResponseSchema> {
+        self.schemas.get(path).copied()
+    }
+}
+
+pub trait ResponseSchemaValidationProvider {
+    /// Returns the response schema validation config.
+    ///
+    /// Returns `None` if response schema validation is disabled.
+    fn response_schema_validation(&self) -> Option<&ResponseSchemaValidationConfig>;
+
+    /// Returns the registered response schema of the route with the given path.
+    fn registered_response_schema(&self, path: &str) -> Option<ResponseSchema>;
+}
@@ -0,0 +1,132 @@
+//! Revocation of JWTs before their expiry.
+//!
+//! A token is identified by its `jti` claim or, if it has none, by the hex encoded SHA-256 digest of the token.
+//! [`ApiJwt`](crate::extractor::jwt::ApiJwt) rejects tokens whose id is revoked.
+//! Revocations only have to be kept until the token expires.
+
+use std::{
+    future::Future,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    extractor::jwt::validation::JwtValidator,
+    store::{KeyValueStore, StoreError},
+};
+
+const KEY_PREFIX: &str = "revoked_token:";
+
+pub trait TokenRevocationProvider {
+    type Error;
+
+    /// Returns whether the token with the given id is revoked.
+    fn is_token_revoked(
+        &self,
+        token_id: &str,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+
+    /// Revokes the token with the given id. The revocation is forgotten after `time_to_live`.
+    fn revoke_token(
+        &self,
+        token_id: &str,
+        time_to_live: Duration,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+#[derive(Deserialize)]
+struct RevocationClaims {
+    jti: Option<String>,
+    exp: Option<u64>,
+}
+
+/// The id and the `exp` claim of a token as used for revocation.
+#[derive(Debug, Clone)]
+pub struct RevocableToken {
+    pub id: String,
+    pub exp: Option<u64>,
+}
+
+impl RevocableToken {
+    /// Reads the `jti` and `exp` claims of the token without validating the token.
+    ///
+    /// Tokens without a `jti` claim or with a malformed payload are identified by their digest.
+    pub fn from_jwt(jwt: &str) -> Self {
+        let claims = JwtValidator::unverified_claims::<RevocationClaims>(jwt).ok();
+        let exp = claims.as_ref().and_then(|claims| claims.exp);
+
+        let id = claims
+            .and_then(|claims| claims.jti)
+            .unwrap_or_else(|| hex::encode(Sha256::digest(jwt.as_bytes())));
+
+        Self { id, exp }
+    }
+
+    /// Returns how long the revocation has to be kept, which is zero for expired tokens.
+    ///
+    /// Falls back to `default_time_to_live` if the expiry of the token is unknown.
+    pub fn time_to_live(&self, default_time_to_live: Duration) -> Duration {
+        let Some(exp) = self.exp else {
+            return default_time_to_live;
+        };
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Duration::from_secs(exp.saturating_sub(now))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenRevocationConfig {
+    /// How long a revocation is kept if the expiry of the token is unknown.
+    #[serde(default = "default_time_to_live_in_seconds")]
+    pub default_time_to_live_in_seconds: u64,
+}
+
+fn default_time_to_live_in_seconds() -> u64 {
+    24 * 60 * 60
+}
+
+impl Default for TokenRevocationConfig {
+    fn default() -> Self {
+        Self {
+            default_time_to_live_in_seconds: default_time_to_live_in_seconds(),
+        }
+    }
+}
+
+/// Keeps each revocation under the prefixed token id.
+impl<K> TokenRevocationProvider for K
+where
+    K: KeyValueStore + Sync,
+    StoreError: From<K::Error>,
+{
+    type Error = StoreError;
+
+    async fn is_token_revoked(&self, token_id: &str) -> Result<bool, Self::Error> {
+        Ok(self
+            .get(&format!("{KEY_PREFIX}{token_id}"))
+            .await?
+            .is_some())
+    }
+
+    async fn revoke_token(
+        &self,
+        token_id: &str,
+        time_to_live: Duration,
+    ) -> Result<(), Self::Error> {
+        self.set(
+            &format!("{KEY_PREFIX}{token_id}"),
+            String::from("1"),
+            time_to_live,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
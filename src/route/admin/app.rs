@@ -0,0 +1,43 @@
+use crate::{
+    openapi::router::{get_with, post_with, ApiRouter},
+    state::ApiState,
+};
+
+pub fn app() -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new()
+        .api_route(
+            "/endpoints",
+            get_with(
+                super::list_endpoint_lifecycles::list_endpoint_lifecycles,
+                |operation| operation.summary("Lists the lifecycle registry"),
+            ),
+        )
+        .api_route(
+            "/usage",
+            get_with(super::get_usage::get_usage, |operation| {
+                operation
+                    .summary("Returns the usage rollups of the current and the last flushed window")
+            }),
+        )
+        .api_route(
+            "/metrics",
+            get_with(super::get_metrics::get_metrics, |operation| {
+                operation.summary("Returns the counters of the server")
+            }),
+        )
+        .api_route(
+            "/revoke_token",
+            post_with(super::revoke_token::revoke_token, |operation| {
+                operation.summary("Revokes a token until it expires")
+            }),
+        )
+        .api_route(
+            "/maintenance",
+            get_with(super::maintenance::get_maintenance, |operation| {
+                operation.summary("Returns whether the maintenance mode is enabled")
+            })
+            .put_with(super::maintenance::set_maintenance, |operation| {
+                operation.summary("Enables or disables the maintenance mode")
+            }),
+        )
+}
@@ -0,0 +1,48 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    catch_panic::PanicCounterProvider,
+    error_metrics::{ErrorCount, ErrorCounterProvider},
+    extractor::authenticated_basic_auth::ApiAuthenticatedBasicAuth,
+    openapi::OperationOutput,
+    slow_request::SlowRequestProvider,
+    state::ApiState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct GetMetricsResponse {
+    /// Number of panics caught since the server started.
+    panics: u64,
+    /// Number of requests exceeding the slow request threshold since the server started.
+    slow_requests: u64,
+    /// Number of error responses by error type and status since the server started.
+    errors: Vec<ErrorCount>,
+}
+
+impl OperationOutput for GetMetricsResponse {}
+
+impl IntoResponse for GetMetricsResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Returns the counters of the server.
+///
+/// This function will reject if [`ApiAuthenticatedBasicAuth`] rejects.
+pub async fn get_metrics(
+    _: ApiAuthenticatedBasicAuth,
+    State(state): State<ApiState>,
+) -> GetMetricsResponse {
+    GetMetricsResponse {
+        panics: state.panic_counter().count(),
+        slow_requests: state.slow_request_counter().count(),
+        errors: state.error_counter().counts(),
+    }
+}
@@ -0,0 +1,47 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    analytics::UsageRollup,
+    error::{ApiError, ErrorVerbosityProvider, NotFoundError},
+    extractor::authenticated_basic_auth::ApiAuthenticatedBasicAuth,
+    openapi::OperationOutput,
+    state::ApiState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct GetUsageResponse {
+    current: Vec<UsageRollup>,
+    last_flushed: Vec<UsageRollup>,
+}
+
+impl OperationOutput for GetUsageResponse {}
+
+impl IntoResponse for GetUsageResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Returns the usage rollups of the current and the last flushed window.
+///
+/// Returns [`NotFoundError`] if usage analytics is disabled.
+/// This function will reject if [`ApiAuthenticatedBasicAuth`] rejects.
+pub async fn get_usage(
+    _: ApiAuthenticatedBasicAuth,
+    State(state): State<ApiState>,
+) -> Result<GetUsageResponse, ApiError> {
+    let usage_analytics = state
+        .usage_analytics()
+        .ok_or_else(|| NotFoundError::new(state.error_verbosity()))?;
+
+    Ok(GetUsageResponse {
+        current: usage_analytics.current_rollups(),
+        last_flushed: usage_analytics.last_flushed_rollups(),
+    })
+}
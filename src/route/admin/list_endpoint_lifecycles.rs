@@ -0,0 +1,39 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    extractor::authenticated_basic_auth::ApiAuthenticatedBasicAuth,
+    lifecycle::{EndpointLifecycleEntry, EndpointLifecycleProvider},
+    openapi::OperationOutput,
+    state::ApiState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ListEndpointLifecyclesResponse {
+    endpoints: Vec<EndpointLifecycleEntry>,
+}
+
+impl OperationOutput for ListEndpointLifecyclesResponse {}
+
+impl IntoResponse for ListEndpointLifecyclesResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Lists the lifecycle registry.
+///
+/// This function will reject if [`ApiAuthenticatedBasicAuth`] rejects.
+pub async fn list_endpoint_lifecycles(
+    _: ApiAuthenticatedBasicAuth,
+    State(state): State<ApiState>,
+) -> ListEndpointLifecyclesResponse {
+    ListEndpointLifecyclesResponse {
+        endpoints: state.endpoint_lifecycles().to_vec(),
+    }
+}
@@ -0,0 +1,60 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extractor::{authenticated_basic_auth::ApiAuthenticatedBasicAuth, json::ApiJson},
+    maintenance::MaintenanceModeProvider,
+    openapi::OperationOutput,
+    state::ApiState,
+};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetMaintenanceRequest {
+    enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MaintenanceResponse {
+    enabled: bool,
+}
+
+impl OperationOutput for MaintenanceResponse {}
+
+impl IntoResponse for MaintenanceResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Returns whether the maintenance mode is enabled.
+///
+/// This function will reject if [`ApiAuthenticatedBasicAuth`] rejects.
+pub async fn get_maintenance(
+    _: ApiAuthenticatedBasicAuth,
+    State(state): State<ApiState>,
+) -> MaintenanceResponse {
+    MaintenanceResponse {
+        enabled: state.maintenance_mode().is_enabled(),
+    }
+}
+
+/// Enables or disables the maintenance mode.
+///
+/// This function will reject if [`ApiAuthenticatedBasicAuth`] rejects.
+pub async fn set_maintenance(
+    _: ApiAuthenticatedBasicAuth,
+    State(state): State<ApiState>,
+    ApiJson(request): ApiJson<SetMaintenanceRequest>,
+) -> MaintenanceResponse {
+    state.maintenance_mode().set_enabled(request.enabled);
+
+    MaintenanceResponse {
+        enabled: request.enabled,
+    }
+}
@@ -0,0 +1,6 @@
+pub mod app;
+pub mod get_metrics;
+pub mod get_usage;
+pub mod list_endpoint_lifecycles;
+pub mod maintenance;
+pub mod revoke_token;
@@ -0,0 +1,52 @@
+use axum::{extract::State, http::StatusCode};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider},
+    extractor::{authenticated_basic_auth::ApiAuthenticatedBasicAuth, json::ApiJson},
+    revocation::{RevocableToken, TokenRevocationProvider},
+    server_error,
+    state::ApiState,
+};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum RevokeTokenRequest {
+    /// The token itself. Revoked by its `jti` or, if it has none, by its digest.
+    Token { token: String },
+    /// The `jti` claim of the token and optionally its `exp` claim.
+    Jti { jti: String, exp: Option<u64> },
+}
+
+/// Revokes a token until it expires.
+///
+/// If the expiry of the token is unknown, the revocation is kept for the configured default time to live.
+/// This function will reject if [`ApiAuthenticatedBasicAuth`] rejects.
+pub async fn revoke_token(
+    _: ApiAuthenticatedBasicAuth,
+    State(state): State<ApiState>,
+    ApiJson(request): ApiJson<RevokeTokenRequest>,
+) -> Result<StatusCode, ApiError> {
+    let token = match request {
+        RevokeTokenRequest::Token { token } => RevocableToken::from_jwt(&token),
+        RevokeTokenRequest::Jti { jti, exp } => RevocableToken { id: jti, exp },
+    };
+
+    let time_to_live = token.time_to_live(state.token_revocation_time_to_live());
+
+    if time_to_live.is_zero() {
+        tracing::debug!(token_id = %token.id, "Token already expired");
+
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
+    state
+        .revoke_token(&token.id, time_to_live)
+        .await
+        .map_err(server_error!(state))?;
+
+    tracing::info!(token_id = %token.id, "Revoked token");
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,20 +1,26 @@
-use axum::{middleware::from_fn_with_state, routing::get, Router};
+use crate::{
+    middleware::api_key::layer::ApiKeyLayer,
+    openapi::router::{get, get_with, ApiRouter},
+    state::ApiState,
+};
 
-use crate::{middleware::validate_api_key_and_put_as_extension, state::ApiState};
-
-pub fn app(state: ApiState) -> Router<ApiState> {
-    Router::<ApiState>::new()
-        .route("/", get(|| async { "API Key Protected" }))
-        .route(
+pub fn app(state: ApiState) -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new()
+        .api_route("/", get(|| async { "API Key Protected" }))
+        .api_route(
             "/do_not_use_extension",
             get(super::do_not_use_extension::do_not_use_extension),
         )
-        .route(
+        .api_route(
             "/valid_api_key_from_extension",
-            get(super::valid_api_key_from_extension::valid_api_key_from_extension),
+            get_with(
+                super::valid_api_key_from_extension::valid_api_key_from_extension,
+                |operation| {
+                    operation.summary(
+                        "Extracts the API key from the extension provided by the API key middleware",
+                    )
+                },
+            ),
         )
-        .layer(from_fn_with_state(
-            state,
-            validate_api_key_and_put_as_extension::validate_api_key_and_put_as_extension,
-        ))
+        .layer(ApiKeyLayer::new(state))
 }
@@ -5,11 +5,15 @@ use axum::{
 };
 use serde::Serialize;
 
+use crate::openapi::OperationOutput;
+
 #[derive(Debug, Serialize)]
 pub struct DoNotUseExtensionResponse {
     message: String,
 }
 
+impl OperationOutput for DoNotUseExtensionResponse {}
+
 impl IntoResponse for DoNotUseExtensionResponse {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
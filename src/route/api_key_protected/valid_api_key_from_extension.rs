@@ -5,20 +5,22 @@ use axum::{
 };
 use serde::Serialize;
 
-use crate::extractor::valid_api_key::ValidApiKey;
+use crate::{extractor::valid_api_key::ValidApiKey, openapi::OperationOutput};
 
 #[derive(Debug, Serialize)]
 pub struct ApiKeyFromExtensionResponse {
     used_api_key: String,
 }
 
+impl OperationOutput for ApiKeyFromExtensionResponse {}
+
 impl IntoResponse for ApiKeyFromExtensionResponse {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
     }
 }
 
-/// Extracts the API key from the [`Extension`] that was provided from [`validate_api_key_and_put_as_extension`](crate::middleware::validate_api_key_and_put_as_extension::validate_api_key_and_put_as_extension) middleware.
+/// Extracts the API key from the [`Extension`] that was provided from [`ApiKeyLayer`](crate::middleware::api_key::layer::ApiKeyLayer) middleware.
 pub async fn valid_api_key_from_extension(
     Extension(valid_api_key): Extension<ValidApiKey>,
 ) -> ApiKeyFromExtensionResponse {
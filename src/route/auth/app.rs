@@ -0,0 +1,26 @@
+use crate::{
+    openapi::router::{get_with, ApiRouter},
+    state::ApiState,
+};
+
+pub fn app() -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new()
+        .api_route(
+            "/login",
+            get_with(super::oidc_login::login, |operation| {
+                operation.summary("Starts the authorization code flow with PKCE")
+            }),
+        )
+        .api_route(
+            "/callback",
+            get_with(super::oidc_login::callback, |operation| {
+                operation.summary("Completes the authorization code flow")
+            }),
+        )
+        .api_route(
+            "/me",
+            get_with(super::oidc_login::me, |operation| {
+                operation.summary("Returns the logged in user")
+            }),
+        )
+}
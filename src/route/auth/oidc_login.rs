@@ -0,0 +1,295 @@
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::{header::SET_COOKIE, HeaderValue, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, LoginError, LoginErrorType, NotFoundError},
+    extractor::{
+        cookie::ApiCookies,
+        jwt::{validation::JwtValidator, JwksProvider},
+        query::ApiQuery,
+        session::ApiSession,
+    },
+    oidc::login::{IdTokenClaims, LoginSession, OidcLogin, PendingLogin, Pkce},
+    openapi::OperationOutput,
+    server_error,
+    session::{new_session_id, Session, SessionStore},
+    state::ApiState,
+};
+
+/// How long a started login can be completed by the callback.
+const PENDING_LOGIN_TIME_TO_LIVE: Duration = Duration::from_secs(10 * 60);
+
+/// Binds a started login to the browser that started it, so that a callback URL can not be completed by another browser.
+const LOGIN_STATE_COOKIE_NAME: &str = "login_state";
+
+fn pending_login_key(login_state: &str) -> String {
+    format!("login:{login_state}")
+}
+
+fn login_state_cookie(value: &str, max_age_in_seconds: u64) -> Option<HeaderValue> {
+    HeaderValue::from_str(&format!(
+        "{LOGIN_STATE_COOKIE_NAME}={value}; HttpOnly; Secure; SameSite=Lax; Path=/; Max-Age={max_age_in_seconds}"
+    ))
+    .ok()
+}
+
+/// Returns whether the path is relative to this server, so that the login can not be used as an open redirect.
+fn is_local_path(path: &str) -> bool {
+    path.starts_with('/') && !path.starts_with("//") && !path.starts_with("/\\")
+}
+
+fn oidc_login(state: &ApiState) -> Result<&OidcLogin, ApiError> {
+    state
+        .oidc_login()
+        .ok_or_else(|| NotFoundError::new(state.error_verbosity()).into())
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LoginQuery {
+    /// The local path to redirect to after the login.
+    pub return_to: Option<String>,
+}
+
+/// Starts the authorization code flow with PKCE.
+///
+/// Redirects to the identity provider's authorization endpoint and sets the login state as cookie.
+/// Returns [`NotFoundError`] if the login is not configured.
+pub async fn login(
+    State(state): State<ApiState>,
+    ApiQuery(query): ApiQuery<LoginQuery>,
+) -> Result<Response, ApiError> {
+    let oidc_login = oidc_login(&state)?;
+
+    let login_state = new_session_id();
+    let pkce = Pkce::generate();
+    let pending_login = PendingLogin {
+        code_verifier: pkce.code_verifier,
+        nonce: new_session_id(),
+        return_to: query
+            .return_to
+            .filter(|return_to| is_local_path(return_to))
+            .unwrap_or_else(|| String::from("/")),
+    };
+
+    let authorization_url =
+        oidc_login.authorization_url(&login_state, &pending_login.nonce, &pkce.code_challenge);
+
+    let pending_login = serde_json::to_value(pending_login).map_err(server_error!(state))?;
+
+    state
+        .store_session(
+            &pending_login_key(&login_state),
+            pending_login,
+            PENDING_LOGIN_TIME_TO_LIVE,
+        )
+        .await
+        .map_err(server_error!(state))?;
+
+    let cookie = login_state_cookie(&login_state, PENDING_LOGIN_TIME_TO_LIVE.as_secs())
+        .ok_or_else(|| {
+            ApiError::from_generic_error(
+                state.error_verbosity(),
+                anyhow::anyhow!("Invalid login state cookie"),
+            )
+        })?;
+
+    tracing::debug!("Redirecting to the identity provider");
+
+    Ok((
+        [(SET_COOKIE, cookie)],
+        Redirect::to(authorization_url.as_str()),
+    )
+        .into_response())
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LoginStateCookie {
+    /// The login state set by the login.
+    pub login_state: Option<String>,
+}
+
+/// Validates the ID token against the identity provider's JWKS with the client id as audience.
+async fn validate_id_token(
+    state: &ApiState,
+    oidc_login: &OidcLogin,
+    id_token: &str,
+) -> Result<IdTokenClaims, ApiError> {
+    let jwks = state
+        .jwks(oidc_login.issuer())
+        .await
+        .map_err(server_error!(state))?
+        .ok_or_else(|| {
+            ApiError::from_generic_error(
+                state.error_verbosity(),
+                anyhow::anyhow!("Login issuer is not trusted"),
+            )
+        })?;
+
+    JwtValidator::validate::<IdTokenClaims, _, _>(
+        id_token,
+        jwks.as_ref(),
+        &[&oidc_login.config().client_id],
+        &[oidc_login.issuer()],
+        state.validation_config(),
+    )
+    .map_err(|err| {
+        tracing::warn!(%err, "Rejection. Invalid ID token");
+
+        LoginError::new(
+            state.error_verbosity(),
+            LoginErrorType::InvalidIdToken { err },
+        )
+        .into()
+    })
+}
+
+/// Completes the authorization code flow.
+///
+/// Rejects if the `state` does not match the login state cookie of the browser.
+/// Exchanges the code for tokens, stores them in a renewed session and redirects to the path the login was started for.
+pub async fn callback(
+    State(state): State<ApiState>,
+    session: Session,
+    ApiQuery(query): ApiQuery<CallbackQuery>,
+    ApiCookies(cookies): ApiCookies<LoginStateCookie>,
+) -> Result<Response, ApiError> {
+    let verbosity = state.error_verbosity();
+    let oidc_login = oidc_login(&state)?;
+
+    let login_state = query.state.ok_or_else(|| {
+        tracing::warn!("Rejection. Missing login state");
+
+        LoginError::new(verbosity, LoginErrorType::InvalidState)
+    })?;
+
+    let started_by_browser = cookies.login_state.is_some_and(|cookie_login_state| {
+        bool::from(cookie_login_state.as_bytes().ct_eq(login_state.as_bytes()))
+    });
+
+    if !started_by_browser {
+        tracing::warn!("Rejection. Login state does not match the login state cookie");
+
+        return Err(LoginError::new(verbosity, LoginErrorType::InvalidState).into());
+    }
+
+    let key = pending_login_key(&login_state);
+
+    let pending_login = state
+        .load_session(&key)
+        .await
+        .map_err(server_error!(state))?;
+
+    // A login state can only be used once.
+    state
+        .remove_session(&key)
+        .await
+        .map_err(server_error!(state))?;
+
+    let Some(pending_login) = pending_login else {
+        tracing::warn!("Rejection. Unknown login state");
+
+        return Err(LoginError::new(verbosity, LoginErrorType::InvalidState).into());
+    };
+
+    let pending_login =
+        serde_json::from_value::<PendingLogin>(pending_login).map_err(server_error!(state))?;
+
+    if let Some(error) = query.error {
+        tracing::warn!(%error, "Rejection. Identity provider returned an error");
+
+        return Err(LoginError::new(
+            verbosity,
+            LoginErrorType::Provider {
+                error,
+                description: query.error_description,
+            },
+        )
+        .into());
+    }
+
+    let code = query.code.ok_or_else(|| {
+        tracing::warn!("Rejection. Missing authorization code");
+
+        LoginError::new(verbosity, LoginErrorType::MissingCode)
+    })?;
+
+    let tokens = oidc_login
+        .exchange_code(&code, &pending_login.code_verifier)
+        .await
+        .map_err(server_error!(state))?;
+
+    let claims = validate_id_token(&state, oidc_login, &tokens.id_token).await?;
+
+    if claims.nonce.as_deref() != Some(pending_login.nonce.as_str()) {
+        tracing::warn!("Rejection. ID token nonce does not match");
+
+        return Err(LoginError::new(verbosity, LoginErrorType::NonceMismatch).into());
+    }
+
+    tracing::debug!(sub = %claims.sub, "Logged in");
+
+    let login_session = LoginSession {
+        sub: claims.sub,
+        name: claims.name,
+        email: claims.email,
+        id_token: tokens.id_token,
+        access_token: tokens.access_token,
+        refresh_token: tokens.refresh_token,
+    };
+
+    session
+        .insert(&login_session)
+        .map_err(server_error!(state))?;
+    session.renew();
+
+    let mut response = Redirect::to(&pending_login.return_to).into_response();
+
+    if let Some(cookie) = login_state_cookie("", 0) {
+        response.headers_mut().append(SET_COOKIE, cookie);
+    }
+
+    Ok(response)
+}
+
+#[derive(Debug, Serialize)]
+pub struct MeResponse {
+    sub: String,
+    name: Option<String>,
+    email: Option<String>,
+}
+
+impl OperationOutput for MeResponse {}
+
+impl IntoResponse for MeResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Returns the logged in user using the [`ApiSession`] extractor.
+///
+/// This function will reject if there is no login session.
+pub async fn me(ApiSession(login_session): ApiSession<LoginSession>) -> MeResponse {
+    MeResponse {
+        sub: login_session.sub,
+        name: login_session.name,
+        email: login_session.email,
+    }
+}
@@ -1,36 +1,164 @@
-use axum::{routing::get, Router};
+use crate::{
+    openapi::router::{get, get_with, ApiRouter},
+    state::ApiState,
+};
 
-use crate::state::ApiState;
-
-pub fn app() -> Router<ApiState> {
-    Router::<ApiState>::new()
-        .route("/", get(|| async { "Index" }))
-        .route(
+pub fn app() -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new()
+        .api_route("/", get(|| async { "Index" }))
+        .api_route(
+            "/extract_valid_api_key_and_authenticated_basic_auth_using_extractor",
+            get(super::extract_all::extract_valid_api_key_and_authenticated_basic_auth_using_extractor),
+        )
+        .api_route(
             "/extract_valid_jwt_claims_using_extractor",
-            get(super::extract_jwt_claims::extract_valid_jwt_claims_using_extractor),
+            get_with(
+                super::extract_jwt_claims::extract_valid_jwt_claims_using_extractor,
+                |operation| operation.summary("Extracts the valid JWT claims"),
+            ),
+        )
+        .api_route(
+            "/extract_admin_jwt_claims_using_extractor",
+            get_with(
+                super::extract_jwt_claims::extract_admin_jwt_claims_using_extractor,
+                |operation| operation.summary("Extracts the JWT claims granting the `admin` role"),
+            ),
+        )
+        .api_route(
+            "/extract_introspected_token_using_extractor",
+            get_with(
+                super::extract_introspected_token::extract_introspected_token_using_extractor,
+                |operation| operation.summary("Extracts the introspected opaque bearer token"),
+            ),
         )
-        .route(
+        .api_route(
             "/extract_bearer_token_using_extractor",
-            get(super::extract_bearer_token::extract_bearer_token_using_extractor),
+            get_with(
+                super::extract_bearer_token::extract_bearer_token_using_extractor,
+                |operation| operation.summary("Extracts the bearer token"),
+            ),
         )
-        .route(
+        .api_route(
             "/extract_authenticated_basic_auth_using_extractor",
-            get(super::extract_authenticated_basic_auth::extract_authenticated_basic_auth_using_extractor),
+            get_with(
+                super::extract_authenticated_basic_auth::extract_authenticated_basic_auth_using_extractor,
+                |operation| operation.summary("Extracts the authenticated basic auth"),
+            ),
         )
-        .route(
+        .api_route(
             "/extract_basic_auth_using_extractor",
-            get(super::extract_basic_auth::extract_basic_auth_using_extractor),
+            get_with(
+                super::extract_basic_auth::extract_basic_auth_using_extractor,
+                |operation| operation.summary("Extracts the basic auth"),
+            ),
+        )
+        .api_route(
+            "/extract_client_info_using_extractor",
+            get_with(
+                super::extract_client_info::extract_client_info_using_extractor,
+                |operation| operation.summary("Extracts the client metadata"),
+            ),
+        )
+        .api_route(
+            "/extract_client_ip_using_extractor",
+            get_with(
+                super::extract_client_ip::extract_client_ip_using_extractor,
+                |operation| operation.summary("Extracts the client IP"),
+            ),
+        )
+        .api_route(
+            "/extract_cookies_using_extractor",
+            get_with(
+                super::extract_cookies::extract_cookies_using_extractor,
+                |operation| operation.summary("Extracts the cookies"),
+            ),
+        )
+        .api_route(
+            "/set_signed_cookies",
+            get_with(
+                super::extract_cookies::set_signed_cookies,
+                |operation| operation.summary("Sets a signed `session_id` cookie"),
+            ),
         )
-        .route(
+        .api_route(
+            "/extract_signed_cookies_using_extractor",
+            get_with(
+                super::extract_cookies::extract_signed_cookies_using_extractor,
+                |operation| operation.summary("Extracts the signed cookies"),
+            ),
+        )
+        .api_route(
+            "/extract_headers_using_extractor",
+            get_with(
+                super::extract_headers::extract_headers_using_extractor,
+                |operation| operation.summary("Extracts typed headers"),
+            ),
+        )
+        .api_route(
+            "/extract_locale_using_extractor",
+            get_with(
+                super::extract_locale::extract_locale_using_extractor,
+                |operation| operation.summary("Extracts the negotiated locale"),
+            ),
+        )
+        .api_route(
+            "/extract_client_cert_using_extractor",
+            get_with(
+                super::extract_client_cert::extract_client_cert_using_extractor,
+                |operation| operation.summary("Extracts the verified client certificate"),
+            ),
+        )
+        .api_route(
+            "/extract_digest_auth_using_extractor",
+            get_with(
+                super::extract_digest_auth::extract_digest_auth_using_extractor,
+                |operation| operation.summary("Authenticates the request using digest auth"),
+            ),
+        )
+        .api_route(
+            "/extract_principal_using_extractor",
+            get_with(
+                super::extract_principal::extract_principal_using_extractor,
+                |operation| operation.summary("Extracts the principal"),
+            ),
+        )
+        .api_route(
+            "/extract_tenant_using_extractor",
+            get_with(
+                super::extract_tenant::extract_tenant_using_extractor,
+                |operation| operation.summary("Extracts the tenant"),
+            ),
+        )
+        .api_route(
             "/extract_api_key_using_extractor",
-            get(super::extract_api_key::extract_api_key_using_extractor),
+            get_with(
+                super::extract_api_key::extract_api_key_using_extractor,
+                |operation| operation.summary("Extracts the API key"),
+            ),
         )
-        .route(
+        .api_route(
             "/extract_valid_api_key_using_optional_extractor",
-            get(super::extract_valid_api_key_optional::extract_valid_api_key_using_optional_extractor),
+            get_with(
+                super::extract_valid_api_key_optional::extract_valid_api_key_using_optional_extractor,
+                |operation| operation.summary("Extracts the optional valid API key"),
+            ),
+        )
+        .api_route(
+            "/extract_valid_api_key_using_optional_strict_extractor",
+            get_with(
+                super::extract_valid_api_key_optional::extract_valid_api_key_using_optional_strict_extractor,
+                |operation| {
+                    operation.summary(
+                        "Extracts the optional valid API key, rejecting invalid API keys",
+                    )
+                },
+            ),
         )
-        .route(
+        .api_route(
             "/extract_valid_api_key_using_extractor",
-            get(super::extract_valid_api_key::extract_valid_api_key_using_extractor),
+            get_with(
+                super::extract_valid_api_key::extract_valid_api_key_using_extractor,
+                |operation| operation.summary("Extracts the valid API key"),
+            ),
         )
 }
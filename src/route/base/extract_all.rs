@@ -0,0 +1,42 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    extractor::{
+        all::All, authenticated_basic_auth::ApiAuthenticatedBasicAuth, valid_api_key::ValidApiKey,
+    },
+    openapi::OperationOutput,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ExtractAllResponse {
+    used_valid_api_key: String,
+    used_username: String,
+}
+
+impl OperationOutput for ExtractAllResponse {}
+
+impl IntoResponse for ExtractAllResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Extracts the valid API key and the authenticated basic auth from the request using the [`All`] extractor.
+///
+/// This function will reject if any of the inner extractors rejects.
+pub async fn extract_valid_api_key_and_authenticated_basic_auth_using_extractor(
+    All((ValidApiKey(api_key, _), ApiAuthenticatedBasicAuth(basic_auth))): All<(
+        ValidApiKey,
+        ApiAuthenticatedBasicAuth,
+    )>,
+) -> ExtractAllResponse {
+    ExtractAllResponse {
+        used_valid_api_key: api_key.value,
+        used_username: basic_auth.username,
+    }
+}
@@ -5,13 +5,15 @@ use axum::{
 };
 use serde::Serialize;
 
-use crate::extractor::api_key::ApiKey;
+use crate::{extractor::api_key::ApiKey, openapi::OperationOutput};
 
 #[derive(Debug, Serialize)]
 pub struct ExtractApiKeyResponse {
     used_api_key: String,
 }
 
+impl OperationOutput for ExtractApiKeyResponse {}
+
 impl IntoResponse for ExtractApiKeyResponse {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
@@ -5,7 +5,9 @@ use axum::{
 };
 use serde::Serialize;
 
-use crate::extractor::authenticated_basic_auth::ApiAuthenticatedBasicAuth;
+use crate::{
+    extractor::authenticated_basic_auth::ApiAuthenticatedBasicAuth, openapi::OperationOutput,
+};
 
 #[derive(Debug, Serialize)]
 pub struct ExtractAuthenticatedBasicAuthResponse {
@@ -13,6 +15,8 @@ pub struct ExtractAuthenticatedBasicAuthResponse {
     used_password: Option<String>,
 }
 
+impl OperationOutput for ExtractAuthenticatedBasicAuthResponse {}
+
 impl IntoResponse for ExtractAuthenticatedBasicAuthResponse {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
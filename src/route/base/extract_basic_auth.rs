@@ -5,7 +5,7 @@ use axum::{
 };
 use serde::Serialize;
 
-use crate::extractor::basic_auth::ApiBasicAuth;
+use crate::{extractor::basic_auth::ApiBasicAuth, openapi::OperationOutput};
 
 #[derive(Debug, Serialize)]
 pub struct ExtractBasicAuthResponse {
@@ -13,6 +13,8 @@ pub struct ExtractBasicAuthResponse {
     used_password: Option<String>,
 }
 
+impl OperationOutput for ExtractBasicAuthResponse {}
+
 impl IntoResponse for ExtractBasicAuthResponse {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
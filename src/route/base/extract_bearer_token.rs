@@ -5,13 +5,18 @@ use axum::{
 };
 use serde::Serialize;
 
-use crate::{extractor::bearer_token::ApiBearerToken, types::used_bearer_token::UsedBearerToken};
+use crate::{
+    extractor::bearer_token::ApiBearerToken, openapi::OperationOutput,
+    types::used_bearer_token::UsedBearerToken,
+};
 
 #[derive(Debug, Serialize)]
 pub struct ExtractBearerTokenResponse {
     used_token: String,
 }
 
+impl OperationOutput for ExtractBearerTokenResponse {}
+
 impl IntoResponse for ExtractBearerTokenResponse {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
@@ -0,0 +1,33 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    extractor::client_cert::{ApiClientCert, ClientCert},
+    openapi::OperationOutput,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ExtractClientCertResponse {
+    client_cert: ClientCert,
+}
+
+impl OperationOutput for ExtractClientCertResponse {}
+
+impl IntoResponse for ExtractClientCertResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Extracts the verified client certificate from the connection using the [`ApiClientCert`] extractor.
+///
+/// This function will reject if no client certificate was presented or it is not authorized.
+pub async fn extract_client_cert_using_extractor(
+    ApiClientCert(client_cert): ApiClientCert,
+) -> ExtractClientCertResponse {
+    ExtractClientCertResponse { client_cert }
+}
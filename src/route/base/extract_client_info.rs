@@ -0,0 +1,10 @@
+use axum::Json;
+
+use crate::extractor::client_info::{ApiClientInfo, ClientInfo};
+
+/// Extracts the client metadata from the request using the [`ApiClientInfo`] extractor.
+pub async fn extract_client_info_using_extractor(
+    ApiClientInfo(client_info): ApiClientInfo,
+) -> Json<ClientInfo> {
+    Json(client_info)
+}
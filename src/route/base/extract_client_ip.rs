@@ -0,0 +1,32 @@
+use std::net::IpAddr;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{extractor::client_ip::ApiClientIp, openapi::OperationOutput};
+
+#[derive(Debug, Serialize)]
+pub struct ExtractClientIpResponse {
+    client_ip: IpAddr,
+}
+
+impl OperationOutput for ExtractClientIpResponse {}
+
+impl IntoResponse for ExtractClientIpResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Extracts the client IP from the request using the [`ApiClientIp`] extractor.
+///
+/// This function will reject if forwarding headers are sent by an untrusted peer.
+pub async fn extract_client_ip_using_extractor(
+    ApiClientIp(client_ip): ApiClientIp,
+) -> ExtractClientIpResponse {
+    ExtractClientIpResponse { client_ip }
+}
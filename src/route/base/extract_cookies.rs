@@ -0,0 +1,77 @@
+use axum::{
+    extract::State,
+    http::{header::SET_COOKIE, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider},
+    extractor::{
+        cookie::{sign_cookie_value, ApiCookies, ApiSignedCookies, CookieSigningKeyProvider},
+        query::ApiQuery,
+    },
+    openapi::{ApiOperation, OperationOutput},
+    state::ApiState,
+};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SessionCookies {
+    session_id: String,
+    theme: Option<String>,
+}
+
+impl OperationOutput for SessionCookies {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.response::<Self>()
+    }
+}
+
+impl IntoResponse for SessionCookies {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Extracts the cookies from the request using the [`ApiCookies`] extractor.
+pub async fn extract_cookies_using_extractor(
+    ApiCookies(cookies): ApiCookies<SessionCookies>,
+) -> SessionCookies {
+    cookies
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetSignedCookiesQuery {
+    session_id: String,
+}
+
+/// Sets a signed `session_id` cookie to be extracted by [`extract_signed_cookies_using_extractor`].
+pub async fn set_signed_cookies(
+    State(state): State<ApiState>,
+    ApiQuery(query): ApiQuery<SetSignedCookiesQuery>,
+) -> Result<Response, ApiError> {
+    let key = state.cookie_signing_key().ok_or_else(|| {
+        ApiError::from_generic_error(
+            state.error_verbosity(),
+            anyhow::anyhow!("Cookie signing key is not configured"),
+        )
+    })?;
+
+    let cookie = format!(
+        "session_id={}; HttpOnly; Path=/",
+        sign_cookie_value(key, "session_id", &query.session_id)
+    );
+
+    Ok((StatusCode::NO_CONTENT, [(SET_COOKIE, cookie)]).into_response())
+}
+
+/// Extracts the signed cookies from the request using the [`ApiSignedCookies`] extractor.
+///
+/// This function will reject if a cookie's signature is invalid.
+pub async fn extract_signed_cookies_using_extractor(
+    ApiSignedCookies(cookies): ApiSignedCookies<SessionCookies>,
+) -> SessionCookies {
+    cookies
+}
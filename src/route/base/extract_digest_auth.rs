@@ -0,0 +1,33 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    extractor::digest_auth::{ApiDigestAuth, DigestAuth},
+    openapi::OperationOutput,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ExtractDigestAuthResponse {
+    digest_auth: DigestAuth,
+}
+
+impl OperationOutput for ExtractDigestAuthResponse {}
+
+impl IntoResponse for ExtractDigestAuthResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Authenticates the request using the [`ApiDigestAuth`] extractor.
+///
+/// This function will reject with a new challenge if the digest credentials are missing or invalid.
+pub async fn extract_digest_auth_using_extractor(
+    ApiDigestAuth(digest_auth): ApiDigestAuth,
+) -> ExtractDigestAuthResponse {
+    ExtractDigestAuthResponse { digest_auth }
+}
@@ -0,0 +1,41 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extractor::headers::ApiHeaders,
+    openapi::{ApiOperation, OperationOutput},
+};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ClientHeaders {
+    #[serde(rename = "user-agent")]
+    user_agent: String,
+    #[serde(rename = "x-client-version")]
+    client_version: Option<u32>,
+}
+
+impl OperationOutput for ClientHeaders {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.response::<Self>()
+    }
+}
+
+impl IntoResponse for ClientHeaders {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Extracts typed headers from the request using the [`ApiHeaders`] extractor.
+///
+/// This function will reject if `user-agent` is missing or `x-client-version` is not a number.
+pub async fn extract_headers_using_extractor(
+    ApiHeaders(headers): ApiHeaders<ClientHeaders>,
+) -> ClientHeaders {
+    headers
+}
@@ -0,0 +1,33 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    extractor::introspected_token::{ApiIntrospectedToken, IntrospectedToken},
+    openapi::OperationOutput,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ExtractIntrospectedTokenResponse {
+    introspected_token: IntrospectedToken,
+}
+
+impl OperationOutput for ExtractIntrospectedTokenResponse {}
+
+impl IntoResponse for ExtractIntrospectedTokenResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Extracts the introspected opaque bearer token using the [`ApiIntrospectedToken`] extractor.
+///
+/// This function will reject if the token is missing or not active.
+pub async fn extract_introspected_token_using_extractor(
+    ApiIntrospectedToken(introspected_token): ApiIntrospectedToken,
+) -> ExtractIntrospectedTokenResponse {
+    ExtractIntrospectedTokenResponse { introspected_token }
+}
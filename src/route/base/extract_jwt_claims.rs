@@ -5,13 +5,24 @@ use axum::{
 };
 use serde::Serialize;
 
-use crate::{claims::Claims, extractor::jwt::ApiJwt};
+use std::marker::PhantomData;
+
+use crate::{
+    claims::Claims,
+    extractor::{
+        jwt::ApiJwt,
+        jwt_roles::{ApiJwtWithRoles, RequiredRoles},
+    },
+    openapi::OperationOutput,
+};
 
 #[derive(Debug, Serialize)]
 pub struct ExtractClaimsResponse {
     claims: Claims,
 }
 
+impl OperationOutput for ExtractClaimsResponse {}
+
 impl IntoResponse for ExtractClaimsResponse {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
@@ -27,3 +38,18 @@ pub async fn extract_valid_jwt_claims_using_extractor(
 ) -> ExtractClaimsResponse {
     ExtractClaimsResponse { claims }
 }
+
+pub struct Admin;
+
+impl RequiredRoles for Admin {
+    const ROLES: &'static [&'static str] = &["admin"];
+}
+
+/// Extracts the JWT claims from the request using the [`ApiJwtWithRoles`] extractor.
+///
+/// This function will reject if the claims do not grant the `admin` role.
+pub async fn extract_admin_jwt_claims_using_extractor(
+    ApiJwtWithRoles(claims, PhantomData): ApiJwtWithRoles<Admin, Claims>,
+) -> ExtractClaimsResponse {
+    ExtractClaimsResponse { claims }
+}
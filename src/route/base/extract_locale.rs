@@ -0,0 +1,41 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    extractor::locale::ApiLocale, locale::LocaleCatalogProvider, openapi::OperationOutput,
+    state::ApiState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ExtractLocaleResponse {
+    locale: String,
+    message: String,
+}
+
+impl OperationOutput for ExtractLocaleResponse {}
+
+impl IntoResponse for ExtractLocaleResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Extracts the negotiated locale from the request using the [`ApiLocale`] extractor.
+///
+/// The message is resolved from the locale catalog for the negotiated locale.
+pub async fn extract_locale_using_extractor(
+    State(state): State<ApiState>,
+    ApiLocale(locale): ApiLocale,
+) -> ExtractLocaleResponse {
+    let message = state
+        .locale_catalog()
+        .message(&locale, "book_found")
+        .to_string();
+
+    ExtractLocaleResponse { locale, message }
+}
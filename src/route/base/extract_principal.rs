@@ -0,0 +1,33 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    extractor::principal::{ApiPrincipal, Principal},
+    openapi::OperationOutput,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ExtractPrincipalResponse {
+    principal: Principal,
+}
+
+impl OperationOutput for ExtractPrincipalResponse {}
+
+impl IntoResponse for ExtractPrincipalResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Extracts the principal using the [`ApiPrincipal`] extractor.
+///
+/// This function will reject if the request carries no credentials or if the credentials are invalid.
+pub async fn extract_principal_using_extractor(
+    ApiPrincipal(principal): ApiPrincipal,
+) -> ExtractPrincipalResponse {
+    ExtractPrincipalResponse { principal }
+}
@@ -0,0 +1,31 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    extractor::tenant::{ApiTenant, Tenant},
+    openapi::OperationOutput,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ExtractTenantResponse {
+    tenant: Tenant,
+}
+
+impl OperationOutput for ExtractTenantResponse {}
+
+impl IntoResponse for ExtractTenantResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Extracts the tenant from the request using the [`ApiTenant`] extractor.
+///
+/// This function will reject if the tenant is missing or unknown.
+pub async fn extract_tenant_using_extractor(ApiTenant(tenant): ApiTenant) -> ExtractTenantResponse {
+    ExtractTenantResponse { tenant }
+}
@@ -5,13 +5,18 @@ use axum::{
 };
 use serde::Serialize;
 
-use crate::extractor::valid_api_key::ValidApiKey;
+use crate::{
+    extractor::valid_api_key::ValidApiKey, openapi::OperationOutput, types::used_api_key::KeyInfo,
+};
 
 #[derive(Debug, Serialize)]
 pub struct ExtractValidApiKeyResponse {
     used_valid_api_key: String,
+    key_info: KeyInfo,
 }
 
+impl OperationOutput for ExtractValidApiKeyResponse {}
+
 impl IntoResponse for ExtractValidApiKeyResponse {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
@@ -22,9 +27,10 @@ impl IntoResponse for ExtractValidApiKeyResponse {
 ///
 /// This function will reject if [`ValidApiKey`] rejects.
 pub async fn extract_valid_api_key_using_extractor(
-    ValidApiKey(key): ValidApiKey,
+    ValidApiKey(key, key_info): ValidApiKey,
 ) -> ExtractValidApiKeyResponse {
     ExtractValidApiKeyResponse {
         used_valid_api_key: key.value,
+        key_info,
     }
 }
@@ -5,13 +5,20 @@ use axum::{
 };
 use serde::Serialize;
 
-use crate::extractor::{optional::Optional, valid_api_key::ValidApiKey};
+use crate::extractor::{
+    optional::{Optional, OptionalStrict},
+    valid_api_key::ValidApiKey,
+};
+
+use crate::openapi::OperationOutput;
 
 #[derive(Debug, Serialize)]
 pub struct OptionalExtractValidApiKeyResponse {
     used_valid_api_key: Option<String>,
 }
 
+impl OperationOutput for OptionalExtractValidApiKeyResponse {}
+
 impl IntoResponse for OptionalExtractValidApiKeyResponse {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
@@ -28,3 +35,14 @@ pub async fn extract_valid_api_key_using_optional_extractor(
         used_valid_api_key: opt_api_key.map(|key| key.0.value),
     }
 }
+
+/// Extracts the valid API key from the request using the [`OptionalStrict`] extractor.
+///
+/// The API key is optional, but this function will reject if the API key is provided and invalid.
+pub async fn extract_valid_api_key_using_optional_strict_extractor(
+    OptionalStrict(opt_api_key): OptionalStrict<ValidApiKey>,
+) -> OptionalExtractValidApiKeyResponse {
+    OptionalExtractValidApiKeyResponse {
+        used_valid_api_key: opt_api_key.map(|key| key.0.value),
+    }
+}
@@ -1,8 +1,19 @@
 pub mod app;
+pub mod extract_all;
 pub mod extract_api_key;
 pub mod extract_authenticated_basic_auth;
 pub mod extract_basic_auth;
 pub mod extract_bearer_token;
+pub mod extract_client_cert;
+mod extract_client_info;
+pub mod extract_client_ip;
+pub mod extract_cookies;
+pub mod extract_digest_auth;
+pub mod extract_headers;
+pub mod extract_introspected_token;
 pub mod extract_jwt_claims;
+pub mod extract_locale;
+pub mod extract_principal;
+pub mod extract_tenant;
 pub mod extract_valid_api_key;
 pub mod extract_valid_api_key_optional;
@@ -1,16 +1,95 @@
-use axum::{routing::get, Router};
+use crate::{
+    openapi::router::{get_with, ApiRouter},
+    state::ApiState,
+};
 
-use crate::state::ApiState;
-
-pub fn app() -> Router<ApiState> {
-    Router::<ApiState>::new()
-        .route("/get_book", get(super::get_book::get_book))
-        .route(
+pub fn app() -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new()
+        .api_route(
+            "/get_book",
+            get_with(super::get_book::get_book, |operation| {
+                operation.summary("Returns a book")
+            }),
+        )
+        .api_route(
+            "/get_book/:id",
+            get_with(super::get_book::get_book_by_path, |operation| {
+                operation.summary("Returns a book by the id in the path")
+            }),
+        )
+        .api_route(
+            "/get_book_localized",
+            get_with(super::get_book::get_book_localized, |operation| {
+                operation.summary("Returns a book with a localized message")
+            }),
+        )
+        .api_route(
+            "/get_book_negotiated",
+            get_with(super::get_book::get_book_negotiated, |operation| {
+                operation.summary(
+                    "Returns a book as JSON, YAML or MessagePack depending on the `Accept` header",
+                )
+            }),
+        )
+        .api_route(
+            "/list_books",
+            get_with(super::list_books::list_books, |operation| {
+                operation.summary("Lists the books")
+            }),
+        )
+        .api_route(
+            "/list_books_with_api_key",
+            get_with(super::list_books::list_books_with_api_key, |operation| {
+                operation.summary("Lists the books, requires an API key")
+            }),
+        )
+        .api_route(
+            "/list_books_with_jwt",
+            get_with(super::list_books::list_books_with_jwt, |operation| {
+                operation.summary("Lists the books, requires a JWT")
+            }),
+        )
+        .api_route(
+            "/list_books_sorted",
+            get_with(super::list_books::list_books_sorted, |operation| {
+                operation.summary("Lists the books sorted and filtered")
+            }),
+        )
+        .api_route(
+            "/list_books_paginated",
+            get_with(super::list_books::list_books_paginated, |operation| {
+                operation.summary("Lists the books page by page")
+            }),
+        )
+        .api_route(
+            "/search_books",
+            get_with(super::search_books::search_books, |operation| {
+                operation.summary("Searches the books")
+            }),
+        )
+        .api_route(
+            "/get_book_conditional",
+            get_with(super::get_book::get_book_conditional, |operation| {
+                operation.summary("Returns a book, supports conditional requests")
+            }),
+        )
+        .api_route(
+            "/get_book_with_deadline",
+            get_with(super::get_book::get_book_with_deadline, |operation| {
+                operation
+                    .summary("Returns a book, aborted once the deadline of the request is reached")
+            }),
+        )
+        .api_route(
             "/get_book_not_found",
-            get(super::get_book::get_book_not_found),
+            get_with(super::get_book::get_book_not_found, |operation| {
+                operation.summary("Responds with a not found resource error")
+            }),
         )
-        .route(
+        .api_route(
             "/get_book_id_too_big",
-            get(super::get_book::get_book_id_too_big),
+            get_with(super::get_book::get_book_id_too_big, |operation| {
+                operation.summary("Responds with an id too big resource error")
+            }),
         )
 }
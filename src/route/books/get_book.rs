@@ -1,15 +1,22 @@
+use std::time::Duration;
+
 use axum::{
     extract::State,
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use chrono::DateTime;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    error::{ErrorVerbosityProvider, ResourceError, ResourceErrorProvider},
-    extractor::query::ApiQuery,
+    error::{ErrorVerbosityProvider, ResourceError, RouteError},
+    extractor::{
+        conditional::ApiConditional, deadline::ApiDeadline, query::ApiQuery, url_parts::ApiUrlParts,
+    },
+    openapi::{ApiOperation, OperationOutput},
+    response::{ApiResponse, Negotiated, Negotiator, ResponseContext},
     state::ApiState,
 };
 
@@ -20,24 +27,50 @@ pub struct GetBookQuery {
     pub id: i64,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetBookPath {
+    pub id: i64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetBookOptionsQuery {
+    #[serde(default)]
+    pub uppercase_title: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct GetBookResponse {
     pub book: Book,
 }
 
+impl OperationOutput for GetBookResponse {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.response::<Self>()
+    }
+}
+
 impl IntoResponse for GetBookResponse {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ResourceError)]
 #[serde(tag = "error_type")]
+#[context(GetBookErrorContext)]
 pub enum GetBookErrorType {
+    #[status(NOT_FOUND)]
+    #[message("Book not found")]
+    #[code(BOOK_NOT_FOUND)]
+    #[context(GetBookErrorContext { context: format!("Book with id {id} not found") })]
     NotFound {
         #[serde(skip)]
         id: i64,
     },
+    #[status(BAD_REQUEST)]
+    #[message("Id too big")]
+    #[code(BOOK_ID_TOO_BIG)]
+    #[context(GetBookErrorContext { context: format!("Id {id} is too big") })]
     IdTooBig {
         #[serde(skip)]
         id: i64,
@@ -49,39 +82,6 @@ pub struct GetBookErrorContext {
     pub context: String,
 }
 
-impl ResourceErrorProvider for GetBookErrorType {
-    type Context = GetBookErrorContext;
-
-    fn headers(&self) -> Option<axum::http::HeaderMap> {
-        None
-    }
-
-    fn status_code(&self) -> StatusCode {
-        match self {
-            GetBookErrorType::NotFound { .. } => StatusCode::NOT_FOUND,
-            GetBookErrorType::IdTooBig { .. } => StatusCode::BAD_REQUEST,
-        }
-    }
-
-    fn message(&self) -> &'static str {
-        match self {
-            GetBookErrorType::NotFound { .. } => "Book not found",
-            GetBookErrorType::IdTooBig { .. } => "Id too big",
-        }
-    }
-
-    fn context(&self) -> Self::Context {
-        match self {
-            GetBookErrorType::NotFound { id } => GetBookErrorContext {
-                context: format!("Book with id {} not found", id),
-            },
-            GetBookErrorType::IdTooBig { id } => GetBookErrorContext {
-                context: format!("Id {} is too big", id),
-            },
-        }
-    }
-}
-
 pub async fn get_book(
     ApiQuery(query): ApiQuery<GetBookQuery>,
     State(_state): State<ApiState>,
@@ -99,6 +99,112 @@ pub async fn get_book(
     })
 }
 
+/// Same as [`get_book`] but wraps the book in an [`ApiResponse`] with a localized message.
+pub async fn get_book_localized(
+    ApiQuery(query): ApiQuery<GetBookQuery>,
+    response_context: ResponseContext,
+    State(state): State<ApiState>,
+) -> ApiResponse<Book> {
+    let book = Book {
+        title: "The Catcher in the Rye".to_string(),
+        author: "J.D. Salinger".to_string(),
+        isbn: "978-0-316-76948-0".to_string(),
+        year: 1951,
+        id: query.id,
+    };
+
+    response_context.respond(&state, book, "book_found")
+}
+
+/// Same as [`get_book`] but takes the id from the path and options from the query.
+pub async fn get_book_by_path(
+    ApiUrlParts(path, query): ApiUrlParts<GetBookPath, GetBookOptionsQuery>,
+) -> GetBookResponse {
+    let title = "The Catcher in the Rye";
+    let title = if query.uppercase_title {
+        title.to_uppercase()
+    } else {
+        title.to_string()
+    };
+
+    GetBookResponse {
+        book: Book {
+            title,
+            author: "J.D. Salinger".to_string(),
+            isbn: "978-0-316-76948-0".to_string(),
+            year: 1951,
+            id: path.id,
+        },
+    }
+}
+
+/// Same as [`get_book`] but serializes the book as JSON, YAML or MessagePack depending on the `Accept` header.
+pub async fn get_book_negotiated(
+    ApiQuery(query): ApiQuery<GetBookQuery>,
+    negotiator: Negotiator,
+) -> Negotiated<GetBookResponse> {
+    negotiator.negotiate(GetBookResponse {
+        book: Book {
+            title: "The Catcher in the Rye".to_string(),
+            author: "J.D. Salinger".to_string(),
+            isbn: "978-0-316-76948-0".to_string(),
+            year: 1951,
+            id: query.id,
+        },
+    })
+}
+
+/// Same as [`get_book`] but supports conditional requests using `If-None-Match` and `If-Modified-Since`.
+pub async fn get_book_conditional(
+    ApiQuery(query): ApiQuery<GetBookQuery>,
+    ApiConditional(preconditions): ApiConditional,
+) -> Response {
+    let book = Book {
+        title: "The Catcher in the Rye".to_string(),
+        author: "J.D. Salinger".to_string(),
+        isbn: "978-0-316-76948-0".to_string(),
+        year: 1951,
+        id: query.id,
+    };
+
+    let etag = format!("\"{}-{}\"", book.id, book.year);
+    let last_modified = DateTime::from_timestamp(0, 0);
+
+    preconditions.respond(Some(&etag), last_modified, GetBookResponse { book })
+}
+
+/// Same as [`get_book`] but simulates a slow lookup that is aborted once the request's deadline is reached.
+///
+/// Books with a negative id are not found.
+pub async fn get_book_with_deadline(
+    ApiQuery(query): ApiQuery<GetBookQuery>,
+    ApiDeadline(deadline): ApiDeadline,
+    State(state): State<ApiState>,
+) -> Result<GetBookResponse, RouteError<GetBookErrorType, GetBookErrorContext>> {
+    let lookup = async {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        (query.id >= 0).then(|| Book {
+            title: "The Catcher in the Rye".to_string(),
+            author: "J.D. Salinger".to_string(),
+            isbn: "978-0-316-76948-0".to_string(),
+            year: 1951,
+            id: query.id,
+        })
+    };
+
+    tracing::debug!(remaining=?deadline.remaining(), "Looking up book");
+
+    let book = deadline.run(lookup).await?.ok_or_else(|| {
+        ResourceError::new(
+            state.error_verbosity(),
+            GetBookErrorType::NotFound { id: query.id },
+        )
+    })?;
+
+    Ok(GetBookResponse { book })
+}
+
 pub async fn get_book_not_found(
     ApiQuery(query): ApiQuery<GetBookQuery>,
     State(state): State<ApiState>,
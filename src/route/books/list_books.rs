@@ -0,0 +1,146 @@
+use std::cmp::Ordering;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    claims::Claims,
+    extractor::{
+        authorized::{Authorized, Policy},
+        jwt::ApiJwt,
+        pagination::{ApiPagination, Paginated},
+        query::ApiQueryOrDefault,
+        sort_filter::{ApiFilter, ApiSort, SortDirection, SortField},
+        valid_api_key::ValidApiKey,
+    },
+};
+
+use super::{search_books::SearchBooksResponse, Book};
+
+/// All filters are optional. A request without a query string lists all books.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct ListBooksQuery {
+    pub author: Option<String>,
+    pub year: Option<u16>,
+}
+
+pub async fn list_books(
+    ApiQueryOrDefault(query): ApiQueryOrDefault<ListBooksQuery>,
+) -> SearchBooksResponse {
+    let books = vec![Book {
+        title: "The Catcher in the Rye".to_string(),
+        author: "J.D. Salinger".to_string(),
+        isbn: "978-0-316-76948-0".to_string(),
+        year: 1951,
+        id: 1,
+    }]
+    .into_iter()
+    .filter(|book| {
+        query
+            .author
+            .as_ref()
+            .is_none_or(|author| &book.author == author)
+    })
+    .filter(|book| query.year.is_none_or(|year| book.year == year))
+    .collect();
+
+    SearchBooksResponse { books }
+}
+
+#[derive(Policy)]
+#[policy("books:read")]
+pub struct BooksRead;
+
+/// Same as [`list_books`] but requires an API key that is granted [`BooksRead`].
+pub async fn list_books_with_api_key(
+    Authorized(_, _): Authorized<BooksRead, ValidApiKey>,
+    query: ApiQueryOrDefault<ListBooksQuery>,
+) -> SearchBooksResponse {
+    list_books(query).await
+}
+
+/// Same as [`list_books`] but requires a JWT that is granted [`BooksRead`].
+pub async fn list_books_with_jwt(
+    Authorized(ApiJwt(claims), _): Authorized<BooksRead, ApiJwt<Claims>>,
+    query: ApiQueryOrDefault<ListBooksQuery>,
+) -> SearchBooksResponse {
+    tracing::debug!(username = %claims.preferred_username, "Listing books");
+
+    list_books(query).await
+}
+
+/// Lists the books page by page.
+pub async fn list_books_paginated(ApiPagination(pagination): ApiPagination) -> Paginated<Book> {
+    const TOTAL: u64 = 95;
+
+    let books = (pagination.offset..TOTAL)
+        .take(pagination.limit as usize)
+        .map(|id| Book {
+            title: "The Catcher in the Rye".to_string(),
+            author: "J.D. Salinger".to_string(),
+            isbn: "978-0-316-76948-0".to_string(),
+            year: 1951,
+            id: id as i64,
+        })
+        .collect();
+
+    pagination.paginate(books, TOTAL)
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BookFilter {
+    pub author: Option<String>,
+    pub year: Option<u16>,
+}
+
+/// Lists the books sorted and filtered by `?sort=-year,title&filter[author]=J.D. Salinger`.
+pub async fn list_books_sorted(
+    ApiSort(sort, _): ApiSort<Book>,
+    ApiFilter(filter): ApiFilter<BookFilter>,
+) -> SearchBooksResponse {
+    let mut books = [
+        ("The Catcher in the Rye", "J.D. Salinger", 1951),
+        ("Franny and Zooey", "J.D. Salinger", 1961),
+        ("Nine Stories", "J.D. Salinger", 1953),
+        ("1984", "George Orwell", 1949),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(id, (title, author, year))| Book {
+        title: title.to_string(),
+        author: author.to_string(),
+        isbn: "978-0-316-76948-0".to_string(),
+        year,
+        id: id as i64,
+    })
+    .filter(|book| {
+        filter
+            .author
+            .as_ref()
+            .is_none_or(|author| &book.author == author)
+    })
+    .filter(|book| filter.year.is_none_or(|year| book.year == year))
+    .collect::<Vec<_>>();
+
+    books.sort_by(|a, b| {
+        sort.iter()
+            .map(|SortField { field, direction }| {
+                let ordering = match field.as_str() {
+                    "title" => a.title.cmp(&b.title),
+                    "author" => a.author.cmp(&b.author),
+                    "isbn" => a.isbn.cmp(&b.isbn),
+                    "year" => a.year.cmp(&b.year),
+                    _ => a.id.cmp(&b.id),
+                };
+
+                match direction {
+                    SortDirection::Ascending => ordering,
+                    SortDirection::Descending => ordering.reverse(),
+                }
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    SearchBooksResponse { books }
+}
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 
 pub mod app;
 pub mod get_book;
+pub mod list_books;
+pub mod search_books;
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Book {
@@ -0,0 +1,75 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extractor::query_extra::ApiQueryExtra,
+    openapi::{ApiOperation, OperationOutput},
+};
+
+use super::Book;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SearchBooksFilter {
+    pub author: Option<String>,
+    pub year: Option<u16>,
+}
+
+/// `?id=1&id=2&filter[author]=J.D. Salinger`
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SearchBooksQuery {
+    #[serde(default)]
+    pub id: Vec<i64>,
+    pub filter: Option<SearchBooksFilter>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SearchBooksResponse {
+    pub books: Vec<Book>,
+}
+
+impl OperationOutput for SearchBooksResponse {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.response::<Self>()
+    }
+}
+
+impl IntoResponse for SearchBooksResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+pub async fn search_books(
+    ApiQueryExtra(query): ApiQueryExtra<SearchBooksQuery>,
+) -> SearchBooksResponse {
+    let filter = query.filter.unwrap_or(SearchBooksFilter {
+        author: None,
+        year: None,
+    });
+
+    let books = query
+        .id
+        .into_iter()
+        .map(|id| Book {
+            title: "The Catcher in the Rye".to_string(),
+            author: "J.D. Salinger".to_string(),
+            isbn: "978-0-316-76948-0".to_string(),
+            year: 1951,
+            id,
+        })
+        .filter(|book| {
+            filter
+                .author
+                .as_ref()
+                .is_none_or(|author| &book.author == author)
+        })
+        .filter(|book| filter.year.is_none_or(|year| book.year == year))
+        .collect();
+
+    SearchBooksResponse { books }
+}
@@ -1,14 +1,39 @@
+use std::time::Duration;
+
 use crate::{
     error::{ApiError, ErrorVerbosityProvider},
+    middleware::timeout::layer::TimeoutLayer,
+    openapi::router::{get_with, ApiRouter},
     server_error,
     state::ApiState,
 };
-use axum::{extract::State, routing::get, Router};
+use axum::extract::State;
 
-pub fn app() -> Router<ApiState> {
-    Router::<ApiState>::new()
-        .route("/internal_server_error", get(internal_server_error))
-        .route("/default_api_error", get(default_api_error))
+pub fn app(state: ApiState) -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new()
+        .api_route(
+            "/internal_server_error",
+            get_with(internal_server_error, |operation| {
+                operation.summary("Responds with an internal server error")
+            }),
+        )
+        .api_route(
+            "/default_api_error",
+            get_with(default_api_error, |operation| {
+                operation.summary("Responds with the default API error")
+            }),
+        )
+        .api_route(
+            "/panic",
+            get_with(panic, |operation| operation.summary("Panics")),
+        )
+        .api_route(
+            "/request_timeout",
+            get_with(request_timeout, |operation| {
+                operation.summary("Takes longer than the overridden timeout of the route")
+            })
+            .layer(TimeoutLayer::new(state, Duration::from_secs(1))),
+        )
 }
 
 pub async fn internal_server_error(State(state): State<ApiState>) -> Result<(), ApiError> {
@@ -21,3 +46,15 @@ pub async fn internal_server_error(State(state): State<ApiState>) -> Result<(),
 pub async fn default_api_error() -> ApiError {
     ApiError::default()
 }
+
+/// Panics, so the [`CatchPanicLayer`](crate::middleware::catch_panic::layer::CatchPanicLayer) responds with an internal server error.
+pub async fn panic() -> &'static str {
+    panic!("Handler panicked on purpose")
+}
+
+/// Takes longer than the overridden timeout of the route.
+pub async fn request_timeout() -> &'static str {
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    "Not timed out"
+}
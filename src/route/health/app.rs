@@ -0,0 +1,13 @@
+use crate::{
+    openapi::router::{get_with, ApiRouter},
+    state::ApiState,
+};
+
+pub fn app() -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new().api_route(
+        "/",
+        get_with(super::check_health::check_health, |operation| {
+            operation.summary("Checks that the dependencies of the server are reachable")
+        }),
+    )
+}
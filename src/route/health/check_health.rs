@@ -0,0 +1,49 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{openapi::OperationOutput, state::ApiState};
+
+#[derive(Debug, Serialize)]
+pub enum HealthStatus {
+    Healthy,
+    Unhealthy,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckHealthResponse {
+    credential_store: HealthStatus,
+}
+
+impl OperationOutput for CheckHealthResponse {}
+
+impl IntoResponse for CheckHealthResponse {
+    fn into_response(self) -> Response {
+        let status_code = match self.credential_store {
+            HealthStatus::Healthy => StatusCode::OK,
+            HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        };
+
+        (status_code, Json(self)).into_response()
+    }
+}
+
+/// Checks that the dependencies of the server are reachable.
+///
+/// Responds with `503 Service Unavailable` if any of them is not.
+pub async fn check_health(State(state): State<ApiState>) -> CheckHealthResponse {
+    let credential_store = match state.credential_store().health_check().await {
+        Ok(()) => HealthStatus::Healthy,
+        Err(err) => {
+            tracing::error!(%err, "Credential store is unhealthy");
+
+            HealthStatus::Unhealthy
+        }
+    };
+
+    CheckHealthResponse { credential_store }
+}
@@ -0,0 +1,32 @@
+use crate::{
+    claims::Claims,
+    middleware::jwt_auth::layer::JwtAuthLayer,
+    openapi::router::{get, get_with, ApiRouter},
+    state::ApiState,
+};
+
+pub fn app(state: ApiState) -> ApiRouter<ApiState> {
+    let summary = "Extracts the claims from the extension provided by the JWT middleware";
+
+    let admin = ApiRouter::<ApiState>::new()
+        .api_route(
+            "/claims_from_extension",
+            get_with(
+                super::claims_from_extension::claims_from_extension,
+                |operation| operation.summary(summary),
+            ),
+        )
+        .layer(JwtAuthLayer::<_, Claims>::new(state.clone()).require_roles(["admin"]));
+
+    ApiRouter::<ApiState>::new()
+        .api_route("/", get(|| async { "JWT Protected" }))
+        .api_route(
+            "/claims_from_extension",
+            get_with(
+                super::claims_from_extension::claims_from_extension,
+                |operation| operation.summary(summary),
+            ),
+        )
+        .nest("/admin", admin)
+        .layer(JwtAuthLayer::<_, Claims>::new(state))
+}
@@ -0,0 +1,28 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::Serialize;
+
+use crate::{claims::Claims, openapi::OperationOutput};
+
+#[derive(Debug, Serialize)]
+pub struct ClaimsFromExtensionResponse {
+    claims: Claims,
+}
+
+impl OperationOutput for ClaimsFromExtensionResponse {}
+
+impl IntoResponse for ClaimsFromExtensionResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Extracts the claims from the [`Extension`] that was provided from [`JwtAuthLayer`](crate::middleware::jwt_auth::layer::JwtAuthLayer) middleware.
+pub async fn claims_from_extension(
+    Extension(claims): Extension<Claims>,
+) -> ClaimsFromExtensionResponse {
+    ClaimsFromExtensionResponse { claims }
+}
@@ -0,0 +1,14 @@
+use crate::{
+    openapi::router::{post_with, ApiRouter},
+    state::ApiState,
+};
+
+pub fn app() -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new().api_route(
+        "/",
+        post_with(super::rp_initiated_logout::logout, |operation| {
+            operation
+                .summary("RP-initiated logout using the identity provider's `end_session_endpoint`")
+        }),
+    )
+}
@@ -0,0 +1,126 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use schemars::JsonSchema;
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+
+use crate::{
+    error::{ApiError, ErrorVerbosityProvider, NotFoundError},
+    extractor::{bearer_token::ApiBearerToken, jwt::ApiJwt, optional::Optional, query::ApiQuery},
+    oidc::login::LoginSession,
+    openapi::OperationOutput,
+    revocation::{RevocableToken, TokenRevocationProvider},
+    server_error,
+    session::Session,
+    state::ApiState,
+};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LogoutQuery {
+    /// Where the identity provider redirects to after the logout.
+    pub post_logout_redirect_uri: Option<String>,
+    /// Redirect to the identity provider instead of returning the logout URL.
+    #[serde(default)]
+    pub redirect: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogoutUrlResponse {
+    logout_url: String,
+}
+
+pub enum LogoutResponse {
+    Redirect(Redirect),
+    Url(LogoutUrlResponse),
+}
+
+impl OperationOutput for LogoutResponse {}
+
+impl IntoResponse for LogoutResponse {
+    fn into_response(self) -> Response {
+        match self {
+            LogoutResponse::Redirect(redirect) => redirect.into_response(),
+            LogoutResponse::Url(url) => (StatusCode::OK, Json(url)).into_response(),
+        }
+    }
+}
+
+/// RP-initiated logout using the identity provider's `end_session_endpoint`.
+///
+/// Removes the session and revokes the optional bearer token until it expires.
+/// The bearer token is only revoked if it is valid, see [`ApiJwt`].
+/// The ID token of a login session is passed as `id_token_hint`.
+/// Redirects to the identity provider if `redirect` is set, otherwise returns the logout URL.
+///
+/// With an OIDC login configured, falls back to its `post_logout_redirect_uri` or `/`
+/// if the identity provider does not support RP-initiated logout.
+/// Otherwise returns [`NotFoundError`] if the identity provider does not support RP-initiated logout.
+pub async fn logout(
+    Optional(jwt): Optional<ApiJwt<IgnoredAny>>,
+    Optional(bearer_token): Optional<ApiBearerToken>,
+    ApiQuery(query): ApiQuery<LogoutQuery>,
+    State(state): State<ApiState>,
+    session: Session,
+) -> Result<LogoutResponse, ApiError> {
+    let id_token = session
+        .get::<LoginSession>()
+        .ok()
+        .flatten()
+        .map(|login_session| login_session.id_token);
+
+    let logout_url = match state.oidc_login() {
+        Some(oidc_login) => oidc_login
+            .logout_url(
+                id_token.as_deref(),
+                query.post_logout_redirect_uri.as_deref(),
+            )
+            .map(String::from)
+            .or_else(|| oidc_login.config().post_logout_redirect_uri.clone())
+            .unwrap_or_else(|| String::from("/")),
+        None => {
+            let end_session_endpoint = state
+                .end_session_endpoint()
+                .ok_or_else(|| NotFoundError::new(state.error_verbosity()))?;
+
+            let mut params = Vec::new();
+
+            if let Some(id_token) = id_token {
+                params.push(("id_token_hint", id_token));
+            }
+
+            if let Some(post_logout_redirect_uri) = query.post_logout_redirect_uri {
+                params.push(("post_logout_redirect_uri", post_logout_redirect_uri));
+            }
+
+            reqwest::Url::parse_with_params(end_session_endpoint, &params)
+                .map_err(server_error!(state))?
+                .into()
+        }
+    };
+
+    session.remove();
+
+    if let (Some(_), Some(ApiBearerToken(token))) = (jwt, bearer_token) {
+        let token = RevocableToken::from_jwt(&token.value);
+        let time_to_live = token.time_to_live(state.token_revocation_time_to_live());
+
+        if !time_to_live.is_zero() {
+            state
+                .revoke_token(&token.id, time_to_live)
+                .await
+                .map_err(server_error!(state))?;
+
+            tracing::debug!(token_id = %token.id, "Revoked token");
+        }
+    }
+
+    tracing::debug!("Logging out");
+
+    match query.redirect {
+        true => Ok(LogoutResponse::Redirect(Redirect::to(&logout_url))),
+        false => Ok(LogoutResponse::Url(LogoutUrlResponse { logout_url })),
+    }
+}
@@ -1,6 +1,17 @@
+pub mod admin;
 pub mod api_key_protected;
+pub mod auth;
 pub mod base;
 pub mod books;
 pub mod error;
+pub mod health;
+pub mod jwt_protected;
+pub mod logout;
+pub mod post_cbor;
+pub mod post_form;
 pub mod post_json;
+pub mod post_msgpack;
+pub mod post_raw;
+pub mod post_xml;
+pub mod token;
 pub mod validated;
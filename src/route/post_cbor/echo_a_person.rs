@@ -0,0 +1,12 @@
+use crate::{
+    extractor::cbor::ApiCbor,
+    response::{Negotiated, Negotiator},
+    route::post_json::echo_a_person::Person,
+};
+
+pub async fn echo_a_person(
+    negotiator: Negotiator,
+    ApiCbor(person): ApiCbor<Person>,
+) -> Negotiated<Person> {
+    negotiator.negotiate(person)
+}
@@ -0,0 +1,2 @@
+pub mod app;
+pub mod echo_a_person;
@@ -0,0 +1,5 @@
+use crate::{extractor::form::ApiForm, route::post_json::echo_a_person::Person};
+
+pub async fn echo_a_person(ApiForm(person): ApiForm<Person>) -> Person {
+    person
+}
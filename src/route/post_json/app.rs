@@ -1,7 +1,19 @@
-use axum::{routing::post, Router};
+use crate::{
+    openapi::router::{post, post_with, ApiRouter},
+    state::ApiState,
+};
 
-use crate::state::ApiState;
-
-pub fn app() -> Router<ApiState> {
-    Router::<ApiState>::new().route("/echo_a_person", post(super::echo_a_person::echo_a_person))
+pub fn app() -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new()
+        .api_route("/echo_a_person", post(super::echo_a_person::echo_a_person))
+        .api_route(
+            "/echo_a_person_request",
+            post(super::echo_a_person_request::echo_a_person_request),
+        )
+        .api_route(
+            "/import_persons",
+            post_with(super::import_persons::import_persons, |operation| {
+                operation.summary("Imports newline-delimited persons one at a time")
+            }),
+        )
 }
@@ -6,7 +6,11 @@ use axum::{
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::extractor::json::ApiJson;
+use crate::{
+    extractor::json::ApiJson,
+    openapi::{ApiOperation, OperationOutput},
+    response_schema::ResponseSchema,
+};
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Person {
@@ -16,9 +20,15 @@ pub struct Person {
     pub city: String,
 }
 
+impl OperationOutput for Person {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.response::<Self>()
+    }
+}
+
 impl IntoResponse for Person {
     fn into_response(self) -> Response {
-        (StatusCode::OK, Json(self)).into_response()
+        (StatusCode::OK, ResponseSchema::of::<Self>(), Json(self)).into_response()
     }
 }
 
@@ -0,0 +1,73 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    extractor::api_request::ApiRequest,
+    openapi::{ApiOperation, OperationInput, OperationOutput},
+};
+
+use super::echo_a_person::Person;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct EchoAPersonQuery {
+    #[serde(default)]
+    pub uppercase_name: bool,
+}
+
+/// Extracts the query, the `x-request-id` and `content-length` headers and the JSON body in a single extractor.
+#[derive(Debug, ApiRequest)]
+pub struct EchoAPersonRequest {
+    #[from(query)]
+    query: EchoAPersonQuery,
+    #[from(header = "x-request-id")]
+    request_id: Option<String>,
+    #[from(header = "content-length")]
+    content_length: u64,
+    #[from(json)]
+    person: Person,
+}
+
+impl OperationInput for EchoAPersonRequest {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.query::<EchoAPersonQuery>().json::<Person>()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct EchoAPersonRequestResponse {
+    request_id: Option<String>,
+    content_length: u64,
+    person: Person,
+}
+
+impl OperationOutput for EchoAPersonRequestResponse {}
+
+impl IntoResponse for EchoAPersonRequestResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+pub async fn echo_a_person_request(
+    EchoAPersonRequest {
+        query,
+        request_id,
+        content_length,
+        mut person,
+    }: EchoAPersonRequest,
+) -> EchoAPersonRequestResponse {
+    if query.uppercase_name {
+        person.name = person.name.to_uppercase();
+    }
+
+    EchoAPersonRequestResponse {
+        request_id,
+        content_length,
+        person,
+    }
+}
@@ -0,0 +1,41 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures::TryStreamExt;
+use serde::Serialize;
+
+use crate::{error::ApiError, extractor::json_lines::ApiJsonLines, openapi::OperationOutput};
+
+use super::echo_a_person::Person;
+
+#[derive(Debug, Serialize)]
+pub struct ImportPersonsResponse {
+    imported: usize,
+}
+
+impl OperationOutput for ImportPersonsResponse {}
+
+impl IntoResponse for ImportPersonsResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Imports newline-delimited persons one at a time using the [`ApiJsonLines`] extractor.
+///
+/// This function will reject with the error of the first invalid line.
+pub async fn import_persons(
+    ApiJsonLines(mut persons): ApiJsonLines<Person>,
+) -> Result<ImportPersonsResponse, ApiError> {
+    let mut imported = 0;
+
+    while let Some(person) = persons.try_next().await? {
+        tracing::debug!(name = %person.name, "Importing person");
+
+        imported += 1;
+    }
+
+    Ok(ImportPersonsResponse { imported })
+}
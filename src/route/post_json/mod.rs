@@ -1,2 +1,4 @@
 pub mod app;
 pub mod echo_a_person;
+pub mod echo_a_person_request;
+pub mod import_persons;
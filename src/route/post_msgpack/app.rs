@@ -0,0 +1,9 @@
+use crate::{
+    openapi::router::{post, ApiRouter},
+    state::ApiState,
+};
+
+pub fn app() -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new()
+        .api_route("/echo_a_person", post(super::echo_a_person::echo_a_person))
+}
@@ -0,0 +1,5 @@
+use crate::{extractor::msgpack::ApiMsgPack, route::post_json::echo_a_person::Person};
+
+pub async fn echo_a_person(ApiMsgPack(person): ApiMsgPack<Person>) -> Person {
+    person
+}
@@ -0,0 +1,23 @@
+use crate::{
+    middleware::body_limit::layer::BodyLimitLayer,
+    openapi::router::{post, post_with, ApiRouter},
+    state::ApiState,
+};
+
+/// Raw bodies are limited to 64 KiB, regardless of the configured `max_body_size_in_bytes`.
+const MAX_RAW_BODY_SIZE_IN_BYTES: usize = 64 * 1024;
+
+pub fn app(state: ApiState) -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new()
+        .api_route("/echo_bytes", post(super::echo::echo_bytes))
+        .api_route("/echo_string", post(super::echo::echo_string))
+        .api_route(
+            "/echo_signed_bytes",
+            post_with(super::echo::echo_signed_bytes, |operation| {
+                operation.summary(
+                    "Echoes the body of a request signed with one of the configured signature keys",
+                )
+            }),
+        )
+        .layer(BodyLimitLayer::new(state, MAX_RAW_BODY_SIZE_IN_BYTES))
+}
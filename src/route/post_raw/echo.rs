@@ -0,0 +1,23 @@
+use axum::body::Bytes;
+
+use crate::extractor::{
+    body::{ApiBytes, ApiString},
+    signed_request::{ApiSignedRequest, SignedRequest},
+};
+
+pub async fn echo_bytes(ApiBytes(bytes): ApiBytes) -> Bytes {
+    bytes
+}
+
+pub async fn echo_string(ApiString(string): ApiString) -> String {
+    string
+}
+
+/// Echoes the body of a request signed with one of the configured signature keys.
+pub async fn echo_signed_bytes(
+    ApiSignedRequest(SignedRequest { key_id, body }): ApiSignedRequest,
+) -> Bytes {
+    tracing::debug!(%key_id, "Echoing signed request");
+
+    body
+}
@@ -0,0 +1,5 @@
+use crate::{extractor::xml::ApiXml, route::post_json::echo_a_person::Person};
+
+pub async fn echo_a_person(ApiXml(person): ApiXml<Person>) -> Person {
+    person
+}
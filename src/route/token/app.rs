@@ -0,0 +1,30 @@
+use crate::{
+    openapi::router::{post_with, ApiRouter},
+    state::ApiState,
+};
+
+pub fn app() -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new()
+        .api_route(
+            "/",
+            post_with(
+                super::issue_token::issue_token,
+                |operation| {
+                    operation.summary(
+                        "Issues an access and a refresh token to the client authenticated with basic auth",
+                    )
+                },
+            ),
+        )
+        .api_route(
+            "/refresh",
+            post_with(
+                super::issue_token::refresh_token,
+                |operation| {
+                    operation.summary(
+                        "Exchanges a refresh token for a new access token and its successor refresh token",
+                    )
+                },
+            ),
+        )
+}
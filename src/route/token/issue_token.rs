@@ -0,0 +1,161 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{
+        ApiError, ErrorVerbosityProvider, NotFoundError, TokenGrantError, TokenGrantErrorType,
+    },
+    extractor::{authenticated_basic_auth::ApiAuthenticatedBasicAuth, form::ApiForm},
+    openapi::OperationOutput,
+    server_error,
+    state::ApiState,
+    token_issuer::{RefreshError, TokenIssuer},
+    types::used_basic_auth::UsedBasicAuth,
+};
+
+fn token_issuer(state: &ApiState) -> Result<&TokenIssuer, ApiError> {
+    state
+        .token_issuer()
+        .ok_or_else(|| NotFoundError::new(state.error_verbosity()).into())
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenResponse {
+    access_token: String,
+    token_type: &'static str,
+    expires_in: u64,
+    refresh_token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+}
+
+impl OperationOutput for TokenResponse {}
+
+impl IntoResponse for TokenResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl TokenResponse {
+    fn new(
+        state: &ApiState,
+        token_issuer: &TokenIssuer,
+        sub: &str,
+        scope: Option<String>,
+        refresh_token: String,
+    ) -> Result<Self, ApiError> {
+        let access_token = token_issuer
+            .issue_access_token(sub, scope.as_deref())
+            .map_err(server_error!(state))?;
+
+        Ok(Self {
+            access_token,
+            token_type: "Bearer",
+            expires_in: token_issuer.access_token_time_to_live().as_secs(),
+            refresh_token,
+            scope,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TokenRequest {
+    /// Must be `client_credentials`.
+    grant_type: String,
+    scope: Option<String>,
+}
+
+/// Issues an access and a refresh token to the client authenticated with basic auth.
+///
+/// The requested scope must be allowed for the client, without one all allowed scopes are granted.
+///
+/// Returns [`NotFoundError`] if the token issuer is not configured.
+pub async fn issue_token(
+    State(state): State<ApiState>,
+    ApiAuthenticatedBasicAuth(UsedBasicAuth { username, .. }): ApiAuthenticatedBasicAuth,
+    ApiForm(request): ApiForm<TokenRequest>,
+) -> Result<TokenResponse, ApiError> {
+    let token_issuer = token_issuer(&state)?;
+
+    if request.grant_type != "client_credentials" {
+        tracing::warn!(grant_type = %request.grant_type, "Rejection. Unsupported grant type");
+
+        return Err(TokenGrantError::new(
+            state.error_verbosity(),
+            TokenGrantErrorType::UnsupportedGrantType {
+                grant_type: request.grant_type,
+            },
+        )
+        .into());
+    }
+
+    let scope = token_issuer
+        .grant_scope(&username, request.scope.as_deref())
+        .map_err(|scope| {
+            tracing::warn!(%username, %scope, "Rejection. Scope not allowed");
+
+            TokenGrantError::new(
+                state.error_verbosity(),
+                TokenGrantErrorType::InvalidScope { scope },
+            )
+        })?;
+
+    let refresh_token = token_issuer
+        .issue_refresh_token(&state, &username, scope.as_deref())
+        .await
+        .map_err(server_error!(state))?;
+
+    tracing::debug!(%username, "Issued token");
+
+    TokenResponse::new(&state, token_issuer, &username, scope, refresh_token)
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RefreshTokenRequest {
+    refresh_token: String,
+}
+
+/// Exchanges a refresh token for a new access token and its successor refresh token.
+///
+/// Presenting a used refresh token revokes all refresh tokens rotated from the same initial token.
+pub async fn refresh_token(
+    State(state): State<ApiState>,
+    ApiForm(request): ApiForm<RefreshTokenRequest>,
+) -> Result<TokenResponse, ApiError> {
+    let verbosity = state.error_verbosity();
+    let token_issuer = token_issuer(&state)?;
+
+    let (record, refresh_token) = token_issuer
+        .rotate_refresh_token(&state, &request.refresh_token)
+        .await
+        .map_err(|err| match err {
+            RefreshError::Invalid => {
+                tracing::warn!("Rejection. Invalid refresh token");
+
+                TokenGrantError::new(verbosity, TokenGrantErrorType::InvalidRefreshToken).into()
+            }
+            RefreshError::Reused => {
+                tracing::warn!("Rejection. Refresh token reused. Revoked token family");
+
+                TokenGrantError::new(verbosity, TokenGrantErrorType::RefreshTokenReused).into()
+            }
+            err => ApiError::from_generic_error(verbosity, err),
+        })?;
+
+    tracing::debug!(sub = %record.sub, "Refreshed token");
+
+    TokenResponse::new(
+        &state,
+        token_issuer,
+        &record.sub,
+        record.scope,
+        refresh_token,
+    )
+}
@@ -1,10 +1,20 @@
-use axum::{routing::post, Router};
+use crate::{
+    openapi::router::{post, ApiRouter},
+    state::ApiState,
+};
 
-use crate::state::ApiState;
-
-pub fn app() -> Router<ApiState> {
-    Router::<ApiState>::new().route(
-        "/validate_a_person",
-        post(super::validate_a_person::validate_a_person),
-    )
+pub fn app() -> ApiRouter<ApiState> {
+    ApiRouter::<ApiState>::new()
+        .api_route(
+            "/validate_a_person",
+            post(super::validate_a_person::validate_a_person),
+        )
+        .api_route(
+            "/validate_a_person_with_unique_name",
+            post(super::validate_a_person_with_unique_name::validate_a_person_with_unique_name),
+        )
+        .api_route(
+            "/validate_a_multipart_person",
+            post(super::validate_a_multipart_person::validate_a_multipart_person),
+        )
 }
@@ -1,2 +1,4 @@
 pub mod app;
+pub mod validate_a_multipart_person;
 pub mod validate_a_person;
+pub mod validate_a_person_with_unique_name;
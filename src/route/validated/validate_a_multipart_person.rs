@@ -0,0 +1,16 @@
+use crate::extractor::{multipart::ApiMultipart, validated::Validated};
+
+use super::validate_a_person::Person;
+
+pub async fn validate_a_multipart_person(
+    Validated(ApiMultipart {
+        fields: person,
+        files,
+    }): Validated<ApiMultipart<Person>>,
+) -> Person {
+    for file in files {
+        tracing::debug!(name=%file.name, file_name=%file.file_name, content_type=?file.content_type, size=file.bytes.len(), "Received file");
+    }
+
+    person
+}
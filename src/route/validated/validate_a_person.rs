@@ -7,9 +7,13 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use crate::extractor::{json::ApiJson, validated::Validated};
+use crate::{
+    extractor::{json::ApiJson, validated::Validated},
+    openapi::{ApiOperation, OperationOutput},
+};
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[schemars(rename = "ValidatedPerson")]
 pub struct Person {
     #[validate(length(min = 5, message = "Must be at least 5 characters long"))]
     pub name: String,
@@ -19,6 +23,12 @@ pub struct Person {
     pub city: String,
 }
 
+impl OperationOutput for Person {
+    fn describe(operation: ApiOperation) -> ApiOperation {
+        operation.response::<Self>()
+    }
+}
+
 impl IntoResponse for Person {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
@@ -0,0 +1,40 @@
+use std::borrow::Cow;
+
+use validator::{ValidationError, ValidationErrors};
+
+use crate::{
+    extractor::{
+        json::ApiJson,
+        validated::{AsyncValidator, ValidatedWith},
+    },
+    state::ApiState,
+};
+
+use super::validate_a_person::Person;
+
+/// Names that are already taken. Stands in for a database lookup.
+const TAKEN_NAMES: &[&str] = &["Alice", "Bobby"];
+
+pub struct UniqueName;
+
+impl AsyncValidator<ApiState, Person> for UniqueName {
+    async fn validate(person: &Person, _state: &ApiState) -> Result<(), ValidationErrors> {
+        if !TAKEN_NAMES.contains(&person.name.as_str()) {
+            return Ok(());
+        }
+
+        let mut errors = ValidationErrors::new();
+        errors.add(
+            "name",
+            ValidationError::new("unique").with_message(Cow::Borrowed("Name is already taken")),
+        );
+
+        Err(errors)
+    }
+}
+
+pub async fn validate_a_person_with_unique_name(
+    ValidatedWith(ApiJson(person), _): ValidatedWith<ApiJson<Person>, UniqueName>,
+) -> Person {
+    person
+}
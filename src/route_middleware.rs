@@ -0,0 +1,33 @@
+//! Middleware attached to path prefixes in the config file.
+//!
+//! Applied to the requests whose path is the prefix itself or below it, in addition to the server wide middleware.
+
+use serde::Deserialize;
+
+use crate::rate_limit::RateLimitConfig;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RouteMiddlewareConfig {
+    /// E.g. `/books` matches `/books` and `/books/1` but not `/bookstore`.
+    pub path_prefix: String,
+    /// Limited separately from the server wide rate limit.
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Overrides the server wide body limit.
+    pub max_body_size_in_bytes: Option<usize>,
+    /// Overrides the server wide timeout, measured from the moment the server received the request.
+    pub request_timeout_in_millis: Option<u64>,
+    pub required_auth: Option<RequiredAuth>,
+}
+
+/// The authentication scheme requests to a path prefix must be authenticated with.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum RequiredAuth {
+    Basic,
+    ApiKey,
+    Jwt {
+        /// Roles or scopes the claims must grant.
+        #[serde(default)]
+        required_roles: Vec<String>,
+    },
+}
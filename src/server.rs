@@ -1,40 +1,197 @@
-use std::{net::SocketAddr, path::Path};
+use std::{future::Future, net::SocketAddr, path::Path, sync::Arc, time::Duration};
 
 use anyhow::Context;
 use axum::{middleware, Router};
+use futures::FutureExt;
+use ipnet::IpNet;
 use serde::Deserialize;
-use tokio::net::TcpListener;
 use tower::ServiceBuilder;
 use tower_http::{
-    compression::CompressionLayer,
     cors::CorsLayer,
-    decompression::RequestDecompressionLayer,
-    trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer},
+    trace::{DefaultOnRequest, DefaultOnResponse, TraceLayer},
 };
 
 use crate::{
+    alert::{monitor::AlertMonitor, AlertConfig, AlertNotifiers},
+    analytics::{sink::TracingUsageSink, UsageAnalytics, UsageAnalyticsConfig},
+    audit::{AuditConfig, ConfiguredAuditSink},
+    body_trace::BodyTraceConfig,
+    claims::Claims,
+    compression::CompressionConfig,
+    concurrency_limit::{ConcurrencyLimitConfig, ConcurrencyLimiter},
+    cors::CorsConfig,
+    credentials::{ConfiguredCredentialStore, CredentialStoreConfig},
+    csrf::CsrfConfig,
+    downstream::{DownstreamClient, DownstreamConfig},
     error::ErrorVerbosity,
-    jwt::JwkRefresher,
+    error_sink::{ConfiguredErrorSink, ErrorReportingConfig},
+    etag::ETagConfig,
+    extractor::{
+        authorized::PolicyConfig, client_cert::ClientCertConfig, cookie::CookieSigningConfig,
+        deadline::DeadlineConfig, digest_auth::DigestAuthConfig,
+        jwt::validation::JwtValidationConfig, multipart::MultipartLimits,
+        pagination::PaginationConfig, principal::ClaimsMappingConfig,
+        signed_request::SignatureVerificationConfig, tenant::TenantConfig,
+    },
+    geoip::{GeoIpConfig, GeoIpResolver},
+    idempotency::IdempotencyConfig,
+    introspection::{IntrospectionConfig, TokenIntrospector},
+    ip_filter::IpFilterConfig,
+    jwt::{default_jwks_max_stale_in_seconds, IdentityProviderConfig, IssuerJwks, JwkRefresher},
+    lifecycle::EndpointLifecycleEntry,
+    listener::{serve::serve, Listener, ListenerConfig},
+    locale::LocaleCatalog,
+    maintenance::{MaintenanceConfig, MaintenanceMode, MaintenanceModeProvider},
     middleware::{
-        method_not_allowed::method_not_allowed, not_found, trace_headers::trace_headers,
-        trace_response_body::trace_response_body,
+        api_key::layer::ApiKeyLayer, audit::AuditLayer, basic_auth::layer::BasicAuthLayer,
+        body_limit::layer::BodyLimitLayer, catch_panic::layer::CatchPanicLayer,
+        concurrency_limit::layer::ConcurrencyLimitLayer, csrf::csrf,
+        endpoint_lifecycle::endpoint_lifecycle, error_format::error_format,
+        error_locale::error_locale, error_metrics::error_metrics, error_reporting::error_reporting,
+        error_verbosity::error_verbosity, etag::etag, geoip::geoip,
+        idempotency::layer::IdempotencyLayer, ip_filter::layer::IpFilterLayer,
+        jwt_auth::layer::JwtAuthLayer, maintenance::maintenance,
+        method_not_allowed::method_not_allowed, not_found, path_prefix::layer::PathPrefixLayer,
+        rate_limit::layer::RateLimitLayer, request_id::request_id,
+        response_body_trace::layer::ResponseBodyTraceLayer,
+        response_schema_validation::response_schema_validation, session::SessionLayer,
+        slow_request::slow_request, timeout::layer::TimeoutLayer, trace_headers::trace_headers,
+        trace_request_body::trace_request_body, usage_analytics::usage_analytics,
     },
+    oidc::login::{OidcLogin, OidcLoginConfig},
+    openapi::{self, router::ApiRouter, OpenApiConfig},
     openid_configuration::OpenIdConfiguration,
-    route::{api_key_protected, base, books, error, post_json, validated},
+    problem_details::ErrorFormatConfig,
+    rate_limit::{RateLimitConfig, RateLimitKeyResolver, RateLimiter},
+    request_id::make_span,
+    response_schema::{ResponseSchemaRegistry, ResponseSchemaValidationConfig},
+    revocation::TokenRevocationConfig,
+    route::{
+        admin, api_key_protected, auth, base, books, error, health, jwt_protected, logout,
+        post_cbor, post_form, post_json, post_msgpack, post_raw, post_xml, token, validated,
+    },
+    route_middleware::{RequiredAuth, RouteMiddlewareConfig},
+    session::SessionConfig,
+    shutdown::{ShutdownConfig, ShutdownHooks},
+    signing::signer::{RequestSigner, SigningKeyConfig},
+    slow_request::SlowRequestConfig,
     state::ApiState,
-    types::{used_api_key::UsedApiKey, used_basic_auth::UsedBasicAuth},
+    store::{ConfiguredStore, StoreConfig},
+    tls::TlsConfig,
+    token_issuer::{TokenIssuer, TokenIssuerConfig},
+    types::{stored_api_key::ConfiguredApiKey, used_basic_auth::ConfiguredBasicAuthUser},
+    verbosity_policy::{VerbosityPolicy, VerbosityPolicyConfig},
 };
 
 #[derive(Debug, Deserialize)]
 pub struct ServerConfig {
-    socket_address: SocketAddr,
+    /// Served in addition to the `listeners`.
+    socket_address: Option<SocketAddr>,
+    #[serde(default)]
+    listeners: Vec<ListenerConfig>,
     error_verbosity: ErrorVerbosity,
+    #[serde(default)]
+    error_verbosity_policy: VerbosityPolicyConfig,
+    #[serde(default)]
+    error_format: ErrorFormatConfig,
+    #[serde(default)]
+    strict_deserialization: bool,
+    #[serde(default)]
+    multipart_limits: MultipartLimits,
+    cookie_signing: Option<CookieSigningConfig>,
+    #[serde(default = "default_max_body_size_in_bytes")]
+    max_body_size_in_bytes: usize,
+    #[serde(default = "default_request_timeout_in_millis")]
+    request_timeout_in_millis: u64,
+    #[serde(default)]
+    compression: CompressionConfig,
+    #[serde(default)]
+    trusted_proxies: Vec<IpNet>,
+    rate_limit: Option<RateLimitConfig>,
+    ip_filter: Option<IpFilterConfig>,
+    #[serde(default)]
+    concurrency_limit: ConcurrencyLimitConfig,
+    #[serde(default)]
+    pagination: PaginationConfig,
+    #[serde(default)]
+    deadline: DeadlineConfig,
+    #[serde(default)]
+    tenant: TenantConfig,
+    #[serde(default)]
+    policies: PolicyConfig,
+    #[serde(default)]
+    signature_verification: SignatureVerificationConfig,
+    #[serde(default)]
+    client_certificates: ClientCertConfig,
+    #[serde(default)]
+    digest_auth: DigestAuthConfig,
+    #[serde(default)]
+    claims_mapping: ClaimsMappingConfig,
     api_key_header_name: String,
-    api_keys: Vec<UsedApiKey>,
-    basic_auth_users: Vec<UsedBasicAuth>,
+    #[serde(default)]
+    credential_store: CredentialStoreConfig,
+    /// Plaintext keys or salted hashes. See the `hash_api_keys` binary to migrate plaintext keys.
+    #[serde(default)]
+    api_keys: Vec<ConfiguredApiKey>,
+    #[serde(default)]
+    basic_auth_users: Vec<ConfiguredBasicAuthUser>,
     openid_configuration_url: String,
     jwks_time_to_live_in_seconds: u64,
+    #[serde(default = "default_jwks_max_stale_in_seconds")]
+    jwks_max_stale_in_seconds: u64,
     audience: Vec<String>,
+    #[serde(default)]
+    additional_identity_providers: Vec<IdentityProviderConfig>,
+    #[serde(default)]
+    jwt_validation: JwtValidationConfig,
+    alerting: Option<AlertConfig>,
+    geoip: Option<GeoIpConfig>,
+    #[serde(default)]
+    endpoint_lifecycles: Vec<EndpointLifecycleEntry>,
+    usage_analytics: Option<UsageAnalyticsConfig>,
+    downstream: Option<DownstreamConfig>,
+    request_signing: Option<SigningKeyConfig>,
+    token_introspection: Option<IntrospectionConfig>,
+    oidc_login: Option<OidcLoginConfig>,
+    #[serde(default)]
+    store: StoreConfig,
+    #[serde(default)]
+    session: SessionConfig,
+    token_issuer: Option<TokenIssuerConfig>,
+    #[serde(default)]
+    token_revocation: TokenRevocationConfig,
+    response_schema_validation: Option<ResponseSchemaValidationConfig>,
+    #[serde(default)]
+    locale_catalog: LocaleCatalog,
+    etag: Option<ETagConfig>,
+    csrf: Option<CsrfConfig>,
+    #[serde(default)]
+    maintenance: MaintenanceConfig,
+    audit: Option<AuditConfig>,
+    error_reporting: Option<ErrorReportingConfig>,
+    idempotency: Option<IdempotencyConfig>,
+    #[serde(default)]
+    body_trace: BodyTraceConfig,
+    slow_requests: Option<SlowRequestConfig>,
+    /// Middleware attached to path prefixes.
+    #[serde(default)]
+    routes: Vec<RouteMiddlewareConfig>,
+    /// Allows any origin, method and header if not set.
+    cors: Option<CorsConfig>,
+    /// Serves the OpenAPI document of the bundled routes if set.
+    openapi: Option<OpenApiConfig>,
+    /// Serves HTTPS instead of HTTP if set.
+    tls: Option<TlsConfig>,
+    #[serde(default)]
+    shutdown: ShutdownConfig,
+}
+
+fn default_max_body_size_in_bytes() -> usize {
+    2 * 1024 * 1024
+}
+
+fn default_request_timeout_in_millis() -> u64 {
+    30_000
 }
 
 impl ServerConfig {
@@ -52,19 +209,34 @@ impl ServerConfig {
 
 pub struct Server {
     config: ServerConfig,
+    shutdown_hooks: ShutdownHooks,
 }
 
 impl Server {
     pub fn new(config: ServerConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            shutdown_hooks: ShutdownHooks::new(),
+        }
+    }
+
+    /// Adds a hook that runs on shutdown after the connections are drained. See [`ShutdownHooks::add`].
+    pub fn on_shutdown<F, Fut>(mut self, name: impl Into<String>, hook: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.shutdown_hooks.add(name, hook);
+
+        self
     }
 
     async fn obtain_openid_config(
-        &self,
+        openid_configuration_url: &str,
         http_client: &reqwest::Client,
     ) -> anyhow::Result<OpenIdConfiguration> {
         let openid_config = http_client
-            .get(&self.config.openid_configuration_url)
+            .get(openid_configuration_url)
             .send()
             .await
             .context("Failed to get OpenID configuration")?
@@ -76,84 +248,535 @@ impl Server {
     }
 
     pub async fn run(self) -> anyhow::Result<()> {
+        let mut shutdown_hooks = self.shutdown_hooks;
+
+        let cors = match self.config.cors {
+            Some(config) => config.into_layer().context("Invalid CORS config")?,
+            None => CorsLayer::permissive(),
+        };
+
         let http_client = reqwest::Client::new();
 
         tracing::trace!("Obtaining OpenID configuration");
-        let openid_config = self.obtain_openid_config(&http_client).await?;
+        let openid_config =
+            Self::obtain_openid_config(&self.config.openid_configuration_url, &http_client).await?;
         tracing::debug!(?openid_config, "Obtained OpenID configuration");
 
-        let jwk_refresher = JwkRefresher::new(
+        let mut issuers = vec![IssuerJwks::new(
             self.config.jwks_time_to_live_in_seconds,
+            self.config.jwks_max_stale_in_seconds,
             openid_config.jwks_uri.clone(),
-            vec![openid_config.issuer],
+            openid_config.issuer.clone(),
             self.config.audience,
-            http_client,
+            http_client.clone(),
         )
         .await
-        .context("Failed to create JwkRefresher")?;
+        .context("Failed to create IssuerJwks")?];
 
-        let state = ApiState::new(
-            self.config.error_verbosity,
-            self.config.api_key_header_name,
+        for identity_provider in self.config.additional_identity_providers {
+            let provider_openid_config = Self::obtain_openid_config(
+                &identity_provider.openid_configuration_url,
+                &http_client,
+            )
+            .await?;
+            tracing::debug!(openid_config=?provider_openid_config, "Obtained OpenID configuration of additional identity provider");
+
+            issuers.push(
+                IssuerJwks::new(
+                    identity_provider.jwks_time_to_live_in_seconds,
+                    identity_provider.jwks_max_stale_in_seconds,
+                    provider_openid_config.jwks_uri,
+                    provider_openid_config.issuer,
+                    identity_provider.audience,
+                    http_client.clone(),
+                )
+                .await
+                .context("Failed to create IssuerJwks")?,
+            );
+        }
+
+        let jwk_refresher = JwkRefresher::new(issuers, self.config.jwt_validation);
+
+        let alert_monitor = self
+            .config
+            .alerting
+            .map(|alerting| {
+                AlertNotifiers::from_config(&alerting, http_client.clone())
+                    .map(|notifiers| AlertMonitor::new(notifiers, alerting.thresholds))
+            })
+            .transpose()?;
+
+        let geoip_resolver = self
+            .config
+            .geoip
+            .map(GeoIpResolver::from_config)
+            .transpose()?;
+
+        let analytics = self.config.usage_analytics.map(|config| {
+            let analytics = Arc::new(UsageAnalytics::new(TracingUsageSink));
+
+            analytics
+                .clone()
+                .spawn_flush_task(Duration::from_secs(config.flush_interval_in_seconds));
+
+            shutdown_hooks.add("flush usage analytics", {
+                let analytics = analytics.clone();
+
+                move || async move { analytics.flush().await }
+            });
+
+            analytics
+        });
+
+        let body_trace = self.config.body_trace;
+
+        let error_sink = self
+            .config
+            .error_reporting
+            .as_ref()
+            .map(|config| ConfiguredErrorSink::from_config(&config.sink, http_client.clone()))
+            .transpose()
+            .context("Failed to create error sink")?
+            .map(Arc::new);
+
+        let audit_sink = match &self.config.audit {
+            Some(config) => Some(Arc::new(
+                ConfiguredAuditSink::from_config(&config.sink, http_client.clone()).await?,
+            )),
+            None => None,
+        };
+
+        let downstream_client = self.config.downstream.map(|config| {
+            DownstreamClient::new(
+                http_client.clone(),
+                openid_config.token_endpoint.clone(),
+                config,
+            )
+        });
+
+        let request_signer = match self.config.request_signing {
+            Some(config) => Some(
+                RequestSigner::from_config(config)
+                    .await
+                    .context("Failed to create RequestSigner")?,
+            ),
+            None => None,
+        };
+
+        let token_introspector = self
+            .config
+            .token_introspection
+            .map(|config| TokenIntrospector::new(http_client.clone(), config));
+
+        let store = ConfiguredStore::from_config(&self.config.store)
+            .await
+            .context("Failed to create store")?;
+
+        let credential_store = ConfiguredCredentialStore::from_config(
+            &self.config.credential_store,
+            &self.config.api_key_header_name,
             self.config.api_keys,
             self.config.basic_auth_users,
+        )
+        .await
+        .context("Failed to create credential store")?;
+
+        let oidc_login = self
+            .config
+            .oidc_login
+            .map(|config| {
+                OidcLogin::new(
+                    http_client.clone(),
+                    openid_config.issuer.clone(),
+                    openid_config.authorization_endpoint.clone(),
+                    openid_config.token_endpoint.clone(),
+                    openid_config.end_session_endpoint.clone(),
+                    config,
+                )
+            })
+            .transpose()
+            .context("Failed to create OidcLogin")?;
+
+        let token_issuer = match self.config.token_issuer {
+            Some(config) => Some(
+                TokenIssuer::from_config(config)
+                    .await
+                    .context("Failed to create TokenIssuer")?,
+            ),
+            None => None,
+        };
+
+        let state = ApiState::new(
+            VerbosityPolicy::new(
+                self.config.error_verbosity,
+                self.config.error_verbosity_policy,
+            ),
+            self.config.strict_deserialization,
+            self.config.multipart_limits,
+            self.config
+                .cookie_signing
+                .map(|config| config.secret.into_bytes()),
+            self.config.max_body_size_in_bytes,
+            self.config.trusted_proxies,
+            self.config.pagination,
+            self.config.deadline,
+            self.config.tenant,
+            self.config.policies,
+            self.config.signature_verification,
+            self.config.client_certificates,
+            self.config.digest_auth,
+            self.config.claims_mapping,
+            self.config.api_key_header_name,
+            credential_store,
             jwk_refresher,
+            alert_monitor,
+            geoip_resolver,
+            self.config.endpoint_lifecycles,
+            analytics,
+            openid_config.end_session_endpoint,
+            downstream_client,
+            request_signer,
+            token_introspector,
+            store,
+            Duration::from_secs(self.config.session.time_to_live_in_seconds),
+            oidc_login,
+            token_issuer,
+            Duration::from_secs(self.config.token_revocation.default_time_to_live_in_seconds),
+            self.config.response_schema_validation,
+            ResponseSchemaRegistry::new()
+                .register::<books::get_book::GetBookResponse>("/books/get_book"),
+            self.config.locale_catalog,
+            self.config.etag,
+            self.config.csrf,
+            MaintenanceMode::new(self.config.maintenance),
+            audit_sink,
+            body_trace.clone(),
+            self.config
+                .slow_requests
+                .as_ref()
+                .map(SlowRequestConfig::threshold),
+            self.config.error_format,
+            error_sink,
+            self.config
+                .error_reporting
+                .as_ref()
+                .is_some_and(|config| config.all_server_errors),
         )
         .await
         .context("Failed to create ApiState")?;
 
-        let app = Router::new()
-            .fallback(not_found::not_found::<ApiState>)
+        let (app, registry) = ApiRouter::new()
             .nest(
                 "/api_key_protected",
                 api_key_protected::app::app(state.clone()),
             )
+            .nest("/jwt_protected", jwt_protected::app::app(state.clone()))
             .nest("/post_json", post_json::app::app())
+            .nest("/post_form", post_form::app::app())
+            .nest("/post_cbor", post_cbor::app::app())
+            .nest("/post_msgpack", post_msgpack::app::app())
+            .nest("/post_xml", post_xml::app::app())
+            .nest("/post_raw", post_raw::app::app(state.clone()))
             .nest("/validated", validated::app::app())
             .nest("/books", books::app::app())
-            .nest("/error", error::app::app())
+            .nest("/error", error::app::app(state.clone()))
+            .nest("/admin", admin::app::app())
+            .nest("/logout", logout::app::app())
+            .nest("/auth", auth::app::app())
+            .nest("/token", token::app::app())
+            .nest("/health", health::app::app())
             .nest("/", base::app::app())
+            .into_parts();
+
+        let app =
+            app.fallback(not_found::not_found::<ApiState>)
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    endpoint_lifecycle::<ApiState>,
+                ));
+
+        let app = match state.usage_analytics() {
+            Some(_) => app.layer(middleware::from_fn_with_state(
+                state.clone(),
+                usage_analytics::<ApiState>,
+            )),
+            None => app,
+        };
+
+        let app = app
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                slow_request::<ApiState>,
+            ))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                response_schema_validation::<ApiState>,
+            ));
+
+        let app = match &self.config.openapi {
+            Some(config) => app.merge(openapi::router(
+                config,
+                &registry.build(self.config.error_verbosity),
+            )?),
+            None => app,
+        };
+
+        let app = self
+            .config
+            .routes
+            .into_iter()
+            .fold(app, |app, route| layer_route_middleware(app, &state, route));
+
+        // Inside the session layer, so replayed responses do not carry the session cookie of the first request.
+        let app = match self.config.idempotency {
+            Some(config) => app.layer(IdempotencyLayer::new(
+                state.clone(),
+                state.clone(),
+                Duration::from_secs(config.time_to_live_in_seconds),
+                Duration::from_millis(config.in_progress_wait_in_millis),
+            )),
+            None => app,
+        };
+
+        let app = app
+            .layer(SessionLayer::new(state.clone()))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                csrf::<ApiState>,
+            ))
             .layer(middleware::from_fn(trace_headers))
             .layer(middleware::from_fn_with_state(
                 state.clone(),
-                trace_response_body::<ApiState>,
+                trace_request_body::<ApiState>,
+            ))
+            .layer(ResponseBodyTraceLayer::new(body_trace))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                etag::<ApiState>,
             ))
             .layer(middleware::from_fn_with_state(
                 state.clone(),
                 method_not_allowed::<ApiState>,
             ))
-            .with_state(state)
-            .layer(
-                ServiceBuilder::new()
-                    .layer(
-                        TraceLayer::new_for_http()
-                            .make_span_with(DefaultMakeSpan::new().level(tracing::Level::INFO))
-                            .on_request(DefaultOnRequest::new().level(tracing::Level::INFO))
-                            .on_response(DefaultOnResponse::new().level(tracing::Level::INFO)),
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                geoip::<ApiState>,
+            ));
+
+        let app = match self.config.rate_limit {
+            Some(config) => app.layer(RateLimitLayer::new(
+                state.clone(),
+                RateLimiter::new(config.algorithm),
+                RateLimitKeyResolver::from(config.key),
+            )),
+            None => app,
+        };
+
+        let app = match self.config.ip_filter {
+            Some(config) => app.layer(IpFilterLayer::new(state.clone(), config)),
+            None => app,
+        };
+
+        let app = app.layer(middleware::from_fn_with_state(
+            state.clone(),
+            maintenance::<ApiState>,
+        ));
+
+        let app = match self.config.audit {
+            Some(config) => app.layer(AuditLayer::new(state.clone(), config)),
+            None => app,
+        };
+
+        #[cfg(unix)]
+        tokio::spawn(toggle_maintenance_on_sighup(state.clone()));
+
+        let concurrency_limiter = ConcurrencyLimiter::from_config(self.config.concurrency_limit);
+
+        let app = app.with_state(state.clone()).layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(request_id))
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    error_format::<ApiState>,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    error_verbosity::<ApiState>,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    error_locale::<ApiState>,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    error_reporting::<ApiState>,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    error_metrics::<ApiState>,
+                ))
+                .layer(
+                    TraceLayer::new_for_http()
+                        .make_span_with(make_span)
+                        .on_request(DefaultOnRequest::new().level(tracing::Level::INFO))
+                        .on_response(DefaultOnResponse::new().level(tracing::Level::INFO)),
+                )
+                .layer(self.config.compression.compression_layer())
+                .layer(self.config.compression.decompression_layer())
+                .layer(BodyLimitLayer::decompressed(
+                    state.clone(),
+                    self.config.compression.max_decompressed_size_in_bytes,
+                ))
+                .layer(cors)
+                .layer(ConcurrencyLimitLayer::new(
+                    state.clone(),
+                    concurrency_limiter,
+                ))
+                .layer(TimeoutLayer::new(
+                    state.clone(),
+                    Duration::from_millis(self.config.request_timeout_in_millis),
+                ))
+                .layer(CatchPanicLayer::new(state)),
+        );
+
+        let acceptor = self
+            .config
+            .tls
+            .map(TlsConfig::into_acceptor)
+            .transpose()
+            .context("Invalid TLS config")?;
+
+        let listeners = self
+            .config
+            .socket_address
+            .map(|socket_address| ListenerConfig::Tcp { socket_address })
+            .into_iter()
+            .chain(self.config.listeners)
+            .collect::<Vec<_>>();
+
+        anyhow::ensure!(!listeners.is_empty(), "No listener configured");
+
+        let shutdown = shutdown_signal().shared();
+        let drain_timeout = Duration::from_secs(self.config.shutdown.drain_timeout_in_seconds);
+
+        // Bind all listeners before serving any, so a failing bind does not leave a partially started server.
+        let mut servers = Vec::with_capacity(listeners.len());
+
+        for config in listeners {
+            let name = config.to_string();
+
+            let server = match config.bind().await.context("Bind failed")? {
+                Listener::Tcp(listener) => {
+                    tracing::info!(listener = %name, tls = acceptor.is_some(), "Starting server");
+
+                    serve(
+                        listener,
+                        acceptor.clone(),
+                        app.clone(),
+                        shutdown.clone(),
+                        drain_timeout,
                     )
-                    .layer(RequestDecompressionLayer::new())
-                    .layer(CompressionLayer::new())
-                    .layer(CorsLayer::permissive()),
-            );
+                    .boxed()
+                }
+                #[cfg(unix)]
+                Listener::Unix(listener) => {
+                    tracing::info!(listener = %name, tls = false, "Starting server");
 
-        tracing::info!(addr = %self.config.socket_address, "Starting server");
+                    serve(listener, None, app.clone(), shutdown.clone(), drain_timeout).boxed()
+                }
+            };
 
-        let listener = TcpListener::bind(&self.config.socket_address)
-            .await
-            .context("Bind failed")?;
+            servers.push(server);
+        }
 
-        axum::serve(
-            listener,
-            app.into_make_service_with_connect_info::<SocketAddr>(),
-        )
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .context("Server failed")?;
+        futures::future::join_all(servers).await;
+
+        shutdown_hooks
+            .run(Duration::from_secs(
+                self.config.shutdown.hook_timeout_in_seconds,
+            ))
+            .await;
+
+        tracing::info!("Server stopped");
 
         Ok(())
     }
 }
 
+/// Applies the middleware of the route config to the requests below its path prefix.
+///
+/// The body limit is applied innermost and the rate limit outermost.
+fn layer_route_middleware(
+    app: Router<ApiState>,
+    state: &ApiState,
+    route: RouteMiddlewareConfig,
+) -> Router<ApiState> {
+    let prefix = route.path_prefix.as_str();
+
+    let app = match route.max_body_size_in_bytes {
+        Some(limit) => app.layer(PathPrefixLayer::new(
+            prefix,
+            BodyLimitLayer::new(state.clone(), limit),
+        )),
+        None => app,
+    };
+
+    let app = match route.required_auth {
+        Some(RequiredAuth::Basic) => app.layer(PathPrefixLayer::new(
+            prefix,
+            BasicAuthLayer::new(state.clone()),
+        )),
+        Some(RequiredAuth::ApiKey) => app.layer(PathPrefixLayer::new(
+            prefix,
+            ApiKeyLayer::new(state.clone()),
+        )),
+        Some(RequiredAuth::Jwt { required_roles }) => app.layer(PathPrefixLayer::new(
+            prefix,
+            JwtAuthLayer::<_, Claims>::new(state.clone()).require_roles(required_roles),
+        )),
+        None => app,
+    };
+
+    let app = match route.request_timeout_in_millis {
+        Some(timeout) => app.layer(PathPrefixLayer::new(
+            prefix,
+            TimeoutLayer::new(state.clone(), Duration::from_millis(timeout)),
+        )),
+        None => app,
+    };
+
+    match route.rate_limit {
+        Some(config) => app.layer(PathPrefixLayer::new(
+            prefix,
+            RateLimitLayer::new(
+                state.clone(),
+                RateLimiter::new(config.algorithm),
+                RateLimitKeyResolver::from(config.key),
+            ),
+        )),
+        None => app,
+    }
+}
+
+/// Toggles the maintenance mode every time `SIGHUP` is received.
+#[cfg(unix)]
+async fn toggle_maintenance_on_sighup(state: ApiState) {
+    let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(err) => {
+            tracing::error!(%err, "Failed to install SIGHUP signal handler");
+
+            return;
+        }
+    };
+
+    while hangup.recv().await.is_some() {
+        tracing::info!("SIGHUP received");
+
+        state.maintenance_mode().toggle();
+    }
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         tokio::signal::ctrl_c()
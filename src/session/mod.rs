@@ -0,0 +1,277 @@
+//! Server-side sessions identified by a random id stored in the [`SESSION_COOKIE_NAME`] cookie.
+//!
+//! The [`SessionLayer`](crate::middleware::session::SessionLayer) loads the session of a request into a [`Session`]
+//! and persists the changes after the response, extending the session's expiry on every request.
+
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    openapi::OperationInput,
+    store::{KeyValueStore, StoreError},
+};
+
+const KEY_PREFIX: &str = "session:";
+
+/// Name of the cookie holding the session id.
+pub const SESSION_COOKIE_NAME: &str = "session";
+
+/// Returns a new random session id.
+///
+/// The id is 32 random bytes, base64 url-safe encoded without padding.
+pub fn new_session_id() -> String {
+    URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>())
+}
+
+/// Stores session data by session id.
+pub trait SessionStore {
+    type Error;
+
+    /// Returns the data of the session or `None` if the session does not exist or has expired.
+    fn load_session(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = Result<Option<serde_json::Value>, Self::Error>> + Send;
+
+    /// Creates or replaces the session. The session expires after `time_to_live`.
+    fn store_session(
+        &self,
+        id: &str,
+        data: serde_json::Value,
+        time_to_live: Duration,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Extends the expiry of the session to `time_to_live` from now.
+    fn touch_session(
+        &self,
+        id: &str,
+        time_to_live: Duration,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Removes the session if it exists.
+    fn remove_session(&self, id: &str) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Marks the session as used. The marker expires after `time_to_live`.
+    ///
+    /// Returns `false` if the session was marked before. Of concurrent calls, only one returns `true`.
+    fn mark_session_used(
+        &self,
+        id: &str,
+        time_to_live: Duration,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+}
+
+pub trait SessionConfigProvider {
+    /// Returns how long a session lives after its last request.
+    fn session_time_to_live(&self) -> Duration;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SessionConfig {
+    #[serde(default = "default_time_to_live_in_seconds")]
+    pub time_to_live_in_seconds: u64,
+}
+
+fn default_time_to_live_in_seconds() -> u64 {
+    8 * 60 * 60
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            time_to_live_in_seconds: default_time_to_live_in_seconds(),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionStoreError {
+    #[error(transparent)]
+    Store(#[from] StoreError),
+    #[error("Failed to (de)serialize session data: {0}")]
+    Data(#[from] serde_json::Error),
+}
+
+/// Keeps each session as JSON under its prefixed id.
+impl<K> SessionStore for K
+where
+    K: KeyValueStore + Sync,
+    StoreError: From<K::Error>,
+{
+    type Error = SessionStoreError;
+
+    async fn load_session(&self, id: &str) -> Result<Option<serde_json::Value>, Self::Error> {
+        let data = self
+            .get(&format!("{KEY_PREFIX}{id}"))
+            .await
+            .map_err(StoreError::from)?;
+
+        Ok(data.map(|data| serde_json::from_str(&data)).transpose()?)
+    }
+
+    async fn store_session(
+        &self,
+        id: &str,
+        data: serde_json::Value,
+        time_to_live: Duration,
+    ) -> Result<(), Self::Error> {
+        let data = serde_json::to_string(&data)?;
+
+        self.set(&format!("{KEY_PREFIX}{id}"), data, time_to_live)
+            .await
+            .map_err(StoreError::from)?;
+
+        Ok(())
+    }
+
+    async fn touch_session(&self, id: &str, time_to_live: Duration) -> Result<(), Self::Error> {
+        self.expire(&format!("{KEY_PREFIX}{id}"), time_to_live)
+            .await
+            .map_err(StoreError::from)?;
+
+        Ok(())
+    }
+
+    async fn remove_session(&self, id: &str) -> Result<(), Self::Error> {
+        self.remove(&format!("{KEY_PREFIX}{id}"))
+            .await
+            .map_err(StoreError::from)?;
+
+        Ok(())
+    }
+
+    async fn mark_session_used(
+        &self,
+        id: &str,
+        time_to_live: Duration,
+    ) -> Result<bool, Self::Error> {
+        Ok(self
+            .set_if_absent(
+                &format!("{KEY_PREFIX}{id}:used"),
+                String::new(),
+                time_to_live,
+            )
+            .await
+            .map_err(StoreError::from)?)
+    }
+}
+
+#[derive(Debug)]
+struct SessionInner {
+    id: Option<String>,
+    data: Option<serde_json::Value>,
+    changed: bool,
+    renew: bool,
+}
+
+/// What the [`SessionLayer`](crate::middleware::session::SessionLayer) has to persist after the response.
+#[derive(Debug)]
+pub enum SessionChange {
+    /// The session was not changed. Its expiry is extended if it exists.
+    Unchanged { id: Option<String> },
+    /// The data was inserted. `previous_id` is set if the session was renewed.
+    Stored {
+        id: String,
+        previous_id: Option<String>,
+        data: serde_json::Value,
+    },
+    /// The session was removed.
+    Removed { id: Option<String> },
+}
+
+/// The session of the current request.
+///
+/// Changes are persisted by the [`SessionLayer`](crate::middleware::session::SessionLayer) after the response.
+#[derive(Debug, Clone)]
+pub struct Session {
+    inner: Arc<Mutex<SessionInner>>,
+}
+
+impl OperationInput for Session {}
+
+impl Session {
+    pub fn new(id: Option<String>, data: Option<serde_json::Value>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SessionInner {
+                id,
+                data,
+                changed: false,
+                renew: false,
+            })),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SessionInner> {
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Returns the session data or `None` if there is no session.
+    pub fn get<T: DeserializeOwned>(&self) -> Result<Option<T>, serde_json::Error> {
+        self.lock()
+            .data
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+    }
+
+    /// Replaces the session data. Creates a new session if there is none.
+    pub fn insert<T: Serialize>(&self, data: &T) -> Result<(), serde_json::Error> {
+        let data = serde_json::to_value(data)?;
+
+        let mut inner = self.lock();
+        inner.data = Some(data);
+        inner.changed = true;
+
+        Ok(())
+    }
+
+    /// Moves the session to a new id, e.g. after a login to prevent session fixation.
+    pub fn renew(&self) {
+        let mut inner = self.lock();
+        inner.renew = true;
+        inner.changed = true;
+    }
+
+    /// Removes the session.
+    pub fn remove(&self) {
+        let mut inner = self.lock();
+        inner.data = None;
+        inner.changed = true;
+    }
+
+    /// Returns what has to be persisted.
+    pub fn change(&self) -> SessionChange {
+        let mut inner = self.lock();
+
+        if !inner.changed {
+            return SessionChange::Unchanged {
+                id: inner.id.clone(),
+            };
+        }
+
+        let Some(data) = inner.data.clone() else {
+            return SessionChange::Removed {
+                id: inner.id.take(),
+            };
+        };
+
+        match (inner.id.clone(), inner.renew) {
+            (Some(id), false) => SessionChange::Stored {
+                id,
+                previous_id: None,
+                data,
+            },
+            (previous_id, _) => SessionChange::Stored {
+                id: new_session_id(),
+                previous_id,
+                data,
+            },
+        }
+    }
+}
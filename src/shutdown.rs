@@ -0,0 +1,71 @@
+//! Graceful shutdown on `CTRL+C` or, on unix, `SIGTERM`.
+//!
+//! The listeners drain their connections: In-flight requests get the drain timeout to complete and no connection is
+//! kept alive. Afterwards the [`ShutdownHooks`] run, e.g. to flush buffered data, before the server returns.
+
+use std::{future::Future, time::Duration};
+
+use futures::{future::BoxFuture, FutureExt};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShutdownConfig {
+    /// How long in-flight requests may take to complete before their connections are closed.
+    #[serde(default = "default_drain_timeout_in_seconds")]
+    pub drain_timeout_in_seconds: u64,
+    /// How long each shutdown hook may take before it is abandoned.
+    #[serde(default = "default_hook_timeout_in_seconds")]
+    pub hook_timeout_in_seconds: u64,
+}
+
+fn default_drain_timeout_in_seconds() -> u64 {
+    30
+}
+
+fn default_hook_timeout_in_seconds() -> u64 {
+    10
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            drain_timeout_in_seconds: default_drain_timeout_in_seconds(),
+            hook_timeout_in_seconds: default_hook_timeout_in_seconds(),
+        }
+    }
+}
+
+type ShutdownHook = Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>;
+
+/// Async functions that run after the connections are drained.
+#[derive(Default)]
+pub struct ShutdownHooks {
+    hooks: Vec<(String, ShutdownHook)>,
+}
+
+impl ShutdownHooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a hook. The `name` identifies the hook in the logs.
+    pub fn add<F, Fut>(&mut self, name: impl Into<String>, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.hooks
+            .push((name.into(), Box::new(move || hook().boxed())));
+    }
+
+    /// Runs the hooks one after another in the order they were added.
+    pub async fn run(self, timeout: Duration) {
+        for (name, hook) in self.hooks {
+            tracing::debug!(%name, "Running shutdown hook");
+
+            if tokio::time::timeout(timeout, hook()).await.is_err() {
+                tracing::warn!(%name, ?timeout, "Shutdown hook timed out");
+            }
+        }
+    }
+}
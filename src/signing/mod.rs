@@ -0,0 +1,59 @@
+//! Request signing shared by the outbound [`RequestSigner`](signer::RequestSigner) and inbound verification.
+//!
+//! The signature is computed over the canonical request:
+//!
+//! ```text
+//! METHOD\nPATH_AND_QUERY\nTIMESTAMP\nHEX(SHA256(BODY))
+//! ```
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+pub mod signer;
+
+/// Id of the key used to sign the request.
+pub const SIGNATURE_KEY_ID_HEADER: &str = "x-signature-key-id";
+/// Unix timestamp in seconds at which the request was signed.
+pub const SIGNATURE_TIMESTAMP_HEADER: &str = "x-signature-timestamp";
+/// `hmac-sha256=<hex>` or `jws=<compact JWS>`.
+pub const SIGNATURE_HEADER: &str = "x-signature";
+
+pub const HMAC_SHA256_PREFIX: &str = "hmac-sha256=";
+pub const JWS_PREFIX: &str = "jws=";
+
+pub type HmacSha256 = Hmac<Sha256>;
+
+/// Claims of a JWS signature.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct SignatureClaims {
+    /// Hex encoded SHA256 of the canonical request.
+    pub canonical_request_sha256: String,
+    pub iat: u64,
+}
+
+pub fn body_sha256(body: &[u8]) -> String {
+    hex::encode(Sha256::digest(body))
+}
+
+pub fn canonical_request(
+    method: &str,
+    path_and_query: &str,
+    timestamp: u64,
+    body: &[u8],
+) -> String {
+    format!(
+        "{method}\n{path_and_query}\n{timestamp}\n{}",
+        body_sha256(body)
+    )
+}
+
+pub fn canonical_request_sha256(canonical_request: &str) -> String {
+    hex::encode(Sha256::digest(canonical_request.as_bytes()))
+}
+
+pub fn hmac_sha256(secret: &[u8], canonical_request: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC can take key of any size");
+    mac.update(canonical_request.as_bytes());
+
+    mac
+}
@@ -0,0 +1,186 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use derivative::Derivative;
+use hmac::Mac;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::{header::HeaderValue, Request};
+use serde::Deserialize;
+
+use super::{
+    canonical_request, canonical_request_sha256, hmac_sha256, SignatureClaims, HMAC_SHA256_PREFIX,
+    JWS_PREFIX, SIGNATURE_HEADER, SIGNATURE_KEY_ID_HEADER, SIGNATURE_TIMESTAMP_HEADER,
+};
+
+#[derive(Derivative, Clone, Deserialize)]
+#[derivative(Debug)]
+#[serde(tag = "algorithm")]
+pub enum SigningKeyConfig {
+    HmacSha256 {
+        key_id: String,
+        #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+        secret: String,
+    },
+    Jws {
+        key_id: String,
+        /// One of the RSA, EC or EdDSA algorithms.
+        jws_algorithm: Algorithm,
+        /// Path to the PEM encoded private key.
+        private_key_path: String,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SigningError {
+    #[error("Request body is a stream and can not be signed")]
+    StreamingBody,
+    #[error("Failed to encode JWS: {0}")]
+    Jws(#[from] jsonwebtoken::errors::Error),
+    #[error("Signature is not a valid header value: {0}")]
+    HeaderValue(#[from] reqwest::header::InvalidHeaderValue),
+}
+
+enum SigningKey {
+    HmacSha256 {
+        secret: Vec<u8>,
+    },
+    Jws {
+        algorithm: Algorithm,
+        encoding_key: EncodingKey,
+    },
+}
+
+/// Reads the PEM encoded private key for one of the RSA, EC or EdDSA algorithms.
+pub async fn read_encoding_key(
+    algorithm: Algorithm,
+    private_key_path: &str,
+) -> anyhow::Result<EncodingKey> {
+    let pem = tokio::fs::read(private_key_path)
+        .await
+        .context("Failed to read signing private key")?;
+
+    match algorithm {
+        Algorithm::RS256
+        | Algorithm::RS384
+        | Algorithm::RS512
+        | Algorithm::PS256
+        | Algorithm::PS384
+        | Algorithm::PS512 => EncodingKey::from_rsa_pem(&pem),
+        Algorithm::ES256 | Algorithm::ES384 => EncodingKey::from_ec_pem(&pem),
+        Algorithm::EdDSA => EncodingKey::from_ed_pem(&pem),
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+            anyhow::bail!("Use HmacSha256 for symmetric signing keys")
+        }
+    }
+    .context("Failed to parse signing private key")
+}
+
+/// Signs outbound requests so that other instances of this crate can authenticate them.
+pub struct RequestSigner {
+    key_id: String,
+    key: SigningKey,
+}
+
+impl RequestSigner {
+    pub async fn from_config(config: SigningKeyConfig) -> anyhow::Result<Self> {
+        match config {
+            SigningKeyConfig::HmacSha256 { key_id, secret } => Ok(Self {
+                key_id,
+                key: SigningKey::HmacSha256 {
+                    secret: secret.into_bytes(),
+                },
+            }),
+            SigningKeyConfig::Jws {
+                key_id,
+                jws_algorithm,
+                private_key_path,
+            } => {
+                let encoding_key = read_encoding_key(jws_algorithm, &private_key_path).await?;
+
+                Ok(Self {
+                    key_id,
+                    key: SigningKey::Jws {
+                        algorithm: jws_algorithm,
+                        encoding_key,
+                    },
+                })
+            }
+        }
+    }
+
+    /// Signs the request by adding the signature headers.
+    #[tracing::instrument(skip_all)]
+    pub fn sign(&self, request: &mut Request) -> Result<(), SigningError> {
+        let body = match request.body() {
+            Some(body) => body.as_bytes().ok_or(SigningError::StreamingBody)?,
+            None => &[],
+        };
+
+        let url = request.url();
+        let path_and_query = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let canonical_request =
+            canonical_request(request.method().as_str(), &path_and_query, timestamp, body);
+
+        let signature = match &self.key {
+            SigningKey::HmacSha256 { secret } => {
+                let mac = hmac_sha256(secret, &canonical_request);
+
+                format!(
+                    "{HMAC_SHA256_PREFIX}{}",
+                    hex::encode(mac.finalize().into_bytes())
+                )
+            }
+            SigningKey::Jws {
+                algorithm,
+                encoding_key,
+            } => {
+                let mut header = Header::new(*algorithm);
+                header.kid = Some(self.key_id.clone());
+
+                let claims = SignatureClaims {
+                    canonical_request_sha256: canonical_request_sha256(&canonical_request),
+                    iat: timestamp,
+                };
+
+                let jws = jsonwebtoken::encode(&header, &claims, encoding_key)?;
+
+                format!("{JWS_PREFIX}{jws}")
+            }
+        };
+
+        let headers = request.headers_mut();
+
+        headers.insert(
+            SIGNATURE_KEY_ID_HEADER,
+            HeaderValue::from_str(&self.key_id)?,
+        );
+        headers.insert(SIGNATURE_TIMESTAMP_HEADER, HeaderValue::from(timestamp));
+        headers.insert(SIGNATURE_HEADER, HeaderValue::from_str(&signature)?);
+
+        tracing::trace!(key_id = %self.key_id, "Signed");
+
+        Ok(())
+    }
+
+    /// Builds, signs and sends the request.
+    pub async fn send(
+        &self,
+        http_client: &reqwest::Client,
+        request: reqwest::RequestBuilder,
+    ) -> anyhow::Result<reqwest::Response> {
+        let mut request = request.build()?;
+
+        self.sign(&mut request)?;
+
+        Ok(http_client.execute(request).await?)
+    }
+}
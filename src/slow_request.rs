@@ -0,0 +1,52 @@
+//! Detection of requests exceeding a latency threshold.
+//!
+//! Slow requests are logged with their route and principal and counted, to find the latency offenders without full tracing.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SlowRequestConfig {
+    /// Requests taking longer than this are logged and counted.
+    #[serde(default = "default_threshold_in_millis")]
+    pub threshold_in_millis: u64,
+}
+
+fn default_threshold_in_millis() -> u64 {
+    1000
+}
+
+impl SlowRequestConfig {
+    pub fn threshold(&self) -> Duration {
+        Duration::from_millis(self.threshold_in_millis)
+    }
+}
+
+/// Counts the slow requests.
+#[derive(Debug, Default)]
+pub struct SlowRequestCounter {
+    count: AtomicU64,
+}
+
+impl SlowRequestCounter {
+    pub fn increment(&self) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+pub trait SlowRequestProvider {
+    /// Returns the latency threshold of the requests.
+    ///
+    /// Returns `None` if slow requests are not detected.
+    fn slow_request_threshold(&self) -> Option<Duration>;
+
+    fn slow_request_counter(&self) -> &SlowRequestCounter;
+}
@@ -1,17 +1,88 @@
 use std::convert::Infallible;
-use std::future::Future;
+use std::net::IpAddr;
+use std::time::Duration;
 use std::{ops::Deref, sync::Arc};
 
+use axum::http::{header::AUTHORIZATION, request::Parts};
+use ipnet::IpNet;
+
+use crate::alert::{monitor::AlertMonitor, AlertNotifiers};
+use crate::analytics::{sink::TracingUsageSink, UsageAnalytics, UsageAnalyticsProvider};
+use crate::audit::{AuditEvent, AuditIdentity, AuditProvider, AuditSink, ConfiguredAuditSink};
+use crate::body_trace::{BodyTraceConfig, BodyTraceProvider};
+use crate::catch_panic::{PanicCounter, PanicCounterProvider};
+use crate::credentials::{api_key_digest, ConfiguredCredentialStore, CredentialStoreError};
+use crate::csrf::{CsrfConfig, CsrfProvider};
+use crate::downstream::DownstreamClient;
 use crate::error::ErrorVerbosityProvider;
+use crate::error_metrics::{ErrorCounter, ErrorCounterProvider};
+use crate::error_sink::{
+    ConfiguredErrorSink, ErrorReport, ErrorReportingProvider, ErrorSink, ReportedError,
+};
+use crate::etag::{ETagConfig, ETagProvider};
 use crate::extractor::api_key::{ApiKeyProvider, ApiKeyProviderError};
-use crate::extractor::basic_auth::{BasicAuthProvider, BasicAuthProviderError};
-use crate::extractor::jwt::JwksProvider;
+use crate::extractor::authorized::{PolicyConfig, PolicyGrants, PolicyProvider, Subject};
+use crate::extractor::basic_auth::{ApiBasicAuth, BasicAuthProvider, BasicAuthProviderError};
+use crate::extractor::body::BodyLimitProvider;
+use crate::extractor::client_cert::{CertAuthProvider, ClientCert, ClientCertConfig};
+use crate::extractor::client_ip::TrustedProxiesProvider;
+use crate::extractor::cookie::{parse_cookies, CookieSigningKeyProvider};
+use crate::extractor::deadline::{DeadlineConfig, DeadlineConfigProvider};
+use crate::extractor::digest_auth::{DigestAuthConfig, DigestAuthProvider, DigestNonceStore};
+use crate::extractor::introspected_token::{IntrospectedToken, IntrospectionProvider};
+use crate::extractor::jwt::{
+    validation::{JwtValidationConfig, JwtValidator},
+    JwksProvider,
+};
+use crate::extractor::multipart::{MultipartLimits, MultipartLimitsProvider};
+use crate::extractor::pagination::{PaginationConfig, PaginationConfigProvider};
+use crate::extractor::principal::{ClaimsMapper, ClaimsMappingConfig, Principal};
+use crate::extractor::signed_request::{SignatureKeyProvider, SignatureVerificationConfig};
+use crate::extractor::tenant::{Tenant, TenantConfig, TenantProvider, TenantSource};
+use crate::extractor::StrictDeserializationProvider;
+use crate::geoip::{GeoIpInfo, GeoIpProvider, GeoIpResolver};
+use crate::idempotency::{idempotency_digest, IdempotencyScopeProvider};
+use crate::introspection::{IntrospectionError, TokenIntrospector};
 use crate::jwt::{JwkError, JwkRefresher};
-
-use crate::{
-    error::ErrorVerbosity,
-    types::{used_api_key::UsedApiKey, used_basic_auth::UsedBasicAuth},
+use crate::lifecycle::{EndpointLifecycleEntry, EndpointLifecycleProvider};
+use crate::locale::{LocaleCatalog, LocaleCatalogProvider};
+use crate::maintenance::{MaintenanceMode, MaintenanceModeProvider};
+use crate::oidc::login::OidcLogin;
+use crate::problem_details::{ErrorFormatConfig, ErrorFormatProvider};
+use crate::response_schema::{
+    ResponseSchema, ResponseSchemaRegistry, ResponseSchemaValidationConfig,
+    ResponseSchemaValidationProvider,
 };
+use crate::session::{SessionConfigProvider, SESSION_COOKIE_NAME};
+use crate::signing::signer::RequestSigner;
+use crate::slow_request::{SlowRequestCounter, SlowRequestProvider};
+use crate::store::{ConfiguredStore, KeyValueStore, StoreError};
+use crate::token_issuer::TokenIssuer;
+use crate::verbosity_policy::{RouteVerbosity, VerbosityPolicy, VerbosityPolicyProvider};
+
+use crate::{error::ErrorVerbosity, types::used_api_key::KeyInfo};
+
+/// The [`ErrorVerbosity`] of the server configuration.
+///
+/// Can only be created by the state, so errors can not be constructed with an arbitrary verbosity.
+/// Obtained through [`ErrorVerbosityProvider::error_verbosity`].
+#[derive(Debug, Clone, Copy)]
+pub struct PrivateErrorVerbosity(ErrorVerbosity);
+
+impl PrivateErrorVerbosity {
+    /// The default [`ErrorVerbosity`], only used by the default [`ApiError`](crate::error::ApiError).
+    pub(crate) const DEFAULT: Self = Self(ErrorVerbosity::StatusCode);
+
+    /// Returns the verbosity.
+    pub fn get(self) -> ErrorVerbosity {
+        self.0
+    }
+
+    #[cfg(test)]
+    pub(crate) fn for_tests(verbosity: ErrorVerbosity) -> Self {
+        Self(verbosity)
+    }
+}
 
 #[derive(Clone)]
 pub struct ApiState {
@@ -21,22 +92,141 @@ pub struct ApiState {
 impl ApiState {
     #[allow(clippy::too_many_arguments)]
     pub async fn new(
-        error_verbosity: ErrorVerbosity,
+        verbosity_policy: VerbosityPolicy,
+        strict_deserialization: bool,
+        multipart_limits: MultipartLimits,
+        cookie_signing_key: Option<Vec<u8>>,
+        max_body_size_in_bytes: usize,
+        trusted_proxies: Vec<IpNet>,
+        pagination: PaginationConfig,
+        deadline: DeadlineConfig,
+        tenant: TenantConfig,
+        policies: PolicyConfig,
+        signature_verification: SignatureVerificationConfig,
+        client_certificates: ClientCertConfig,
+        digest_auth: DigestAuthConfig,
+        claims_mapping: ClaimsMappingConfig,
         api_key_header_name: String,
-        api_keys: Vec<UsedApiKey>,
-        basic_auth_users: Vec<UsedBasicAuth>,
+        credential_store: ConfiguredCredentialStore,
         jwk_refresher: JwkRefresher,
+        alert_monitor: Option<AlertMonitor<AlertNotifiers>>,
+        geoip_resolver: Option<GeoIpResolver>,
+        endpoint_lifecycles: Vec<EndpointLifecycleEntry>,
+        usage_analytics: Option<Arc<UsageAnalytics<TracingUsageSink>>>,
+        end_session_endpoint: Option<String>,
+        downstream_client: Option<DownstreamClient>,
+        request_signer: Option<RequestSigner>,
+        token_introspector: Option<TokenIntrospector>,
+        store: ConfiguredStore,
+        session_time_to_live: Duration,
+        oidc_login: Option<OidcLogin>,
+        token_issuer: Option<TokenIssuer>,
+        token_revocation_time_to_live: Duration,
+        response_schema_validation: Option<ResponseSchemaValidationConfig>,
+        response_schema_registry: ResponseSchemaRegistry,
+        locale_catalog: LocaleCatalog,
+        etag: Option<ETagConfig>,
+        csrf: Option<CsrfConfig>,
+        maintenance_mode: MaintenanceMode,
+        audit_sink: Option<Arc<ConfiguredAuditSink>>,
+        body_trace: BodyTraceConfig,
+        slow_request_threshold: Option<Duration>,
+        error_format: ErrorFormatConfig,
+        error_sink: Option<Arc<ConfiguredErrorSink>>,
+        report_all_server_errors: bool,
     ) -> anyhow::Result<Self> {
         Ok(Self {
             inner: Arc::new(ApiStateInner {
-                error_verbosity,
+                verbosity_policy,
+                strict_deserialization,
+                multipart_limits,
+                cookie_signing_key,
+                max_body_size_in_bytes,
+                trusted_proxies,
+                pagination,
+                deadline,
+                tenant,
+                policies,
+                signature_verification,
+                client_certificates,
+                digest_realm: digest_auth.realm,
+                digest_nonces: DigestNonceStore::new(Duration::from_secs(
+                    digest_auth.nonce_time_to_live_in_seconds,
+                )),
+                claims_mapping,
                 api_key_header_name,
-                api_keys,
-                basic_auth_users,
+                credential_store,
                 jwk_refresher,
+                alert_monitor,
+                geoip_resolver,
+                endpoint_lifecycles,
+                usage_analytics,
+                end_session_endpoint,
+                downstream_client,
+                request_signer,
+                token_introspector,
+                store,
+                session_time_to_live,
+                oidc_login,
+                token_issuer,
+                token_revocation_time_to_live,
+                response_schema_validation,
+                response_schema_registry,
+                locale_catalog: Arc::new(locale_catalog),
+                etag,
+                csrf,
+                maintenance_mode,
+                audit_sink,
+                body_trace,
+                panic_counter: PanicCounter::default(),
+                slow_request_threshold,
+                slow_request_counter: SlowRequestCounter::default(),
+                error_format,
+                error_sink,
+                report_all_server_errors,
+                error_counter: ErrorCounter::default(),
             }),
         })
     }
+
+    pub fn usage_analytics(&self) -> Option<&UsageAnalytics<TracingUsageSink>> {
+        self.usage_analytics.as_deref()
+    }
+
+    /// Returns the identity provider's `end_session_endpoint` used for RP-initiated logout.
+    pub fn end_session_endpoint(&self) -> Option<&str> {
+        self.end_session_endpoint.as_deref()
+    }
+
+    /// Returns the client for calling downstream APIs.
+    pub fn downstream_client(&self) -> Option<&DownstreamClient> {
+        self.downstream_client.as_ref()
+    }
+
+    /// Returns the signer for outbound requests.
+    pub fn request_signer(&self) -> Option<&RequestSigner> {
+        self.request_signer.as_ref()
+    }
+
+    /// Returns the OpenID Connect login if configured.
+    pub fn oidc_login(&self) -> Option<&OidcLogin> {
+        self.oidc_login.as_ref()
+    }
+
+    /// Returns the issuer of local tokens if configured.
+    pub fn token_issuer(&self) -> Option<&TokenIssuer> {
+        self.token_issuer.as_ref()
+    }
+
+    /// Returns how long a revocation is kept if the expiry of the token is unknown.
+    pub fn token_revocation_time_to_live(&self) -> Duration {
+        self.token_revocation_time_to_live
+    }
+
+    /// Returns the store API keys and basic auth users are read from.
+    pub fn credential_store(&self) -> &ConfiguredCredentialStore {
+        &self.credential_store
+    }
 }
 
 impl Deref for ApiState {
@@ -48,74 +238,517 @@ impl Deref for ApiState {
 }
 
 pub struct ApiStateInner {
-    error_verbosity: ErrorVerbosity,
+    verbosity_policy: VerbosityPolicy,
+    strict_deserialization: bool,
+    multipart_limits: MultipartLimits,
+    cookie_signing_key: Option<Vec<u8>>,
+    max_body_size_in_bytes: usize,
+    trusted_proxies: Vec<IpNet>,
+    pagination: PaginationConfig,
+    deadline: DeadlineConfig,
+    tenant: TenantConfig,
+    policies: PolicyConfig,
+    signature_verification: SignatureVerificationConfig,
+    client_certificates: ClientCertConfig,
+    digest_realm: String,
+    digest_nonces: DigestNonceStore,
+    claims_mapping: ClaimsMappingConfig,
     api_key_header_name: String,
-    api_keys: Vec<UsedApiKey>,
-    basic_auth_users: Vec<UsedBasicAuth>,
+    credential_store: ConfiguredCredentialStore,
     jwk_refresher: JwkRefresher,
+    alert_monitor: Option<AlertMonitor<AlertNotifiers>>,
+    geoip_resolver: Option<GeoIpResolver>,
+    endpoint_lifecycles: Vec<EndpointLifecycleEntry>,
+    usage_analytics: Option<Arc<UsageAnalytics<TracingUsageSink>>>,
+    end_session_endpoint: Option<String>,
+    downstream_client: Option<DownstreamClient>,
+    request_signer: Option<RequestSigner>,
+    token_introspector: Option<TokenIntrospector>,
+    store: ConfiguredStore,
+    session_time_to_live: Duration,
+    oidc_login: Option<OidcLogin>,
+    token_issuer: Option<TokenIssuer>,
+    token_revocation_time_to_live: Duration,
+    response_schema_validation: Option<ResponseSchemaValidationConfig>,
+    response_schema_registry: ResponseSchemaRegistry,
+    locale_catalog: Arc<LocaleCatalog>,
+    etag: Option<ETagConfig>,
+    csrf: Option<CsrfConfig>,
+    maintenance_mode: MaintenanceMode,
+    audit_sink: Option<Arc<ConfiguredAuditSink>>,
+    body_trace: BodyTraceConfig,
+    panic_counter: PanicCounter,
+    slow_request_threshold: Option<Duration>,
+    slow_request_counter: SlowRequestCounter,
+    error_format: ErrorFormatConfig,
+    error_sink: Option<Arc<ConfiguredErrorSink>>,
+    report_all_server_errors: bool,
+    error_counter: ErrorCounter,
 }
 
 impl ErrorVerbosityProvider for ApiState {
-    fn error_verbosity(&self) -> ErrorVerbosity {
-        self.error_verbosity
+    /// Returns the most verbose level the errors of the current route may be rendered with.
+    ///
+    /// Errors are lowered to the level of their category when they are rendered.
+    fn error_verbosity(&self) -> PrivateErrorVerbosity {
+        PrivateErrorVerbosity(RouteVerbosity::current().map_or_else(
+            || self.verbosity_policy.default_verbosity(),
+            |route_verbosity| route_verbosity.max(),
+        ))
     }
 }
 
-impl ApiKeyProvider for ApiState {
+impl VerbosityPolicyProvider for ApiState {
+    fn verbosity_policy(&self) -> &VerbosityPolicy {
+        &self.verbosity_policy
+    }
+}
+
+impl StrictDeserializationProvider for ApiState {
+    fn strict_deserialization(&self) -> bool {
+        self.strict_deserialization
+    }
+}
+
+impl CookieSigningKeyProvider for ApiState {
+    fn cookie_signing_key(&self) -> Option<&[u8]> {
+        self.cookie_signing_key.as_deref()
+    }
+}
+
+impl TrustedProxiesProvider for ApiState {
+    fn trusted_proxies(&self) -> &[IpNet] {
+        &self.trusted_proxies
+    }
+}
+
+impl DeadlineConfigProvider for ApiState {
+    fn deadline_config(&self) -> DeadlineConfig {
+        self.deadline
+    }
+}
+
+impl TenantProvider for ApiState {
+    type Error = Infallible;
+
+    fn tenant_source(&self) -> &TenantSource {
+        &self.tenant.source
+    }
+
+    async fn resolve_tenant(&self, id: &str) -> Result<Option<Tenant>, Self::Error> {
+        let known =
+            self.tenant.tenants.is_empty() || self.tenant.tenants.iter().any(|tenant| tenant == id);
+
+        Ok(known.then(|| Tenant { id: id.to_owned() }))
+    }
+}
+
+impl PolicyProvider for ApiState {
+    type Error = Infallible;
+
+    async fn is_authorized(
+        &self,
+        policy: &str,
+        subject: &Subject<'_>,
+    ) -> Result<bool, Self::Error> {
+        let authorized = match self.policies.get(policy) {
+            Some(grants) => grants.grants(policy, subject),
+            None => PolicyGrants::default().grants(policy, subject),
+        };
+
+        Ok(authorized)
+    }
+}
+
+impl SignatureKeyProvider for ApiState {
+    type Error = Infallible;
+
+    async fn signature_secret(&self, key_id: &str) -> Result<Option<Vec<u8>>, Self::Error> {
+        let secret = self
+            .signature_verification
+            .keys
+            .iter()
+            .find(|key| key.key_id == key_id)
+            .map(|key| key.secret.clone().into_bytes());
+
+        Ok(secret)
+    }
+
+    fn max_signature_skew(&self) -> Duration {
+        Duration::from_secs(self.signature_verification.max_skew_in_seconds)
+    }
+}
+
+impl CertAuthProvider for ApiState {
     type Error = Infallible;
 
+    async fn authorize_client_cert(&self, cert: &ClientCert) -> Result<bool, Self::Error> {
+        Ok(self.client_certificates.allows(cert))
+    }
+}
+
+impl PaginationConfigProvider for ApiState {
+    fn pagination_config(&self) -> PaginationConfig {
+        self.pagination
+    }
+}
+
+impl BodyLimitProvider for ApiState {
+    fn max_body_size_in_bytes(&self) -> usize {
+        self.max_body_size_in_bytes
+    }
+}
+
+impl MultipartLimitsProvider for ApiState {
+    fn multipart_limits(&self) -> MultipartLimits {
+        self.multipart_limits
+    }
+}
+
+impl ApiKeyProvider for ApiState {
+    type Error = CredentialStoreError;
+
     fn header_name(&self) -> &str {
         &self.api_key_header_name
     }
 
-    async fn validate(&self, key: &str) -> Result<(), ApiKeyProviderError<Self::Error>> {
-        for valid_key in self.api_keys.iter() {
-            if valid_key.value == key {
-                return Ok(());
-            }
-        }
+    async fn validate(&self, key: &str) -> Result<KeyInfo, ApiKeyProviderError<Self::Error>> {
+        self.credential_store.validate_api_key(key).await
+    }
 
-        Err(ApiKeyProviderError::Invalid)
+    fn on_invalid(&self, _key: &str, client_ip: Option<IpAddr>) {
+        if let (Some(alert_monitor), Some(client_ip)) = (&self.alert_monitor, client_ip) {
+            alert_monitor.record_invalid_api_key(client_ip);
+        }
     }
 }
 
 impl BasicAuthProvider for ApiState {
-    type Error = Infallible;
+    type Error = CredentialStoreError;
 
     async fn authenticate(
         &self,
         username: &str,
         password: Option<&str>,
     ) -> Result<(), BasicAuthProviderError<Self::Error>> {
-        for valid_user in self.basic_auth_users.iter() {
-            if valid_user.username == username && valid_user.password.as_deref() == password {
-                return Ok(());
-            }
-        }
+        self.credential_store.authenticate(username, password).await
+    }
+}
 
-        Err(BasicAuthProviderError::Unauthenticated)
+/// Used by the [`BasicAuthLayer`](crate::middleware::basic_auth::layer::BasicAuthLayer) of the route config.
+impl crate::middleware::basic_auth::provider::BasicAuthProvider for ApiState {
+    async fn authenticate(&self, username: &str, password: Option<&str>) -> bool {
+        self.credential_store
+            .authenticate(username, password)
+            .await
+            .is_ok()
+    }
+}
+
+impl DigestAuthProvider for ApiState {
+    type Error = Infallible;
+
+    fn digest_realm(&self) -> &str {
+        &self.digest_realm
+    }
+
+    fn digest_nonces(&self) -> &DigestNonceStore {
+        &self.digest_nonces
+    }
+
+    async fn digest_password(&self, username: &str) -> Result<Option<String>, Self::Error> {
+        Ok(self.credential_store.plaintext_password(username))
     }
 }
 
 impl JwksProvider for ApiState {
     type Error = JwkError;
 
-    fn jwks(
+    async fn jwks(
+        &self,
+        issuer: &str,
+    ) -> Result<Option<impl AsRef<jsonwebtoken::jwk::JwkSet>>, Self::Error> {
+        let jwks = self.jwk_refresher.jwks(issuer).await;
+
+        if let Some(alert_monitor) = &self.alert_monitor {
+            match (&jwks, self.jwk_refresher.last_refresh_error(issuer)) {
+                (Err(err), _) => alert_monitor.record_jwks_refresh_failure(err),
+                (Ok(_), Some(err)) => alert_monitor.record_jwks_refresh_failure(&err),
+                (Ok(_), None) => alert_monitor.record_jwks_refresh_success(),
+            }
+        }
+
+        jwks
+    }
+
+    async fn refresh_jwks(&self, issuer: &str) -> Result<(), Self::Error> {
+        self.jwk_refresher.refresh_jwks(issuer).await
+    }
+
+    fn audience<'a>(&'a self, issuer: &str) -> &'a [impl ToString + 'a] {
+        self.jwk_refresher.audience(issuer)
+    }
+
+    fn validation_config(&self) -> &JwtValidationConfig {
+        self.jwk_refresher.validation_config()
+    }
+}
+
+impl IntrospectionProvider for ApiState {
+    type Error = IntrospectionError;
+
+    async fn introspect(&self, token: &str) -> Result<IntrospectedToken, Self::Error> {
+        match &self.token_introspector {
+            Some(token_introspector) => token_introspector.introspect(token).await,
+            None => Err(IntrospectionError::NotConfigured),
+        }
+    }
+}
+
+impl ClaimsMapper for ApiState {
+    fn map_claims(&self, claims: &serde_json::Map<String, serde_json::Value>) -> Option<Principal> {
+        self.claims_mapping.map_claims(claims)
+    }
+}
+
+impl KeyValueStore for ApiState {
+    type Error = StoreError;
+
+    async fn get(&self, key: &str) -> Result<Option<String>, Self::Error> {
+        self.store.get(key).await
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: String,
+        time_to_live: Duration,
+    ) -> Result<(), Self::Error> {
+        self.store.set(key, value, time_to_live).await
+    }
+
+    async fn set_if_absent(
         &self,
-    ) -> impl Future<Output = Result<impl AsRef<jsonwebtoken::jwk::JwkSet>, Self::Error>> + Send
-    {
-        self.jwk_refresher.jwks()
+        key: &str,
+        value: String,
+        time_to_live: Duration,
+    ) -> Result<bool, Self::Error> {
+        self.store.set_if_absent(key, value, time_to_live).await
+    }
+
+    async fn expire(&self, key: &str, time_to_live: Duration) -> Result<(), Self::Error> {
+        self.store.expire(key, time_to_live).await
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), Self::Error> {
+        self.store.remove(key).await
+    }
+}
+
+impl SessionConfigProvider for ApiState {
+    fn session_time_to_live(&self) -> Duration {
+        self.session_time_to_live
+    }
+}
+
+impl GeoIpProvider for ApiState {
+    fn lookup(&self, ip: IpAddr) -> Option<GeoIpInfo> {
+        self.geoip_resolver
+            .as_ref()
+            .map(|geoip_resolver| geoip_resolver.lookup(ip))
+    }
+
+    fn is_country_blocked(&self, country: &str) -> bool {
+        self.geoip_resolver
+            .as_ref()
+            .is_some_and(|geoip_resolver| geoip_resolver.is_country_blocked(country))
+    }
+}
+
+impl EndpointLifecycleProvider for ApiState {
+    fn endpoint_lifecycles(&self) -> &[EndpointLifecycleEntry] {
+        &self.endpoint_lifecycles
+    }
+}
+
+impl UsageAnalyticsProvider for ApiState {
+    fn record_usage(&self, principal: Option<String>, route: String, latency: Duration) {
+        if let Some(usage_analytics) = &self.usage_analytics {
+            usage_analytics.record(principal, route, latency);
+        }
+    }
+}
+
+impl ResponseSchemaValidationProvider for ApiState {
+    fn response_schema_validation(&self) -> Option<&ResponseSchemaValidationConfig> {
+        self.response_schema_validation.as_ref()
+    }
+
+    fn registered_response_schema(&self, path: &str) -> Option<ResponseSchema> {
+        self.response_schema_registry.get(path)
+    }
+}
+
+impl LocaleCatalogProvider for ApiState {
+    fn locale_catalog(&self) -> &Arc<LocaleCatalog> {
+        &self.locale_catalog
+    }
+}
+
+impl ETagProvider for ApiState {
+    fn etag_config(&self) -> Option<&ETagConfig> {
+        self.etag.as_ref()
+    }
+}
+
+impl CsrfProvider for ApiState {
+    fn csrf_config(&self) -> Option<&CsrfConfig> {
+        self.csrf.as_ref()
+    }
+}
+
+impl MaintenanceModeProvider for ApiState {
+    fn maintenance_mode(&self) -> &MaintenanceMode {
+        &self.maintenance_mode
+    }
+}
+
+impl PanicCounterProvider for ApiState {
+    fn panic_counter(&self) -> &PanicCounter {
+        &self.panic_counter
     }
+}
+
+impl ErrorFormatProvider for ApiState {
+    fn error_format_config(&self) -> &ErrorFormatConfig {
+        &self.error_format
+    }
+}
 
-    fn audience(&self) -> &[impl ToString] {
-        self.jwk_refresher.audience()
+impl SlowRequestProvider for ApiState {
+    fn slow_request_threshold(&self) -> Option<Duration> {
+        self.slow_request_threshold
     }
 
-    fn issuer(&self) -> &[impl ToString] {
-        self.jwk_refresher.issuer()
+    fn slow_request_counter(&self) -> &SlowRequestCounter {
+        &self.slow_request_counter
     }
+}
+
+impl IdempotencyScopeProvider for ApiState {
+    /// The digest of the credentials and the session id, so the keys of different clients never collide.
+    ///
+    /// Requests without credentials and without a session have no scope.
+    fn idempotency_scope(&self, parts: &Parts) -> Option<String> {
+        let header = |name: &str| parts.headers.get(name).map(|value| value.as_bytes());
+
+        let authorization = header(AUTHORIZATION.as_str());
+        let api_key = header(&self.api_key_header_name);
+        let session_id = parse_cookies(parts, self.error_verbosity())
+            .ok()
+            .and_then(|cookies| {
+                cookies
+                    .into_iter()
+                    .find(|(name, _)| *name == SESSION_COOKIE_NAME)
+            })
+            .map(|(_, id)| id.as_bytes());
 
-    fn validate_nbf(&self) -> bool {
-        self.jwk_refresher.validate_nbf()
+        if authorization.is_none() && api_key.is_none() && session_id.is_none() {
+            return None;
+        }
+
+        Some(idempotency_digest([
+            authorization.unwrap_or_default(),
+            api_key.unwrap_or_default(),
+            session_id.unwrap_or_default(),
+        ]))
+    }
+}
+
+impl BodyTraceProvider for ApiState {
+    fn body_trace_config(&self) -> &BodyTraceConfig {
+        &self.body_trace
+    }
+}
+
+impl ErrorCounterProvider for ApiState {
+    fn error_counter(&self) -> &ErrorCounter {
+        &self.error_counter
+    }
+}
+
+impl ErrorReportingProvider for ApiState {
+    fn reports_error(&self, error: &ReportedError) -> bool {
+        self.error_sink.is_some() && (error.internal || self.report_all_server_errors)
+    }
+
+    fn report_error(&self, report: ErrorReport) {
+        let Some(error_sink) = self.error_sink.clone() else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            if let Err(err) = error_sink.report(&report).await {
+                tracing::error!(%err, request_id = ?report.request_id, "Failed to report error");
+            }
+        });
+    }
+}
+
+impl AuditProvider for ApiState {
+    /// The basic auth username, the digest of the API key and the unverified subject of the bearer token.
+    fn audit_identities(&self, parts: &Parts) -> Vec<AuditIdentity> {
+        let mut identities = Vec::new();
+
+        let authorization = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|authorization| authorization.to_str().ok());
+
+        match authorization.and_then(|authorization| authorization.split_once(' ')) {
+            Some(("Basic", _)) => {
+                if let Ok(ApiBasicAuth(used_basic_auth)) =
+                    ApiBasicAuth::from_req_parts(parts, self.error_verbosity())
+                {
+                    identities.push(AuditIdentity::Basic {
+                        username: used_basic_auth.username,
+                    });
+                }
+            }
+            Some(("Bearer", token)) => {
+                let subject = JwtValidator::unverified_claims::<
+                    serde_json::Map<String, serde_json::Value>,
+                >(token.trim())
+                .ok()
+                .and_then(|claims| claims.get("sub")?.as_str().map(ToOwned::to_owned));
+
+                identities.push(AuditIdentity::Bearer { subject });
+            }
+            _ => {}
+        }
+
+        if let Some(api_key) = parts
+            .headers
+            .get(&self.api_key_header_name)
+            .and_then(|api_key| api_key.to_str().ok())
+        {
+            identities.push(AuditIdentity::ApiKey {
+                digest: api_key_digest(api_key),
+            });
+        }
+
+        identities
+    }
+
+    fn record_audit_event(&self, event: AuditEvent) {
+        let Some(audit_sink) = self.audit_sink.clone() else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            if let Err(err) = audit_sink.record(&event).await {
+                tracing::error!(%err, request_id = ?event.request_id, "Failed to record audit event");
+            }
+        });
     }
 }
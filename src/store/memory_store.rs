@@ -0,0 +1,104 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::RwLock;
+
+use super::KeyValueStore;
+
+struct MemoryEntry {
+    value: String,
+    expires_at: Instant,
+}
+
+/// Keeps the values in memory.
+///
+/// Values are lost on restart and are not shared between instances.
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: RwLock<HashMap<String, MemoryEntry>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl KeyValueStore for MemoryStore {
+    type Error = Infallible;
+
+    async fn get(&self, key: &str) -> Result<Option<String>, Self::Error> {
+        let entries = self.entries.read().await;
+
+        let value = entries
+            .get(key)
+            .filter(|entry| Instant::now() < entry.expires_at)
+            .map(|entry| entry.value.clone());
+
+        Ok(value)
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: String,
+        time_to_live: Duration,
+    ) -> Result<(), Self::Error> {
+        let mut entries = self.entries.write().await;
+
+        let now = Instant::now();
+        entries.retain(|_, entry| now < entry.expires_at);
+        entries.insert(
+            key.to_owned(),
+            MemoryEntry {
+                value,
+                expires_at: now + time_to_live,
+            },
+        );
+
+        Ok(())
+    }
+
+    async fn set_if_absent(
+        &self,
+        key: &str,
+        value: String,
+        time_to_live: Duration,
+    ) -> Result<bool, Self::Error> {
+        let mut entries = self.entries.write().await;
+
+        let now = Instant::now();
+        entries.retain(|_, entry| now < entry.expires_at);
+
+        if entries.contains_key(key) {
+            return Ok(false);
+        }
+
+        entries.insert(
+            key.to_owned(),
+            MemoryEntry {
+                value,
+                expires_at: now + time_to_live,
+            },
+        );
+
+        Ok(true)
+    }
+
+    async fn expire(&self, key: &str, time_to_live: Duration) -> Result<(), Self::Error> {
+        if let Some(entry) = self.entries.write().await.get_mut(key) {
+            entry.expires_at = Instant::now() + time_to_live;
+        }
+
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), Self::Error> {
+        self.entries.write().await.remove(key);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+//! A key/value store with per-key expiry.
+//!
+//! Sessions, token revocations and idempotency records are kept in the one store selected by the [`StoreConfig`],
+//! each under its own key prefix.
+
+use std::{convert::Infallible, future::Future, time::Duration};
+
+use serde::Deserialize;
+
+pub mod memory_store;
+#[cfg(feature = "redis")]
+pub mod redis_store;
+
+use memory_store::MemoryStore;
+#[cfg(feature = "redis")]
+use redis_store::RedisStore;
+
+/// Stores string values by key. Every value expires after the time to live it was set with.
+pub trait KeyValueStore {
+    type Error;
+
+    /// Returns the value of the key or `None` if the key does not exist or has expired.
+    fn get(&self, key: &str) -> impl Future<Output = Result<Option<String>, Self::Error>> + Send;
+
+    /// Creates or replaces the value of the key.
+    fn set(
+        &self,
+        key: &str,
+        value: String,
+        time_to_live: Duration,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Sets the value only if the key does not exist.
+    ///
+    /// Returns whether the value was set.
+    fn set_if_absent(
+        &self,
+        key: &str,
+        value: String,
+        time_to_live: Duration,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+
+    /// Extends the expiry of the key to `time_to_live` from now if the key exists.
+    fn expire(
+        &self,
+        key: &str,
+        time_to_live: Duration,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Removes the key if it exists.
+    fn remove(&self, key: &str) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// Where the sessions, token revocations and idempotency records are stored.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "type")]
+pub enum StoreConfig {
+    #[default]
+    Memory,
+    #[cfg(feature = "redis")]
+    Redis { url: String },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StoreError {
+    #[cfg(feature = "redis")]
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+}
+
+impl From<Infallible> for StoreError {
+    fn from(err: Infallible) -> Self {
+        match err {}
+    }
+}
+
+/// The store selected by the [`StoreConfig`].
+pub enum ConfiguredStore {
+    Memory(MemoryStore),
+    #[cfg(feature = "redis")]
+    Redis(RedisStore),
+}
+
+impl ConfiguredStore {
+    pub async fn from_config(config: &StoreConfig) -> Result<Self, StoreError> {
+        match config {
+            StoreConfig::Memory => Ok(Self::Memory(MemoryStore::new())),
+            #[cfg(feature = "redis")]
+            StoreConfig::Redis { url } => Ok(Self::Redis(RedisStore::connect(url).await?)),
+        }
+    }
+}
+
+impl KeyValueStore for ConfiguredStore {
+    type Error = StoreError;
+
+    async fn get(&self, key: &str) -> Result<Option<String>, Self::Error> {
+        match self {
+            Self::Memory(store) => Ok(store.get(key).await?),
+            #[cfg(feature = "redis")]
+            Self::Redis(store) => store.get(key).await,
+        }
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: String,
+        time_to_live: Duration,
+    ) -> Result<(), Self::Error> {
+        match self {
+            Self::Memory(store) => Ok(store.set(key, value, time_to_live).await?),
+            #[cfg(feature = "redis")]
+            Self::Redis(store) => store.set(key, value, time_to_live).await,
+        }
+    }
+
+    async fn set_if_absent(
+        &self,
+        key: &str,
+        value: String,
+        time_to_live: Duration,
+    ) -> Result<bool, Self::Error> {
+        match self {
+            Self::Memory(store) => Ok(store.set_if_absent(key, value, time_to_live).await?),
+            #[cfg(feature = "redis")]
+            Self::Redis(store) => store.set_if_absent(key, value, time_to_live).await,
+        }
+    }
+
+    async fn expire(&self, key: &str, time_to_live: Duration) -> Result<(), Self::Error> {
+        match self {
+            Self::Memory(store) => Ok(store.expire(key, time_to_live).await?),
+            #[cfg(feature = "redis")]
+            Self::Redis(store) => store.expire(key, time_to_live).await,
+        }
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), Self::Error> {
+        match self {
+            Self::Memory(store) => Ok(store.remove(key).await?),
+            #[cfg(feature = "redis")]
+            Self::Redis(store) => store.remove(key).await,
+        }
+    }
+}
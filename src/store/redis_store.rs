@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use redis::{aio::ConnectionManager, AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+
+use super::{KeyValueStore, StoreError};
+
+/// Keeps the values in Redis with the value's expiry as the key's expiry.
+///
+/// Values are shared between instances.
+#[derive(Clone)]
+pub struct RedisStore {
+    connection: ConnectionManager,
+}
+
+impl RedisStore {
+    pub async fn connect(url: &str) -> Result<Self, StoreError> {
+        let client = redis::Client::open(url)?;
+        let connection = ConnectionManager::new(client).await?;
+
+        Ok(Self { connection })
+    }
+}
+
+/// Redis expiries are whole seconds and must not be zero.
+fn seconds(time_to_live: Duration) -> u64 {
+    time_to_live.as_secs().max(1)
+}
+
+impl KeyValueStore for RedisStore {
+    type Error = StoreError;
+
+    async fn get(&self, key: &str) -> Result<Option<String>, Self::Error> {
+        Ok(self.connection.clone().get(key).await?)
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: String,
+        time_to_live: Duration,
+    ) -> Result<(), Self::Error> {
+        self.connection
+            .clone()
+            .set_ex::<_, _, ()>(key, value, seconds(time_to_live))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn set_if_absent(
+        &self,
+        key: &str,
+        value: String,
+        time_to_live: Duration,
+    ) -> Result<bool, Self::Error> {
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(seconds(time_to_live)));
+
+        let set: Option<String> = self
+            .connection
+            .clone()
+            .set_options(key, value, options)
+            .await?;
+
+        Ok(set.is_some())
+    }
+
+    async fn expire(&self, key: &str, time_to_live: Duration) -> Result<(), Self::Error> {
+        self.connection
+            .clone()
+            .expire::<_, ()>(key, seconds(time_to_live) as i64)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), Self::Error> {
+        self.connection.clone().del::<_, ()>(key).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,313 @@
+//! TLS termination with rustls.
+//!
+//! The certificate is selected by the server name the client sends with SNI, falling back to the default certificate.
+//! Both HTTP/1.1 and HTTP/2 are offered with ALPN.
+//!
+//! Certificates read from files are reloaded when the files change, so renewed certificates are served without a
+//! restart. Connections that are already established keep the certificate of their handshake.
+//!
+//! With [`ClientAuthConfig`], clients have to present a certificate issued by one of the configured CAs (mutual TLS).
+//! The verified chain is available to the [`ApiClientCert`](crate::extractor::client_cert::ApiClientCert) extractor.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context;
+use derivative::Derivative;
+use rustls::{
+    crypto::{
+        ring::{default_provider, sign::any_supported_type},
+        CryptoProvider,
+    },
+    server::{danger::ClientCertVerifier, ClientHello, ResolvesServerCert, WebPkiClientVerifier},
+    sign::CertifiedKey,
+    RootCertStore, ServerConfig,
+};
+use serde::Deserialize;
+use tokio_rustls::TlsAcceptor;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TlsConfig {
+    /// Served to clients without SNI and to clients asking for a server name without a certificate.
+    pub certificate: CertificateConfig,
+    /// Certificates selected by the server name the client sends with SNI.
+    #[serde(default)]
+    pub sni: Vec<SniCertificateConfig>,
+    /// How often the certificate files are checked for changes. Reloading is disabled if not set.
+    pub reload_interval_in_seconds: Option<u64>,
+    /// Verifies client certificates if set.
+    pub client_auth: Option<ClientAuthConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientAuthConfig {
+    /// PEM encoded CA certificates that client certificates are verified against. Read once on startup.
+    pub ca_cert_path: PathBuf,
+    /// Accepts clients without a certificate. Presented certificates are still verified.
+    #[serde(default)]
+    pub optional: bool,
+}
+
+impl ClientAuthConfig {
+    fn verifier(
+        &self,
+        provider: Arc<CryptoProvider>,
+    ) -> anyhow::Result<Arc<dyn ClientCertVerifier>> {
+        let ca_pem = read(&self.ca_cert_path)?;
+
+        let mut roots = RootCertStore::empty();
+
+        for cert in rustls_pemfile::certs(&mut ca_pem.as_slice()) {
+            roots
+                .add(cert.context("Invalid CA certificate PEM")?)
+                .context("Invalid CA certificate")?;
+        }
+
+        anyhow::ensure!(!roots.is_empty(), "No CA certificate found in PEM");
+
+        let builder = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+
+        let builder = match self.optional {
+            true => builder.allow_unauthenticated(),
+            false => builder,
+        };
+
+        builder
+            .build()
+            .context("Failed to build client certificate verifier")
+    }
+}
+
+/// A PEM encoded certificate chain and its private key.
+#[derive(Derivative, Clone, Deserialize)]
+#[derivative(Debug)]
+#[serde(tag = "type")]
+pub enum CertificateConfig {
+    Path {
+        cert_path: PathBuf,
+        key_path: PathBuf,
+    },
+    Pem {
+        cert_pem: String,
+        #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+        key_pem: String,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SniCertificateConfig {
+    /// Exact names or wildcards like `*.example.com`, which match a single label.
+    pub server_names: Vec<String>,
+    pub certificate: CertificateConfig,
+}
+
+impl CertificateConfig {
+    fn load(&self) -> anyhow::Result<Arc<CertifiedKey>> {
+        let (cert_pem, key_pem) = match self {
+            Self::Path {
+                cert_path,
+                key_path,
+            } => (read(cert_path)?, read(key_path)?),
+            Self::Pem { cert_pem, key_pem } => {
+                (cert_pem.as_bytes().to_vec(), key_pem.as_bytes().to_vec())
+            }
+        };
+
+        let certs = rustls_pemfile::certs(&mut cert_pem.as_slice())
+            .collect::<Result<Vec<_>, _>>()
+            .context("Invalid certificate PEM")?;
+
+        anyhow::ensure!(!certs.is_empty(), "No certificate found in PEM");
+
+        let key = rustls_pemfile::private_key(&mut key_pem.as_slice())
+            .context("Invalid private key PEM")?
+            .context("No private key found in PEM")?;
+
+        let key = any_supported_type(&key).context("Unsupported private key")?;
+
+        Ok(Arc::new(CertifiedKey::new(certs, key)))
+    }
+
+    fn paths(&self) -> Vec<&Path> {
+        match self {
+            Self::Path {
+                cert_path,
+                key_path,
+            } => vec![cert_path, key_path],
+            Self::Pem { .. } => Vec::new(),
+        }
+    }
+}
+
+fn read(path: &Path) -> anyhow::Result<Vec<u8>> {
+    std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+#[derive(Debug)]
+struct Certificates {
+    default: Arc<CertifiedKey>,
+    by_server_name: HashMap<String, Arc<CertifiedKey>>,
+}
+
+impl Certificates {
+    fn load(config: &TlsConfig) -> anyhow::Result<Self> {
+        let default = config
+            .certificate
+            .load()
+            .context("Failed to load default certificate")?;
+
+        let mut by_server_name = HashMap::new();
+
+        for sni in config.sni.iter() {
+            let certificate = sni.certificate.load().with_context(|| {
+                format!(
+                    "Failed to load certificate of {}",
+                    sni.server_names.join(", ")
+                )
+            })?;
+
+            for server_name in sni.server_names.iter() {
+                by_server_name.insert(server_name.to_ascii_lowercase(), certificate.clone());
+            }
+        }
+
+        Ok(Self {
+            default,
+            by_server_name,
+        })
+    }
+
+    fn resolve(&self, server_name: Option<&str>) -> Arc<CertifiedKey> {
+        server_name
+            .map(str::to_ascii_lowercase)
+            .and_then(|server_name| {
+                self.by_server_name.get(&server_name).or_else(|| {
+                    let (_, parent) = server_name.split_once('.')?;
+
+                    self.by_server_name.get(&format!("*.{parent}"))
+                })
+            })
+            .unwrap_or(&self.default)
+            .clone()
+    }
+}
+
+/// Resolves the certificate of a handshake by SNI and swaps the certificates on reload.
+#[derive(Debug)]
+pub struct CertificateResolver {
+    config: TlsConfig,
+    certificates: RwLock<Arc<Certificates>>,
+}
+
+impl CertificateResolver {
+    pub fn new(config: TlsConfig) -> anyhow::Result<Self> {
+        let certificates = Certificates::load(&config)?;
+
+        Ok(Self {
+            config,
+            certificates: RwLock::new(Arc::new(certificates)),
+        })
+    }
+
+    /// Reloads all certificates. The current certificates are kept if any of them fails to load.
+    pub fn reload(&self) -> anyhow::Result<()> {
+        let certificates = Certificates::load(&self.config)?;
+
+        *self
+            .certificates
+            .write()
+            .expect("certificates lock poisoned") = Arc::new(certificates);
+
+        Ok(())
+    }
+
+    fn modified(&self) -> Vec<Option<SystemTime>> {
+        std::iter::once(&self.config.certificate)
+            .chain(self.config.sni.iter().map(|sni| &sni.certificate))
+            .flat_map(CertificateConfig::paths)
+            .map(|path| {
+                std::fs::metadata(path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Spawns a task that reloads the certificates every `interval` if one of the certificate files changed.
+    pub fn spawn_reload_task(self: Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            let mut modified = self.modified();
+            let mut interval = tokio::time::interval(interval);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                let current = self.modified();
+
+                if current == modified {
+                    continue;
+                }
+
+                modified = current;
+
+                match self.reload() {
+                    Ok(()) => tracing::info!("TLS certificates reloaded"),
+                    Err(err) => tracing::error!(?err, "Failed to reload TLS certificates"),
+                }
+            }
+        });
+    }
+}
+
+impl ResolvesServerCert for CertificateResolver {
+    fn resolve(&self, client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        let certificates = self
+            .certificates
+            .read()
+            .expect("certificates lock poisoned")
+            .clone();
+
+        Some(certificates.resolve(client_hello.server_name()))
+    }
+}
+
+impl TlsConfig {
+    /// Loads the certificates and starts reloading them if configured.
+    pub fn into_acceptor(self) -> anyhow::Result<TlsAcceptor> {
+        let provider = Arc::new(default_provider());
+
+        let verifier = self
+            .client_auth
+            .as_ref()
+            .map(|client_auth| client_auth.verifier(provider.clone()))
+            .transpose()
+            .context("Invalid client auth config")?;
+
+        let reload_interval = self.reload_interval_in_seconds.map(Duration::from_secs);
+        let resolver = Arc::new(CertificateResolver::new(self)?);
+
+        if let Some(interval) = reload_interval {
+            resolver.clone().spawn_reload_task(interval);
+        }
+
+        let builder = ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .context("Failed to select TLS protocol versions")?;
+
+        let builder = match verifier {
+            Some(verifier) => builder.with_client_cert_verifier(verifier),
+            None => builder.with_no_client_auth(),
+        };
+
+        let mut config = builder.with_cert_resolver(resolver);
+
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
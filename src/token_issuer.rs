@@ -0,0 +1,320 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    session::{new_session_id, SessionStore},
+    signing::signer::{read_encoding_key, SigningKeyConfig},
+};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenIssuerConfig {
+    /// The `iss` claim of the issued tokens.
+    pub issuer: String,
+    /// The `aud` claim of the issued tokens.
+    pub audience: Vec<String>,
+    pub key: SigningKeyConfig,
+    #[serde(default = "default_access_token_time_to_live_in_seconds")]
+    pub access_token_time_to_live_in_seconds: u64,
+    #[serde(default = "default_refresh_token_time_to_live_in_seconds")]
+    pub refresh_token_time_to_live_in_seconds: u64,
+    /// The scopes each client may request, by client name. Clients without an entry are granted no scope.
+    #[serde(default)]
+    pub allowed_scopes: HashMap<String, Vec<String>>,
+}
+
+fn default_access_token_time_to_live_in_seconds() -> u64 {
+    5 * 60
+}
+
+fn default_refresh_token_time_to_live_in_seconds() -> u64 {
+    24 * 60 * 60
+}
+
+/// Claims of the issued access tokens.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssuedClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: Vec<String>,
+    pub exp: u64,
+    pub iat: u64,
+    pub nbf: u64,
+    pub jti: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+/// A refresh token as kept in the [`SessionStore`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshTokenRecord {
+    pub sub: String,
+    pub scope: Option<String>,
+    /// All refresh tokens rotated from the same initial token share the family.
+    family: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RefreshError<E> {
+    #[error("Refresh token is unknown or expired")]
+    Invalid,
+    #[error("Refresh token was already used")]
+    Reused,
+    #[error("Failed to (de)serialize refresh token: {0}")]
+    Data(#[source] serde_json::Error),
+    #[error("Session store error: {0}")]
+    Store(#[source] E),
+}
+
+fn refresh_token_key(token: &str) -> String {
+    format!("refresh_token:{token}")
+}
+
+fn refresh_token_family_key(family: &str) -> String {
+    format!("refresh_token_family:{family}")
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Mints signed access tokens and rotates opaque refresh tokens.
+///
+/// Refresh tokens are single use. Presenting a used refresh token revokes the whole family,
+/// as either the client or an attacker holds a stolen token.
+pub struct TokenIssuer {
+    issuer: String,
+    audience: Vec<String>,
+    key_id: String,
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    access_token_time_to_live: Duration,
+    refresh_token_time_to_live: Duration,
+    allowed_scopes: HashMap<String, Vec<String>>,
+}
+
+impl TokenIssuer {
+    pub async fn from_config(config: TokenIssuerConfig) -> anyhow::Result<Self> {
+        let (key_id, algorithm, encoding_key) = match config.key {
+            SigningKeyConfig::HmacSha256 { key_id, secret } => (
+                key_id,
+                Algorithm::HS256,
+                EncodingKey::from_secret(secret.as_bytes()),
+            ),
+            SigningKeyConfig::Jws {
+                key_id,
+                jws_algorithm,
+                private_key_path,
+            } => (
+                key_id,
+                jws_algorithm,
+                read_encoding_key(jws_algorithm, &private_key_path).await?,
+            ),
+        };
+
+        Ok(Self {
+            issuer: config.issuer,
+            audience: config.audience,
+            key_id,
+            algorithm,
+            encoding_key,
+            access_token_time_to_live: Duration::from_secs(
+                config.access_token_time_to_live_in_seconds,
+            ),
+            refresh_token_time_to_live: Duration::from_secs(
+                config.refresh_token_time_to_live_in_seconds,
+            ),
+            allowed_scopes: config.allowed_scopes,
+        })
+    }
+
+    pub fn access_token_time_to_live(&self) -> Duration {
+        self.access_token_time_to_live
+    }
+
+    /// Returns the scope granted to the client for the requested scope.
+    ///
+    /// Without a requested scope, all allowed scopes of the client are granted.
+    /// Returns the first requested scope the client is not allowed as error.
+    pub fn grant_scope(
+        &self,
+        client: &str,
+        requested: Option<&str>,
+    ) -> Result<Option<String>, String> {
+        let allowed = self
+            .allowed_scopes
+            .get(client)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let granted = match requested {
+            Some(requested) => {
+                let mut granted = Vec::new();
+
+                for scope in requested.split_whitespace() {
+                    if !allowed.iter().any(|allowed| allowed == scope) {
+                        return Err(scope.to_owned());
+                    }
+
+                    if !granted.contains(&scope) {
+                        granted.push(scope);
+                    }
+                }
+
+                granted.join(" ")
+            }
+            None => allowed.join(" "),
+        };
+
+        Ok((!granted.is_empty()).then_some(granted))
+    }
+
+    /// Mints a signed access token for the subject.
+    pub fn issue_access_token(
+        &self,
+        sub: &str,
+        scope: Option<&str>,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        let now = unix_now();
+
+        let claims = IssuedClaims {
+            iss: self.issuer.clone(),
+            sub: sub.to_owned(),
+            aud: self.audience.clone(),
+            exp: now + self.access_token_time_to_live.as_secs(),
+            iat: now,
+            nbf: now,
+            jti: new_session_id(),
+            scope: scope.map(String::from),
+        };
+
+        let mut header = Header::new(self.algorithm);
+        header.kid = Some(self.key_id.clone());
+
+        encode(&header, &claims, &self.encoding_key)
+    }
+
+    /// Issues a new refresh token. Starts a new family if `family` is `None`.
+    async fn store_refresh_token<S: SessionStore>(
+        &self,
+        store: &S,
+        sub: &str,
+        scope: Option<&str>,
+        family: Option<String>,
+    ) -> Result<String, RefreshError<S::Error>> {
+        let token = new_session_id();
+        let family = family.unwrap_or_else(new_session_id);
+
+        let record = RefreshTokenRecord {
+            sub: sub.to_owned(),
+            scope: scope.map(String::from),
+            family: family.clone(),
+        };
+
+        store
+            .store_session(
+                &refresh_token_key(&token),
+                serde_json::to_value(record).map_err(RefreshError::Data)?,
+                self.refresh_token_time_to_live,
+            )
+            .await
+            .map_err(RefreshError::Store)?;
+
+        store
+            .store_session(
+                &refresh_token_family_key(&family),
+                serde_json::Value::String(token.clone()),
+                self.refresh_token_time_to_live,
+            )
+            .await
+            .map_err(RefreshError::Store)?;
+
+        Ok(token)
+    }
+
+    /// Issues a refresh token starting a new family.
+    pub async fn issue_refresh_token<S: SessionStore>(
+        &self,
+        store: &S,
+        sub: &str,
+        scope: Option<&str>,
+    ) -> Result<String, RefreshError<S::Error>> {
+        self.store_refresh_token(store, sub, scope, None).await
+    }
+
+    /// Marks the refresh token as used and issues its successor.
+    ///
+    /// The token is marked atomically, so concurrent rotations of the same token issue a single successor.
+    /// Returns the record of the used token and the new refresh token.
+    pub async fn rotate_refresh_token<S: SessionStore>(
+        &self,
+        store: &S,
+        token: &str,
+    ) -> Result<(RefreshTokenRecord, String), RefreshError<S::Error>> {
+        let key = refresh_token_key(token);
+
+        let record = store
+            .load_session(&key)
+            .await
+            .map_err(RefreshError::Store)?
+            .ok_or(RefreshError::Invalid)?;
+
+        let record =
+            serde_json::from_value::<RefreshTokenRecord>(record).map_err(RefreshError::Data)?;
+
+        let first_use = store
+            .mark_session_used(&key, self.refresh_token_time_to_live)
+            .await
+            .map_err(RefreshError::Store)?;
+
+        if !first_use {
+            self.revoke_family(store, &record.family).await?;
+
+            return Err(RefreshError::Reused);
+        }
+
+        let successor = self
+            .store_refresh_token(
+                store,
+                &record.sub,
+                record.scope.as_deref(),
+                Some(record.family.clone()),
+            )
+            .await?;
+
+        Ok((record, successor))
+    }
+
+    /// Removes the latest refresh token of the family so that it can not be used anymore.
+    async fn revoke_family<S: SessionStore>(
+        &self,
+        store: &S,
+        family: &str,
+    ) -> Result<(), RefreshError<S::Error>> {
+        let family_key = refresh_token_family_key(family);
+
+        let latest = store
+            .load_session(&family_key)
+            .await
+            .map_err(RefreshError::Store)?;
+
+        if let Some(serde_json::Value::String(latest)) = latest {
+            store
+                .remove_session(&refresh_token_key(&latest))
+                .await
+                .map_err(RefreshError::Store)?;
+        }
+
+        store
+            .remove_session(&family_key)
+            .await
+            .map_err(RefreshError::Store)
+    }
+}
@@ -1,3 +1,4 @@
+pub mod stored_api_key;
 pub mod used_api_key;
 pub mod used_basic_auth;
 pub mod used_bearer_token;
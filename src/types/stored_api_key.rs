@@ -0,0 +1,216 @@
+use argon2::{
+    password_hash::{PasswordHashString, SaltString},
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+};
+use derivative::Derivative;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use super::used_api_key::KeyInfo;
+
+/// The algorithm used to hash API keys.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, clap::ValueEnum)]
+pub enum ApiKeyHashAlgorithm {
+    /// Argon2id with the default parameters.
+    #[default]
+    Argon2,
+    /// SHA-256 over a random salt followed by the key.
+    ///
+    /// Cheaper to verify than Argon2. Only use for long, randomly generated keys.
+    Sha256,
+}
+
+/// Number of leading characters of a key stored next to its hash.
+const API_KEY_PREFIX_LENGTH: usize = 8;
+
+/// Keys shorter than this can not be hashed, so that the prefix reveals only a small part of the key.
+const MIN_HASHED_API_KEY_LENGTH: usize = 4 * API_KEY_PREFIX_LENGTH;
+
+/// A salted hash of an API key.
+///
+/// Hashes are only verified against keys starting with their `prefix`.
+/// With unique prefixes, a presented key is hashed at most once instead of once per configured key.
+#[derive(Derivative, Clone, Deserialize, Serialize)]
+#[derivative(Debug)]
+#[serde(tag = "type")]
+pub enum HashedApiKey {
+    Argon2 {
+        /// The PHC string of the hash, e.g. `$argon2id$v=19$...`.
+        #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+        hash: String,
+        /// The non-secret first characters of the key.
+        prefix: String,
+    },
+    Sha256 {
+        /// The hex encoded salt.
+        salt: String,
+        /// The hex encoded SHA-256 digest of the salt followed by the key.
+        #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+        hash: String,
+        /// The non-secret first characters of the key.
+        prefix: String,
+    },
+}
+
+impl HashedApiKey {
+    /// Hashes the given key with a random salt.
+    ///
+    /// Fails for keys shorter than 32 characters.
+    pub fn hash(algorithm: ApiKeyHashAlgorithm, key: &str) -> anyhow::Result<Self> {
+        if key.chars().count() < MIN_HASHED_API_KEY_LENGTH {
+            anyhow::bail!(
+                "API keys must have at least {MIN_HASHED_API_KEY_LENGTH} characters to be hashed"
+            );
+        }
+
+        let prefix = key.chars().take(API_KEY_PREFIX_LENGTH).collect::<String>();
+
+        match algorithm {
+            ApiKeyHashAlgorithm::Argon2 => {
+                let salt = SaltString::generate(&mut OsRng);
+                let hash = Argon2::default()
+                    .hash_password(key.as_bytes(), &salt)
+                    .map_err(|err| anyhow::anyhow!("Failed to hash API key: {err}"))?;
+
+                Ok(Self::Argon2 {
+                    hash: PasswordHashString::from(hash).to_string(),
+                    prefix,
+                })
+            }
+            ApiKeyHashAlgorithm::Sha256 => {
+                let mut salt = [0u8; 16];
+                OsRng.fill_bytes(&mut salt);
+
+                Ok(Self::Sha256 {
+                    salt: hex::encode(salt),
+                    hash: hex::encode(sha256_salted(&salt, key)),
+                    prefix,
+                })
+            }
+        }
+    }
+
+    /// Returns the non-secret first characters of the key.
+    pub fn prefix(&self) -> &str {
+        match self {
+            Self::Argon2 { prefix, .. } | Self::Sha256 { prefix, .. } => prefix,
+        }
+    }
+
+    /// Verifies the given key against the hash in constant time.
+    ///
+    /// Keys not starting with the stored prefix are rejected without hashing them.
+    /// Returns `false` if the stored hash is malformed.
+    pub fn verify(&self, key: &str) -> bool {
+        if !key.starts_with(self.prefix()) {
+            return false;
+        }
+
+        match self {
+            Self::Argon2 { hash, .. } => PasswordHash::new(hash)
+                .map(|hash| {
+                    Argon2::default()
+                        .verify_password(key.as_bytes(), &hash)
+                        .is_ok()
+                })
+                .unwrap_or(false),
+            Self::Sha256 { salt, hash, .. } => {
+                let (Ok(salt), Ok(hash)) = (hex::decode(salt), hex::decode(hash)) else {
+                    return false;
+                };
+
+                sha256_salted(&salt, key).as_slice().ct_eq(&hash).into()
+            }
+        }
+    }
+}
+
+fn sha256_salted(salt: &[u8], key: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(key.as_bytes());
+    hasher.finalize().into()
+}
+
+/// An API key as it is stored in the config.
+///
+/// Either the plaintext key or a salted hash of it.
+#[derive(Derivative, Clone, Deserialize, Serialize)]
+#[derivative(Debug)]
+#[serde(untagged)]
+pub enum StoredApiKey {
+    Plaintext(#[derivative(Debug(format_with = "crate::utils::mask_fmt"))] String),
+    Hashed(HashedApiKey),
+}
+
+impl StoredApiKey {
+    /// Verifies the given key against the stored key in constant time.
+    pub fn verify(&self, key: &str) -> bool {
+        match self {
+            Self::Plaintext(value) => value.as_bytes().ct_eq(key.as_bytes()).into(),
+            Self::Hashed(hashed) => hashed.verify(key),
+        }
+    }
+
+    /// Returns the prefix of a hashed key.
+    pub fn prefix(&self) -> Option<&str> {
+        match self {
+            Self::Plaintext(_) => None,
+            Self::Hashed(hashed) => Some(hashed.prefix()),
+        }
+    }
+
+    /// Hashes the key if it is stored in plaintext.
+    ///
+    /// Already hashed keys are returned unchanged.
+    pub fn into_hashed(self, algorithm: ApiKeyHashAlgorithm) -> anyhow::Result<Self> {
+        match self {
+            Self::Plaintext(value) => Ok(Self::Hashed(HashedApiKey::hash(algorithm, &value)?)),
+            hashed @ Self::Hashed(_) => Ok(hashed),
+        }
+    }
+}
+
+/// An API key entry of the config.
+///
+/// Either just the stored key or the stored key with its [`KeyInfo`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ConfiguredApiKey {
+    Key(StoredApiKey),
+    WithInfo {
+        key: StoredApiKey,
+        #[serde(flatten)]
+        info: KeyInfo,
+    },
+}
+
+impl ConfiguredApiKey {
+    /// Returns the stored key.
+    pub fn stored(&self) -> &StoredApiKey {
+        match self {
+            Self::Key(stored) | Self::WithInfo { key: stored, .. } => stored,
+        }
+    }
+
+    /// Verifies the given key and returns its info if it matches.
+    pub fn verify(&self, key: &str) -> Option<KeyInfo> {
+        match self {
+            Self::Key(stored) => stored.verify(key).then(KeyInfo::default),
+            Self::WithInfo { key: stored, info } => stored.verify(key).then(|| info.clone()),
+        }
+    }
+
+    /// Hashes the key if it is stored in plaintext.
+    pub fn into_hashed(self, algorithm: ApiKeyHashAlgorithm) -> anyhow::Result<Self> {
+        match self {
+            Self::Key(stored) => Ok(Self::Key(stored.into_hashed(algorithm)?)),
+            Self::WithInfo { key, info } => Ok(Self::WithInfo {
+                key: key.into_hashed(algorithm)?,
+                info,
+            }),
+        }
+    }
+}
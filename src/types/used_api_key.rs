@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// A struct to hold the used API key.
@@ -10,3 +11,40 @@ pub struct UsedApiKey {
     // TODO: can use a heapless string here.
     pub value: String,
 }
+
+/// Metadata of a valid API key.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct KeyInfo {
+    /// The owner of the key, e.g. a client or service name.
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// The scopes granted to the key.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// The key is rejected after this point in time.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// The rate limit of the key.
+    #[serde(default)]
+    pub rate_limit: Option<KeyRateLimit>,
+}
+
+impl KeyInfo {
+    /// Returns whether the key has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+
+    /// Returns whether the key was granted the given scope.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|granted| granted == scope)
+    }
+}
+
+/// The number of requests a key may make within a window.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct KeyRateLimit {
+    pub requests: u64,
+    pub window_in_seconds: u64,
+}
@@ -1,5 +1,11 @@
+use argon2::{
+    password_hash::{PasswordHashString, SaltString},
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+};
 use derivative::Derivative;
+use rand::rngs::OsRng;
 use serde::Deserialize;
+use subtle::ConstantTimeEq;
 
 /// A struct to hold the used basic auth.
 #[derive(Derivative, Clone, Deserialize)]
@@ -9,3 +15,83 @@ pub struct UsedBasicAuth {
     #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
     pub password: Option<String>,
 }
+
+/// The algorithm used to hash basic auth passwords.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum PasswordHashAlgorithm {
+    /// Argon2id with the default parameters.
+    #[default]
+    Argon2,
+    /// Bcrypt with the default cost.
+    Bcrypt,
+}
+
+/// Hashes the given password with a random salt.
+///
+/// Returns the PHC string for Argon2 and the modular crypt string for Bcrypt.
+pub fn hash_password(algorithm: PasswordHashAlgorithm, password: &str) -> anyhow::Result<String> {
+    match algorithm {
+        PasswordHashAlgorithm::Argon2 => {
+            let salt = SaltString::generate(&mut OsRng);
+            let hash = Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .map_err(|err| anyhow::anyhow!("Failed to hash password: {err}"))?;
+
+            Ok(PasswordHashString::from(hash).to_string())
+        }
+        PasswordHashAlgorithm::Bcrypt => Ok(bcrypt::hash(password, bcrypt::DEFAULT_COST)?),
+    }
+}
+
+/// Verifies the given password against a stored plaintext password or hash.
+///
+/// The hash takes precedence and may be a Bcrypt (`$2b$...`) or Argon2 (`$argon2id$...`) hash.
+/// Plaintext passwords are compared in constant time.
+pub fn verify_password(
+    password: Option<&str>,
+    password_hash: Option<&str>,
+    given: Option<&str>,
+) -> bool {
+    match (password_hash, password, given) {
+        (Some(hash), _, Some(given)) if hash.starts_with("$2") => {
+            bcrypt::verify(given, hash).unwrap_or(false)
+        }
+        (Some(hash), _, Some(given)) => PasswordHash::new(hash)
+            .map(|hash| {
+                Argon2::default()
+                    .verify_password(given.as_bytes(), &hash)
+                    .is_ok()
+            })
+            .unwrap_or(false),
+        (Some(_), _, None) => false,
+        (None, Some(password), Some(given)) => password.as_bytes().ct_eq(given.as_bytes()).into(),
+        (None, None, None) => true,
+        (None, _, _) => false,
+    }
+}
+
+/// A basic auth user of the config.
+#[derive(Derivative, Clone, Deserialize)]
+#[derivative(Debug)]
+pub struct ConfiguredBasicAuthUser {
+    pub username: String,
+    /// The plaintext password.
+    #[serde(default)]
+    #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+    pub password: Option<String>,
+    /// A Bcrypt or Argon2 hash of the password. Takes precedence over `password`.
+    #[serde(default)]
+    #[derivative(Debug(format_with = "crate::utils::mask_fmt"))]
+    pub password_hash: Option<String>,
+}
+
+impl ConfiguredBasicAuthUser {
+    /// Verifies the given password of the user.
+    pub fn verify(&self, password: Option<&str>) -> bool {
+        verify_password(
+            self.password.as_deref(),
+            self.password_hash.as_deref(),
+            password,
+        )
+    }
+}
@@ -0,0 +1,223 @@
+//! Error verbosity per error category and per route prefix.
+//!
+//! Errors are created with the most verbose level the policy of the route may need
+//! and lowered to the level of their category when they are rendered.
+//! The policy of the route is resolved by the [`error_verbosity`](crate::middleware::error_verbosity::error_verbosity) middleware
+//! and read through [`RouteVerbosity::current`].
+
+use std::{cmp::Reverse, collections::HashMap, future::Future, sync::Arc};
+
+use axum::http::StatusCode;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ErrorVerbosity;
+
+tokio::task_local! {
+    static CURRENT_ROUTE_VERBOSITY: Arc<RouteVerbosity>;
+}
+
+/// The class of an error that can be given its own verbosity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ErrorCategory {
+    /// Internal server errors.
+    Internal,
+    /// Malformed requests, e.g. invalid bodies, query parameters or headers.
+    Request,
+    /// Requests failing validation.
+    Validation,
+    /// Missing or invalid credentials.
+    Auth,
+    /// Authenticated or anonymous requests that are not allowed, e.g. by the IP filter or a CSRF check.
+    Access,
+    /// Unknown paths and methods.
+    Routing,
+    /// Rate limits, timeouts and unavailability.
+    Availability,
+    /// Errors of specific resources.
+    Resource,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VerbosityPolicyConfig {
+    /// Overrides the `error_verbosity` for the errors of these categories.
+    #[serde(default)]
+    pub categories: HashMap<ErrorCategory, ErrorVerbosity>,
+    /// Overrides for the requests whose path starts with a prefix. The longest matching prefix is used.
+    #[serde(default)]
+    pub routes: Vec<RouteVerbosityConfig>,
+    /// The status codes [`ErrorVerbosity::RandomStatus`] draws from.
+    #[serde(default = "default_random_status_codes")]
+    pub random_status_codes: Vec<u16>,
+    /// Whether internal server errors with context include the backtrace captured by `anyhow`.
+    ///
+    /// Backtraces are only captured if `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` is set.
+    #[serde(default)]
+    pub backtraces: bool,
+}
+
+fn default_random_status_codes() -> Vec<u16> {
+    vec![200, 301, 400, 401, 403, 404, 405, 418, 500, 502, 503]
+}
+
+impl Default for VerbosityPolicyConfig {
+    fn default() -> Self {
+        Self {
+            categories: HashMap::new(),
+            routes: Vec::new(),
+            random_status_codes: default_random_status_codes(),
+            backtraces: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RouteVerbosityConfig {
+    pub path_prefix: String,
+    /// Overrides the `error_verbosity` for the requests below the prefix.
+    pub verbosity: Option<ErrorVerbosity>,
+    /// Overrides the verbosity of the categories for the requests below the prefix.
+    #[serde(default)]
+    pub categories: HashMap<ErrorCategory, ErrorVerbosity>,
+}
+
+/// The verbosity of the errors of the requests below a path prefix.
+#[derive(Debug)]
+pub struct RouteVerbosity {
+    verbosity: ErrorVerbosity,
+    categories: HashMap<ErrorCategory, ErrorVerbosity>,
+    random_status_codes: Arc<[StatusCode]>,
+    backtraces: bool,
+}
+
+impl RouteVerbosity {
+    /// Returns the verbosity of the errors of the category.
+    pub fn verbosity(&self, category: ErrorCategory) -> ErrorVerbosity {
+        self.categories
+            .get(&category)
+            .copied()
+            .unwrap_or(self.verbosity)
+    }
+
+    /// Returns the most verbose level of all categories, used to create the errors.
+    pub fn max(&self) -> ErrorVerbosity {
+        self.categories
+            .values()
+            .copied()
+            .fold(self.verbosity, Ord::max)
+    }
+
+    /// Returns a random status code for [`ErrorVerbosity::RandomStatus`].
+    pub fn random_status_code(&self) -> StatusCode {
+        self.random_status_codes
+            .choose(&mut rand::thread_rng())
+            .copied()
+            .unwrap_or(StatusCode::NOT_FOUND)
+    }
+
+    /// Returns whether internal server errors include their backtrace.
+    pub fn includes_backtraces(&self) -> bool {
+        self.backtraces
+    }
+
+    /// Returns the verbosity of the route of the request that is currently handled.
+    ///
+    /// Returns `None` outside of [`RouteVerbosity::scope`].
+    pub fn current() -> Option<Arc<Self>> {
+        CURRENT_ROUTE_VERBOSITY.try_with(Clone::clone).ok()
+    }
+
+    /// Runs the future with this verbosity as the [`RouteVerbosity::current`] verbosity.
+    pub async fn scope<F: Future>(self: Arc<Self>, future: F) -> F::Output {
+        CURRENT_ROUTE_VERBOSITY.scope(self, future).await
+    }
+}
+
+/// Resolves the [`RouteVerbosity`] of the requests.
+///
+/// Replaces the single `error_verbosity` of the server with levels per category and route prefix.
+#[derive(Debug)]
+pub struct VerbosityPolicy {
+    default: Arc<RouteVerbosity>,
+    /// Sorted by descending prefix length, so the first match is the longest one.
+    routes: Vec<(String, Arc<RouteVerbosity>)>,
+}
+
+impl VerbosityPolicy {
+    pub fn new(verbosity: ErrorVerbosity, config: VerbosityPolicyConfig) -> Self {
+        let random_status_codes = config
+            .random_status_codes
+            .iter()
+            .filter_map(|&code| match StatusCode::from_u16(code) {
+                Ok(status_code) => Some(status_code),
+                Err(_) => {
+                    tracing::warn!(%code, "Ignoring invalid random status code");
+
+                    None
+                }
+            })
+            .collect::<Arc<[_]>>();
+
+        let mut routes = config
+            .routes
+            .into_iter()
+            .map(|route| {
+                let mut categories = config.categories.clone();
+                categories.extend(route.categories);
+
+                let route_verbosity = RouteVerbosity {
+                    verbosity: route.verbosity.unwrap_or(verbosity),
+                    categories,
+                    random_status_codes: random_status_codes.clone(),
+                    backtraces: config.backtraces,
+                };
+
+                (
+                    route.path_prefix.trim_end_matches('/').to_owned(),
+                    Arc::new(route_verbosity),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        routes.sort_by_key(|(prefix, _)| Reverse(prefix.len()));
+
+        Self {
+            default: Arc::new(RouteVerbosity {
+                verbosity,
+                categories: config.categories,
+                random_status_codes,
+                backtraces: config.backtraces,
+            }),
+            routes,
+        }
+    }
+
+    /// Returns the verbosity of the route the path belongs to.
+    pub fn for_path(&self, path: &str) -> Arc<RouteVerbosity> {
+        self.routes
+            .iter()
+            .find(|(prefix, _)| {
+                path.strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .map_or_else(|| self.default.clone(), |(_, route)| route.clone())
+    }
+
+    /// Returns the verbosity used outside of a request.
+    pub fn default_verbosity(&self) -> ErrorVerbosity {
+        self.default.max()
+    }
+}
+
+/// Returns a random status code from the [`RouteVerbosity::current`] verbosity.
+///
+/// Falls back to [`StatusCode::NOT_FOUND`] outside of [`RouteVerbosity::scope`].
+pub fn random_status_code() -> StatusCode {
+    RouteVerbosity::current().map_or(StatusCode::NOT_FOUND, |route_verbosity| {
+        route_verbosity.random_status_code()
+    })
+}
+
+pub trait VerbosityPolicyProvider {
+    fn verbosity_policy(&self) -> &VerbosityPolicy;
+}
@@ -0,0 +1,483 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Expr, Fields,
+    GenericArgument, Ident, LitStr, PathArguments, Type,
+};
+
+/// Derives `axum::extract::FromRequest` for a struct whose fields are extracted from different parts of the request.
+///
+/// Every field must be annotated with one of:
+///
+/// - `#[from(path)]`: `ApiPath`
+/// - `#[from(query)]`: `ApiQuery`
+/// - `#[from(header = "x-foo")]`: the header value parsed with `FromStr`. Use `Option<T>` for optional headers.
+/// - `#[from(json)]`: `ApiJson`
+/// - `#[from(form)]`: `ApiForm`
+///
+/// At most one field may be extracted from the body.
+/// The extractor rejects with the `ApiError` of the first field that fails.
+///
+/// ```rust,ignore
+/// #[derive(ApiRequest)]
+/// pub struct CreateBookRequest {
+///     #[from(query)]
+///     query: CreateBookQuery,
+///     #[from(header = "x-request-id")]
+///     request_id: Option<String>,
+///     #[from(json)]
+///     body: CreateBookBody,
+/// }
+/// ```
+#[proc_macro_derive(ApiRequest, attributes(from))]
+pub fn derive_api_request(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `Policy` for a unit struct from the `#[policy("...")]` attribute.
+///
+/// ```rust,ignore
+/// #[derive(Policy)]
+/// #[policy("books:read")]
+/// pub struct BooksRead;
+/// ```
+#[proc_macro_derive(Policy, attributes(policy))]
+pub fn derive_policy(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_policy(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_policy(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let mut attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("policy"));
+
+    let Some(attr) = attrs.next() else {
+        return Err(syn::Error::new(
+            input.span(),
+            "missing `#[policy(\"...\")]` attribute",
+        ));
+    };
+
+    if let Some(attr) = attrs.next() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "duplicate `#[policy(...)]` attribute",
+        ));
+    }
+
+    let name: LitStr = attr.parse_args()?;
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::the_axum::extractor::authorized::Policy for #ident #ty_generics #where_clause {
+            const NAME: &'static str = #name;
+        }
+    })
+}
+
+/// Derives `ResourceErrorProvider` for an enum of route errors.
+///
+/// Every variant must be annotated with:
+///
+/// - `#[status(NOT_FOUND)]`: the `StatusCode` constant.
+/// - `#[message("Book not found")]`: the message.
+/// - `#[code(BOOK_NOT_FOUND)]`: the constant in `error_codes`.
+///
+/// The context type is set on the enum with `#[context(Type)]`, `()` if omitted.
+/// Variants build their context with `#[context(expr)]`, where the fields of the variant are in scope by name
+/// (`_0`, `_1`, ... for tuple variants). Variants without a context use `Default::default()`.
+///
+/// ```rust,ignore
+/// #[derive(Debug, Serialize, ResourceError)]
+/// #[serde(tag = "error_type")]
+/// #[context(GetBookErrorContext)]
+/// pub enum GetBookErrorType {
+///     #[status(NOT_FOUND)]
+///     #[message("Book not found")]
+///     #[code(BOOK_NOT_FOUND)]
+///     #[context(GetBookErrorContext { context: format!("Book with id {id} not found") })]
+///     NotFound {
+///         #[serde(skip)]
+///         id: i64,
+///     },
+/// }
+/// ```
+#[proc_macro_derive(ResourceError, attributes(status, message, code, context))]
+pub fn derive_resource_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_resource_error(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Returns the single attribute with the name, `None` if it is missing.
+fn unique_attr<'a>(attrs: &'a [Attribute], name: &str) -> syn::Result<Option<&'a Attribute>> {
+    let mut attrs = attrs.iter().filter(|attr| attr.path().is_ident(name));
+
+    let attr = attrs.next();
+
+    if let Some(duplicate) = attrs.next() {
+        return Err(syn::Error::new(
+            duplicate.span(),
+            format!("duplicate `#[{name}(...)]` attribute"),
+        ));
+    }
+
+    Ok(attr)
+}
+
+/// Returns the single attribute with the name, failing if it is missing.
+fn required_attr<'a>(
+    attrs: &'a [Attribute],
+    name: &str,
+    span: proc_macro2::Span,
+) -> syn::Result<&'a Attribute> {
+    unique_attr(attrs, name)?
+        .ok_or_else(|| syn::Error::new(span, format!("missing `#[{name}(...)]` attribute")))
+}
+
+fn expand_resource_error(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "`ResourceError` can only be derived for enums",
+        ));
+    };
+
+    let crate_path = quote!(::the_axum);
+
+    let context_type = match unique_attr(&input.attrs, "context")? {
+        Some(attr) => {
+            let ty: Type = attr.parse_args()?;
+            quote!(#ty)
+        }
+        None => quote!(()),
+    };
+
+    let mut status_arms = Vec::new();
+    let mut message_arms = Vec::new();
+    let mut code_arms = Vec::new();
+    let mut type_arms = Vec::new();
+    let mut context_arms = Vec::new();
+
+    for variant in data.variants.iter() {
+        let name = &variant.ident;
+        let span = variant.span();
+
+        let status: Ident = required_attr(&variant.attrs, "status", span)?.parse_args()?;
+        let message: LitStr = required_attr(&variant.attrs, "message", span)?.parse_args()?;
+        let code: Ident = required_attr(&variant.attrs, "code", span)?.parse_args()?;
+
+        let pattern = quote!(Self::#name { .. });
+
+        status_arms.push(quote!(#pattern => ::axum::http::StatusCode::#status));
+        message_arms.push(quote!(#pattern => #message));
+        code_arms.push(quote!(#pattern => #crate_path::error_codes::#code));
+        type_arms.push(quote!(#pattern => stringify!(#name)));
+
+        let context_arm = match unique_attr(&variant.attrs, "context")? {
+            Some(attr) => {
+                let context: Expr = attr.parse_args()?;
+
+                let bindings = match &variant.fields {
+                    Fields::Named(fields) => {
+                        let names = fields.named.iter().map(|field| &field.ident);
+                        quote!(Self::#name { #(#names,)* })
+                    }
+                    Fields::Unnamed(fields) => {
+                        let names = (0..fields.unnamed.len())
+                            .map(|i| Ident::new(&format!("_{i}"), proc_macro2::Span::call_site()));
+                        quote!(Self::#name ( #(#names,)* ))
+                    }
+                    Fields::Unit => quote!(Self::#name),
+                };
+
+                quote!(#[allow(unused_variables)] #bindings => #context)
+            }
+            None => quote!(#pattern => ::core::default::Default::default()),
+        };
+
+        context_arms.push(context_arm);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #crate_path::error::ResourceErrorProvider for #ident #ty_generics #where_clause {
+            type Context = #context_type;
+
+            fn headers(&self) -> Option<::axum::http::HeaderMap> {
+                None
+            }
+
+            fn status_code(&self) -> ::axum::http::StatusCode {
+                match self {
+                    #(#status_arms,)*
+                }
+            }
+
+            fn message(&self) -> &'static str {
+                match self {
+                    #(#message_arms,)*
+                }
+            }
+
+            fn error_code(&self) -> &'static str {
+                match self {
+                    #(#code_arms,)*
+                }
+            }
+
+            fn error_type(&self) -> &'static str {
+                match self {
+                    #(#type_arms,)*
+                }
+            }
+
+            fn context(&self) -> Self::Context {
+                match self {
+                    #(#context_arms,)*
+                }
+            }
+        }
+    })
+}
+
+enum Source {
+    Path,
+    Query,
+    Header(LitStr),
+    Json,
+    Form,
+}
+
+impl Source {
+    fn is_body(&self) -> bool {
+        matches!(self, Source::Json | Source::Form)
+    }
+}
+
+fn source(field: &syn::Field) -> syn::Result<Source> {
+    let mut attrs = field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("from"));
+
+    let Some(attr) = attrs.next() else {
+        return Err(syn::Error::new(
+            field.span(),
+            "missing `#[from(...)]` attribute",
+        ));
+    };
+
+    if let Some(attr) = attrs.next() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "duplicate `#[from(...)]` attribute",
+        ));
+    }
+
+    let mut source = None;
+
+    attr.parse_nested_meta(|meta| {
+        let parsed = if meta.path.is_ident("path") {
+            Source::Path
+        } else if meta.path.is_ident("query") {
+            Source::Query
+        } else if meta.path.is_ident("header") {
+            Source::Header(meta.value()?.parse()?)
+        } else if meta.path.is_ident("json") {
+            Source::Json
+        } else if meta.path.is_ident("form") {
+            Source::Form
+        } else {
+            return Err(
+                meta.error("expected one of `path`, `query`, `header = \"...\"`, `json` or `form`")
+            );
+        };
+
+        if source.replace(parsed).is_some() {
+            return Err(meta.error("only one source is allowed per field"));
+        }
+
+        Ok(())
+    })?;
+
+    source.ok_or_else(|| syn::Error::new(attr.span(), "missing source"))
+}
+
+/// Returns `T` if the type is `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "`ApiRequest` can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            input.span(),
+            "`ApiRequest` can only be derived for structs with named fields",
+        ));
+    };
+
+    let crate_path = quote!(::the_axum);
+
+    let mut bounds = Vec::new();
+    let mut parts_extractions = Vec::new();
+    let mut body_extraction = None;
+    let mut field_names = Vec::new();
+
+    for field in fields.named.iter() {
+        let name = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let source = source(field)?;
+
+        field_names.push(name);
+
+        if source.is_body() && body_extraction.is_some() {
+            return Err(syn::Error::new(
+                field.span(),
+                "only one field can be extracted from the body",
+            ));
+        }
+
+        match source {
+            Source::Path | Source::Query => {
+                let extractor = match source {
+                    Source::Path => quote!(#crate_path::extractor::path::ApiPath),
+                    _ => quote!(#crate_path::extractor::query::ApiQuery),
+                };
+
+                bounds.push(quote! {
+                    #extractor<#ty>: ::axum::extract::FromRequestParts<__S, Rejection = #crate_path::error::ApiError>
+                });
+                parts_extractions.push(quote! {
+                    let #extractor(#name) = <#extractor<#ty> as ::axum::extract::FromRequestParts<__S>>::from_request_parts(&mut __parts, __state).await?;
+                });
+            }
+            Source::Header(header) => {
+                let parse = match option_inner(ty) {
+                    Some(inner) => quote! {
+                        #crate_path::extractor::api_request::header::<#inner>(&__parts, #header, __verbosity)?
+                    },
+                    None => quote! {
+                        #crate_path::extractor::api_request::required_header::<#ty>(&__parts, #header, __verbosity)?
+                    },
+                };
+
+                parts_extractions.push(quote! {
+                    let #name = #parse;
+                });
+            }
+            Source::Json | Source::Form => {
+                let extractor = match source {
+                    Source::Json => quote!(#crate_path::extractor::json::ApiJson),
+                    _ => quote!(#crate_path::extractor::form::ApiForm),
+                };
+
+                bounds.push(quote! {
+                    #extractor<#ty>: ::axum::extract::FromRequest<__S, Rejection = #crate_path::error::ApiError>
+                });
+                body_extraction = Some(quote! {
+                    let #extractor(#name) = <#extractor<#ty> as ::axum::extract::FromRequest<__S>>::from_request(__req, __state).await?;
+                });
+            }
+        }
+    }
+
+    let body_extraction = body_extraction.unwrap_or_default();
+
+    let ident = &input.ident;
+    let span_name = format!("{}_extractor", snake_case(&ident.to_string()));
+
+    let mut generics = input.generics.clone();
+    generics.params.push(syn::parse_quote!(__S));
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates = where_clause.map(|where_clause| &where_clause.predicates);
+
+    Ok(quote! {
+        #[::axum::async_trait]
+        impl #impl_generics ::axum::extract::FromRequest<__S> for #ident #ty_generics
+        where
+            __S: Send + Sync + #crate_path::error::ErrorVerbosityProvider,
+            #(#bounds,)*
+            #predicates
+        {
+            type Rejection = #crate_path::error::ApiError;
+
+            #[::tracing::instrument(name = #span_name, skip_all)]
+            async fn from_request(
+                __req: ::axum::extract::Request,
+                __state: &__S,
+            ) -> Result<Self, Self::Rejection> {
+                #[allow(unused_variables)]
+                let __verbosity = #crate_path::error::ErrorVerbosityProvider::error_verbosity(__state);
+
+                #[allow(unused_mut)]
+                let (mut __parts, __body) = __req.into_parts();
+
+                #(#parts_extractions)*
+
+                #[allow(unused_variables)]
+                let __req = ::axum::extract::Request::from_parts(__parts, __body);
+
+                #body_extraction
+
+                ::tracing::trace!("Extracted");
+
+                Ok(Self { #(#field_names,)* })
+            }
+        }
+    })
+}